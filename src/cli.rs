@@ -1,20 +1,26 @@
 mod catalog;
+mod chain;
 mod commands;
 mod csv;
 mod database;
 mod datastore;
+mod metrics;
 mod multisig;
+mod network;
 mod reward_distributor;
 mod sage_client;
 mod utils;
 mod xchandles;
 
 pub use catalog::*;
+pub use chain::*;
 pub use commands::*;
 pub use csv::*;
 pub use database::*;
 pub use datastore::*;
+pub use metrics::*;
 pub use multisig::*;
+pub use network::*;
 pub use reward_distributor::*;
 pub use sage_client::*;
 pub use utils::*;
@@ -1,5 +1,6 @@
 mod broadcast_state_update;
 mod catalog_api_client;
+mod catalog_listener;
 mod continue_launch;
 mod initiate_launch;
 mod listen;
@@ -12,6 +13,7 @@ mod verify_deployment;
 
 pub use broadcast_state_update::*;
 pub use catalog_api_client::*;
+pub use catalog_listener::*;
 pub use continue_launch::*;
 pub use initiate_launch::*;
 pub use listen::*;
@@ -1,8 +1,8 @@
 use chia_protocol::{Bytes, Bytes32};
 use chia_wallet_sdk::{
     driver::{
-        CatalogRegistryConstants, CatalogRegistryState, DelegatedStateAction, MedievalVault,
-        SingletonInfo, XchandlesRegistryReceivedMessagePrefix,
+        CatalogRegistryState, DelegatedStateAction, MedievalVault, SingletonInfo,
+        XchandlesRegistryReceivedMessagePrefix,
     },
     types::{
         puzzles::{DefaultCatMakerArgs, StateSchedulerLayerSolution},
@@ -14,7 +14,7 @@ use clvmr::NodePtr;
 
 use crate::{
     get_constants, hex_string_to_bytes32, multisig_broadcast_thing_finish,
-    multisig_broadcast_thing_start, parse_amount, quick_sync_catalog, CliError,
+    multisig_broadcast_thing_start, parse_amount, quick_sync_catalog, CliError, Network,
 };
 
 pub async fn catalog_broadcast_state_update(
@@ -22,17 +22,17 @@ pub async fn catalog_broadcast_state_update(
     new_payment_asset_amount_str: String,
     launcher_id_str: String,
     signatures_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let new_payment_asset_id = hex_string_to_bytes32(&new_payment_asset_id_str)?;
     let new_payment_asset_amount = parse_amount(&new_payment_asset_amount_str, true)?;
 
     let (signature_from_signers, pubkeys, client, mut ctx, medieval_vault) =
-        multisig_broadcast_thing_start(signatures_str, launcher_id_str, testnet11).await?;
+        multisig_broadcast_thing_start(signatures_str, launcher_id_str, network).await?;
 
     println!("\nSyncing CATalog... ");
-    let catalog_constants = CatalogRegistryConstants::get(testnet11);
+    let catalog_constants = network.catalog_constants();
     let mut catalog = quick_sync_catalog(&client, &mut ctx, catalog_constants).await?;
     println!("Done!");
 
@@ -68,7 +68,7 @@ pub async fn catalog_broadcast_state_update(
         hex::encode(new_state.cat_maker_puzzle_hash.to_bytes())
     );
 
-    let constants = get_constants(testnet11);
+    let constants = get_constants(network);
     let medieval_vault_coin_id = medieval_vault.coin.coin_id();
     let medieval_vault_inner_ph = medieval_vault.info.inner_puzzle_hash();
 
@@ -107,7 +107,7 @@ pub async fn catalog_broadcast_state_update(
         &mut ctx,
         signature_from_signers + &pending_sig,
         fee_str,
-        testnet11,
+        network,
         medieval_vault_coin_id,
         None,
     )
@@ -3,7 +3,7 @@ use chia_puzzle_types::LineageProof;
 use reqwest::Client;
 use std::time::Duration;
 
-use crate::{hex_string_to_bytes32, CliError};
+use crate::{hex_string_to_bytes32, listener_error, CliError, Network};
 use chia_wallet_sdk::driver::Slot;
 use chia_wallet_sdk::types::puzzles::{CatalogSlotValue, SlotInfo};
 
@@ -36,11 +36,11 @@ impl CatalogApiClient {
         Self::new("http://localhost:3000")
     }
 
-    pub fn get(testnet11: bool) -> Self {
-        if testnet11 {
-            Self::testnet()
-        } else {
-            Self::mainnet()
+    pub fn get(network: &Network) -> Self {
+        match &network.catalog_api_url {
+            Some(url) => Self::new(url),
+            None if network.is_testnet() => Self::testnet(),
+            None => Self::mainnet(),
         }
     }
 
@@ -72,6 +72,9 @@ impl CatalogApiClient {
             hex::encode(asset_id)
         );
         let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(listener_error(response).await);
+        }
 
         let neighbors_resp = response.json::<CatalogNeighborResponse>().await?;
 
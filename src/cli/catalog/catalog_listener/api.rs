@@ -0,0 +1,295 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Path, Query, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chia_protocol::Bytes32;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tower_http::cors::{Any, CorsLayer};
+
+use super::cat_store::CatStore;
+use super::store::CatalogRegistryStore;
+use super::types::{
+    CatMetadataJson, CatResponse, CatalogPriceResponse, RecentCatRegistrationItem,
+    RecentCatRegistrationsQuery, RecentCatRegistrationsResponse,
+};
+use crate::{
+    hex32, parse_launcher_id, record_http_metrics, ApiError, FreshnessState, ListenerMetrics,
+    ReadyResponse, RegistryStatusItem, RegistryTip, StatusResponse, MAX_PEAK_AGE_SECONDS,
+    MAX_PEAK_LAG,
+};
+
+#[derive(Clone)]
+pub struct CatalogApiState {
+    pub registry: Arc<dyn CatalogRegistryStore>,
+    pub cats: Arc<dyn CatStore>,
+    pub freshness: Arc<RwLock<FreshnessState>>,
+    /// Synced registry tip; the listener is not ready until one is published.
+    pub registry_tip: Arc<RwLock<Option<RegistryTip>>>,
+    pub registry_launcher_id: Bytes32,
+    /// Optional clock override for tests (unix seconds).
+    pub now_unix_override: Option<u64>,
+    /// Shared with the indexer and websocket loop; rendered on `/metrics`.
+    pub metrics: Arc<ListenerMetrics>,
+}
+
+impl CatalogApiState {
+    pub fn new(
+        registry: Arc<dyn CatalogRegistryStore>,
+        cats: Arc<dyn CatStore>,
+        freshness: FreshnessState,
+        registry_launcher_id: Bytes32,
+    ) -> Self {
+        Self {
+            registry,
+            cats,
+            freshness: Arc::new(RwLock::new(freshness)),
+            registry_tip: Arc::new(RwLock::new(None)),
+            registry_launcher_id,
+            now_unix_override: None,
+            metrics: ListenerMetrics::shared("catalog"),
+        }
+    }
+
+    fn now_unix(&self) -> u64 {
+        self.now_unix_override.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// Public CATalog router: CORS is credential-free for any origin on GET/HEAD.
+pub fn catalog_router(state: CatalogApiState) -> Router {
+    let metrics = Arc::clone(&state.metrics);
+    Router::new()
+        .route("/healthz", get(|| async { StatusCode::OK }))
+        .route("/readyz", get(get_readyz).head(head_readyz))
+        .route("/status", get(get_status))
+        .route("/cat/{asset_id}", get(get_cat).head(head_cat))
+        .route(
+            "/recent-registrations",
+            get(get_recent_registrations).head(head_recent_registrations),
+        )
+        .route("/price", get(get_price).head(head_price))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&metrics),
+            record_http_metrics,
+        ))
+        .route("/metrics", get(move || async move { metrics.response() }))
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods([Method::GET, Method::HEAD, Method::OPTIONS])
+                .allow_headers(Any),
+        )
+        .with_state(state)
+}
+
+/// `indexed_peak_height` when fresh; `index_stale` otherwise.
+pub async fn require_catalog_fresh(state: &CatalogApiState) -> Result<u32, ApiError> {
+    let freshness = state.freshness.read().await;
+    if !freshness.is_fresh(state.now_unix()) {
+        return Err(ApiError::index_stale(
+            freshness.indexed_peak_height,
+            freshness.upstream_peak_height,
+        ));
+    }
+    Ok(freshness.indexed_peak_height)
+}
+
+/// `index_stale` naming the registry, for reads that need a synced registry state.
+async fn unsynced_registry(state: &CatalogApiState) -> ApiError {
+    let freshness = state.freshness.read().await;
+    ApiError::index_stale(
+        freshness.indexed_peak_height,
+        freshness.upstream_peak_height,
+    )
+    .with_details(json!({
+        "indexed_peak_height": freshness.indexed_peak_height,
+        "upstream_peak_height": freshness.upstream_peak_height,
+        "unsynced_registry": hex32(state.registry_launcher_id),
+    }))
+}
+
+/// Readiness fails closed: fresh index and a synced registry tip.
+async fn require_ready(state: &CatalogApiState) -> Result<ReadyResponse, ApiError> {
+    let indexed_peak_height = require_catalog_fresh(state).await?;
+    if state.registry_tip.read().await.is_none() {
+        return Err(unsynced_registry(state).await);
+    }
+    Ok(ReadyResponse {
+        ready: true,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_status(state: &CatalogApiState) -> StatusResponse {
+    let now = state.now_unix();
+    let freshness = state.freshness.read().await.clone();
+    let tip = *state.registry_tip.read().await;
+    let stale_reasons = freshness
+        .stale_reasons(now)
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    StatusResponse {
+        ready: stale_reasons.is_empty() && tip.is_some(),
+        stale_reasons,
+        indexed_peak_height: freshness.indexed_peak_height,
+        upstream_peak_height: freshness.upstream_peak_height,
+        peak_lag: freshness
+            .upstream_peak_height
+            .saturating_sub(freshness.indexed_peak_height),
+        max_peak_lag: MAX_PEAK_LAG,
+        last_successful_peak_unix: freshness.last_successful_peak_unix,
+        seconds_since_last_peak: now.saturating_sub(freshness.last_successful_peak_unix),
+        max_peak_age_seconds: MAX_PEAK_AGE_SECONDS,
+        confirmed_timestamp: freshness.confirmed_timestamp,
+        rolling_back: freshness.rolling_back,
+        resyncing: freshness.resyncing,
+        last_reorg_unix: freshness.last_reorg_unix,
+        last_reorg_height: freshness.last_reorg_height,
+        registries: vec![RegistryStatusItem {
+            launcher_id: hex32(state.registry_launcher_id),
+            tip_coin_id: tip.map(|t| hex32(t.coin_id)),
+            tip_indexed_height: tip.map(|t| t.indexed_height),
+        }],
+    }
+}
+
+async fn lookup_cat(state: &CatalogApiState, raw_asset_id: &str) -> Result<CatResponse, ApiError> {
+    let asset_id = parse_launcher_id(raw_asset_id).ok_or_else(ApiError::invalid_asset_id)?;
+    let indexed_peak_height = require_catalog_fresh(state).await?;
+    let cat = state
+        .cats
+        .get(state.registry_launcher_id, asset_id)
+        .await
+        .ok_or_else(ApiError::cat_not_registered)?;
+    Ok(CatResponse {
+        registry_launcher_id: hex32(cat.registry_launcher_id),
+        asset_id: hex32(cat.asset_id),
+        metadata: cat.metadata.as_ref().map(CatMetadataJson::from),
+        confirmation_height: cat.confirmation_height,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_recent_registrations(
+    state: &CatalogApiState,
+    query: &RecentCatRegistrationsQuery,
+) -> Result<RecentCatRegistrationsResponse, ApiError> {
+    let indexed_peak_height = require_catalog_fresh(state).await?;
+
+    let limit = query.limit.unwrap_or(50).min(50) as usize;
+    let stats = state.cats.get_stats(state.registry_launcher_id).await;
+    let items = stats
+        .events
+        .iter()
+        .rev()
+        .take(limit)
+        .map(|ev| RecentCatRegistrationItem {
+            asset_id: hex32(ev.asset_id),
+            ticker: ev.ticker.clone(),
+            confirmation_height: ev.confirmation_height,
+        })
+        .collect();
+
+    Ok(RecentCatRegistrationsResponse {
+        items,
+        total_registered: stats.total_registered,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_price(state: &CatalogApiState) -> Result<CatalogPriceResponse, ApiError> {
+    let indexed_peak_height = require_catalog_fresh(state).await?;
+    let Some(current) = state
+        .registry
+        .get(state.registry_launcher_id)
+        .await
+        .and_then(|rec| rec.current)
+    else {
+        return Err(unsynced_registry(state).await);
+    };
+    Ok(CatalogPriceResponse {
+        registry_launcher_id: hex32(state.registry_launcher_id),
+        cat_maker_puzzle_hash: hex32(current.cat_maker_puzzle_hash),
+        registration_price: current.registration_price,
+        confirmation_height: current.confirmation_height,
+        indexed_peak_height,
+    })
+}
+
+async fn get_readyz(State(state): State<CatalogApiState>) -> Result<impl IntoResponse, ApiError> {
+    let body = require_ready(&state).await?;
+    Ok(Json(body))
+}
+
+async fn head_readyz(State(state): State<CatalogApiState>) -> Result<StatusCode, ApiError> {
+    let _ = require_ready(&state).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_status(State(state): State<CatalogApiState>) -> impl IntoResponse {
+    Json(lookup_status(&state).await)
+}
+
+async fn get_cat(
+    State(state): State<CatalogApiState>,
+    Path(asset_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_cat(&state, &asset_id).await?;
+    Ok(Json(body))
+}
+
+async fn head_cat(
+    State(state): State<CatalogApiState>,
+    Path(asset_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_cat(&state, &asset_id).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_recent_registrations(
+    State(state): State<CatalogApiState>,
+    Query(query): Query<RecentCatRegistrationsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_recent_registrations(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_recent_registrations(
+    State(state): State<CatalogApiState>,
+    Query(query): Query<RecentCatRegistrationsQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_recent_registrations(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_price(State(state): State<CatalogApiState>) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_price(&state).await?;
+    Ok(Json(body))
+}
+
+async fn head_price(State(state): State<CatalogApiState>) -> Result<StatusCode, ApiError> {
+    let _ = lookup_price(&state).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Bind + serve helper used by production `catalog listen` and the real-HTTP test fixture.
+pub async fn serve_catalog_listener(
+    state: CatalogApiState,
+    bind: std::net::SocketAddr,
+) -> Result<(), std::io::Error> {
+    let app = catalog_router(state);
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    axum::serve(listener, app).await
+}
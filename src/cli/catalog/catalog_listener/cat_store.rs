@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use chia_wallet_sdk::types::puzzles::CatNftMetadata;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::CliError;
+
+/// Initial CAT NFT metadata revealed by the registration's eve spend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCatMetadata {
+    pub ticker: String,
+    pub name: String,
+    pub description: String,
+    pub precision: u8,
+    pub hidden_puzzle_hash: Option<Bytes32>,
+    pub image_uris: Vec<String>,
+    pub image_hash: Bytes32,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: Option<Bytes32>,
+    pub license_uris: Vec<String>,
+    pub license_hash: Option<Bytes32>,
+}
+
+impl From<CatNftMetadata> for StoredCatMetadata {
+    fn from(metadata: CatNftMetadata) -> Self {
+        Self {
+            ticker: metadata.ticker,
+            name: metadata.name,
+            description: metadata.description,
+            precision: metadata.precision,
+            hidden_puzzle_hash: metadata.hidden_puzzle_hash,
+            image_uris: metadata.image_uris,
+            image_hash: metadata.image_hash,
+            metadata_uris: metadata.metadata_uris,
+            metadata_hash: metadata.metadata_hash,
+            license_uris: metadata.license_uris,
+            license_hash: metadata.license_hash,
+        }
+    }
+}
+
+/// Canonical registration projection for one CAT.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCat {
+    pub registry_launcher_id: Bytes32,
+    pub asset_id: Bytes32,
+    /// `None` when the eve NFT spend could not be tied to exactly this registration.
+    pub metadata: Option<StoredCatMetadata>,
+    pub confirmation_height: u32,
+}
+
+/// One confirmed registration for the recent feed (oldest-first in storage).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCatEvent {
+    pub asset_id: Bytes32,
+    pub ticker: Option<String>,
+    pub confirmation_height: u32,
+}
+
+/// Per-registry cumulative register count and confirmed event log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CatalogRegistrationStats {
+    pub total_registered: u64,
+    pub events: Vec<StoredCatEvent>,
+}
+
+#[async_trait::async_trait]
+pub trait CatStore: Send + Sync {
+    async fn get(&self, registry_launcher_id: Bytes32, asset_id: Bytes32) -> Option<StoredCat>;
+    async fn upsert(&self, cat: StoredCat) -> Result<(), CliError>;
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+    ) -> Result<(), CliError>;
+    async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)>;
+
+    async fn get_stats(&self, registry_launcher_id: Bytes32) -> CatalogRegistrationStats;
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: CatalogRegistrationStats,
+    ) -> Result<(), CliError>;
+    async fn all_stats_registry_ids(&self) -> Vec<Bytes32>;
+}
+
+#[derive(Default)]
+pub struct MemoryCatStore {
+    cats: RwLock<HashMap<(Bytes32, Bytes32), StoredCat>>,
+    stats: RwLock<HashMap<Bytes32, CatalogRegistrationStats>>,
+}
+
+impl MemoryCatStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+}
+
+#[async_trait::async_trait]
+impl CatStore for MemoryCatStore {
+    async fn get(&self, registry_launcher_id: Bytes32, asset_id: Bytes32) -> Option<StoredCat> {
+        self.cats
+            .read()
+            .await
+            .get(&(registry_launcher_id, asset_id))
+            .cloned()
+    }
+
+    async fn upsert(&self, cat: StoredCat) -> Result<(), CliError> {
+        self.cats
+            .write()
+            .await
+            .insert((cat.registry_launcher_id, cat.asset_id), cat);
+        Ok(())
+    }
+
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+    ) -> Result<(), CliError> {
+        self.cats
+            .write()
+            .await
+            .remove(&(registry_launcher_id, asset_id));
+        Ok(())
+    }
+
+    async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
+        self.cats.read().await.keys().copied().collect()
+    }
+
+    async fn get_stats(&self, registry_launcher_id: Bytes32) -> CatalogRegistrationStats {
+        self.stats
+            .read()
+            .await
+            .get(&registry_launcher_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: CatalogRegistrationStats,
+    ) -> Result<(), CliError> {
+        self.stats.write().await.insert(registry_launcher_id, stats);
+        Ok(())
+    }
+
+    async fn all_stats_registry_ids(&self) -> Vec<Bytes32> {
+        self.stats.read().await.keys().copied().collect()
+    }
+}
+
+/// Max confirmed registrations retained for the recent feed (oldest-first).
+pub const RECENT_CAT_EVENTS_MAX: usize = 50;
+
+/// Append a confirmed registration, dropping the oldest when over the cap.
+pub fn push_cat_event(stats: &mut CatalogRegistrationStats, ev: StoredCatEvent) {
+    stats.events.push(ev);
+    if stats.events.len() > RECENT_CAT_EVENTS_MAX {
+        stats.events.remove(0);
+    }
+}
+
+/// Drop orphaned recent events and reverse their register counts for a reorganization.
+pub fn rollback_cat_stats_to_before(stats: &mut CatalogRegistrationStats, from_height: u32) {
+    while let Some(ev) = stats.events.last() {
+        if ev.confirmation_height < from_height {
+            break;
+        }
+        stats.events.pop();
+        stats.total_registered = stats.total_registered.saturating_sub(1);
+    }
+}
+
+/// SQLite-backed CAT projections used by the production `catalog listen` process.
+pub struct DbCatStore {
+    db: Arc<futures::lock::Mutex<crate::Db>>,
+}
+
+impl DbCatStore {
+    pub fn new(db: Arc<futures::lock::Mutex<crate::Db>>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl CatStore for DbCatStore {
+    async fn get(&self, registry_launcher_id: Bytes32, asset_id: Bytes32) -> Option<StoredCat> {
+        let db = self.db.lock().await;
+        let json = db
+            .get_catalog_cat_record_json(registry_launcher_id, asset_id)
+            .await
+            .ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    async fn upsert(&self, cat: StoredCat) -> Result<(), CliError> {
+        let json = serde_json::to_string(&cat)?;
+        let db = self.db.lock().await;
+        db.upsert_catalog_cat_record_json(cat.registry_launcher_id, cat.asset_id, &json)
+            .await
+    }
+
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+    ) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_catalog_cat_record(registry_launcher_id, asset_id)
+            .await
+    }
+
+    async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
+        let db = self.db.lock().await;
+        db.all_catalog_cat_keys().await.unwrap_or_default()
+    }
+
+    async fn get_stats(&self, registry_launcher_id: Bytes32) -> CatalogRegistrationStats {
+        let db = self.db.lock().await;
+        let Ok(Some(json)) = db
+            .get_catalog_registration_stats_json(registry_launcher_id)
+            .await
+        else {
+            return CatalogRegistrationStats::default();
+        };
+        serde_json::from_str(&json).unwrap_or_default()
+    }
+
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: CatalogRegistrationStats,
+    ) -> Result<(), CliError> {
+        let json = serde_json::to_string(&stats)?;
+        let db = self.db.lock().await;
+        db.upsert_catalog_registration_stats_json(registry_launcher_id, &json)
+            .await
+    }
+
+    async fn all_stats_registry_ids(&self) -> Vec<Bytes32> {
+        let db = self.db.lock().await;
+        db.all_catalog_registration_stats_registry_ids()
+            .await
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollback_cat_stats_drops_orphaned_events_and_counts() {
+        let mut stats = CatalogRegistrationStats::default();
+        for (tag, height) in [(1u8, 90u32), (2, 100), (3, 110)] {
+            push_cat_event(
+                &mut stats,
+                StoredCatEvent {
+                    asset_id: Bytes32::new([tag; 32]),
+                    ticker: None,
+                    confirmation_height: height,
+                },
+            );
+            stats.total_registered += 1;
+        }
+
+        rollback_cat_stats_to_before(&mut stats, 100);
+        assert_eq!(stats.total_registered, 1);
+        assert_eq!(stats.events.len(), 1);
+        assert_eq!(stats.events[0].confirmation_height, 90);
+    }
+}
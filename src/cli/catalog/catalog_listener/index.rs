@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use tokio::sync::RwLock;
+
+use super::cat_store::{
+    push_cat_event, rollback_cat_stats_to_before, CatStore, StoredCat, StoredCatEvent,
+    StoredCatMetadata,
+};
+use super::store::{
+    push_catalog_replacement, rollback_catalog_to_before, CatalogRegistryRecord,
+    CatalogRegistryStore, StoredCatalogState,
+};
+use crate::{CliError, FreshnessState, ListenerMetrics};
+
+/// Projects confirmed CATalog registry spends into the registry and CAT stores.
+pub struct CatalogIndexer {
+    pub registry: Arc<dyn CatalogRegistryStore>,
+    pub cats: Arc<dyn CatStore>,
+    pub freshness: Arc<RwLock<FreshnessState>>,
+    pub metrics: Arc<ListenerMetrics>,
+}
+
+impl CatalogIndexer {
+    pub fn new(
+        registry: Arc<dyn CatalogRegistryStore>,
+        cats: Arc<dyn CatStore>,
+        freshness: Arc<RwLock<FreshnessState>>,
+    ) -> Self {
+        Self {
+            registry,
+            cats,
+            freshness,
+            metrics: ListenerMetrics::shared("catalog"),
+        }
+    }
+
+    /// Report into the process-wide metrics served on `/metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<ListenerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub async fn note_peak(&self, indexed: u32, upstream: u32, now_unix: u64) {
+        let mut f = self.freshness.write().await;
+        f.indexed_peak_height = indexed;
+        f.upstream_peak_height = upstream;
+        f.last_successful_peak_unix = now_unix;
+        f.rolling_back = false;
+        f.resyncing = false;
+    }
+
+    pub async fn note_upstream_peak(&self, upstream: u32) {
+        self.freshness.write().await.upstream_peak_height = upstream;
+    }
+
+    pub async fn begin_resync(&self) {
+        self.freshness.write().await.resyncing = true;
+    }
+
+    /// Apply one confirmed registry spend: the new registry state plus every CAT it registered.
+    pub async fn on_registry_transition(
+        &self,
+        launcher_id: Bytes32,
+        state: StoredCatalogState,
+        registered: &[(Bytes32, Option<StoredCatMetadata>)],
+    ) -> Result<(), CliError> {
+        let height = state.confirmation_height;
+        let mut record = self
+            .registry
+            .get(launcher_id)
+            .await
+            .unwrap_or(CatalogRegistryRecord {
+                launcher_id,
+                current: None,
+                history: Vec::new(),
+            });
+        push_catalog_replacement(&mut record, state, height);
+        self.registry.upsert(record).await?;
+
+        let mut stats = self.cats.get_stats(launcher_id).await;
+        for (asset_id, metadata) in registered {
+            // Replays after a restart see registrations that are already projected.
+            if self.cats.get(launcher_id, *asset_id).await.is_some() {
+                continue;
+            }
+            self.cats
+                .upsert(StoredCat {
+                    registry_launcher_id: launcher_id,
+                    asset_id: *asset_id,
+                    metadata: metadata.clone(),
+                    confirmation_height: height,
+                })
+                .await?;
+            push_cat_event(
+                &mut stats,
+                StoredCatEvent {
+                    asset_id: *asset_id,
+                    ticker: metadata.as_ref().map(|m| m.ticker.clone()),
+                    confirmation_height: height,
+                },
+            );
+            stats.total_registered = stats.total_registered.saturating_add(1);
+        }
+        self.cats.set_stats(launcher_id, stats).await?;
+
+        self.metrics.transitions_projected.inc();
+        Ok(())
+    }
+
+    /// Pre-final reorganization: drop registrations and registry states at or above `from_height`.
+    pub async fn rollback(&self, from_height: u32) -> Result<(), CliError> {
+        {
+            let mut f = self.freshness.write().await;
+            f.rolling_back = true;
+            f.last_reorg_unix = Some(FreshnessState::now_unix());
+            f.last_reorg_height = Some(from_height);
+        }
+        self.metrics.rollbacks.inc();
+
+        for launcher_id in self.registry.all_launcher_ids().await {
+            let Some(mut rec) = self.registry.get(launcher_id).await else {
+                continue;
+            };
+            rollback_catalog_to_before(&mut rec, from_height);
+            if rec.current.is_none() && rec.history.is_empty() {
+                self.registry.remove(launcher_id).await?;
+            } else {
+                self.registry.upsert(rec).await?;
+            }
+        }
+
+        let mut touched_registries: HashSet<Bytes32> = self
+            .cats
+            .all_stats_registry_ids()
+            .await
+            .into_iter()
+            .collect();
+        for (registry, asset_id) in self.cats.all_keys().await {
+            touched_registries.insert(registry);
+            let Some(cat) = self.cats.get(registry, asset_id).await else {
+                continue;
+            };
+            if cat.confirmation_height >= from_height {
+                self.cats.remove(registry, asset_id).await?;
+            }
+        }
+        for registry in touched_registries {
+            let mut stats = self.cats.get_stats(registry).await;
+            rollback_cat_stats_to_before(&mut stats, from_height);
+            self.cats.set_stats(registry, stats).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryCatStore, MemoryCatalogRegistryStore};
+
+    fn b32(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn state(tag: u8, height: u32, price: u64) -> StoredCatalogState {
+        StoredCatalogState {
+            coin_id: b32(tag),
+            parent_coin_id: b32(tag.wrapping_sub(1)),
+            cat_maker_puzzle_hash: b32(0xcc),
+            registration_price: price,
+            confirmation_height: height,
+        }
+    }
+
+    #[tokio::test]
+    async fn rollback_drops_orphaned_registrations_and_restores_price() {
+        let registry = b32(0xaa);
+        let freshness = Arc::new(RwLock::new(FreshnessState::fresh_at(0, 0)));
+        let indexer = CatalogIndexer::new(
+            MemoryCatalogRegistryStore::shared(),
+            MemoryCatStore::shared(),
+            Arc::clone(&freshness),
+        );
+
+        indexer
+            .on_registry_transition(registry, state(1, 100, 10), &[(b32(0x01), None)])
+            .await
+            .unwrap();
+        indexer
+            .on_registry_transition(
+                registry,
+                state(2, 110, 20),
+                &[(b32(0x02), None), (b32(0x01), None)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(indexer.cats.get_stats(registry).await.total_registered, 2);
+
+        indexer.rollback(105).await.unwrap();
+        assert!(freshness.read().await.rolling_back);
+        assert_eq!(freshness.read().await.last_reorg_height, Some(105));
+        assert!(indexer.cats.get(registry, b32(0x02)).await.is_none());
+        assert!(indexer.cats.get(registry, b32(0x01)).await.is_some());
+        assert_eq!(indexer.cats.get_stats(registry).await.total_registered, 1);
+        let current = indexer.registry.get(registry).await.unwrap().current;
+        assert_eq!(current.map(|s| s.registration_price), Some(10));
+    }
+}
@@ -0,0 +1,25 @@
+//! Public CATalog listener: registry state, registered CATs, and HTTP reads.
+//!
+//! Shares freshness, error envelope, and metrics with the XCHandles listener.
+
+mod api;
+mod cat_store;
+mod index;
+mod store;
+mod types;
+
+pub use api::{catalog_router, require_catalog_fresh, serve_catalog_listener, CatalogApiState};
+pub use cat_store::{
+    push_cat_event, rollback_cat_stats_to_before, CatStore, CatalogRegistrationStats, DbCatStore,
+    MemoryCatStore, StoredCat, StoredCatEvent, StoredCatMetadata, RECENT_CAT_EVENTS_MAX,
+};
+pub use index::CatalogIndexer;
+pub use store::{
+    prune_catalog_history, push_catalog_replacement, rollback_catalog_to_before,
+    CatalogRegistryRecord, CatalogRegistryStore, DbCatalogRegistryStore,
+    MemoryCatalogRegistryStore, StoredCatalogState,
+};
+pub use types::{
+    CatMetadataJson, CatResponse, CatalogPriceResponse, RecentCatRegistrationItem,
+    RecentCatRegistrationsQuery, RecentCatRegistrationsResponse,
+};
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::CliError;
+
+/// Registry singleton state after one confirmed CATalog spend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCatalogState {
+    /// Unspent registry coin created by the spend.
+    pub coin_id: Bytes32,
+    pub parent_coin_id: Bytes32,
+    pub cat_maker_puzzle_hash: Bytes32,
+    pub registration_price: u64,
+    pub confirmation_height: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogRegistryRecord {
+    pub launcher_id: Bytes32,
+    pub current: Option<StoredCatalogState>,
+    /// Prior states: every replacement within 32 blocks plus one older predecessor.
+    pub history: Vec<StoredCatalogState>,
+}
+
+#[async_trait::async_trait]
+pub trait CatalogRegistryStore: Send + Sync {
+    async fn get(&self, launcher_id: Bytes32) -> Option<CatalogRegistryRecord>;
+    async fn upsert(&self, record: CatalogRegistryRecord) -> Result<(), CliError>;
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError>;
+    async fn all_launcher_ids(&self) -> Vec<Bytes32>;
+}
+
+#[derive(Default)]
+pub struct MemoryCatalogRegistryStore {
+    inner: RwLock<HashMap<Bytes32, CatalogRegistryRecord>>,
+}
+
+impl MemoryCatalogRegistryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+}
+
+#[async_trait::async_trait]
+impl CatalogRegistryStore for MemoryCatalogRegistryStore {
+    async fn get(&self, launcher_id: Bytes32) -> Option<CatalogRegistryRecord> {
+        self.inner.read().await.get(&launcher_id).cloned()
+    }
+
+    async fn upsert(&self, record: CatalogRegistryRecord) -> Result<(), CliError> {
+        self.inner.write().await.insert(record.launcher_id, record);
+        Ok(())
+    }
+
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
+        self.inner.write().await.remove(&launcher_id);
+        Ok(())
+    }
+
+    async fn all_launcher_ids(&self) -> Vec<Bytes32> {
+        self.inner.read().await.keys().copied().collect()
+    }
+}
+
+/// Retain current state, every replaced state in the last 32 blocks, and one older predecessor.
+pub fn prune_catalog_history(history: &mut Vec<StoredCatalogState>, peak: u32) {
+    if history.is_empty() {
+        return;
+    }
+    let cutoff = peak.saturating_sub(32);
+    let mut keep_recent = Vec::new();
+    let mut older = None;
+    for state in history.drain(..) {
+        if state.confirmation_height >= cutoff {
+            keep_recent.push(state);
+        } else {
+            older = Some(state);
+        }
+    }
+    if let Some(pred) = older {
+        history.push(pred);
+    }
+    history.extend(keep_recent);
+}
+
+pub fn push_catalog_replacement(
+    record: &mut CatalogRegistryRecord,
+    new_state: StoredCatalogState,
+    peak: u32,
+) {
+    if let Some(prev) = record.current.take() {
+        record.history.push(prev);
+    }
+    record.current = Some(new_state);
+    prune_catalog_history(&mut record.history, peak);
+}
+
+/// Restore the registry state confirmed before a pre-final reorganization at `from_height`.
+pub fn rollback_catalog_to_before(record: &mut CatalogRegistryRecord, from_height: u32) {
+    if let Some(cur) = &record.current {
+        if cur.confirmation_height >= from_height {
+            record.current = None;
+        }
+    }
+    let mut restored = None;
+    let mut kept = Vec::new();
+    for state in record.history.drain(..) {
+        if state.confirmation_height < from_height {
+            restored = Some(state.clone());
+            kept.push(state);
+        }
+    }
+    record.history = kept;
+    if record.current.is_none() {
+        record.current = restored;
+    }
+}
+
+/// SQLite-backed store used by the production `catalog listen` process.
+pub struct DbCatalogRegistryStore {
+    db: Arc<futures::lock::Mutex<crate::Db>>,
+}
+
+impl DbCatalogRegistryStore {
+    pub fn new(db: Arc<futures::lock::Mutex<crate::Db>>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl CatalogRegistryStore for DbCatalogRegistryStore {
+    async fn get(&self, launcher_id: Bytes32) -> Option<CatalogRegistryRecord> {
+        let db = self.db.lock().await;
+        let json = db
+            .get_catalog_registry_record_json(launcher_id)
+            .await
+            .ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    async fn upsert(&self, record: CatalogRegistryRecord) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
+        let db = self.db.lock().await;
+        db.upsert_catalog_registry_record_json(record.launcher_id, &json)
+            .await
+    }
+
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_catalog_registry_record(launcher_id).await
+    }
+
+    async fn all_launcher_ids(&self) -> Vec<Bytes32> {
+        let db = self.db.lock().await;
+        db.all_catalog_registry_ids().await.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(tag: u8, height: u32) -> StoredCatalogState {
+        StoredCatalogState {
+            coin_id: Bytes32::new([tag; 32]),
+            parent_coin_id: Bytes32::default(),
+            cat_maker_puzzle_hash: Bytes32::new([0xcc; 32]),
+            registration_price: 100,
+            confirmation_height: height,
+        }
+    }
+
+    #[test]
+    fn rollback_restores_last_state_before_fork() {
+        let mut record = CatalogRegistryRecord {
+            launcher_id: Bytes32::default(),
+            current: None,
+            history: Vec::new(),
+        };
+        push_catalog_replacement(&mut record, state(1, 100), 100);
+        push_catalog_replacement(&mut record, state(2, 110), 110);
+        push_catalog_replacement(&mut record, state(3, 120), 120);
+
+        rollback_catalog_to_before(&mut record, 115);
+        assert_eq!(record.current, Some(state(2, 110)));
+        assert_eq!(record.history.len(), 2);
+
+        rollback_catalog_to_before(&mut record, 90);
+        assert_eq!(record.current, None);
+        assert!(record.history.is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use super::cat_store::StoredCatMetadata;
+use crate::hex32;
+
+/// Public CAT NFT metadata; hashes are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatMetadataJson {
+    pub ticker: String,
+    pub name: String,
+    pub description: String,
+    pub precision: u8,
+    pub hidden_puzzle_hash: Option<String>,
+    pub image_uris: Vec<String>,
+    pub image_hash: String,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: Option<String>,
+    pub license_uris: Vec<String>,
+    pub license_hash: Option<String>,
+}
+
+impl From<&StoredCatMetadata> for CatMetadataJson {
+    fn from(metadata: &StoredCatMetadata) -> Self {
+        Self {
+            ticker: metadata.ticker.clone(),
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            precision: metadata.precision,
+            hidden_puzzle_hash: metadata.hidden_puzzle_hash.map(hex32),
+            image_uris: metadata.image_uris.clone(),
+            image_hash: hex32(metadata.image_hash),
+            metadata_uris: metadata.metadata_uris.clone(),
+            metadata_hash: metadata.metadata_hash.map(hex32),
+            license_uris: metadata.license_uris.clone(),
+            license_hash: metadata.license_hash.map(hex32),
+        }
+    }
+}
+
+/// `GET /cat/{asset_id}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatResponse {
+    pub registry_launcher_id: String,
+    pub asset_id: String,
+    pub metadata: Option<CatMetadataJson>,
+    pub confirmation_height: u32,
+    pub indexed_peak_height: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecentCatRegistrationsQuery {
+    /// Newest-first page size; capped at 50. Omitted defaults to 50.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentCatRegistrationItem {
+    pub asset_id: String,
+    pub ticker: Option<String>,
+    pub confirmation_height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentCatRegistrationsResponse {
+    pub items: Vec<RecentCatRegistrationItem>,
+    pub total_registered: u64,
+    pub indexed_peak_height: u32,
+}
+
+/// `GET /price`: the confirmed registry state a registration must pay against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogPriceResponse {
+    pub registry_launcher_id: String,
+    pub cat_maker_puzzle_hash: String,
+    pub registration_price: u64,
+    pub confirmation_height: u32,
+    pub indexed_peak_height: u32,
+}
@@ -3,22 +3,22 @@ use std::collections::{HashMap, HashSet};
 use chia_protocol::{Bytes32, SpendBundle};
 use chia_puzzle_types::{cat::CatArgs, singleton::SingletonStruct, CoinProof, LineageProof};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin, spend_settlement_cats, CatLayer,
-        CatalogRegisterAction, CatalogRegistryConstants, DriverError, Layer, Offer, PrecommitCoin,
-        PrecommitLayer, Puzzle, SingleCatSpend, Spend, SpendContext,
+        CatalogRegisterAction, DriverError, Layer, Offer, PrecommitCoin, PrecommitLayer, Puzzle,
+        SingleCatSpend, Spend, SpendContext,
     },
-    types::{Conditions, MAINNET_CONSTANTS, TESTNET11_CONSTANTS},
+    types::Conditions,
 };
 use clvm_traits::clvm_quote;
 use clvm_utils::{ToTreeHash, TreeHash};
 use clvmr::{serde::node_from_bytes, NodePtr};
 
 use crate::{
-    assets_xch_and_cat, assets_xch_only, confirm_pushed_transaction, hex_string_to_bytes32,
-    load_catalog_premine_csv, no_assets, parse_amount, sync_catalog, yes_no_prompt,
-    CatalogPremineRecord, CliError, Db, SageClient,
+    assets_xch_and_cat, assets_xch_only, confirm_pushed_transaction, get_chain_client,
+    get_constants, hex_string_to_bytes32, load_catalog_premine_csv, no_assets, parse_amount,
+    sync_catalog, yes_no_prompt, CatalogPremineRecord, CliError, Db, Network, SageClient,
 };
 use chia_wallet_sdk::driver::CatalogPrecommitValue;
 use chia_wallet_sdk::types::puzzles::CatNftMetadata;
@@ -70,12 +70,12 @@ fn precommit_value_for_cat(
 pub async fn catalog_continue_launch(
     payment_asset_id_str: String,
     cats_per_spend: usize,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     println!("Time to unroll a CATalog! Yee-haw!");
 
-    let premine_csv_filename = if testnet11 {
+    let premine_csv_filename = if network.is_testnet() {
         "catalog_premine_testnet11.csv"
     } else {
         "catalog_premine_mainnet.csv"
@@ -85,16 +85,12 @@ pub async fn catalog_continue_launch(
     let cats_to_launch = load_catalog_premine_csv(premine_csv_filename)?;
 
     println!("Initializing Chia RPC client...");
-    let client = if testnet11 {
-        CoinsetClient::testnet11()
-    } else {
-        CoinsetClient::mainnet()
-    };
+    let client = get_chain_client(network)?;
 
     println!("Opening database...");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
 
-    let constants = CatalogRegistryConstants::get(testnet11);
+    let constants = network.catalog_constants();
     if constants.price_singleton_launcher_id == Bytes32::default()
         || constants.launcher_id == Bytes32::default()
     {
@@ -257,11 +253,7 @@ pub async fn catalog_continue_launch(
                 security_coin,
                 security_coin_conditions,
                 &security_coin_sk,
-                if testnet11 {
-                    &TESTNET11_CONSTANTS
-                } else {
-                    &MAINNET_CONSTANTS
-                },
+                get_constants(network),
             )?;
 
             // Spend CAT
@@ -530,11 +522,7 @@ pub async fn catalog_continue_launch(
         security_coin,
         security_coin_conditions,
         &security_coin_sk,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(
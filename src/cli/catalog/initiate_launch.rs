@@ -5,8 +5,9 @@ use crate::{
         utils::{yes_no_prompt, CliError},
         Db,
     },
-    confirm_pushed_transaction, get_coinset_client, get_prefix, load_catalog_state_schedule_csv,
-    no_assets, parse_amount, print_medieval_vault_configuration, SageClient,
+    confirm_pushed_transaction, get_chain_client, get_constants, get_prefix,
+    load_catalog_state_schedule_csv, no_assets, parse_amount, print_medieval_vault_configuration,
+    Network, SageClient,
 };
 use chia_bls::PublicKey;
 use chia_protocol::{Bytes32, Coin, SpendBundle};
@@ -18,10 +19,7 @@ use chia_wallet_sdk::{
         DriverError, Launcher, MedievalVaultHint, MedievalVaultInfo, Offer, SingletonInfo,
         SpendContext, StateSchedulerInfo,
     },
-    types::{
-        conditions::RunCatTail, puzzles::DefaultCatMakerArgs, Conditions, Mod, MAINNET_CONSTANTS,
-        TESTNET11_CONSTANTS,
-    },
+    types::{conditions::RunCatTail, puzzles::DefaultCatMakerArgs, Conditions, Mod},
     utils::Address,
 };
 use clvm_utils::ToTreeHash;
@@ -110,7 +108,7 @@ fn get_additional_info_for_launch(
 pub async fn catalog_initiate_launch(
     pubkeys_str: String,
     m: usize,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     println!("Welcome to the CATalog launch setup, deployer.");
@@ -131,9 +129,9 @@ pub async fn catalog_initiate_launch(
 
     println!("First things first, this multisig will have control over the price singleton once the state schedule is over:");
     print_medieval_vault_configuration(m, &pubkeys)?;
-    println!("  Testnet: {}", testnet11);
+    println!("  Network: {}", network.name);
 
-    let price_schedule_csv_filename = if testnet11 {
+    let price_schedule_csv_filename = if network.is_testnet() {
         "catalog_price_schedule_testnet11.csv"
     } else {
         "catalog_price_schedule_mainnet.csv"
@@ -152,7 +150,7 @@ pub async fn catalog_initiate_launch(
         );
     }
 
-    let premine_csv_filename = if testnet11 {
+    let premine_csv_filename = if network.is_testnet() {
         "catalog_premine_testnet11.csv"
     } else {
         "catalog_premine_mainnet.csv"
@@ -171,12 +169,12 @@ pub async fn catalog_initiate_launch(
     yes_no_prompt("Is all the data above correct?")?;
 
     println!("Initializing Chia RPC client...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
 
     println!("Opening database...");
-    let db = Db::new(false).await?;
+    let db = Db::new(network, false).await?;
 
-    let constants = CatalogRegistryConstants::get(testnet11);
+    let constants = network.catalog_constants();
     let singleton_coin_maybe = db
         .get_last_unspent_singleton_coin(constants.launcher_id)
         .await?;
@@ -189,7 +187,7 @@ pub async fn catalog_initiate_launch(
         db.delete_all_singleton_coins(constants.launcher_id).await?;
     }
 
-    let prefix = get_prefix(testnet11);
+    let prefix = get_prefix(network);
     let royalty_address = Address::new(constants.royalty_address, prefix.clone()).encode()?;
     let precommit_payout_address =
         Address::new(constants.precommit_payout_puzzle_hash, prefix).encode()?;
@@ -247,13 +245,9 @@ pub async fn catalog_initiate_launch(
         &offer,
         1,
         get_additional_info_for_launch,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
         (
-            CatalogRegistryConstants::get(testnet11),
+            network.catalog_constants(),
             price_schedule
                 .into_iter()
                 .map(|ps| {
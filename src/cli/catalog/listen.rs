@@ -1,25 +1,40 @@
-use std::net::SocketAddr;
+use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use axum::debug_handler;
 use axum::extract::{Query, State};
-use axum::http::{HeaderValue, Method};
-use axum::response::{IntoResponse, Response};
+use axum::http::Method;
+use axum::middleware;
 use axum::{http::StatusCode, routing::get, Json, Router};
+use chia_protocol::{Bytes32, CoinSpend};
+use chia_puzzles::SINGLETON_LAUNCHER_HASH;
 use chia_wallet_sdk::coinset::ChiaRpcClient;
-use chia_wallet_sdk::driver::{CatalogRegistryConstants, SpendContext};
-use chia_wallet_sdk::types::puzzles::CatalogSlotValue;
+use chia_wallet_sdk::driver::{
+    CatalogRegistry, CatalogRegistryConstants, NftInfo, Puzzle, SpendContext,
+};
+use chia_wallet_sdk::types::puzzles::{CatNftMetadata, CatalogSlotValue};
+use clvm_traits::FromClvm;
+use clvmr::serde::node_from_bytes;
 use clvmr::Allocator;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
-use tower_http::cors::CorsLayer;
+use tokio::sync::RwLock;
+use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
-    get_coinset_client, hex_string_to_bytes32, sync_catalog, CliError, CoinsetWebSocketMessage, Db,
+    block_spends_at_height, catalog_router, checkpoint_fork_point, finish_transition,
+    get_chain_client, listener_bind_addr, neighbors_lookup_error, parse_launcher_id,
+    record_http_metrics, reorg_rollback_from, require_catalog_fresh, sync_catalog_detailed,
+    ApiError, CatStore, CatalogApiState, CatalogIndexer, CatalogRegistryStore,
+    CatalogSpentTransition, ChainClient, ChainSource, CliError, Db, DbCatStore,
+    DbCatalogRegistryStore, DbTransitionScope, FreshnessState, ListenerMetrics, Network,
+    RegistryTip, StoredCatMetadata, StoredCatalogState, TransitionScope, HEADER_CHECKPOINT_WINDOW,
 };
 
+/// `header_checkpoints.listener` key for this listener's window.
+pub const CATALOG_CHECKPOINT_LISTENER: &str = "catalog";
+
 #[derive(Debug, Deserialize)]
 struct CatalogNeighborsQuery {
     asset_id: String,
@@ -43,40 +58,87 @@ pub struct CatalogNeighborResponse {
     pub right_parent_parent_info: String,
     pub right_parent_inner_puzzle_hash: String,
     pub right_parent_amount: u64,
+
+    /// Peak the proofs were read at; absent from listeners that predate it.
+    #[serde(default)]
+    pub indexed_peak_height: Option<u32>,
 }
 
 #[derive(Clone)]
 struct AppState {
     db: Arc<futures::lock::Mutex<Db>>,
-    testnet11: bool,
+    /// Freshness gate shared with the public CATalog routes.
+    api: CatalogApiState,
 }
 
-pub async fn catalog_listen(testnet11: bool) -> Result<(), CliError> {
-    let db = Db::new(true).await?;
-    let db = Arc::new(futures::lock::Mutex::new(db));
+/// Port `catalog listen` serves on when `BIND_ADDR` is unset.
+pub const CATALOG_LISTEN_DEFAULT_PORT: u16 = 3000;
 
-    let state = AppState {
-        db: Arc::clone(&db),
-        testnet11,
+pub async fn catalog_listen(network: &Network) -> Result<(), CliError> {
+    let db = Db::new(network, false).await?;
+    // API reads get their own handle so they never see or wait on an open transition.
+    let api_db = Arc::new(futures::lock::Mutex::new(db.reader()));
+    let db = Arc::new(futures::lock::Mutex::new(db));
+    let launcher_id = network.catalog_constants().launcher_id;
+
+    let registry_store: Arc<dyn CatalogRegistryStore> =
+        DbCatalogRegistryStore::new(Arc::clone(&db));
+    let cats: Arc<dyn CatStore> = DbCatStore::new(Arc::clone(&db));
+    let freshness = Arc::new(RwLock::new(FreshnessState::fresh_at(
+        0,
+        FreshnessState::now_unix(),
+    )));
+    let metrics = ListenerMetrics::shared("catalog");
+    let indexer = Arc::new(
+        CatalogIndexer::new(
+            Arc::clone(&registry_store),
+            Arc::clone(&cats),
+            Arc::clone(&freshness),
+        )
+        .with_metrics(Arc::clone(&metrics)),
+    );
+    let registry_tip = Arc::new(RwLock::new(None));
+
+    let api_state = CatalogApiState {
+        registry: DbCatalogRegistryStore::new(Arc::clone(&api_db)),
+        cats: DbCatStore::new(Arc::clone(&api_db)),
+        freshness: Arc::clone(&freshness),
+        registry_tip: Arc::clone(&registry_tip),
+        registry_launcher_id: launcher_id,
+        now_unix_override: None,
+        metrics: Arc::clone(&metrics),
+    };
+    let neighbors_state = AppState {
+        db: api_db,
+        api: api_state.clone(),
     };
 
+    // Not fresh until the first peak after the initial sync.
+    indexer.begin_resync().await;
+
     rustls::crypto::ring::default_provider()
         .install_default()
         .expect("Failed to install rustls crypto provider");
 
-    // API
-    let api_state = state.clone();
     tokio::spawn(async move {
-        if let Err(e) = start_api_server(api_state).await {
+        if let Err(e) = start_api_server(api_state, neighbors_state).await {
             eprintln!("API server error: {}", e);
         }
     });
 
-    // Updates
     loop {
-        match connect_websocket(testnet11, Arc::clone(&db)).await {
+        match connect_websocket(
+            network,
+            Arc::clone(&db),
+            Arc::clone(&indexer),
+            Arc::clone(&registry_tip),
+        )
+        .await
+        {
             Ok(_resp) => (),
             Err(e) => {
+                indexer.begin_resync().await;
+                metrics.websocket_reconnects.inc();
                 println!("WebSocket error: {}", e);
                 println!("Reconnecting in 5 seconds...");
                 tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
@@ -85,22 +147,32 @@ pub async fn catalog_listen(testnet11: bool) -> Result<(), CliError> {
     }
 }
 
-async fn start_api_server(state: AppState) -> Result<(), CliError> {
-    // API routes
-    let app = Router::new()
+async fn start_api_server(
+    listener_state: CatalogApiState,
+    neighbors_state: AppState,
+) -> Result<(), CliError> {
+    let neighbors = Router::new()
         .route("/", get(health_check))
         .route("/neighbors", get(get_neighbors))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&listener_state.metrics),
+            record_http_metrics,
+        ))
         .layer(
             CorsLayer::new()
-                .allow_origin("*".parse::<HeaderValue>().unwrap())
-                .allow_methods([Method::GET, Method::OPTIONS, Method::POST]),
+                .allow_origin(Any)
+                .allow_methods([Method::GET, Method::HEAD, Method::OPTIONS])
+                .allow_headers(Any),
         )
-        .with_state(state);
+        .with_state(neighbors_state);
+
+    // `catalog_router` carries its own CORS layer.
+    let app = catalog_router(listener_state).merge(neighbors);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
+    let addr = listener_bind_addr(CATALOG_LISTEN_DEFAULT_PORT);
     println!("API server listening on {}", addr);
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(listener, app).await.unwrap();
 
     Ok(())
@@ -110,22 +182,13 @@ async fn health_check() -> StatusCode {
     StatusCode::OK
 }
 
-impl IntoResponse for CliError {
-    fn into_response(self) -> Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Error: {}", self),
-        )
-            .into_response()
-    }
-}
-
 #[debug_handler]
 async fn get_neighbors(
     Query(params): Query<CatalogNeighborsQuery>,
     State(state): State<AppState>,
-) -> Result<Json<CatalogNeighborResponse>, CliError> {
-    let asset_id = hex_string_to_bytes32(&params.asset_id)?;
+) -> Result<Json<CatalogNeighborResponse>, ApiError> {
+    let indexed_peak_height = require_catalog_fresh(&state.api).await?;
+    let asset_id = parse_launcher_id(&params.asset_id).ok_or_else(ApiError::invalid_asset_id)?;
 
     let mut allocator = Allocator::new();
 
@@ -134,11 +197,12 @@ async fn get_neighbors(
 
         db.get_catalog_neighbors::<CatalogSlotValue>(
             &mut allocator,
-            CatalogRegistryConstants::get(state.testnet11).launcher_id,
+            state.api.registry_launcher_id,
             asset_id,
         )
         .await
-    }?;
+    }
+    .map_err(|e| neighbors_lookup_error(e, ApiError::cat_not_found))?;
 
     let response = CatalogNeighborResponse {
         asset_id: params.asset_id.clone(),
@@ -160,105 +224,392 @@ async fn get_neighbors(
             right.proof.parent_inner_puzzle_hash.to_bytes(),
         ),
         right_parent_amount: right.proof.parent_amount,
+
+        indexed_peak_height: Some(indexed_peak_height),
     };
 
     Ok(Json(response))
 }
 
+/// Initial metadata of every CAT NFT minted by `registry_coin_id`: the eve NFT spends whose
+/// launcher the registry coin created in the same block.
+fn minted_cat_metadata(
+    allocator: &mut Allocator,
+    block_spends: &[CoinSpend],
+    registry_coin_id: Bytes32,
+) -> Vec<StoredCatMetadata> {
+    let launcher_ids = block_spends
+        .iter()
+        .filter(|s| {
+            s.coin.parent_coin_info == registry_coin_id
+                && s.coin.puzzle_hash == SINGLETON_LAUNCHER_HASH.into()
+        })
+        .map(|s| s.coin.coin_id())
+        .collect::<Vec<_>>();
+
+    let mut minted = Vec::new();
+    for spend in block_spends
+        .iter()
+        .filter(|s| launcher_ids.contains(&s.coin.parent_coin_info))
+    {
+        let Ok(puzzle_ptr) = node_from_bytes(allocator, &spend.puzzle_reveal) else {
+            continue;
+        };
+        let puzzle = Puzzle::parse(allocator, puzzle_ptr);
+        let Ok(Some((info, _))) = NftInfo::parse(allocator, puzzle) else {
+            continue;
+        };
+        if let Ok(metadata) = CatNftMetadata::from_clvm(allocator, info.metadata.ptr()) {
+            minted.push(metadata.into());
+        }
+    }
+    minted
+}
+
+/// Registry state and registrations for one spent transition, ready for the indexer.
+async fn indexed_transition(
+    client: &ChainClient,
+    metrics: &ListenerMetrics,
+    transition: &CatalogSpentTransition,
+) -> Result<
+    (
+        StoredCatalogState,
+        Vec<(Bytes32, Option<StoredCatMetadata>)>,
+    ),
+    CliError,
+> {
+    let registry = &transition.registry;
+    let state = StoredCatalogState {
+        coin_id: registry.coin.coin_id(),
+        parent_coin_id: registry.coin.parent_coin_info,
+        cat_maker_puzzle_hash: registry.info.state.cat_maker_puzzle_hash,
+        registration_price: registry.info.state.registration_price,
+        confirmation_height: transition.height,
+    };
+    if transition.registered_asset_ids.is_empty() {
+        return Ok((state, Vec::new()));
+    }
+
+    // Metadata is only attributed when one CAT is registered per spend; with several, the
+    // minted NFTs cannot be matched to asset IDs from the block alone.
+    let block_spends = block_spends_at_height(client, metrics, transition.height).await?;
+    let mut allocator = Allocator::new();
+    let mut minted = minted_cat_metadata(&mut allocator, &block_spends, transition.spent_coin_id);
+    let single = transition.registered_asset_ids.len() == 1 && minted.len() == 1;
+    let registered = transition
+        .registered_asset_ids
+        .iter()
+        .map(|asset_id| (*asset_id, if single { minted.pop() } else { None }))
+        .collect();
+    Ok((state, registered))
+}
+
+/// Walk the registry to its tip and index every spent transition at or above `from_height`.
+async fn sync_and_index_catalog(
+    client: &ChainClient,
+    db: &Arc<futures::lock::Mutex<Db>>,
+    indexer: &CatalogIndexer,
+    constants: CatalogRegistryConstants,
+    from_height: u32,
+) -> Result<CatalogRegistry, CliError> {
+    let synced = {
+        let mut ctx = SpendContext::new();
+        let mut db = db.lock().await;
+        sync_catalog_detailed(client, &mut db, &mut ctx, constants).await?
+    };
+    for transition in &synced.spent_transitions {
+        if transition.height < from_height {
+            continue;
+        }
+        let (state, registered) =
+            indexed_transition(client, indexer.metrics.as_ref(), transition).await?;
+        indexer
+            .on_registry_transition(constants.launcher_id, state, &registered)
+            .await?;
+    }
+    Ok(synced.registry)
+}
+
+/// Pre-final reorganization: drop projections from `from_height`, rewind the saved registry
+/// tip, then replay every transition from the fork point in one commit.
+async fn resync_after_reorg(
+    client: &ChainClient,
+    db: &Arc<futures::lock::Mutex<Db>>,
+    scope: &dyn TransitionScope,
+    indexer: &CatalogIndexer,
+    constants: CatalogRegistryConstants,
+    from_height: u32,
+) -> Result<CatalogRegistry, CliError> {
+    scope.begin().await?;
+    let result = async {
+        indexer.rollback(from_height).await?;
+        db.lock()
+            .await
+            .rewind_singleton_to_before(constants.launcher_id, from_height)
+            .await?;
+        sync_and_index_catalog(client, db, indexer, constants, from_height).await
+    }
+    .await;
+    finish_transition(scope, result).await
+}
+
+async fn publish_registry_tip(
+    registry_tip: &RwLock<Option<RegistryTip>>,
+    registry: &CatalogRegistry,
+    indexed_height: u32,
+) {
+    *registry_tip.write().await = Some(RegistryTip {
+        coin_id: registry.coin.coin_id(),
+        indexed_height,
+    });
+}
+
 async fn connect_websocket(
-    testnet11: bool,
+    network: &Network,
     db: Arc<futures::lock::Mutex<Db>>,
+    indexer: Arc<CatalogIndexer>,
+    registry_tip: Arc<RwLock<Option<RegistryTip>>>,
 ) -> Result<(), CliError> {
     println!("Syncing CATalog (initial)...");
-    let client = get_coinset_client(testnet11);
-    let constants = CatalogRegistryConstants::get(testnet11);
-
-    let mut catalog = {
-        let mut db = db.lock().await;
-        let mut ctx = SpendContext::new();
+    let client = get_chain_client(network)?;
+    let constants = network.catalog_constants();
+    let metrics = indexer.metrics.as_ref();
+    let scope = DbTransitionScope { db: &db, metrics };
+
+    // Checkpoints from the previous run are checked first so a reorg while we were
+    // down rolls the projections back instead of resuming a stale tip.
+    let upstream_peak = metrics
+        .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+        .await?
+        .blockchain_state
+        .map(|s| s.peak.height)
+        .ok_or_else(|| CliError::Custom("no blockchain state at startup".to_string()))?;
+    indexer.note_upstream_peak(upstream_peak).await;
+    let mut checkpoints = db
+        .lock()
+        .await
+        .header_checkpoints(CATALOG_CHECKPOINT_LISTENER)
+        .await?;
+    let mut upstream_hashes = std::collections::HashMap::new();
+    for &(height, _) in checkpoints.iter().filter(|(h, _)| *h <= upstream_peak) {
+        if let Some(rec) = metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                client.get_block_record_by_height(height),
+            )
+            .await?
+            .block_record
+        {
+            upstream_hashes.insert(height, rec.header_hash);
+        }
+    }
+    let fork = checkpoint_fork_point(&checkpoints, upstream_peak, |h| {
+        upstream_hashes.get(&h).copied()
+    });
 
-        sync_catalog(&client, &mut db, &mut ctx, constants).await?
+    let mut catalog = if let Some(from_height) = fork {
+        eprintln!(
+            "[catalog-listen] chain reorg while offline: rolling back from height {from_height}"
+        );
+        let catalog = resync_after_reorg(
+            &client,
+            &db,
+            &scope,
+            indexer.as_ref(),
+            constants,
+            from_height,
+        )
+        .await?;
+        checkpoints.retain(|(h, _)| *h < from_height);
+        db.lock()
+            .await
+            .delete_header_checkpoints_from(CATALOG_CHECKPOINT_LISTENER, from_height)
+            .await?;
+        catalog
+    } else {
+        scope.begin().await?;
+        let result = sync_and_index_catalog(&client, &db, indexer.as_ref(), constants, 0).await;
+        finish_transition(&scope, result).await?
     };
 
-    let ws_url = format!("{}/ws", client.base_url().replace("https://", "wss://"));
-    println!("Connecting to WebSocket at {}", ws_url);
-
-    let (ws_stream, _) = connect_async(ws_url)
+    // A registry that has never been spent since launch has no transition to replay;
+    // seed its state so `/price` can answer.
+    if indexer
+        .registry
+        .get(constants.launcher_id)
         .await
-        .map_err(|e| CliError::Custom(format!("Failed to connect: {}", e)))?;
-
-    println!("WebSocket connected");
+        .and_then(|rec| rec.current)
+        .is_none()
+    {
+        let confirmation_height = metrics
+            .observe_rpc(
+                "get_coin_record_by_name",
+                client.get_coin_record_by_name(catalog.coin.coin_id()),
+            )
+            .await?
+            .coin_record
+            .map(|r| r.confirmed_block_index)
+            .unwrap_or(0);
+        scope.begin().await?;
+        let result = indexer
+            .on_registry_transition(
+                constants.launcher_id,
+                StoredCatalogState {
+                    coin_id: catalog.coin.coin_id(),
+                    parent_coin_id: catalog.coin.parent_coin_info,
+                    cat_maker_puzzle_hash: catalog.info.state.cat_maker_puzzle_hash,
+                    registration_price: catalog.info.state.registration_price,
+                    confirmation_height,
+                },
+                &[],
+            )
+            .await;
+        finish_transition(&scope, result).await?;
+    }
+    publish_registry_tip(&registry_tip, &catalog, upstream_peak).await;
+
+    let mut recent_peaks: VecDeque<(u32, Bytes32)> = checkpoints
+        .into_iter()
+        .filter(|(h, _)| *h < upstream_peak)
+        .collect();
+    if let Some(rec) = metrics
+        .observe_rpc(
+            "get_block_record_by_height",
+            client.get_block_record_by_height(upstream_peak),
+        )
+        .await?
+        .block_record
+    {
+        recent_peaks.push_back((upstream_peak, rec.header_hash));
+    }
+    while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+        recent_peaks.pop_front();
+    }
+    db.lock()
+        .await
+        .save_header_checkpoints(CATALOG_CHECKPOINT_LISTENER, recent_peaks.make_contiguous())
+        .await?;
 
-    let (mut _write, mut read) = ws_stream.split();
+    let mut peaks = client.peak_notifications().await?;
     let mut last_clear_time = SystemTime::now();
 
-    while let Some(message) = read.next().await {
-        match message {
-            Ok(Message::Text(text)) => match serde_json::from_str::<CoinsetWebSocketMessage>(&text)
-            {
-                Ok(msg) => {
-                    if msg.message_type() == "peak" {
-                        let now = SystemTime::now();
-                        println!(
-                            "[{}] Received new peak",
-                            now.duration_since(UNIX_EPOCH).unwrap().as_secs()
-                        );
-
-                        let coin_resp = client
-                            .get_coin_record_by_name(catalog.coin.coin_id())
-                            .await?;
-
-                        if let Some(coin_record) = coin_resp.coin_record {
-                            if coin_record.spent {
-                                print!(
-                                    "Latest CATalog coin was spent at height {}... ",
-                                    coin_record.spent_block_index
-                                );
-
-                                catalog = {
-                                    let mut ctx = SpendContext::new();
-                                    let mut db = db.lock().await;
-                                    sync_catalog(&client, &mut db, &mut ctx, constants).await?
-                                };
-                                println!("synced :)")
-                            }
-                        } else {
-                            return Err(CliError::Custom(
-                                "Weird - coin record not found after peak update.".to_string(),
-                            ));
-                        }
-
-                        if last_clear_time.elapsed().unwrap().as_secs() > 60 * 30 {
-                            // 30 minutes in seconds
-                            if let Some(current_blockchain_state) =
-                                client.get_blockchain_state().await?.blockchain_state
-                            {
-                                print!("Clearing cache (every 30m)... ");
-                                let cutoff = current_blockchain_state.peak.height - 128;
-                                {
-                                    let db = db.lock().await;
-                                    db.delete_slots_spent_before(cutoff).await?;
-                                    db.delete_singleton_coins_spent_before(cutoff).await?;
-                                }
-                                println!("done :)");
-                                last_clear_time = now;
-                            }
-                        }
-                    }
+    while peaks.next().await.is_some() {
+        let now = SystemTime::now();
+        let now_unix = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        println!("[{}] Received new peak", now_unix);
+
+        let Some(tip) = metrics
+            .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+            .await?
+            .blockchain_state
+            .map(|s| s.peak.height)
+        else {
+            continue;
+        };
+        indexer.note_upstream_peak(tip).await;
+
+        let tip_rec = metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                client.get_block_record_by_height(tip),
+            )
+            .await?
+            .block_record;
+        if let Some(rec) = &tip_rec {
+            let needs_hash_at = match recent_peaks.back() {
+                Some(&(last_height, last_hash)) => {
+                    tip != last_height.saturating_add(1) || rec.prev_hash != last_hash
                 }
-                Err(e) => {
-                    println!("Failed to parse message: {}, text: {}", e, text);
+                None => false,
+            };
+            let mut hash_at_cache = std::collections::HashMap::new();
+            if needs_hash_at {
+                for &(h, _) in &recent_peaks {
+                    if let Some(br) = metrics
+                        .observe_rpc(
+                            "get_block_record_by_height",
+                            client.get_block_record_by_height(h),
+                        )
+                        .await?
+                        .block_record
+                    {
+                        hash_at_cache.insert(h, br.header_hash);
+                    }
                 }
-            },
-            Ok(Message::Close(_)) => {
-                println!("WebSocket closed by server");
-                break;
             }
-            Err(e) => {
-                println!("WebSocket error: {}", e);
-                break;
+            if let Some(from_height) =
+                reorg_rollback_from(recent_peaks.make_contiguous(), tip, rec.prev_hash, |h| {
+                    hash_at_cache.get(&h).copied()
+                })
+            {
+                eprintln!("chain reorg: rolling back from height {from_height}");
+                catalog = resync_after_reorg(
+                    &client,
+                    &db,
+                    &scope,
+                    indexer.as_ref(),
+                    constants,
+                    from_height,
+                )
+                .await?;
+                recent_peaks.retain(|(h, _)| *h < from_height);
+            }
+        }
+
+        let coin_record = metrics
+            .observe_rpc(
+                "get_coin_record_by_name",
+                client.get_coin_record_by_name(catalog.coin.coin_id()),
+            )
+            .await?
+            .coin_record
+            .ok_or(CliError::Custom(
+                "Weird - coin record not found after peak update.".to_string(),
+            ))?;
+        if coin_record.spent {
+            print!(
+                "Latest CATalog coin was spent at height {}... ",
+                coin_record.spent_block_index
+            );
+            scope.begin().await?;
+            let result = sync_and_index_catalog(&client, &db, indexer.as_ref(), constants, 0).await;
+            catalog = finish_transition(&scope, result).await?;
+            println!("synced :)")
+        }
+        metrics.blocks_processed.inc();
+
+        if let Some(rec) = &tip_rec {
+            recent_peaks.retain(|(h, _)| *h < tip);
+            recent_peaks.push_back((tip, rec.header_hash));
+            while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+                recent_peaks.pop_front();
+            }
+            let started = Instant::now();
+            db.lock()
+                .await
+                .save_header_checkpoints(
+                    CATALOG_CHECKPOINT_LISTENER,
+                    recent_peaks.make_contiguous(),
+                )
+                .await?;
+            metrics.observe_sqlite_write("header_checkpoints", started.elapsed());
+        }
+        publish_registry_tip(&registry_tip, &catalog, tip).await;
+        indexer.note_peak(tip, tip, now_unix).await;
+
+        if last_clear_time.elapsed().unwrap().as_secs() > 60 * 30 {
+            // 30 minutes in seconds
+            print!("Clearing cache (every 30m)... ");
+            let cutoff = tip.saturating_sub(128);
+            let started = Instant::now();
+            {
+                let db = db.lock().await;
+                db.delete_slots_spent_before(cutoff).await?;
+                db.delete_singleton_coins_spent_before(cutoff).await?;
             }
-            _ => {}
+            metrics.observe_sqlite_write("prune", started.elapsed());
+            println!("done :)");
+            last_clear_time = now;
         }
     }
 
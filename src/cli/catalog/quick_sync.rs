@@ -1,13 +1,13 @@
 use chia_protocol::CoinSpend;
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{CatalogRegistry, CatalogRegistryConstants, SpendContext},
 };
 
-use crate::CliError;
+use crate::{ChainClient, CliError};
 
 pub async fn quick_sync_catalog(
-    client: &CoinsetClient,
+    client: &ChainClient,
     ctx: &mut SpendContext,
     constants: CatalogRegistryConstants,
 ) -> Result<CatalogRegistry, CliError> {
@@ -4,8 +4,8 @@ use chia_wallet_sdk::{
     coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin, CatLayer, CatalogPrecommitValue,
-        CatalogRefundAction, CatalogRegisterAction, CatalogRegistryConstants, Layer, Offer,
-        PrecommitCoin, PrecommitLayer, Puzzle, Slot, Spend, SpendContext,
+        CatalogRefundAction, CatalogRegisterAction, Layer, Offer, PrecommitCoin, PrecommitLayer,
+        Puzzle, Slot, Spend, SpendContext,
     },
     types::{
         puzzles::{CatNftMetadata, CatalogSlotValue, DefaultCatMakerArgs},
@@ -17,10 +17,10 @@ use clvm_utils::ToTreeHash;
 use clvmr::{serde::node_from_bytes, NodePtr};
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_constants, get_prefix,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants, get_prefix,
     hex_string_to_bytes, hex_string_to_bytes32, no_assets, parse_amount,
     print_spend_bundle_to_file, quick_sync_catalog, sync_catalog, wait_for_coin, yes_no_prompt,
-    CatalogApiClient, CliError, Db, SageClient,
+    CatalogApiClient, CliError, Db, Network, SageClient,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -77,7 +77,7 @@ pub async fn catalog_register(
     license_hash_str: Option<String>,
     recipient_address: Option<String>,
     refund: bool,
-    testnet11: bool,
+    network: &Network,
     local: bool,
     log: bool,
     payment_asset_id_str: String,
@@ -93,8 +93,8 @@ pub async fn catalog_register(
     }
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
-    let catalog_constants = CatalogRegistryConstants::get(testnet11);
+    let cli = get_chain_client(network)?;
+    let catalog_constants = network.catalog_constants();
     let sage = SageClient::new()?;
 
     let fee = parse_amount(&fee_str, false)?;
@@ -116,7 +116,7 @@ pub async fn catalog_register(
 
     print!("First, let's sync CATalog... ");
     let mut catalog = if local {
-        let mut db = Db::new(false).await?;
+        let mut db = Db::new(network, false).await?;
         sync_catalog(&cli, &mut db, &mut ctx, catalog_constants).await?
     } else {
         quick_sync_catalog(&cli, &mut ctx, catalog_constants).await?
@@ -302,7 +302,7 @@ pub async fn catalog_register(
                 && payment_cat_amount == catalog.info.state.registration_price
             {
                 if local {
-                    let db = Db::new(true).await?;
+                    let db = Db::new(network, true).await?;
                     let Some(slot_value_hash) = db
                         .get_catalog_indexed_slot_value(registered_asset_id)
                         .await?
@@ -333,7 +333,7 @@ pub async fn catalog_register(
                     let registered_asset_id_minus_one =
                         Bytes32::from(registered_asset_id_minus_one);
 
-                    let catalog_api_client = CatalogApiClient::get(testnet11);
+                    let catalog_api_client = CatalogApiClient::get(network);
                     let (_left, right) = catalog_api_client
                         .get_neighbors(catalog_constants.launcher_id, registered_asset_id_minus_one)
                         .await?;
@@ -361,7 +361,7 @@ pub async fn catalog_register(
                 .reserve_fee(1)
         } else {
             let (left_slot, right_slot) = if local {
-                let db = Db::new(true).await?;
+                let db = Db::new(network, true).await?;
                 db.get_catalog_neighbors(
                     &mut ctx,
                     catalog_constants.launcher_id,
@@ -369,7 +369,7 @@ pub async fn catalog_register(
                 )
                 .await?
             } else {
-                let catalog_api_client = CatalogApiClient::get(testnet11);
+                let catalog_api_client = CatalogApiClient::get(network);
 
                 catalog_api_client
                     .get_neighbors(catalog_constants.launcher_id, registered_asset_id)
@@ -397,7 +397,7 @@ pub async fn catalog_register(
             security_coin,
             sec_conds,
             &security_coin_sk,
-            get_constants(testnet11),
+            get_constants(network),
         )?;
 
         let sb = offer.take(SpendBundle::new(
@@ -441,7 +441,7 @@ pub async fn catalog_register(
     yes_no_prompt("Continue with registration?")?;
 
     let precommit_coin_address =
-        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(testnet11)).encode()?;
+        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(network)).encode()?;
     let send_resp = sage
         .send_cat(
             hex::encode(payment_asset_id),
@@ -1,16 +1,13 @@
 use chia_protocol::Bytes;
 use chia_wallet_sdk::{
-    driver::{
-        CatalogRegistryConstants, CatalogRegistryState, MedievalVault,
-        XchandlesRegistryReceivedMessagePrefix,
-    },
+    driver::{CatalogRegistryState, MedievalVault, XchandlesRegistryReceivedMessagePrefix},
     types::{puzzles::DefaultCatMakerArgs, Mod},
 };
 use clvm_utils::ToTreeHash;
 
 use crate::{
     get_constants, hex_string_to_bytes32, multisig_sign_thing_finish, multisig_sign_thing_start,
-    parse_amount, quick_sync_catalog, CliError,
+    parse_amount, quick_sync_catalog, CliError, Network,
 };
 
 pub async fn catalog_sign_state_update(
@@ -18,7 +15,7 @@ pub async fn catalog_sign_state_update(
     new_payment_asset_amount_str: String,
     my_pubkey_str: String,
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     debug: bool,
 ) -> Result<(), CliError> {
     let new_payment_asset_id = hex_string_to_bytes32(&new_payment_asset_id_str)?;
@@ -32,10 +29,10 @@ pub async fn catalog_sign_state_update(
     };
 
     let (my_pubkey, mut ctx, client, medieval_vault) =
-        multisig_sign_thing_start(my_pubkey_str, launcher_id_str, testnet11).await?;
+        multisig_sign_thing_start(my_pubkey_str, launcher_id_str, network).await?;
 
     println!("\nSyncing CATalog... ");
-    let catalog_constants = CatalogRegistryConstants::get(testnet11);
+    let catalog_constants = network.catalog_constants();
     let catalog = quick_sync_catalog(&client, &mut ctx, catalog_constants).await?;
     println!("Done!");
 
@@ -68,7 +65,7 @@ pub async fn catalog_sign_state_update(
         catalog_constants.launcher_id,
         medieval_vault.coin,
         &medieval_vault.info,
-        get_constants(testnet11).genesis_challenge,
+        get_constants(network).genesis_challenge,
     )
     .map_err(CliError::Driver)?;
 
@@ -77,7 +74,7 @@ pub async fn catalog_sign_state_update(
         delegated_puzzle,
         &medieval_vault,
         my_pubkey,
-        testnet11,
+        network,
         debug,
     )
     .await
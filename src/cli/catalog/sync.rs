@@ -4,7 +4,7 @@ use chia_protocol::{Bytes32, Coin};
 use chia_puzzle_types::Memos;
 use chia_puzzle_types::{singleton::LauncherSolution, LineageProof, Proof};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         CatalogRegistry, CatalogRegistryConstants, CatalogRegistryInfo, CatalogRegistryState,
         DriverError, Layer, Puzzle, SingletonLayer, Slot, SpendContext,
@@ -17,14 +17,45 @@ use chia_wallet_sdk::{
 use clvm_utils::{tree_hash, ToTreeHash};
 use clvmr::NodePtr;
 
-use crate::{CliError, Db};
+use crate::{ChainClient, CliError, Db};
+
+/// One spent CATalog coin encountered while walking to the unspent tip.
+#[derive(Debug, Clone)]
+pub struct CatalogSpentTransition {
+    pub height: u32,
+    /// Registry coin spent at `height`.
+    pub spent_coin_id: Bytes32,
+    /// Registry after the spend (its coin is the one created at `height`).
+    pub registry: CatalogRegistry,
+    /// Asset IDs whose slots first appear in this spend, in creation order.
+    pub registered_asset_ids: Vec<Bytes32>,
+}
+
+/// Result of a full CATalog singleton walk, including each spend for indexer replay.
+#[derive(Debug, Clone)]
+pub struct CatalogSyncResult {
+    pub registry: CatalogRegistry,
+    pub spent_transitions: Vec<CatalogSpentTransition>,
+}
 
 pub async fn sync_catalog(
-    client: &CoinsetClient,
+    client: &ChainClient,
     db: &mut Db,
     ctx: &mut SpendContext,
     constants: CatalogRegistryConstants,
 ) -> Result<CatalogRegistry, CliError> {
+    Ok(sync_catalog_detailed(client, db, ctx, constants)
+        .await?
+        .registry)
+}
+
+pub async fn sync_catalog_detailed(
+    client: &ChainClient,
+    db: &mut Db,
+    ctx: &mut SpendContext,
+    constants: CatalogRegistryConstants,
+) -> Result<CatalogSyncResult, CliError> {
+    let mut spent_transitions = Vec::new();
     let (mut catalog, mut skip_save): (CatalogRegistry, bool) =
         if let Some((_coin_id, parent_coin_id)) = db
             .get_last_unspent_singleton_coin(constants.launcher_id)
@@ -271,7 +302,28 @@ pub async fn sync_catalog(
                 .await?;
         }
 
+        let spent_asset_ids = catalog
+            .pending_spend
+            .spent_slots
+            .iter()
+            .map(|sv| sv.asset_id)
+            .collect::<HashSet<Bytes32>>();
+        let mut registered_asset_ids = Vec::new();
+        for slot_value in catalog.pending_spend.created_slots.iter() {
+            if !spent_asset_ids.contains(&slot_value.asset_id)
+                && !registered_asset_ids.contains(&slot_value.asset_id)
+            {
+                registered_asset_ids.push(slot_value.asset_id);
+            }
+        }
+
         catalog = catalog.child(catalog.pending_spend.latest_state.1);
+        spent_transitions.push(CatalogSpentTransition {
+            height: coin_record.spent_block_index,
+            spent_coin_id: coin_record.coin.coin_id(),
+            registry: catalog.clone(),
+            registered_asset_ids,
+        });
     }
 
     if let Some(mempool_items) = client
@@ -285,10 +337,16 @@ pub async fn sync_catalog(
                 mempool_items[0].spend_bundle.clone(),
                 constants,
             )? {
-                return Ok(new_catalog);
+                return Ok(CatalogSyncResult {
+                    registry: new_catalog,
+                    spent_transitions,
+                });
             }
         }
     }
 
-    Ok(catalog)
+    Ok(CatalogSyncResult {
+        registry: catalog,
+        spent_transitions,
+    })
 }
@@ -2,16 +2,16 @@ use chia_protocol::{Bytes32, SpendBundle};
 use chia_wallet_sdk::{
     coinset::ChiaRpcClient,
     driver::{
-        create_security_coin, decode_offer, spend_security_coin, CatalogRegistryConstants,
-        CatalogRegistryState, DelegatedStateAction, Offer, SpendContext,
+        create_security_coin, decode_offer, spend_security_coin, CatalogRegistryState,
+        DelegatedStateAction, Offer, SpendContext,
     },
-    types::{Conditions, MAINNET_CONSTANTS, TESTNET11_CONSTANTS},
+    types::Conditions,
 };
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_last_onchain_timestamp,
-    no_assets, parse_amount, print_spend_bundle_to_file, sync_multisig_singleton, yes_no_prompt,
-    CliError, Db, MultisigSingleton, SageClient,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants,
+    get_last_onchain_timestamp, no_assets, parse_amount, print_spend_bundle_to_file,
+    sync_multisig_singleton, yes_no_prompt, CliError, Db, MultisigSingleton, Network, SageClient,
 };
 
 use super::sync_catalog;
@@ -25,10 +25,10 @@ pub fn scheduler_activation_reached(
 }
 
 pub async fn catalog_unroll_state_scheduler(
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
-    let constants = CatalogRegistryConstants::get(testnet11);
+    let constants = network.catalog_constants();
 
     if constants.price_singleton_launcher_id == Bytes32::default()
         || constants.launcher_id == Bytes32::default()
@@ -36,7 +36,7 @@ pub async fn catalog_unroll_state_scheduler(
         return Err(CliError::ConstantsNotSet);
     }
 
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let mut ctx = SpendContext::new();
 
     let (MultisigSingleton::StateScheduler(state_scheduler), _) =
@@ -53,7 +53,7 @@ pub async fn catalog_unroll_state_scheduler(
         ));
     };
 
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
 
     let mut catalog = sync_catalog(&cli, &mut db, &mut ctx, constants).await?;
 
@@ -117,11 +117,7 @@ pub async fn catalog_unroll_state_scheduler(
         security_coin,
         security_coin_conditions,
         &security_coin_sk,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
     )?;
 
     state_scheduler.spend(&mut ctx, catalog_inner_ph.into())?;
@@ -11,8 +11,8 @@ use chia_wallet_sdk::types::Mod;
 use chia_wallet_sdk::{
     coinset::ChiaRpcClient,
     driver::{
-        CatalogRegistry, CatalogRegistryConstants, CatalogRegistryInfo, CatalogRegistryState,
-        Layer, Puzzle, SingletonLayer, Slot, SpendContext, UniquenessPrelauncher,
+        CatalogRegistry, CatalogRegistryInfo, CatalogRegistryState, Layer, Puzzle, SingletonLayer,
+        Slot, SpendContext, UniquenessPrelauncher,
     },
     types::{
         puzzles::{CatalogSlotValue, DefaultCatMakerArgs, SlotInfo, ANY_METADATA_UPDATER_HASH},
@@ -24,9 +24,9 @@ use clvmr::serde::node_from_bytes;
 use clvmr::NodePtr;
 
 use crate::{
-    get_coinset_client, initial_cat_inner_puzzle_ptr, load_catalog_premine_csv,
+    get_chain_client, initial_cat_inner_puzzle_ptr, load_catalog_premine_csv,
     load_catalog_state_schedule_csv, print_medieval_vault_configuration,
-    CatalogStateScheduleRecord, CliError, MultisigSingleton,
+    CatalogStateScheduleRecord, CliError, MultisigSingleton, Network,
 };
 
 use crate::sync_multisig_singleton;
@@ -55,14 +55,17 @@ pub fn catalog_trusted_schedule_matches(
     true
 }
 
-pub async fn catalog_verify_deployment(testnet11: bool) -> Result<(), CliError> {
+pub async fn catalog_verify_deployment(network: &Network) -> Result<(), CliError> {
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
-    let catalog_constants = CatalogRegistryConstants::get(testnet11);
+    let cli = get_chain_client(network)?;
+    let catalog_constants = network.catalog_constants();
 
-    println!("Verifying CATalog deployment (testnet: {})...", testnet11);
+    println!(
+        "Verifying CATalog deployment (network: {})...",
+        network.name
+    );
 
-    let premine_csv_filename = if testnet11 {
+    let premine_csv_filename = if network.is_testnet() {
         "catalog_premine_testnet11.csv"
     } else {
         "catalog_premine_mainnet.csv"
@@ -336,7 +339,7 @@ pub async fn catalog_verify_deployment(testnet11: bool) -> Result<(), CliError>
         ));
     };
 
-    let price_schedule_csv_filename = if testnet11 {
+    let price_schedule_csv_filename = if network.is_testnet() {
         "catalog_price_schedule_testnet11.csv"
     } else {
         "catalog_price_schedule_mainnet.csv"
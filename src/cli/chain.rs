@@ -0,0 +1,714 @@
+//! Chain backends behind one interface.
+//!
+//! Commands talk to whichever [`ChainClient`] [`get_chain_client`] picks: Coinset by
+//! default, or a local full node over its mutual-TLS RPC. Listeners only need the
+//! narrower [`ChainSource`], which [`MemoryChainSource`] also implements so the
+//! follow and reorg paths can run offline.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chia_protocol::{Bytes32, Coin, CoinSpend, SpendBundle};
+use chia_wallet_sdk::coinset::{ChiaRpcClient, CoinRecord, CoinsetClient};
+use clvm_utils::ToTreeHash;
+use futures_util::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::broadcast;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+
+use crate::{CliError, CoinsetWebSocketMessage, Network, RpcKind};
+
+/// Full node RPC URL; when set, every command and listener uses it instead of Coinset.
+pub const FULL_NODE_RPC_URL_ENV: &str = "CHIA_FULL_NODE_RPC_URL";
+/// Directory holding `ca/private_ca.crt` and `full_node/private_full_node.{crt,key}`.
+pub const FULL_NODE_SSL_DIR_ENV: &str = "CHIA_FULL_NODE_SSL_DIR";
+
+/// How often a full node backend polls for a new peak; it has no public websocket.
+pub const FULL_NODE_PEAK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// One item per new peak; the stream ends when the upstream connection drops.
+pub type PeakNotifications = Pin<Box<dyn Stream<Item = ()> + Send>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainBlockRecord {
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub prev_hash: Bytes32,
+    /// `None` for non-transaction blocks.
+    pub timestamp: Option<u64>,
+}
+
+#[async_trait::async_trait]
+pub trait ChainSource: Send + Sync {
+    async fn peak(&self) -> Result<Option<ChainBlockRecord>, CliError>;
+    async fn block_record_at(&self, height: u32) -> Result<Option<ChainBlockRecord>, CliError>;
+    async fn block_spends(&self, header_hash: Bytes32) -> Result<Vec<CoinSpend>, CliError>;
+    async fn coin_records_by_names(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError>;
+    async fn coin_records_by_hint(
+        &self,
+        hint: Bytes32,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError>;
+    async fn puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: Option<u32>,
+    ) -> Result<Option<CoinSpend>, CliError>;
+    /// Mempool spend bundles that spend `coin_id`.
+    async fn mempool_spend_bundles(&self, coin_id: Bytes32) -> Result<Vec<SpendBundle>, CliError>;
+    /// Submits to the mempool; a rejected bundle is an error.
+    async fn push_spend_bundle(&self, spend_bundle: SpendBundle) -> Result<(), CliError>;
+    async fn peak_notifications(&self) -> Result<PeakNotifications, CliError>;
+}
+
+/// Full node RPC authenticated with the node's private client certificate.
+#[derive(Debug, Clone)]
+pub struct FullNodeClient {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl FullNodeClient {
+    pub fn new(
+        base_url: &str,
+        ca_pem: &[u8],
+        cert_pem: &[u8],
+        key_pem: &[u8],
+    ) -> Result<Self, CliError> {
+        let mut identity = cert_pem.to_vec();
+        identity.extend_from_slice(key_pem);
+        let client = reqwest::Client::builder()
+            .use_rustls_tls()
+            .identity(reqwest::Identity::from_pem(&identity)?)
+            // Only the node's private CA is trusted; its certificates are issued for
+            // `chia.net` rather than the host the node is reached on.
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(reqwest::Certificate::from_pem(ca_pem)?)
+            .danger_accept_invalid_hostnames(true)
+            .build()?;
+
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        })
+    }
+
+    /// Loads `ca/private_ca.crt` and `full_node/private_full_node.{crt,key}` under a Chia
+    /// `config/ssl` directory.
+    pub fn from_ssl_dir(base_url: &str, ssl_dir: &Path) -> Result<Self, CliError> {
+        let ca = std::fs::read(ssl_dir.join("ca/private_ca.crt"))?;
+        let cert = std::fs::read(ssl_dir.join("full_node/private_full_node.crt"))?;
+        let key = std::fs::read(ssl_dir.join("full_node/private_full_node.key"))?;
+        Self::new(base_url, &ca, &cert, &key)
+    }
+}
+
+impl ChiaRpcClient for FullNodeClient {
+    type Error = reqwest::Error;
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn make_post_request<R, B>(&self, endpoint: &str, body: B) -> Result<R, Self::Error>
+    where
+        B: Serialize,
+        R: DeserializeOwned,
+    {
+        self.client
+            .post(format!("{}/{}", self.base_url, endpoint))
+            .json(&body)
+            .send()
+            .await?
+            .json::<R>()
+            .await
+    }
+}
+
+/// RPC backend every command runs against.
+pub enum ChainClient {
+    Coinset {
+        client: CoinsetClient,
+        /// Overrides the `{base_url}/ws` peak feed.
+        websocket_url: Option<String>,
+    },
+    FullNode(FullNodeClient),
+}
+
+impl ChiaRpcClient for ChainClient {
+    type Error = reqwest::Error;
+
+    fn base_url(&self) -> &str {
+        match self {
+            Self::Coinset { client, .. } => client.base_url(),
+            Self::FullNode(client) => client.base_url(),
+        }
+    }
+
+    async fn make_post_request<R, B>(&self, endpoint: &str, body: B) -> Result<R, Self::Error>
+    where
+        B: Serialize,
+        R: DeserializeOwned,
+    {
+        match self {
+            Self::Coinset { client, .. } => client.make_post_request(endpoint, body).await,
+            Self::FullNode(client) => client.make_post_request(endpoint, body).await,
+        }
+    }
+}
+
+fn default_ssl_dir() -> Result<PathBuf, CliError> {
+    let root = match std::env::var("CHIA_ROOT") {
+        Ok(root) => PathBuf::from(root),
+        Err(_) => dirs::home_dir()
+            .ok_or(CliError::HomeDirectoryNotFound)?
+            .join(".chia/mainnet"),
+    };
+    Ok(root.join("config/ssl"))
+}
+
+/// The RPC backend configured for `network`, unless [`FULL_NODE_RPC_URL_ENV`] points at a
+/// local node.
+///
+/// The node's TLS pair is read from [`FULL_NODE_SSL_DIR_ENV`] (or the network's
+/// `full_node_ssl_dir`), falling back to `$CHIA_ROOT/config/ssl` and then
+/// `~/.chia/mainnet/config/ssl`.
+pub fn get_chain_client(network: &Network) -> Result<ChainClient, CliError> {
+    let full_node_url = match std::env::var(FULL_NODE_RPC_URL_ENV) {
+        Ok(url) => Some(url),
+        Err(_) if network.rpc_kind == RpcKind::FullNode => network.rpc_url.clone(),
+        Err(_) => None,
+    };
+    if let Some(url) = full_node_url {
+        let ssl_dir = match std::env::var(FULL_NODE_SSL_DIR_ENV) {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => match &network.full_node_ssl_dir {
+                Some(dir) => dir.clone(),
+                None => default_ssl_dir()?,
+            },
+        };
+        return Ok(ChainClient::FullNode(FullNodeClient::from_ssl_dir(
+            &url, &ssl_dir,
+        )?));
+    }
+
+    let client = match &network.rpc_url {
+        Some(url) => CoinsetClient::new(url.clone()),
+        None if network.is_testnet() => CoinsetClient::testnet11(),
+        None => CoinsetClient::mainnet(),
+    };
+    Ok(ChainClient::Coinset {
+        client,
+        websocket_url: network.websocket_url.clone(),
+    })
+}
+
+async fn coinset_peak_notifications(ws_url: &str) -> Result<PeakNotifications, CliError> {
+    println!("Connecting to WebSocket at {}", ws_url);
+
+    let (ws_stream, _) = connect_async(ws_url)
+        .await
+        .map_err(|e| CliError::Custom(format!("Failed to connect: {}", e)))?;
+
+    println!("WebSocket connected");
+
+    let (_write, read) = ws_stream.split();
+    let peaks = read
+        .take_while(|message| {
+            let open = match message {
+                Ok(Message::Close(_)) => {
+                    println!("WebSocket closed by server");
+                    false
+                }
+                Err(e) => {
+                    println!("WebSocket error: {}", e);
+                    false
+                }
+                _ => true,
+            };
+            std::future::ready(open)
+        })
+        .filter_map(|message| {
+            let peak = match message {
+                Ok(Message::Text(text)) => {
+                    match serde_json::from_str::<CoinsetWebSocketMessage>(&text) {
+                        Ok(msg) => msg.message_type() == "peak",
+                        Err(e) => {
+                            println!("Failed to parse message: {}, text: {}", e, text);
+                            false
+                        }
+                    }
+                }
+                _ => false,
+            };
+            std::future::ready(peak.then_some(()))
+        });
+    Ok(Box::pin(peaks))
+}
+
+/// Polls `get_blockchain_state` and yields whenever the peak header changes.
+fn polled_peak_notifications(client: FullNodeClient, interval: Duration) -> PeakNotifications {
+    Box::pin(futures_util::stream::unfold(
+        (client, None::<Bytes32>),
+        move |(client, last)| async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match client.get_blockchain_state().await {
+                    Ok(resp) => {
+                        let Some(state) = resp.blockchain_state else {
+                            continue;
+                        };
+                        if last != Some(state.peak.header_hash) {
+                            return Some(((), (client, Some(state.peak.header_hash))));
+                        }
+                    }
+                    Err(e) => {
+                        println!("Full node RPC error: {}", e);
+                        return None;
+                    }
+                }
+            }
+        },
+    ))
+}
+
+#[async_trait::async_trait]
+impl ChainSource for ChainClient {
+    async fn peak(&self) -> Result<Option<ChainBlockRecord>, CliError> {
+        Ok(self
+            .get_blockchain_state()
+            .await?
+            .blockchain_state
+            .map(|state| ChainBlockRecord {
+                height: state.peak.height,
+                header_hash: state.peak.header_hash,
+                prev_hash: state.peak.prev_hash,
+                timestamp: state.peak.timestamp,
+            }))
+    }
+
+    async fn block_record_at(&self, height: u32) -> Result<Option<ChainBlockRecord>, CliError> {
+        Ok(self
+            .get_block_record_by_height(height)
+            .await?
+            .block_record
+            .map(|record| ChainBlockRecord {
+                height: record.height,
+                header_hash: record.header_hash,
+                prev_hash: record.prev_hash,
+                timestamp: record.timestamp,
+            }))
+    }
+
+    async fn block_spends(&self, header_hash: Bytes32) -> Result<Vec<CoinSpend>, CliError> {
+        Ok(self
+            .get_block_spends(header_hash)
+            .await?
+            .block_spends
+            .unwrap_or_default())
+    }
+
+    async fn coin_records_by_names(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError> {
+        Ok(self
+            .get_coin_records_by_names(coin_ids, None, None, Some(include_spent), None)
+            .await?
+            .coin_records
+            .unwrap_or_default())
+    }
+
+    async fn coin_records_by_hint(
+        &self,
+        hint: Bytes32,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError> {
+        Ok(self
+            .get_coin_records_by_hint(hint, None, None, Some(include_spent), None)
+            .await?
+            .coin_records
+            .unwrap_or_default())
+    }
+
+    async fn puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: Option<u32>,
+    ) -> Result<Option<CoinSpend>, CliError> {
+        Ok(self
+            .get_puzzle_and_solution(coin_id, height)
+            .await?
+            .coin_solution)
+    }
+
+    async fn mempool_spend_bundles(&self, coin_id: Bytes32) -> Result<Vec<SpendBundle>, CliError> {
+        Ok(self
+            .get_mempool_items_by_coin_name(coin_id)
+            .await?
+            .mempool_items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| item.spend_bundle)
+            .collect())
+    }
+
+    async fn push_spend_bundle(&self, spend_bundle: SpendBundle) -> Result<(), CliError> {
+        let resp = self.push_tx(spend_bundle).await?;
+        match resp.error {
+            Some(error) => Err(CliError::Custom(error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn peak_notifications(&self) -> Result<PeakNotifications, CliError> {
+        match self {
+            Self::Coinset {
+                client,
+                websocket_url,
+            } => {
+                let ws_url = match websocket_url {
+                    Some(url) => url.clone(),
+                    None => format!("{}/ws", client.base_url().replace("https://", "wss://")),
+                };
+                coinset_peak_notifications(&ws_url).await
+            }
+            Self::FullNode(client) => Ok(polled_peak_notifications(
+                client.clone(),
+                FULL_NODE_PEAK_POLL_INTERVAL,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MemoryChain {
+    blocks: Vec<(ChainBlockRecord, Vec<CoinSpend>)>,
+    coins: HashMap<Bytes32, CoinRecord>,
+    hints: HashMap<Bytes32, Vec<Bytes32>>,
+    spends: HashMap<Bytes32, CoinSpend>,
+    mempool: Vec<SpendBundle>,
+    /// Bumped on every rewind so replacement blocks get new header hashes.
+    forks: u32,
+}
+
+/// In-memory chain: tests add coins and blocks by hand and the listeners read it
+/// like any other backend. Spends are recorded, not validated.
+pub struct MemoryChainSource {
+    chain: Mutex<MemoryChain>,
+    peaks: broadcast::Sender<()>,
+}
+
+impl Default for MemoryChainSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryChainSource {
+    pub fn new() -> Self {
+        Self {
+            chain: Mutex::new(MemoryChain::default()),
+            peaks: broadcast::channel(64).0,
+        }
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    pub fn height(&self) -> Option<u32> {
+        self.chain
+            .lock()
+            .unwrap()
+            .blocks
+            .last()
+            .map(|(record, _)| record.height)
+    }
+
+    /// Adds an unspent coin confirmed at the current peak (height 0 before any block).
+    pub fn create_coin(&self, coin: Coin, hint: Option<Bytes32>, timestamp: u64) {
+        let mut chain = self.chain.lock().unwrap();
+        let height = chain.blocks.last().map_or(0, |(record, _)| record.height);
+        chain.coins.insert(
+            coin.coin_id(),
+            CoinRecord {
+                coin,
+                coinbase: false,
+                confirmed_block_index: height,
+                spent: false,
+                spent_block_index: 0,
+                timestamp,
+            },
+        );
+        if let Some(hint) = hint {
+            chain.hints.entry(hint).or_default().push(coin.coin_id());
+        }
+    }
+
+    /// Appends a block spending `spends`, drops mempool bundles it conflicts with,
+    /// and notifies peak subscribers. Coins the spends create are added separately.
+    pub fn push_block(&self, timestamp: Option<u64>, spends: Vec<CoinSpend>) -> ChainBlockRecord {
+        let record = {
+            let mut chain = self.chain.lock().unwrap();
+            let (height, prev_hash) = match chain.blocks.last() {
+                Some((last, _)) => (last.height + 1, last.header_hash),
+                None => (0, Bytes32::default()),
+            };
+            let record = ChainBlockRecord {
+                height,
+                header_hash: (height, (prev_hash, chain.forks)).tree_hash().into(),
+                prev_hash,
+                timestamp,
+            };
+            for spend in &spends {
+                let coin_id = spend.coin.coin_id();
+                let coin_record = chain.coins.entry(coin_id).or_insert(CoinRecord {
+                    coin: spend.coin,
+                    coinbase: false,
+                    confirmed_block_index: height,
+                    spent: false,
+                    spent_block_index: 0,
+                    timestamp: timestamp.unwrap_or_default(),
+                });
+                coin_record.spent = true;
+                coin_record.spent_block_index = height;
+                chain.spends.insert(coin_id, spend.clone());
+            }
+            let spent: Vec<Bytes32> = spends.iter().map(|s| s.coin.coin_id()).collect();
+            chain.mempool.retain(|bundle| {
+                !bundle
+                    .coin_spends
+                    .iter()
+                    .any(|cs| spent.contains(&cs.coin.coin_id()))
+            });
+            chain.blocks.push((record, spends));
+            record
+        };
+        let _ = self.peaks.send(());
+        record
+    }
+
+    /// Drops every block above `height` and what they confirmed or spent, as a reorg would.
+    pub fn rewind_to(&self, height: u32) {
+        let mut chain = self.chain.lock().unwrap();
+        chain.blocks.retain(|(record, _)| record.height <= height);
+        chain
+            .coins
+            .retain(|_, record| record.confirmed_block_index <= height);
+        let mut unspent = Vec::new();
+        for (coin_id, record) in chain.coins.iter_mut() {
+            if record.spent && record.spent_block_index > height {
+                record.spent = false;
+                record.spent_block_index = 0;
+                unspent.push(*coin_id);
+            }
+        }
+        for coin_id in unspent {
+            chain.spends.remove(&coin_id);
+        }
+        let coins = &chain.coins;
+        let hints = chain
+            .hints
+            .iter()
+            .map(|(hint, ids)| {
+                let live = ids.iter().copied().filter(|id| coins.contains_key(id));
+                (*hint, live.collect::<Vec<_>>())
+            })
+            .collect();
+        chain.hints = hints;
+        chain.forks += 1;
+    }
+
+    pub fn mempool(&self) -> Vec<SpendBundle> {
+        self.chain.lock().unwrap().mempool.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainSource for MemoryChainSource {
+    async fn peak(&self) -> Result<Option<ChainBlockRecord>, CliError> {
+        Ok(self
+            .chain
+            .lock()
+            .unwrap()
+            .blocks
+            .last()
+            .map(|(record, _)| *record))
+    }
+
+    async fn block_record_at(&self, height: u32) -> Result<Option<ChainBlockRecord>, CliError> {
+        Ok(self
+            .chain
+            .lock()
+            .unwrap()
+            .blocks
+            .iter()
+            .find(|(record, _)| record.height == height)
+            .map(|(record, _)| *record))
+    }
+
+    async fn block_spends(&self, header_hash: Bytes32) -> Result<Vec<CoinSpend>, CliError> {
+        Ok(self
+            .chain
+            .lock()
+            .unwrap()
+            .blocks
+            .iter()
+            .find(|(record, _)| record.header_hash == header_hash)
+            .map(|(_, spends)| spends.clone())
+            .unwrap_or_default())
+    }
+
+    async fn coin_records_by_names(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError> {
+        let chain = self.chain.lock().unwrap();
+        Ok(coin_ids
+            .iter()
+            .filter_map(|id| chain.coins.get(id))
+            .filter(|record| include_spent || !record.spent)
+            .cloned()
+            .collect())
+    }
+
+    async fn coin_records_by_hint(
+        &self,
+        hint: Bytes32,
+        include_spent: bool,
+    ) -> Result<Vec<CoinRecord>, CliError> {
+        let chain = self.chain.lock().unwrap();
+        Ok(chain
+            .hints
+            .get(&hint)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| chain.coins.get(id))
+            .filter(|record| include_spent || !record.spent)
+            .cloned()
+            .collect())
+    }
+
+    async fn puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        _height: Option<u32>,
+    ) -> Result<Option<CoinSpend>, CliError> {
+        Ok(self.chain.lock().unwrap().spends.get(&coin_id).cloned())
+    }
+
+    async fn mempool_spend_bundles(&self, coin_id: Bytes32) -> Result<Vec<SpendBundle>, CliError> {
+        Ok(self
+            .chain
+            .lock()
+            .unwrap()
+            .mempool
+            .iter()
+            .filter(|bundle| {
+                bundle
+                    .coin_spends
+                    .iter()
+                    .any(|cs| cs.coin.coin_id() == coin_id)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn push_spend_bundle(&self, spend_bundle: SpendBundle) -> Result<(), CliError> {
+        self.chain.lock().unwrap().mempool.push(spend_bundle);
+        Ok(())
+    }
+
+    async fn peak_notifications(&self) -> Result<PeakNotifications, CliError> {
+        let receiver = self.peaks.subscribe();
+        Ok(Box::pin(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                match receiver.recv().await {
+                    // A lagged subscriber still only needs to know a peak happened.
+                    Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => Some(((), receiver)),
+                    Err(broadcast::error::RecvError::Closed) => None,
+                }
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Program;
+
+    use super::*;
+
+    fn h(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn spend_of(coin: Coin) -> CoinSpend {
+        CoinSpend::new(coin, Program::default(), Program::default())
+    }
+
+    #[tokio::test]
+    async fn memory_chain_tracks_spends_mempool_and_rewinds() {
+        let chain = MemoryChainSource::new();
+        let mut peaks = chain.peak_notifications().await.unwrap();
+        let coin = Coin::new(h(1), h(2), 1);
+        chain.create_coin(coin, Some(h(9)), 100);
+        chain.push_block(Some(100), Vec::new());
+        assert_eq!(peaks.next().await, Some(()));
+
+        chain
+            .push_spend_bundle(SpendBundle::new(vec![spend_of(coin)], Default::default()))
+            .await
+            .unwrap();
+        assert_eq!(
+            chain
+                .mempool_spend_bundles(coin.coin_id())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let spent_at = chain.push_block(Some(118), vec![spend_of(coin)]);
+        assert_eq!(spent_at.height, 1);
+        assert!(chain.mempool().is_empty());
+        let record = &chain.coin_records_by_hint(h(9), true).await.unwrap()[0];
+        assert!(record.spent);
+        assert_eq!(record.spent_block_index, 1);
+        assert!(chain
+            .coin_records_by_hint(h(9), false)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            chain.block_spends(spent_at.header_hash).await.unwrap(),
+            vec![spend_of(coin)]
+        );
+
+        chain.rewind_to(0);
+        assert!(chain
+            .puzzle_and_solution(coin.coin_id(), None)
+            .await
+            .unwrap()
+            .is_none());
+        let replacement = chain.push_block(Some(120), Vec::new());
+        assert_eq!(replacement.height, 1);
+        assert_eq!(replacement.prev_hash, spent_at.prev_hash);
+        assert_ne!(replacement.header_hash, spent_at.header_hash);
+        assert_eq!(
+            chain.peak().await.unwrap().map(|peak| peak.header_hash),
+            Some(replacement.header_hash)
+        );
+    }
+}
@@ -8,16 +8,20 @@ use crate::{
 use super::{
     catalog_broadcast_state_update, catalog_continue_launch, catalog_initiate_launch,
     catalog_listen, catalog_register, catalog_sign_state_update, catalog_unroll_state_scheduler,
-    catalog_verify_deployment, datastore_launch, datastore_update, datastore_view,
-    multisig_broadcast_rekey, multisig_launch, multisig_sign_rekey, multisig_verify_signature,
-    multisig_view, reward_distributor_add_rewards, reward_distributor_broadcast_entry_update,
+    catalog_verify_deployment, datastore_launch, datastore_update, datastore_view, db_check,
+    db_export, db_import, db_migrate, multisig_broadcast_rekey, multisig_launch,
+    multisig_sign_rekey, multisig_verify_signature, multisig_view, reward_distributor_add_rewards,
+    reward_distributor_broadcast_entry_update, reward_distributor_broadcast_entry_updates,
     reward_distributor_clawback_rewards, reward_distributor_commit_available_rewards,
-    reward_distributor_commit_rewards, reward_distributor_initiate_payout,
-    reward_distributor_launch, reward_distributor_new_epoch, reward_distributor_refresh,
-    reward_distributor_sign_entry_update, reward_distributor_sync, reward_distributor_view,
+    reward_distributor_commit_rewards, reward_distributor_entry,
+    reward_distributor_initiate_payout, reward_distributor_keep, reward_distributor_launch,
+    reward_distributor_listen, reward_distributor_new_epoch, reward_distributor_payout_all,
+    reward_distributor_refresh, reward_distributor_sign_entry_update,
+    reward_distributor_sign_entry_updates, reward_distributor_sync, reward_distributor_view,
     xchandles_continue_launch, xchandles_expire, xchandles_extend, xchandles_initiate_launch,
     xchandles_initiate_update, xchandles_listen, xchandles_register,
-    xchandles_unroll_state_scheduler, xchandles_verify_deployment, xchandles_view,
+    xchandles_unroll_state_scheduler, xchandles_verify_deployment, xchandles_view, NetworkArgs,
+    SnapshotRegistry, DEFAULT_PAYOUT_BUNDLE_MAX_COST,
 };
 
 #[derive(Parser)]
@@ -26,6 +30,9 @@ use super::{
     about = "A CLI for interacting with the first dApps that use the slot primitive: CATalog, XCHandles, and reward distributors"
 )]
 struct Cli {
+    #[command(flatten)]
+    network: NetworkArgs,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -58,6 +65,39 @@ enum Commands {
         #[command(subcommand)]
         action: DatastoreCliAction,
     },
+    /// Manage the local database
+    Db {
+        #[command(subcommand)]
+        action: DbCliAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCliAction {
+    /// Apply pending schema migrations
+    Migrate,
+    /// Report the schema version and any pending migrations or missing columns
+    Check,
+    /// Write a registry's indexed state to a portable snapshot file
+    Export {
+        /// Which listener's registry to export
+        #[arg(long, value_enum)]
+        registry: SnapshotRegistry,
+
+        /// Registry launcher id; defaults to the network's CATalog for `catalog`
+        #[arg(long)]
+        launcher_id: Option<String>,
+
+        /// Snapshot file to write
+        #[arg(long)]
+        output: String,
+    },
+    /// Verify a snapshot against the chain and load it so a listener resumes from it
+    Import {
+        /// Snapshot file written by `db export`
+        #[arg(long)]
+        input: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -67,10 +107,6 @@ enum MultisigCliAction {
         /// Vault (singleton) launcher id
         #[arg(long)]
         launcher_id: String,
-
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
     },
     /// Launch a standalone multisig (e.g., for a manager)
     Launch {
@@ -82,10 +118,6 @@ enum MultisigCliAction {
         #[arg(long)]
         m: usize,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -108,10 +140,6 @@ enum MultisigCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use debug signing method (pk prompt)
         #[arg(long, default_value_t = false)]
         debug: bool,
@@ -134,10 +162,6 @@ enum MultisigCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -171,10 +195,6 @@ enum CatalogCliAction {
         #[arg(short)]
         m: usize,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use for the launch, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -189,30 +209,18 @@ enum CatalogCliAction {
         #[arg(long)]
         cats_per_spend: usize,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
     },
     /// Unrolls the state scheduler
     UnrollStateScheduler {
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
     },
     /// Verifies the built-in deployment is valid
-    VerifyDeployment {
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-    },
+    VerifyDeployment,
     /// Register a new CAT
     Register {
         /// TAIL reveal (hex CLVM)
@@ -275,10 +283,6 @@ enum CatalogCliAction {
         #[arg(long, default_value_t = false)]
         refund: bool,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use local database instead of CATalog API
         #[arg(long, default_value_t = false)]
         local: bool,
@@ -292,11 +296,7 @@ enum CatalogCliAction {
         fee: String,
     },
     /// Listen for CATalog spends
-    Listen {
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-    },
+    Listen,
     /// Sign a CATalog state update transaction
     SignStateUpdate {
         /// New payment asset id
@@ -315,10 +315,6 @@ enum CatalogCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use debug signing method (pk prompt)
         #[arg(long, default_value_t = false)]
         debug: bool,
@@ -341,10 +337,6 @@ enum CatalogCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -379,10 +371,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         premine: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use for the launch, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -421,10 +409,6 @@ enum XchandlesCliAction {
         #[arg(long, default_value = "31557600")]
         registration_period: u64,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -439,10 +423,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use local database instead of XCHandles API
         #[arg(long, default_value_t = false)]
         local: bool,
@@ -464,10 +444,6 @@ enum XchandlesCliAction {
         /// Verify only the first N CSV rows (default: all)
         #[arg(long)]
         expected_count: Option<usize>,
-
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
     },
     /// Registers a new handle
     Register {
@@ -503,10 +479,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         refund: bool,
 
-        /// Use testnet11
-        #[arg(long)]
-        testnet11: bool,
-
         /// Payment asset id
         #[arg(long)]
         payment_asset_id: String,
@@ -545,10 +517,6 @@ enum XchandlesCliAction {
         #[arg(long, default_value = "1")]
         num_periods: u64,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Payment asset id
         #[arg(long)]
         payment_asset_id: String,
@@ -587,10 +555,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         min_height: Option<u32>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use local database instead of XCHandles API
         #[arg(long, default_value_t = false)]
         local: bool,
@@ -613,10 +577,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         new_nft: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use local database instead of XCHandles API
         #[arg(long, default_value_t = false)]
         local: bool,
@@ -659,10 +619,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         refund: bool,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Payment asset id
         #[arg(long)]
         payment_asset_id: String,
@@ -689,13 +645,9 @@ enum XchandlesCliAction {
     },
     /// Listen for XCHandles spends
     Listen {
-        /// XCHandles (sub)registry launcher ids (comma-separated list)
+        /// XCHandles (sub)registry launcher ids (comma-separated list); defaults to the network's
         #[arg(long)]
-        launcher_ids: String,
-
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
+        launcher_ids: Option<String>,
     },
     /// Shows up-to-date information about an XCHandles registry
     View {
@@ -703,10 +655,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Payment asset id hint
         #[arg(long)]
         payment_asset_id: Option<String>,
@@ -757,10 +705,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         multisig_launcher_id: String,
 
-        /// Testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Debug signing mode
         #[arg(long, default_value_t = false)]
         debug: bool,
@@ -803,10 +747,6 @@ enum XchandlesCliAction {
         #[arg(long)]
         signatures: String,
 
-        /// Testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -877,10 +817,6 @@ enum RewardDistributorCliAction {
         #[arg(long, default_value = "8000")]
         withdrawal_share_bps: u64,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -903,10 +839,6 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         clawback_address: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -925,10 +857,6 @@ enum RewardDistributorCliAction {
         #[arg(long, default_value_t = 32)]
         max_coins: usize,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -951,10 +879,6 @@ enum RewardDistributorCliAction {
         #[arg(long, required = false)]
         reward_amount: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -969,10 +893,6 @@ enum RewardDistributorCliAction {
         #[arg(long, required = false)]
         update_time: Option<u64>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -983,10 +903,6 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         launcher_id: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1013,10 +929,6 @@ enum RewardDistributorCliAction {
         #[arg(long, default_value_t = false)]
         remove_entry: bool,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Use debug signing method (pk prompt)
         #[arg(long, default_value_t = false)]
         debug: bool,
@@ -1043,9 +955,42 @@ enum RewardDistributorCliAction {
         #[arg(long, default_value_t = false)]
         remove_entry: bool,
 
-        /// Use testnet11
+        /// Fee to use, in XCH
+        #[arg(long, default_value = "0.0025")]
+        fee: String,
+    },
+    /// Signs one multisig update applying every entry add, removal and share change needed
+    /// to match a roster CSV (columns: payout_puzzle_hash,shares)
+    SignEntryUpdates {
+        /// Reward distributor singleton launcher id
+        #[arg(long)]
+        launcher_id: String,
+
+        /// Roster CSV; current entries come from `reward-distributor listen`'s index
+        #[arg(long)]
+        csv: String,
+
+        /// Pubkey to sign with (hex string)
+        #[arg(long)]
+        my_pubkey: String,
+
+        /// Use debug signing method (pk prompt)
         #[arg(long, default_value_t = false)]
-        testnet11: bool,
+        debug: bool,
+    },
+    /// Broadcasts the roster update signed with sign-entry-updates
+    BroadcastEntryUpdates {
+        /// Reward distributor singleton launcher id
+        #[arg(long)]
+        launcher_id: String,
+
+        /// Roster CSV that was signed
+        #[arg(long)]
+        csv: String,
+
+        /// Signatures (comma-separated list)
+        #[arg(long)]
+        sigs: String,
 
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
@@ -1073,10 +1018,6 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         custody_address: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1091,10 +1032,6 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         custody_address: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1109,10 +1046,6 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         reward_amount: String,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1127,23 +1060,30 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         custody_address: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
     },
+    /// Pays out every indexed entry above the payout threshold, packing as many payouts
+    /// per bundle as the cost limit allows (needs `reward-distributor listen`'s index)
+    PayoutAll {
+        /// Reward distributor singleton launcher id
+        #[arg(long)]
+        launcher_id: String,
+
+        /// Maximum CLVM cost of the distributor spends in one bundle
+        #[arg(long, default_value_t = DEFAULT_PAYOUT_BUNDLE_MAX_COST)]
+        max_cost: u64,
+
+        /// Fee to use for each bundle, in XCH
+        #[arg(long, default_value = "0.0025")]
+        fee: String,
+    },
     /// Views up-to-date information about a reward distributor
     View {
         /// Reward distributor singleton launcher id
         #[arg(long)]
         launcher_id: String,
-
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
     },
     /// Refresh locked NFT share counts from an updated whitelist CSV
     Refresh {
@@ -1159,14 +1099,73 @@ enum RewardDistributorCliAction {
         #[arg(long)]
         custody_address: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
     },
+    /// Shows the rewards an entry can claim, projected to the latest (or given) timestamp
+    Entry {
+        /// Reward distributor singleton launcher id
+        #[arg(long)]
+        launcher_id: String,
+
+        /// Custody address (xch1...) of the entry; defaults to first wallet derivation
+        #[arg(long)]
+        custody_address: Option<String>,
+
+        /// Launcher id of an NFT locked in the distributor; shows the entry that staked it
+        #[arg(long)]
+        nft: Option<String>,
+
+        /// Coin id of a locked CAT stake; shows the entry that staked it
+        #[arg(long)]
+        stake_coin: Option<String>,
+
+        /// Timestamp to project rewards to; defaults to the latest transaction block
+        #[arg(long)]
+        update_time: Option<u64>,
+    },
+    /// Index reward distributor spends and serve state, entries, commitments and epochs
+    Listen {
+        /// Reward distributor launcher ids (comma-separated list)
+        #[arg(long)]
+        launcher_ids: String,
+    },
+    /// Keep distributors running: start epochs, sync and commit deposited rewards.
+    /// Fees are paid by the hot wallet key in SLOT_MACHINE_KEEPER_SECRET_KEY.
+    Keep {
+        /// Reward distributor launcher ids (comma-separated list)
+        #[arg(long)]
+        launcher_ids: String,
+
+        /// Address that can claw back swept commitments (defaults to no clawback)
+        #[arg(long)]
+        clawback_address: Option<String>,
+
+        /// Maximum number of deposited coins to commit per spend
+        #[arg(long, default_value_t = 32)]
+        max_coins: usize,
+
+        /// Seconds between checks
+        #[arg(long, default_value_t = 60)]
+        interval_seconds: u64,
+
+        /// Sync once the last update is this close to the max seconds offset
+        #[arg(long, default_value_t = 120)]
+        sync_margin_seconds: u64,
+
+        /// Consecutive failures tolerated per distributor before it is dropped
+        #[arg(long, default_value_t = 3)]
+        max_retries: u32,
+
+        /// Fee to use for each spend, in XCH
+        #[arg(long, default_value = "0.0025")]
+        fee: String,
+
+        /// Total fees the keeper may spend before stopping, in XCH
+        #[arg(long, default_value = "0.1")]
+        fee_budget: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1185,10 +1184,6 @@ enum DatastoreCliAction {
         #[arg(long)]
         description: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1215,10 +1210,6 @@ enum DatastoreCliAction {
         #[arg(long)]
         description: Option<String>,
 
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
-
         /// Fee to use, in XCH
         #[arg(long, default_value = "0.0025")]
         fee: String,
@@ -1228,37 +1219,33 @@ enum DatastoreCliAction {
         /// DataStore singleton launcher id
         #[arg(long)]
         launcher_id: String,
-
-        /// Use testnet11
-        #[arg(long, default_value_t = false)]
-        testnet11: bool,
     },
 }
 
 pub async fn run_cli() {
     let args = Cli::parse();
+    let network = match args.network.resolve() {
+        Ok(network) => network,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            return;
+        }
+    };
 
     let res = match args.command {
         Commands::Multisig { action } => match action {
-            MultisigCliAction::View {
-                launcher_id,
-                testnet11,
-            } => multisig_view(launcher_id, testnet11).await,
-            MultisigCliAction::Launch {
-                pubkeys,
-                m,
-                testnet11,
-                fee,
-            } => multisig_launch(pubkeys, m, testnet11, fee).await,
+            MultisigCliAction::View { launcher_id } => multisig_view(launcher_id, &network).await,
+            MultisigCliAction::Launch { pubkeys, m, fee } => {
+                multisig_launch(pubkeys, m, &network, fee).await
+            }
             MultisigCliAction::SignRekey {
                 new_pubkeys,
                 new_m,
                 my_pubkey,
                 launcher_id,
-                testnet11,
                 debug,
             } => {
-                multisig_sign_rekey(new_pubkeys, new_m, my_pubkey, launcher_id, testnet11, debug)
+                multisig_sign_rekey(new_pubkeys, new_m, my_pubkey, launcher_id, &network, debug)
                     .await
             }
             MultisigCliAction::BroadcastRekey {
@@ -1266,11 +1253,9 @@ pub async fn run_cli() {
                 new_m,
                 sigs,
                 launcher_id,
-                testnet11,
                 fee,
             } => {
-                multisig_broadcast_rekey(new_pubkeys, new_m, sigs, launcher_id, testnet11, fee)
-                    .await
+                multisig_broadcast_rekey(new_pubkeys, new_m, sigs, launcher_id, &network, fee).await
             }
             MultisigCliAction::VerifySignature {
                 raw_message,
@@ -1279,24 +1264,18 @@ pub async fn run_cli() {
             } => multisig_verify_signature(raw_message, pubkey, signature).await,
         },
         Commands::Catalog { action } => match action {
-            CatalogCliAction::InitiateLaunch {
-                pubkeys,
-                m,
-                testnet11,
-                fee,
-            } => catalog_initiate_launch(pubkeys, m, testnet11, fee).await,
+            CatalogCliAction::InitiateLaunch { pubkeys, m, fee } => {
+                catalog_initiate_launch(pubkeys, m, &network, fee).await
+            }
             CatalogCliAction::ContinueLaunch {
                 payment_asset_id,
                 cats_per_spend,
-                testnet11,
                 fee,
-            } => catalog_continue_launch(payment_asset_id, cats_per_spend, testnet11, fee).await,
-            CatalogCliAction::UnrollStateScheduler { testnet11, fee } => {
-                catalog_unroll_state_scheduler(testnet11, fee).await
-            }
-            CatalogCliAction::VerifyDeployment { testnet11 } => {
-                catalog_verify_deployment(testnet11).await
+            } => catalog_continue_launch(payment_asset_id, cats_per_spend, &network, fee).await,
+            CatalogCliAction::UnrollStateScheduler { fee } => {
+                catalog_unroll_state_scheduler(&network, fee).await
             }
+            CatalogCliAction::VerifyDeployment => catalog_verify_deployment(&network).await,
             CatalogCliAction::Register {
                 tail_reveal,
                 ticker,
@@ -1311,7 +1290,6 @@ pub async fn run_cli() {
                 license_hash,
                 recipient,
                 refund,
-                testnet11,
                 local,
                 log,
                 payment_asset_id,
@@ -1332,7 +1310,7 @@ pub async fn run_cli() {
                     license_hash,
                     recipient,
                     refund,
-                    testnet11,
+                    &network,
                     local,
                     log,
                     payment_asset_id,
@@ -1341,13 +1319,12 @@ pub async fn run_cli() {
                 )
                 .await
             }
-            CatalogCliAction::Listen { testnet11 } => catalog_listen(testnet11).await,
+            CatalogCliAction::Listen => catalog_listen(&network).await,
             CatalogCliAction::SignStateUpdate {
                 new_payment_asset_id,
                 new_payment_asset_amount,
                 my_pubkey,
                 launcher_id,
-                testnet11,
                 debug,
             } => {
                 catalog_sign_state_update(
@@ -1355,7 +1332,7 @@ pub async fn run_cli() {
                     new_payment_asset_amount,
                     my_pubkey,
                     launcher_id,
-                    testnet11,
+                    &network,
                     debug,
                 )
                 .await
@@ -1365,7 +1342,6 @@ pub async fn run_cli() {
                 new_payment_asset_amount,
                 sigs,
                 launcher_id,
-                testnet11,
                 fee,
             } => {
                 catalog_broadcast_state_update(
@@ -1373,7 +1349,7 @@ pub async fn run_cli() {
                     new_payment_asset_amount,
                     launcher_id,
                     sigs,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1387,7 +1363,6 @@ pub async fn run_cli() {
                 relative_block_height,
                 registration_period,
                 premine,
-                testnet11,
                 fee,
             } => {
                 xchandles_initiate_launch(
@@ -1397,7 +1372,7 @@ pub async fn run_cli() {
                     relative_block_height,
                     registration_period,
                     premine,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1411,7 +1386,6 @@ pub async fn run_cli() {
                 handles_per_spend,
                 premine,
                 registration_period,
-                testnet11,
                 fee,
                 yes,
             } => {
@@ -1424,7 +1398,7 @@ pub async fn run_cli() {
                     handles_per_spend,
                     premine,
                     registration_period,
-                    testnet11,
+                    &network,
                     fee,
                     yes,
                 )
@@ -1432,16 +1406,14 @@ pub async fn run_cli() {
             }
             XchandlesCliAction::UnrollStateScheduler {
                 launcher_id,
-                testnet11,
                 local,
                 fee,
-            } => xchandles_unroll_state_scheduler(launcher_id, testnet11, local, fee).await,
+            } => xchandles_unroll_state_scheduler(launcher_id, &network, local, fee).await,
             XchandlesCliAction::VerifyDeployment {
                 launcher_id,
                 premine,
                 expected_count,
-                testnet11,
-            } => xchandles_verify_deployment(launcher_id, premine, expected_count, testnet11).await,
+            } => xchandles_verify_deployment(launcher_id, premine, expected_count, &network).await,
             XchandlesCliAction::Register {
                 launcher_id,
                 handle,
@@ -1451,7 +1423,6 @@ pub async fn run_cli() {
                 secret,
                 start_time,
                 refund,
-                testnet11,
                 payment_asset_id,
                 payment_cat_base_price,
                 registration_period,
@@ -1468,7 +1439,7 @@ pub async fn run_cli() {
                     secret,
                     start_time,
                     refund,
-                    testnet11,
+                    &network,
                     payment_asset_id,
                     payment_cat_base_price,
                     registration_period,
@@ -1482,7 +1453,6 @@ pub async fn run_cli() {
                 launcher_id,
                 handle,
                 num_periods,
-                testnet11,
                 payment_asset_id,
                 payment_cat_base_price,
                 registration_period,
@@ -1493,7 +1463,7 @@ pub async fn run_cli() {
                     launcher_id,
                     handle,
                     num_periods,
-                    testnet11,
+                    &network,
                     payment_asset_id,
                     payment_cat_base_price,
                     registration_period,
@@ -1507,7 +1477,6 @@ pub async fn run_cli() {
                 handle,
                 new_nft,
                 min_height,
-                testnet11,
                 local,
                 fee,
             } => {
@@ -1516,7 +1485,7 @@ pub async fn run_cli() {
                     handle,
                     new_nft,
                     min_height,
-                    testnet11,
+                    &network,
                     local,
                     fee,
                 )
@@ -1526,12 +1495,9 @@ pub async fn run_cli() {
                 launcher_id,
                 handle,
                 new_nft,
-                testnet11,
                 local,
                 fee,
-            } => {
-                xchandles_execute_update(launcher_id, handle, new_nft, testnet11, local, fee).await
-            }
+            } => xchandles_execute_update(launcher_id, handle, new_nft, &network, local, fee).await,
             XchandlesCliAction::Expire {
                 launcher_id,
                 handle,
@@ -1541,7 +1507,6 @@ pub async fn run_cli() {
                 expire_time,
                 num_periods,
                 refund,
-                testnet11,
                 payment_asset_id,
                 payment_cat_base_price,
                 registration_period,
@@ -1558,7 +1523,7 @@ pub async fn run_cli() {
                     secret,
                     expire_time,
                     refund,
-                    testnet11,
+                    &network,
                     payment_asset_id,
                     payment_cat_base_price,
                     registration_period,
@@ -1568,20 +1533,18 @@ pub async fn run_cli() {
                 )
                 .await
             }
-            XchandlesCliAction::Listen {
-                testnet11,
-                launcher_ids,
-            } => xchandles_listen(launcher_ids, testnet11).await,
+            XchandlesCliAction::Listen { launcher_ids } => {
+                xchandles_listen(launcher_ids, &network).await
+            }
             XchandlesCliAction::View {
                 launcher_id,
-                testnet11,
                 payment_asset_id,
                 payment_cat_base_price,
                 registration_period,
             } => {
                 xchandles_view(
                     launcher_id,
-                    testnet11,
+                    &network,
                     payment_asset_id,
                     payment_cat_base_price,
                     registration_period,
@@ -1598,7 +1561,6 @@ pub async fn run_cli() {
                 registration_period,
                 my_pubkey,
                 multisig_launcher_id,
-                testnet11,
                 debug,
             } => {
                 xchandles_sign_state_update(
@@ -1611,7 +1573,7 @@ pub async fn run_cli() {
                     registration_period,
                     my_pubkey,
                     multisig_launcher_id,
-                    testnet11,
+                    &network,
                     debug,
                 )
                 .await
@@ -1626,7 +1588,6 @@ pub async fn run_cli() {
                 registration_period,
                 multisig_launcher_id,
                 signatures,
-                testnet11,
                 fee,
             } => {
                 xchandles_broadcast_state_update(
@@ -1639,7 +1600,7 @@ pub async fn run_cli() {
                     registration_period,
                     multisig_launcher_id,
                     signatures,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1662,7 +1623,6 @@ pub async fn run_cli() {
                 withdrawal_share_bps,
                 reserve_asset_id,
                 comment,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_launch(
@@ -1681,7 +1641,7 @@ pub async fn run_cli() {
                     withdrawal_share_bps,
                     reserve_asset_id,
                     comment,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1691,7 +1651,6 @@ pub async fn run_cli() {
                 reward_amount,
                 epoch_start,
                 clawback_address,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_commit_rewards(
@@ -1699,7 +1658,7 @@ pub async fn run_cli() {
                     reward_amount,
                     epoch_start,
                     clawback_address,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1708,14 +1667,13 @@ pub async fn run_cli() {
                 launcher_id,
                 clawback_address,
                 max_coins,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_commit_available_rewards(
                     launcher_id,
                     clawback_address,
                     max_coins,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1725,7 +1683,6 @@ pub async fn run_cli() {
                 clawback_address,
                 epoch_start,
                 reward_amount,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_clawback_rewards(
@@ -1733,7 +1690,7 @@ pub async fn run_cli() {
                     clawback_address,
                     epoch_start,
                     reward_amount,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1741,21 +1698,17 @@ pub async fn run_cli() {
             RewardDistributorCliAction::Sync {
                 launcher_id,
                 update_time,
-                testnet11,
-                fee,
-            } => reward_distributor_sync(launcher_id, update_time, testnet11, fee).await,
-            RewardDistributorCliAction::NewEpoch {
-                launcher_id,
-                testnet11,
                 fee,
-            } => reward_distributor_new_epoch(launcher_id, testnet11, fee).await,
+            } => reward_distributor_sync(launcher_id, update_time, &network, fee).await,
+            RewardDistributorCliAction::NewEpoch { launcher_id, fee } => {
+                reward_distributor_new_epoch(launcher_id, &network, fee).await
+            }
             RewardDistributorCliAction::SignEntryUpdate {
                 launcher_id,
                 entry_payout_puzzle_hash,
                 entry_shares,
                 my_pubkey,
                 remove_entry,
-                testnet11,
                 debug,
             } => {
                 reward_distributor_sign_entry_update(
@@ -1764,7 +1717,7 @@ pub async fn run_cli() {
                     entry_shares,
                     my_pubkey,
                     remove_entry,
-                    testnet11,
+                    &network,
                     debug,
                 )
                 .await
@@ -1775,7 +1728,6 @@ pub async fn run_cli() {
                 entry_shares,
                 sigs,
                 remove_entry,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_broadcast_entry_update(
@@ -1784,18 +1736,35 @@ pub async fn run_cli() {
                     entry_shares,
                     sigs,
                     remove_entry,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
             }
+            RewardDistributorCliAction::SignEntryUpdates {
+                launcher_id,
+                csv,
+                my_pubkey,
+                debug,
+            } => {
+                reward_distributor_sign_entry_updates(launcher_id, csv, my_pubkey, &network, debug)
+                    .await
+            }
+            RewardDistributorCliAction::BroadcastEntryUpdates {
+                launcher_id,
+                csv,
+                sigs,
+                fee,
+            } => {
+                reward_distributor_broadcast_entry_updates(launcher_id, csv, sigs, &network, fee)
+                    .await
+            }
             RewardDistributorCliAction::Stake {
                 launcher_id,
                 nft,
                 stake_amount,
                 csv,
                 custody_address,
-                testnet11,
                 fee,
             } => {
                 reward_distributor_stake(
@@ -1804,7 +1773,7 @@ pub async fn run_cli() {
                     stake_amount,
                     csv,
                     custody_address,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
@@ -1812,36 +1781,77 @@ pub async fn run_cli() {
             RewardDistributorCliAction::Unstake {
                 launcher_id,
                 custody_address,
-                testnet11,
                 fee,
-            } => reward_distributor_unstake(launcher_id, custody_address, testnet11, fee).await,
+            } => reward_distributor_unstake(launcher_id, custody_address, &network, fee).await,
             RewardDistributorCliAction::AddRewards {
                 launcher_id,
                 reward_amount,
-                testnet11,
                 fee,
-            } => reward_distributor_add_rewards(launcher_id, reward_amount, testnet11, fee).await,
+            } => reward_distributor_add_rewards(launcher_id, reward_amount, &network, fee).await,
             RewardDistributorCliAction::InitiatePayout {
                 launcher_id,
                 custody_address,
-                testnet11,
                 fee,
             } => {
-                reward_distributor_initiate_payout(launcher_id, custody_address, testnet11, fee)
+                reward_distributor_initiate_payout(launcher_id, custody_address, &network, fee)
                     .await
             }
-            RewardDistributorCliAction::View {
+            RewardDistributorCliAction::PayoutAll {
                 launcher_id,
-                testnet11,
-            } => reward_distributor_view(launcher_id, testnet11).await,
+                max_cost,
+                fee,
+            } => reward_distributor_payout_all(launcher_id, max_cost, &network, fee).await,
+            RewardDistributorCliAction::View { launcher_id } => {
+                reward_distributor_view(launcher_id, &network).await
+            }
             RewardDistributorCliAction::Refresh {
                 launcher_id,
                 csv,
                 custody_address,
-                testnet11,
                 fee,
+            } => reward_distributor_refresh(launcher_id, csv, custody_address, &network, fee).await,
+            RewardDistributorCliAction::Entry {
+                launcher_id,
+                custody_address,
+                nft,
+                stake_coin,
+                update_time,
+            } => {
+                reward_distributor_entry(
+                    launcher_id,
+                    custody_address,
+                    nft,
+                    stake_coin,
+                    update_time,
+                    &network,
+                )
+                .await
+            }
+            RewardDistributorCliAction::Listen { launcher_ids } => {
+                reward_distributor_listen(launcher_ids, &network).await
+            }
+            RewardDistributorCliAction::Keep {
+                launcher_ids,
+                clawback_address,
+                max_coins,
+                interval_seconds,
+                sync_margin_seconds,
+                max_retries,
+                fee,
+                fee_budget,
             } => {
-                reward_distributor_refresh(launcher_id, csv, custody_address, testnet11, fee).await
+                reward_distributor_keep(
+                    launcher_ids,
+                    clawback_address,
+                    max_coins,
+                    interval_seconds,
+                    sync_margin_seconds,
+                    max_retries,
+                    &network,
+                    fee,
+                    fee_budget,
+                )
+                .await
             }
         },
         Commands::Datastore { action } => match action {
@@ -1849,16 +1859,14 @@ pub async fn run_cli() {
                 csv,
                 label,
                 description,
-                testnet11,
                 fee,
-            } => datastore_launch(csv, label, description, testnet11, fee).await,
+            } => datastore_launch(csv, label, description, &network, fee).await,
             DatastoreCliAction::Update {
                 launcher_id,
                 old_csv,
                 new_csv,
                 label,
                 description,
-                testnet11,
                 fee,
             } => {
                 datastore_update(
@@ -1867,15 +1875,22 @@ pub async fn run_cli() {
                     new_csv,
                     label,
                     description,
-                    testnet11,
+                    &network,
                     fee,
                 )
                 .await
             }
-            DatastoreCliAction::View {
+            DatastoreCliAction::View { launcher_id } => datastore_view(launcher_id, &network).await,
+        },
+        Commands::Db { action } => match action {
+            DbCliAction::Migrate => db_migrate(&network).await,
+            DbCliAction::Check => db_check(&network).await,
+            DbCliAction::Export {
+                registry,
                 launcher_id,
-                testnet11,
-            } => datastore_view(launcher_id, testnet11).await,
+                output,
+            } => db_export(&network, registry, launcher_id, output).await,
+            DbCliAction::Import { input } => db_import(&network, input).await,
         },
     };
 
@@ -255,6 +255,29 @@ pub fn load_datastore_nft_csv<P: AsRef<Path>>(
     Ok(records)
 }
 
+/// Desired managed distributor roster row: `payout_puzzle_hash,shares`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RewardDistributorEntryRecord {
+    #[serde(deserialize_with = "hex_string_to_bytes32")]
+    pub payout_puzzle_hash: Bytes32,
+    pub shares: u64,
+}
+
+pub fn load_reward_distributor_entries_csv<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<RewardDistributorEntryRecord>, CliError> {
+    let file = File::open(path)?;
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(file);
+
+    let mut records = Vec::new();
+    for result in rdr.deserialize() {
+        let record: RewardDistributorEntryRecord = result.map_err(CliError::Csv)?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,11 @@
+mod commands;
+mod migrations;
+mod snapshot;
+
+pub use commands::*;
+pub use migrations::*;
+pub use snapshot::*;
+
 use chia_protocol::Bytes32;
 use chia_puzzle_types::LineageProof;
 use chia_wallet_sdk::{
@@ -11,194 +19,193 @@ use clvmr::{
     serde::{node_from_bytes, node_to_bytes},
     Allocator,
 };
+use futures::lock::{Mutex, MutexGuard};
 use sqlx::{
-    sqlite::{SqlitePoolOptions, SqliteRow},
-    Pool, Row, Sqlite,
+    pool::PoolConnection,
+    sqlite::{
+        SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePoolOptions, SqliteRow,
+    },
+    Pool, Row, Sqlite, Transaction,
 };
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
 use std::time::Duration;
 
-use super::CliError;
+use super::{CliError, HandleSearchFilter, HandleSearchMode, Network};
+
+/// How long a statement waits on another process's write lock before failing.
+pub const DB_BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct Db {
     pool: Pool<Sqlite>,
+    /// Open transition transaction; while set, every statement runs inside it.
+    tx: Mutex<Option<Transaction<'static, Sqlite>>>,
 }
 
-impl Db {
-    pub async fn new(skip_create_tables: bool) -> Result<Self, CliError> {
-        let pool = SqlitePoolOptions::new()
-            .idle_timeout(Duration::from_secs(5))
-            .acquire_timeout(Duration::from_secs(5))
-            .connect("sqlite://data.db?mode=rwc")
-            .await?;
-
-        if !skip_create_tables {
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS slots (
-                    singleton_launcher_id BLOB NOT NULL,
-                    nonce INTEGER NOT NULL,
-                    slot_value_hash BLOB NOT NULL,
-                    spent_block_height INTEGER NOT NULL,
-                    slot_value BLOB NOT NULL,
-                    parent_parent_coin_info BLOB NOT NULL,
-                    parent_inner_puzzle_hash BLOB NOT NULL,
-                    parent_amount INTEGER NOT NULL,
-                    PRIMARY KEY (singleton_launcher_id, nonce, slot_value_hash, parent_parent_coin_info, parent_amount)
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+/// Connection a single statement runs on.
+enum DbConn<'a> {
+    Pooled(PoolConnection<Sqlite>),
+    Transaction(MutexGuard<'a, Option<Transaction<'static, Sqlite>>>),
+}
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS catalog_indexed_slot_values (
-                    asset_id BLOB PRIMARY KEY,
-                    slot_value_hash BLOB NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+impl Deref for DbConn<'_> {
+    type Target = SqliteConnection;
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS xchandles_indexed_slot_values (
-                    launcher_id BLOB NOT NULL,
-                    handle_hash BLOB NOT NULL,
-                    slot_value_hash BLOB NOT NULL,
-                    PRIMARY KEY (launcher_id, handle_hash)
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    fn deref(&self) -> &SqliteConnection {
+        match self {
+            DbConn::Pooled(conn) => conn,
+            DbConn::Transaction(guard) => guard.as_deref().expect("transaction checked in conn()"),
+        }
+    }
+}
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS singleton_coins (
-                    launcher_id BLOB NOT NULL,
-                    coin_id BLOB NOT NULL PRIMARY KEY,
-                    parent_coin_id BLOB,
-                    spent_block_height NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+impl DerefMut for DbConn<'_> {
+    fn deref_mut(&mut self) -> &mut SqliteConnection {
+        match self {
+            DbConn::Pooled(conn) => conn,
+            DbConn::Transaction(guard) => {
+                guard.as_deref_mut().expect("transaction checked in conn()")
+            }
+        }
+    }
+}
 
-            sqlx::query(
-                "
-                CREATE INDEX IF NOT EXISTS idx_singleton_coins_launcher_spent 
-                ON singleton_coins(launcher_id, spent_block_height)
-                ",
-            )
-            .execute(&pool)
-            .await?;
+impl Db {
+    /// Opens the database `network` resolves to and refuses it if it was created for
+    /// another network.
+    pub async fn new(network: &Network, skip_migrations: bool) -> Result<Self, CliError> {
+        let path = network.db_path();
+        let db = Self::open(&path, skip_migrations).await?;
+        db.check_network(network, &path, skip_migrations).await?;
+        Ok(db)
+    }
 
-            sqlx::query(
-                "
-                CREATE INDEX IF NOT EXISTS idx_slots_neighbors 
-                ON slots(singleton_launcher_id, nonce, spent_block_height, slot_value_hash)
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    /// WAL lets CLI commands read while a listener writes; writers still queue up
+    /// behind each other for at most [`DB_BUSY_TIMEOUT`].
+    ///
+    /// Pending [`MIGRATIONS`] are applied first; with `skip_migrations` the schema must
+    /// already be current instead.
+    pub async fn open(path: &Path, skip_migrations: bool) -> Result<Self, CliError> {
+        let db = Self::connect(path).await?;
+        if skip_migrations {
+            let version = db.schema_version().await?;
+            let latest = latest_schema_version();
+            if version != latest {
+                return Err(CliError::Custom(format!(
+                    "database {} is at schema v{version} but this binary expects v{latest}; run `db migrate` first",
+                    path.display()
+                )));
+            }
+        } else {
+            db.migrate().await?;
+        }
+        Ok(db)
+    }
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS reward_distributor_configurations (
-                    launcher_id BLOB PRIMARY KEY,
-                    constants BLOB NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
+    /// Connects without touching the schema; `db migrate` and `db check` start here.
+    pub async fn connect(path: &Path) -> Result<Self, CliError> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(DB_BUSY_TIMEOUT);
+        let pool = SqlitePoolOptions::new()
+            .idle_timeout(Duration::from_secs(5))
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(options)
             .await?;
+        Ok(Self::from_pool(pool))
+    }
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS xchandles_configurations (
-                    launcher_id BLOB PRIMARY KEY,
-                    constants BLOB NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    fn from_pool(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool,
+            tx: Mutex::new(None),
+        }
+    }
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS followed_singletons (
-                    launcher_id BLOB PRIMARY KEY,
-                    record_json TEXT NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    /// Another handle on the same pool with its own transaction slot. Listener APIs read
+    /// through one so they never join the indexer's open transaction or wait on its lock,
+    /// and only ever see committed writes.
+    pub fn reader(&self) -> Self {
+        Self::from_pool(self.pool.clone())
+    }
 
+    /// Stamps a new database with `network`'s genesis challenge, then checks every later
+    /// open against it.
+    async fn check_network(
+        &self,
+        network: &Network,
+        path: &Path,
+        skip_migrations: bool,
+    ) -> Result<(), CliError> {
+        if !skip_migrations {
             sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS handle_slot_records (
-                    registry_launcher_id BLOB NOT NULL,
-                    handle_hash BLOB NOT NULL,
-                    record_json TEXT NOT NULL,
-                    expiration INTEGER NOT NULL DEFAULT 0,
-                    PRIMARY KEY (registry_launcher_id, handle_hash)
-                )
-                ",
+                "INSERT OR IGNORE INTO db_network (id, name, genesis_challenge) VALUES (0, ?, ?)",
             )
-            .execute(&pool)
+            .bind(&network.name)
+            .bind(network.constants.genesis_challenge.to_vec())
+            .execute(&self.pool)
             .await?;
+        }
 
-            sqlx::query(
-                "
-                CREATE INDEX IF NOT EXISTS idx_handle_slots_expiring
-                ON handle_slot_records (registry_launcher_id, expiration)
-                ",
-            )
-            .execute(&pool)
+        let row = sqlx::query("SELECT name, genesis_challenge FROM db_network WHERE id = 0")
+            .fetch_optional(&self.pool)
             .await?;
+        let Some(row) = row else {
+            return Ok(());
+        };
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS registration_records (
-                    registry_launcher_id BLOB NOT NULL,
-                    handle_hash BLOB NOT NULL,
-                    record_json TEXT NOT NULL,
-                    PRIMARY KEY (registry_launcher_id, handle_hash)
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+        let genesis_challenge: Vec<u8> = row.get("genesis_challenge");
+        if genesis_challenge != network.constants.genesis_challenge.to_vec() {
+            let name: String = row.get("name");
+            return Err(CliError::Custom(format!(
+                "database {} belongs to network '{name}', not '{}'; pass --db-path to use another file",
+                path.display(),
+                network.name
+            )));
+        }
+        Ok(())
+    }
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS registration_registry_stats (
-                    registry_launcher_id BLOB PRIMARY KEY,
-                    state_json TEXT NOT NULL
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    async fn conn(&self) -> Result<DbConn<'_>, CliError> {
+        let tx = self.tx.lock().await;
+        if tx.is_some() {
+            return Ok(DbConn::Transaction(tx));
+        }
+        drop(tx);
+        Ok(DbConn::Pooled(self.pool.acquire().await?))
+    }
 
-            sqlx::query(
-                "
-                CREATE TABLE IF NOT EXISTS pending_update_records (
-                    registry_launcher_id BLOB NOT NULL,
-                    handle_hash BLOB NOT NULL,
-                    record_json TEXT NOT NULL,
-                    PRIMARY KEY (registry_launcher_id, handle_hash)
-                )
-                ",
-            )
-            .execute(&pool)
-            .await?;
+    /// Start a transaction on a connection of its own; every following statement through
+    /// this handle joins it until commit or rollback, while [`Db::reader`] handles do not.
+    pub async fn begin_transaction(&self) -> Result<(), CliError> {
+        let mut tx = self.tx.lock().await;
+        if tx.is_some() {
+            return Err(CliError::Custom(
+                "database transaction already open".to_string(),
+            ));
         }
+        *tx = Some(self.pool.begin().await?);
+        Ok(())
+    }
 
-        Ok(Self { pool })
+    pub async fn commit_transaction(&self) -> Result<(), CliError> {
+        let Some(tx) = self.tx.lock().await.take() else {
+            return Err(CliError::Custom(
+                "no database transaction to commit".to_string(),
+            ));
+        };
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Discard every write since `begin_transaction`; a no-op when none is open.
+    pub async fn rollback_transaction(&self) -> Result<(), CliError> {
+        if let Some(tx) = self.tx.lock().await.take() {
+            tx.rollback().await?;
+        }
+        Ok(())
     }
 
     pub async fn save_slot<SV>(
@@ -235,7 +242,7 @@ impl Db {
         .bind(slot.proof.parent_parent_coin_info.to_vec())
         .bind(slot.proof.parent_inner_puzzle_hash.to_vec())
         .bind(slot.proof.parent_amount as i64)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -289,7 +296,7 @@ impl Db {
         .bind(nonce as i64)
         .bind(slot_value_hash.to_vec())
         .bind(spent_block_height)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -319,7 +326,7 @@ impl Db {
         .bind(singleton_launcher_id.to_vec())
         .bind(nonce as i64)
         .bind(slot_value_hash.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -345,7 +352,7 @@ impl Db {
                 .bind(singleton_launcher_id.to_vec())
                 .bind(nonce as i64)
                 .bind(spent_block_height)
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
                 .await
                 .map_err(CliError::Sqlx)?;
 
@@ -370,7 +377,7 @@ impl Db {
         .bind(nonce as i64)
         .bind(slot_value_hash.to_vec())
         .bind(spent_block_height)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -387,7 +394,7 @@ impl Db {
             ",
         )
         .bind(singleton_launcher_id.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -400,7 +407,7 @@ impl Db {
             DELETE FROM catalog_indexed_slot_values
             ",
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -418,7 +425,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -441,7 +448,7 @@ impl Db {
         .bind(singleton_launcher_id.to_vec())
         .bind(nonce as i64)
         .bind(slot_value_hash.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -462,7 +469,7 @@ impl Db {
         )
         .bind(asset_id.to_vec())
         .bind(slot_value_hash.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -485,7 +492,7 @@ impl Db {
         .bind(launcher_id.to_vec())
         .bind(handle_hash.to_vec())
         .bind(slot_value_hash.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -502,7 +509,7 @@ impl Db {
             ",
         )
         .bind(asset_id.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -527,7 +534,7 @@ impl Db {
         )
         .bind(launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -552,7 +559,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -607,7 +614,10 @@ impl Db {
         .bind(launcher_id.to_vec())
         .bind(asset_id.to_vec());
 
-        let rows = query.fetch_all(&self.pool).await.map_err(CliError::Sqlx)?;
+        let rows = query
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
 
         let mut left_slots = Vec::new();
         let mut right_slots = Vec::new();
@@ -671,7 +681,10 @@ impl Db {
         .bind(handle_hash.to_vec())
         .bind(XchandlesSlotNonce::HANDLE.to_u64() as i64);
 
-        let rows = query.fetch_all(&self.pool).await.map_err(CliError::Sqlx)?;
+        let rows = query
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
 
         let mut left_slots = Vec::new();
         let mut right_slots = Vec::new();
@@ -703,7 +716,7 @@ impl Db {
             ",
         )
         .bind(spent_block_height_threshold)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -717,7 +730,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -734,7 +747,7 @@ impl Db {
             ",
         )
         .bind(spent_block_height_threshold)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -755,7 +768,7 @@ impl Db {
         )
         .bind(coin_record.confirmed_block_index)
         .bind(coin_record.coin.parent_coin_info.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -770,7 +783,7 @@ impl Db {
         .bind(coin_record.coin.coin_id().to_vec())
         .bind(coin_record.coin.parent_coin_info.to_vec())
         .bind(coin_record.spent_block_index)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -787,7 +800,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -801,6 +814,70 @@ impl Db {
         Ok(Some((coin_id, parent_coin_id)))
     }
 
+    /// Rewind a singleton's saved coins and slots to the state live before `height`, so the
+    /// next sync walks every spend from that height again.
+    pub async fn rewind_singleton_to_before(
+        &self,
+        launcher_id: Bytes32,
+        height: u32,
+    ) -> Result<(), CliError> {
+        // A slot's parent is the singleton coin whose spend created it, so slots whose
+        // parent-parent is the parent of a coin spent at or after `height` were orphaned.
+        sqlx::query(
+            "
+            DELETE FROM slots
+            WHERE singleton_launcher_id = ?1 AND parent_parent_coin_info IN (
+                SELECT parent_coin_id FROM singleton_coins WHERE launcher_id = ?1 AND spent_block_height >= ?2
+            )
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+
+        sqlx::query(
+            "
+            DELETE FROM singleton_coins
+            WHERE launcher_id = ?1 AND parent_coin_id IN (
+                SELECT coin_id FROM singleton_coins WHERE launcher_id = ?1 AND spent_block_height >= ?2
+            )
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+
+        sqlx::query(
+            "
+            UPDATE singleton_coins SET spent_block_height = 0
+            WHERE launcher_id = ?1 AND spent_block_height >= ?2
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+
+        sqlx::query(
+            "
+            UPDATE slots SET spent_block_height = 0
+            WHERE singleton_launcher_id = ?1 AND spent_block_height >= ?2
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+
+        Ok(())
+    }
+
     pub async fn save_reward_distributor_configuration(
         &self,
         allocator: &mut Allocator,
@@ -818,7 +895,7 @@ impl Db {
         )
         .bind(launcher_id.to_vec())
         .bind(constants_bytes)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -841,7 +918,7 @@ impl Db {
         )
         .bind(constants.launcher_id.to_vec())
         .bind(constants_bytes)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -859,7 +936,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -885,7 +962,7 @@ impl Db {
             ",
         )
         .bind(launcher_id.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -906,7 +983,7 @@ impl Db {
     ) -> Result<Option<String>, CliError> {
         let row = sqlx::query("SELECT record_json FROM followed_singletons WHERE launcher_id = ?1")
             .bind(launcher_id.to_vec())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(CliError::Sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("record_json")))
@@ -926,7 +1003,7 @@ impl Db {
         )
         .bind(launcher_id.to_vec())
         .bind(record_json)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -935,7 +1012,7 @@ impl Db {
     pub async fn delete_followed_singleton(&self, launcher_id: Bytes32) -> Result<(), CliError> {
         sqlx::query("DELETE FROM followed_singletons WHERE launcher_id = ?1")
             .bind(launcher_id.to_vec())
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await
             .map_err(CliError::Sqlx)?;
         Ok(())
@@ -943,7 +1020,7 @@ impl Db {
 
     pub async fn all_followed_singleton_ids(&self) -> Result<Vec<Bytes32>, CliError> {
         let rows = sqlx::query("SELECT launcher_id FROM followed_singletons")
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.conn().await?)
             .await
             .map_err(CliError::Sqlx)?;
         rows.iter()
@@ -964,18 +1041,21 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("record_json")))
     }
 
+    /// `launchers` is the current state's `(owner, resolved)` pair; `None` clears the
+    /// slot from the reverse index.
     pub async fn upsert_handle_slot_record_json(
         &self,
         registry_launcher_id: Bytes32,
         handle_hash: Bytes32,
         record_json: &str,
         expiration: u64,
+        launchers: Option<(Bytes32, Bytes32)>,
     ) -> Result<(), CliError> {
         sqlx::query(
             "
@@ -993,12 +1073,90 @@ impl Db {
         .bind(handle_hash.to_vec())
         .bind(record_json)
         .bind(expiration as i64)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        self.set_handle_slot_launchers(registry_launcher_id, handle_hash, launchers)
+            .await
+    }
+
+    async fn set_handle_slot_launchers(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        launchers: Option<(Bytes32, Bytes32)>,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            DELETE FROM handle_slot_launchers
+            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
+        let Some((owner, resolved)) = launchers else {
+            return Ok(());
+        };
+        for (role, launcher_id) in [("owner", owner), ("resolved", resolved)] {
+            sqlx::query(
+                "
+                INSERT INTO handle_slot_launchers (
+                    registry_launcher_id, handle_hash, role, launcher_id
+                )
+                VALUES (?1, ?2, ?3, ?4)
+                ",
+            )
+            .bind(registry_launcher_id.to_vec())
+            .bind(handle_hash.to_vec())
+            .bind(role)
+            .bind(launcher_id.to_vec())
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        }
         Ok(())
     }
 
+    /// `(handle_slot_record_json, handle)` for named slots whose current `role`
+    /// launcher is `launcher_id`, ordered by handle then registry.
+    pub async fn list_named_handle_slots_by_launcher(
+        &self,
+        role: &str,
+        launcher_id: Bytes32,
+    ) -> Result<Vec<(String, String)>, CliError> {
+        let rows = sqlx::query(
+            "
+            SELECT h.record_json AS record_json, s.handle AS handle
+            FROM handle_slot_launchers l
+            JOIN handle_slot_records h
+              ON h.registry_launcher_id = l.registry_launcher_id
+             AND h.handle_hash = l.handle_hash
+            JOIN handle_search_index s
+              ON s.registry_launcher_id = l.registry_launcher_id
+             AND s.handle_hash = l.handle_hash
+            WHERE l.role = ?1 AND l.launcher_id = ?2
+            ORDER BY s.handle ASC, l.registry_launcher_id ASC
+            ",
+        )
+        .bind(role)
+        .bind(launcher_id.to_vec())
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    r.try_get::<String, _>("record_json")
+                        .map_err(CliError::Sqlx)?,
+                    r.try_get::<String, _>("handle").map_err(CliError::Sqlx)?,
+                ))
+            })
+            .collect()
+    }
+
     pub async fn list_named_handle_slots_in_expiration_window(
         &self,
         registry_launcher_id: Bytes32,
@@ -1041,7 +1199,7 @@ impl Db {
         .bind(after_expiration as i64)
         .bind(after_handle)
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
 
@@ -1058,31 +1216,138 @@ impl Db {
         Ok(out)
     }
 
-    pub async fn delete_handle_slot_record(
+    /// `(handle_slot_record_json, handle)` for named slots matching `filter`'s expiration
+    /// window and text constraint, ordered by handle. Fuzzy terms are only narrowed by
+    /// length here; callers re-check every row with `filter.matches`.
+    pub async fn search_named_handle_slots(
         &self,
         registry_launcher_id: Bytes32,
-        handle_hash: Bytes32,
-    ) -> Result<(), CliError> {
-        sqlx::query(
+        filter: &HandleSearchFilter,
+        limit: usize,
+    ) -> Result<Vec<(String, String)>, CliError> {
+        let (min_len, max_len) = filter.length_bounds();
+        let text_clause = match filter.mode {
+            // Handles are `[a-z0-9]`, so every extension of the term sorts below `term || '{'`.
+            HandleSearchMode::Prefix => "s.handle >= ?4 AND s.handle < ?4 || '{'",
+            HandleSearchMode::Substring => "instr(s.handle, ?4) > 0",
+            HandleSearchMode::Fuzzy => "?4 IS NOT NULL",
+        };
+        // Fuzzy rows are filtered after the query, so the limit cannot be pushed down.
+        let sql_limit = match filter.mode {
+            HandleSearchMode::Fuzzy => -1,
+            _ => limit as i64,
+        };
+        let sql = format!(
             "
-            DELETE FROM handle_slot_records
-            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+            SELECT h.record_json AS record_json, s.handle AS handle
+            FROM handle_search_index s
+            JOIN handle_slot_records h
+              ON h.registry_launcher_id = s.registry_launcher_id
+             AND h.handle_hash = s.handle_hash
+            WHERE s.registry_launcher_id = ?1
+              AND h.expiration >= ?2 AND h.expiration <= ?3
+              AND length(s.handle) BETWEEN ?5 AND ?6
+              AND {text_clause}
+            ORDER BY s.handle ASC
+            LIMIT ?7
+            "
+        );
+        let rows = sqlx::query(&sql)
+            .bind(registry_launcher_id.to_vec())
+            .bind(filter.min_expiration.min(i64::MAX as u64) as i64)
+            .bind(filter.max_expiration.min(i64::MAX as u64) as i64)
+            .bind(filter.term.as_str())
+            .bind(min_len as i64)
+            .bind(max_len as i64)
+            .bind(sql_limit)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            let Ok(json) = row.try_get::<String, _>("record_json") else {
+                continue;
+            };
+            let Ok(handle) = row.try_get::<String, _>("handle") else {
+                continue;
+            };
+            out.push((json, handle));
+        }
+        Ok(out)
+    }
+
+    /// `(handle_length, active, expired)` per length for one registry's named slots.
+    pub async fn count_named_handle_slots_by_length(
+        &self,
+        registry_launcher_id: Bytes32,
+        now: u64,
+    ) -> Result<Vec<(usize, u64, u64)>, CliError> {
+        let rows = sqlx::query(
+            "
+            SELECT length(s.handle) AS handle_length,
+                   SUM(CASE WHEN h.expiration > ?2 THEN 1 ELSE 0 END) AS active,
+                   SUM(CASE WHEN h.expiration > ?2 THEN 0 ELSE 1 END) AS expired
+            FROM handle_search_index s
+            JOIN handle_slot_records h
+              ON h.registry_launcher_id = s.registry_launcher_id
+             AND h.handle_hash = s.handle_hash
+            -- Only slots with a current state carry launcher rows.
+            JOIN handle_slot_launchers l
+              ON l.registry_launcher_id = s.registry_launcher_id
+             AND l.handle_hash = s.handle_hash
+             AND l.role = 'owner'
+            WHERE s.registry_launcher_id = ?1 AND s.handle != ''
+            GROUP BY handle_length
+            ORDER BY handle_length ASC
             ",
         )
         .bind(registry_launcher_id.to_vec())
-        .bind(handle_hash.to_vec())
-        .execute(&self.pool)
+        .bind(now.min(i64::MAX as u64) as i64)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
-        Ok(())
-    }
 
-    pub async fn all_handle_slot_keys(&self) -> Result<Vec<(Bytes32, Bytes32)>, CliError> {
-        let rows = sqlx::query("SELECT registry_launcher_id, handle_hash FROM handle_slot_records")
-            .fetch_all(&self.pool)
-            .await
-            .map_err(CliError::Sqlx)?;
-        rows.iter()
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            let (Ok(length), Ok(active), Ok(expired)) = (
+                row.try_get::<i64, _>("handle_length"),
+                row.try_get::<i64, _>("active"),
+                row.try_get::<i64, _>("expired"),
+            ) else {
+                continue;
+            };
+            out.push((length as usize, active as u64, expired as u64));
+        }
+        Ok(out)
+    }
+
+    pub async fn delete_handle_slot_record(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            DELETE FROM handle_slot_records
+            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        self.set_handle_slot_launchers(registry_launcher_id, handle_hash, None)
+            .await
+    }
+
+    pub async fn all_handle_slot_keys(&self) -> Result<Vec<(Bytes32, Bytes32)>, CliError> {
+        let rows = sqlx::query("SELECT registry_launcher_id, handle_hash FROM handle_slot_records")
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        rows.iter()
             .map(|r| {
                 Ok((
                     column_to_bytes32(r.get::<&[u8], _>("registry_launcher_id"))?,
@@ -1105,7 +1370,7 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("record_json")))
@@ -1128,7 +1393,33 @@ impl Db {
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
         .bind(record_json)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+
+        sqlx::query(
+            "
+            DELETE FROM handle_search_index
+            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        sqlx::query(
+            "
+            INSERT INTO handle_search_index (registry_launcher_id, handle_hash, handle)
+            SELECT ?1, ?2, json_extract(?3, '$.current.handle')
+            WHERE json_extract(?3, '$.current.handle') IS NOT NULL
+              AND json_extract(?3, '$.current.handle') != ''
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .bind(record_json)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -1147,7 +1438,18 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        sqlx::query(
+            "
+            DELETE FROM handle_search_index
+            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -1156,7 +1458,7 @@ impl Db {
     pub async fn all_registration_keys(&self) -> Result<Vec<(Bytes32, Bytes32)>, CliError> {
         let rows =
             sqlx::query("SELECT registry_launcher_id, handle_hash FROM registration_records")
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
                 .await
                 .map_err(CliError::Sqlx)?;
         rows.iter()
@@ -1180,7 +1482,7 @@ impl Db {
             ",
         )
         .bind(registry_launcher_id.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("state_json")))
@@ -1201,7 +1503,7 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(state_json)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -1209,7 +1511,7 @@ impl Db {
 
     pub async fn all_registration_stats_registry_ids(&self) -> Result<Vec<Bytes32>, CliError> {
         let rows = sqlx::query("SELECT registry_launcher_id FROM registration_registry_stats")
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.conn().await?)
             .await
             .map_err(CliError::Sqlx)?;
         rows.iter()
@@ -1217,6 +1519,55 @@ impl Db {
             .collect()
     }
 
+    /// Records one registry spend's activity; `false` when that coin already has one.
+    pub async fn insert_registration_activity_delta_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        registry_coin_id: Bytes32,
+        confirmation_height: u32,
+        delta_json: &str,
+    ) -> Result<bool, CliError> {
+        let result = sqlx::query(
+            "
+            INSERT OR IGNORE INTO registration_activity_deltas
+                (registry_launcher_id, registry_coin_id, confirmation_height, delta_json)
+            VALUES (?1, ?2, ?3, ?4)
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(registry_coin_id.to_vec())
+        .bind(confirmation_height)
+        .bind(delta_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Deletes and returns a registry's activity deltas confirmed at or after `from_height`.
+    pub async fn take_registration_activity_deltas_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        from_height: u32,
+    ) -> Result<Vec<String>, CliError> {
+        let rows = sqlx::query(
+            "
+            DELETE FROM registration_activity_deltas
+            WHERE registry_launcher_id = ?1 AND confirmation_height >= ?2
+            RETURNING delta_json
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(from_height)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| r.get::<String, _>("delta_json"))
+            .collect())
+    }
+
     pub async fn get_pending_update_record_json(
         &self,
         registry_launcher_id: Bytes32,
@@ -1230,7 +1581,7 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("record_json")))
@@ -1253,7 +1604,7 @@ impl Db {
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
         .bind(record_json)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -1272,7 +1623,7 @@ impl Db {
         )
         .bind(registry_launcher_id.to_vec())
         .bind(handle_hash.to_vec())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(CliError::Sqlx)?;
         Ok(())
@@ -1281,7 +1632,7 @@ impl Db {
     pub async fn all_pending_update_keys(&self) -> Result<Vec<(Bytes32, Bytes32)>, CliError> {
         let rows =
             sqlx::query("SELECT registry_launcher_id, handle_hash FROM pending_update_records")
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
                 .await
                 .map_err(CliError::Sqlx)?;
         rows.iter()
@@ -1293,6 +1644,525 @@ impl Db {
             })
             .collect()
     }
+
+    pub async fn upsert_handle_history_entry_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        confirmation_height: u32,
+        log_index: u32,
+        entry_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO handle_history
+                (registry_launcher_id, handle_hash, confirmation_height, log_index, entry_json)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(registry_launcher_id, handle_hash, confirmation_height, log_index)
+            DO UPDATE SET entry_json = excluded.entry_json
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .bind(confirmation_height)
+        .bind(log_index)
+        .bind(entry_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    /// Newest first; `before` is an exclusive `(confirmation_height, log_index)` bound.
+    pub async fn list_handle_history_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        before: Option<(u32, u32)>,
+        limit: usize,
+    ) -> Result<Vec<String>, CliError> {
+        let (before_height, before_index) = before.unwrap_or((u32::MAX, u32::MAX));
+        let rows = sqlx::query(
+            "
+            SELECT entry_json FROM handle_history
+            WHERE registry_launcher_id = ?1 AND handle_hash = ?2
+              AND (confirmation_height < ?3
+                   OR (confirmation_height = ?3 AND log_index < ?4))
+            ORDER BY confirmation_height DESC, log_index DESC
+            LIMIT ?5
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(handle_hash.to_vec())
+        .bind(before_height)
+        .bind(before_index)
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| r.get::<String, _>("entry_json"))
+            .collect())
+    }
+
+    pub async fn delete_handle_history_from(&self, from_height: u32) -> Result<(), CliError> {
+        sqlx::query("DELETE FROM handle_history WHERE confirmation_height >= ?1")
+            .bind(from_height)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn get_catalog_registry_record_json(
+        &self,
+        launcher_id: Bytes32,
+    ) -> Result<Option<String>, CliError> {
+        let row =
+            sqlx::query("SELECT record_json FROM catalog_registry_records WHERE launcher_id = ?1")
+                .bind(launcher_id.to_vec())
+                .fetch_optional(&mut *self.conn().await?)
+                .await
+                .map_err(CliError::Sqlx)?;
+        Ok(row.map(|r| r.get::<String, _>("record_json")))
+    }
+
+    pub async fn upsert_catalog_registry_record_json(
+        &self,
+        launcher_id: Bytes32,
+        record_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO catalog_registry_records (launcher_id, record_json)
+            VALUES (?1, ?2)
+            ON CONFLICT(launcher_id) DO UPDATE SET record_json = excluded.record_json
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(record_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn delete_catalog_registry_record(
+        &self,
+        launcher_id: Bytes32,
+    ) -> Result<(), CliError> {
+        sqlx::query("DELETE FROM catalog_registry_records WHERE launcher_id = ?1")
+            .bind(launcher_id.to_vec())
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn all_catalog_registry_ids(&self) -> Result<Vec<Bytes32>, CliError> {
+        let rows = sqlx::query("SELECT launcher_id FROM catalog_registry_records")
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| column_to_bytes32(r.get::<&[u8], _>("launcher_id")))
+            .collect()
+    }
+
+    pub async fn get_catalog_cat_record_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+    ) -> Result<Option<String>, CliError> {
+        let row = sqlx::query(
+            "
+            SELECT record_json FROM catalog_cat_records
+            WHERE registry_launcher_id = ?1 AND asset_id = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(asset_id.to_vec())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(row.map(|r| r.get::<String, _>("record_json")))
+    }
+
+    pub async fn upsert_catalog_cat_record_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+        record_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO catalog_cat_records (registry_launcher_id, asset_id, record_json)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(registry_launcher_id, asset_id) DO UPDATE SET
+                record_json = excluded.record_json
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(asset_id.to_vec())
+        .bind(record_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn delete_catalog_cat_record(
+        &self,
+        registry_launcher_id: Bytes32,
+        asset_id: Bytes32,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            DELETE FROM catalog_cat_records
+            WHERE registry_launcher_id = ?1 AND asset_id = ?2
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(asset_id.to_vec())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn all_catalog_cat_keys(&self) -> Result<Vec<(Bytes32, Bytes32)>, CliError> {
+        let rows = sqlx::query("SELECT registry_launcher_id, asset_id FROM catalog_cat_records")
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    column_to_bytes32(r.get::<&[u8], _>("registry_launcher_id"))?,
+                    column_to_bytes32(r.get::<&[u8], _>("asset_id"))?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn get_catalog_registration_stats_json(
+        &self,
+        registry_launcher_id: Bytes32,
+    ) -> Result<Option<String>, CliError> {
+        let row = sqlx::query(
+            "
+            SELECT state_json FROM catalog_registration_stats
+            WHERE registry_launcher_id = ?1
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(row.map(|r| r.get::<String, _>("state_json")))
+    }
+
+    pub async fn upsert_catalog_registration_stats_json(
+        &self,
+        registry_launcher_id: Bytes32,
+        state_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO catalog_registration_stats (registry_launcher_id, state_json)
+            VALUES (?1, ?2)
+            ON CONFLICT(registry_launcher_id) DO UPDATE SET
+                state_json = excluded.state_json
+            ",
+        )
+        .bind(registry_launcher_id.to_vec())
+        .bind(state_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn all_catalog_registration_stats_registry_ids(
+        &self,
+    ) -> Result<Vec<Bytes32>, CliError> {
+        let rows = sqlx::query("SELECT registry_launcher_id FROM catalog_registration_stats")
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| column_to_bytes32(r.get::<&[u8], _>("registry_launcher_id")))
+            .collect()
+    }
+
+    pub async fn get_reward_distributor_record_json(
+        &self,
+        launcher_id: Bytes32,
+    ) -> Result<Option<String>, CliError> {
+        let row = sqlx::query(
+            "SELECT record_json FROM reward_distributor_records WHERE launcher_id = ?1",
+        )
+        .bind(launcher_id.to_vec())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(row.map(|r| r.get::<String, _>("record_json")))
+    }
+
+    pub async fn upsert_reward_distributor_record_json(
+        &self,
+        launcher_id: Bytes32,
+        record_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO reward_distributor_records (launcher_id, record_json)
+            VALUES (?1, ?2)
+            ON CONFLICT(launcher_id) DO UPDATE SET record_json = excluded.record_json
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(record_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn delete_reward_distributor_record(
+        &self,
+        launcher_id: Bytes32,
+    ) -> Result<(), CliError> {
+        sqlx::query("DELETE FROM reward_distributor_records WHERE launcher_id = ?1")
+            .bind(launcher_id.to_vec())
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn all_reward_distributor_ids(&self) -> Result<Vec<Bytes32>, CliError> {
+        let rows = sqlx::query("SELECT launcher_id FROM reward_distributor_records")
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| column_to_bytes32(r.get::<&[u8], _>("launcher_id")))
+            .collect()
+    }
+
+    /// `slot_json` of every `kind` slot of `launcher_id`, oldest first.
+    pub async fn list_reward_distributor_slots_of_kind(
+        &self,
+        launcher_id: Bytes32,
+        kind: &str,
+    ) -> Result<Vec<String>, CliError> {
+        let rows = sqlx::query(
+            "
+            SELECT slot_json FROM reward_distributor_slots
+            WHERE launcher_id = ?1 AND kind = ?2
+            ORDER BY created_height ASC, value_hash ASC
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(kind)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| r.get::<String, _>("slot_json"))
+            .collect())
+    }
+
+    /// `slot_json` of every slot of `launcher_id` hinted with `hint`, oldest first.
+    pub async fn list_reward_distributor_slots_by_hint(
+        &self,
+        launcher_id: Bytes32,
+        hint: Bytes32,
+    ) -> Result<Vec<String>, CliError> {
+        let rows = sqlx::query(
+            "
+            SELECT slot_json FROM reward_distributor_slots
+            WHERE launcher_id = ?1 AND hint = ?2
+            ORDER BY created_height ASC, value_hash ASC
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(hint.to_vec())
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| r.get::<String, _>("slot_json"))
+            .collect())
+    }
+
+    /// `slot_json` of slots of `launcher_id` created or spent at or above `height`.
+    pub async fn list_reward_distributor_slots_touched_from(
+        &self,
+        launcher_id: Bytes32,
+        height: u32,
+    ) -> Result<Vec<String>, CliError> {
+        let rows = sqlx::query(
+            "
+            SELECT slot_json FROM reward_distributor_slots
+            WHERE launcher_id = ?1 AND (created_height >= ?2 OR spent_height >= ?2)
+            ORDER BY created_height ASC, value_hash ASC
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(height)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| r.get::<String, _>("slot_json"))
+            .collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upsert_reward_distributor_slot_json(
+        &self,
+        launcher_id: Bytes32,
+        value_hash: Bytes32,
+        created_height: u32,
+        kind: &str,
+        hint: Bytes32,
+        spent_height: Option<u32>,
+        slot_json: &str,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            INSERT INTO reward_distributor_slots (
+                launcher_id, value_hash, created_height, kind, hint, spent_height, slot_json
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(launcher_id, value_hash, created_height) DO UPDATE SET
+                spent_height = excluded.spent_height,
+                slot_json = excluded.slot_json
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(value_hash.to_vec())
+        .bind(created_height)
+        .bind(kind)
+        .bind(hint.to_vec())
+        .bind(spent_height)
+        .bind(slot_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn delete_reward_distributor_slot(
+        &self,
+        launcher_id: Bytes32,
+        value_hash: Bytes32,
+        created_height: u32,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            DELETE FROM reward_distributor_slots
+            WHERE launcher_id = ?1 AND value_hash = ?2 AND created_height = ?3
+            ",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(value_hash.to_vec())
+        .bind(created_height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    pub async fn delete_reward_distributor_slots_spent_before(
+        &self,
+        kind: &str,
+        height: u32,
+    ) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            DELETE FROM reward_distributor_slots
+            WHERE kind = ?1 AND spent_height IS NOT NULL AND spent_height < ?2
+            ",
+        )
+        .bind(kind)
+        .bind(height)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        Ok(())
+    }
+
+    /// Persisted `(height, header_hash)` window for `listener`, oldest first.
+    pub async fn header_checkpoints(
+        &self,
+        listener: &str,
+    ) -> Result<Vec<(u32, Bytes32)>, CliError> {
+        let rows = sqlx::query(
+            "SELECT height, header_hash FROM header_checkpoints WHERE listener = ?1 ORDER BY height ASC",
+        )
+        .bind(listener)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(CliError::Sqlx)?;
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    r.get::<i64, _>("height") as u32,
+                    column_to_bytes32(r.get::<&[u8], _>("header_hash"))?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Replace the persisted window for `listener` with `window`.
+    pub async fn save_header_checkpoints(
+        &self,
+        listener: &str,
+        window: &[(u32, Bytes32)],
+    ) -> Result<(), CliError> {
+        sqlx::query("DELETE FROM header_checkpoints WHERE listener = ?1")
+            .bind(listener)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+
+        for (height, header_hash) in window {
+            sqlx::query(
+                "INSERT INTO header_checkpoints (listener, height, header_hash) VALUES (?1, ?2, ?3)",
+            )
+            .bind(listener)
+            .bind(*height)
+            .bind(header_hash.to_vec())
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop checkpoints for `listener` at or above `height` after a reorganization.
+    pub async fn delete_header_checkpoints_from(
+        &self,
+        listener: &str,
+        height: u32,
+    ) -> Result<(), CliError> {
+        sqlx::query("DELETE FROM header_checkpoints WHERE listener = ?1 AND height >= ?2")
+            .bind(listener)
+            .bind(height)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(CliError::Sqlx)?;
+
+        Ok(())
+    }
 }
 
 pub fn column_to_bytes32(column_value: &[u8]) -> Result<Bytes32, CliError> {
@@ -1302,3 +2172,130 @@ pub fn column_to_bytes32(column_value: &[u8]) -> Result<Bytes32, CliError> {
             .map_err(|_| CliError::DbColumnParse())?,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn refuses_a_database_created_for_another_network() {
+        let path = std::env::temp_dir().join(format!("slot-machine-{}.db", uuid::Uuid::new_v4()));
+        let mut mainnet = Network::mainnet();
+        mainnet.db_path = Some(path.clone());
+        let mut testnet11 = Network::testnet11();
+        testnet11.db_path = Some(path.clone());
+
+        drop(Db::new(&mainnet, false).await.unwrap());
+        assert!(Db::new(&mainnet, true).await.is_ok());
+        assert!(Db::new(&testnet11, false).await.is_err());
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&Db::new(&mainnet, true).await.unwrap().pool)
+            .await
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[tokio::test]
+    async fn readers_only_see_committed_transitions() {
+        let path = std::env::temp_dir().join(format!("slot-machine-{}.db", uuid::Uuid::new_v4()));
+        let mut network = Network::testnet11();
+        network.db_path = Some(path.clone());
+        let db = Db::new(&network, false).await.unwrap();
+        let reader = db.reader();
+        let registry = Bytes32::new([0x01; 32]);
+
+        db.begin_transaction().await.unwrap();
+        db.upsert_registration_stats_json(registry, "{}")
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_registration_stats_json(registry).await.unwrap(),
+            Some("{}".to_string())
+        );
+        assert_eq!(
+            reader.get_registration_stats_json(registry).await.unwrap(),
+            None
+        );
+
+        db.commit_transaction().await.unwrap();
+        assert_eq!(
+            reader.get_registration_stats_json(registry).await.unwrap(),
+            Some("{}".to_string())
+        );
+
+        drop((db, reader));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[tokio::test]
+    async fn rewind_drops_slots_created_by_orphaned_spends() {
+        let path = std::env::temp_dir().join(format!("slot-machine-{}.db", uuid::Uuid::new_v4()));
+        let mut network = Network::testnet11();
+        network.db_path = Some(path.clone());
+        let db = Db::new(&network, false).await.unwrap();
+
+        let launcher_id = Bytes32::new([0x01; 32]);
+        let b32 = |fill: u8| Bytes32::new([fill; 32]).to_vec();
+        // Singleton lineage 0xa0 -> 0xa1 (spent at 10) -> 0xa2 (spent at 20) -> 0xa3.
+        for (coin_id, parent_coin_id, spent_block_height) in
+            [(0xa1, 0xa0, 10_u32), (0xa2, 0xa1, 20), (0xa3, 0xa2, 0)]
+        {
+            sqlx::query(
+                "INSERT INTO singleton_coins (launcher_id, coin_id, parent_coin_id, spent_block_height) VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(launcher_id.to_vec())
+            .bind(b32(coin_id))
+            .bind(b32(parent_coin_id))
+            .bind(spent_block_height)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        }
+        // One slot from the spend at 10 (parent 0xa1) and one from the spend at 20 (parent 0xa2).
+        for (value_hash, parent_parent_coin_info) in [(0xb1, 0xa0), (0xb2, 0xa1)] {
+            sqlx::query(
+                "
+                INSERT INTO slots (
+                    singleton_launcher_id, nonce, slot_value_hash, spent_block_height,
+                    slot_value, parent_parent_coin_info, parent_inner_puzzle_hash, parent_amount
+                ) VALUES (?1, 1, ?2, 0, x'80', ?3, ?4, 1)
+                ",
+            )
+            .bind(launcher_id.to_vec())
+            .bind(b32(value_hash))
+            .bind(b32(parent_parent_coin_info))
+            .bind(b32(0xcc))
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        }
+
+        db.rewind_singleton_to_before(launcher_id, 15)
+            .await
+            .unwrap();
+
+        let slots: Vec<Vec<u8>> = sqlx::query_scalar("SELECT slot_value_hash FROM slots")
+            .fetch_all(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(slots, vec![b32(0xb1)]);
+        let coins: Vec<Vec<u8>> =
+            sqlx::query_scalar("SELECT coin_id FROM singleton_coins ORDER BY coin_id")
+                .fetch_all(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(coins, vec![b32(0xa1), b32(0xa2)]);
+
+        drop(db);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{get_chain_client, hex_string_to_bytes32, CliError, Db, Network};
+
+use super::{latest_schema_version, DbSnapshot, SnapshotRegistry, MIGRATIONS};
+
+pub async fn db_migrate(network: &Network) -> Result<(), CliError> {
+    let path = network.db_path();
+    let db = Db::connect(&path).await?;
+
+    let version = db.schema_version().await?;
+    println!(
+        "Database {} is at schema v{} (latest v{}).",
+        path.display(),
+        version,
+        latest_schema_version()
+    );
+
+    let applied = db.migrate().await?;
+    db.check_network(network, &path, false).await?;
+
+    if applied.is_empty() {
+        println!("Nothing to migrate.");
+    }
+    for migration in applied {
+        println!("  applied v{} {}", migration.version, migration.name);
+    }
+    Ok(())
+}
+
+pub async fn db_check(network: &Network) -> Result<(), CliError> {
+    let path = network.db_path();
+    let db = Db::connect(&path).await?;
+    let report = db.check_schema().await?;
+
+    println!(
+        "Database {} is at schema v{} (latest v{}).",
+        path.display(),
+        report.version,
+        report.latest
+    );
+    for migration in MIGRATIONS.iter().filter(|m| m.version > report.version) {
+        println!("  pending v{} {}", migration.version, migration.name);
+    }
+    for mismatch in &report.mismatches {
+        println!("  {mismatch}");
+    }
+
+    if !report.is_current() {
+        return Err(CliError::Custom(
+            "database schema is not current; run `db migrate`".to_string(),
+        ));
+    }
+    println!("Schema OK.");
+    Ok(())
+}
+
+pub async fn db_export(
+    network: &Network,
+    registry: SnapshotRegistry,
+    launcher_id: Option<String>,
+    output: String,
+) -> Result<(), CliError> {
+    let launcher_id = match (registry, launcher_id) {
+        (_, Some(launcher_id)) => hex_string_to_bytes32(&launcher_id)?,
+        (SnapshotRegistry::Catalog, None) => network.catalog_constants().launcher_id,
+        (SnapshotRegistry::Xchandles, None) => {
+            return Err(CliError::Custom(
+                "--launcher-id is required for an XCHandles snapshot".to_string(),
+            ))
+        }
+    };
+
+    let db = Db::new(network, false).await?;
+    let snapshot = db.export_snapshot(network, registry, launcher_id).await?;
+    std::fs::write(&output, serde_json::to_vec(&snapshot)?)?;
+
+    let rows: usize = snapshot.tables.iter().map(|table| table.rows.len()).sum();
+    println!(
+        "Exported {rows} rows for registry {} at height {} (header {}) to {output}.",
+        hex::encode(launcher_id),
+        snapshot.height,
+        hex::encode(snapshot.header_hash)
+    );
+    Ok(())
+}
+
+pub async fn db_import(network: &Network, input: String) -> Result<(), CliError> {
+    let snapshot: DbSnapshot = serde_json::from_slice(&std::fs::read(&input)?)?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
+    db.import_snapshot(network, &snapshot, &client).await?;
+
+    println!(
+        "Imported registry {} at height {}; the {} listener resumes from there.",
+        hex::encode(snapshot.launcher_id),
+        snapshot.height,
+        snapshot.registry.listener()
+    );
+    Ok(())
+}
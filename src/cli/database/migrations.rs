@@ -0,0 +1,753 @@
+//! Ordered schema migrations for the local SQLite cache.
+//!
+//! Every migration runs once, in its own transaction, and records its version in
+//! `schema_migrations`. Steps stay idempotent (`IF NOT EXISTS`, [`AddColumnIfMissing`])
+//! so a database written before versioning existed is adopted by replaying them.
+//! Append new migrations; never edit one that has shipped.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Pool, Row, Sqlite, SqliteConnection,
+};
+
+use super::Db;
+use crate::{CliError, HandleSlotRecord};
+
+use MigrationStep::{AddColumnIfMissing, BackfillHandleSlotLaunchers, Sql};
+
+pub enum MigrationStep {
+    Sql(&'static str),
+    /// SQLite has no `ADD COLUMN IF NOT EXISTS`.
+    AddColumnIfMissing {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+    },
+    /// Index the owner / resolved launchers of handle slots stored before
+    /// `handle_slot_launchers` existed; the launchers only live in `record_json`.
+    BackfillHandleSlotLaunchers,
+}
+
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub steps: &'static [MigrationStep],
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS slots (
+                    singleton_launcher_id BLOB NOT NULL,
+                    nonce INTEGER NOT NULL,
+                    slot_value_hash BLOB NOT NULL,
+                    spent_block_height INTEGER NOT NULL,
+                    slot_value BLOB NOT NULL,
+                    parent_parent_coin_info BLOB NOT NULL,
+                    parent_inner_puzzle_hash BLOB NOT NULL,
+                    parent_amount INTEGER NOT NULL,
+                    PRIMARY KEY (singleton_launcher_id, nonce, slot_value_hash, parent_parent_coin_info, parent_amount)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS catalog_indexed_slot_values (
+                    asset_id BLOB PRIMARY KEY,
+                    slot_value_hash BLOB NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS xchandles_indexed_slot_values (
+                    launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    slot_value_hash BLOB NOT NULL,
+                    PRIMARY KEY (launcher_id, handle_hash)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS singleton_coins (
+                    launcher_id BLOB NOT NULL,
+                    coin_id BLOB NOT NULL PRIMARY KEY,
+                    parent_coin_id BLOB,
+                    spent_block_height NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_singleton_coins_launcher_spent
+                ON singleton_coins(launcher_id, spent_block_height)
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_slots_neighbors
+                ON slots(singleton_launcher_id, nonce, spent_block_height, slot_value_hash)
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS reward_distributor_configurations (
+                    launcher_id BLOB PRIMARY KEY,
+                    constants BLOB NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS xchandles_configurations (
+                    launcher_id BLOB PRIMARY KEY,
+                    constants BLOB NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS followed_singletons (
+                    launcher_id BLOB PRIMARY KEY,
+                    record_json TEXT NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS handle_slot_records (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    record_json TEXT NOT NULL,
+                    expiration INTEGER NOT NULL DEFAULT 0,
+                    PRIMARY KEY (registry_launcher_id, handle_hash)
+                )
+                ",
+            ),
+            // Early listener databases created `handle_slot_records` without it.
+            AddColumnIfMissing {
+                table: "handle_slot_records",
+                column: "expiration",
+                definition: "INTEGER NOT NULL DEFAULT 0",
+            },
+            Sql(
+                "
+                UPDATE handle_slot_records
+                SET expiration = COALESCE(json_extract(record_json, '$.current.expiration'), 0)
+                WHERE expiration = 0
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_handle_slots_expiring
+                ON handle_slot_records (registry_launcher_id, expiration)
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS registration_records (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    record_json TEXT NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, handle_hash)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS registration_registry_stats (
+                    registry_launcher_id BLOB PRIMARY KEY,
+                    state_json TEXT NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS pending_update_records (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    record_json TEXT NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, handle_hash)
+                )
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 2,
+        name: "header_checkpoints",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS header_checkpoints (
+                    listener TEXT NOT NULL,
+                    height INTEGER NOT NULL,
+                    header_hash BLOB NOT NULL,
+                    PRIMARY KEY (listener, height)
+                )
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 3,
+        name: "handle_search_index",
+        steps: &[
+            // Handle names from `registration_records`, kept in step by every
+            // registration upsert/delete so rollbacks reach it too.
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS handle_search_index (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    handle TEXT NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, handle_hash)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_handle_search_handle
+                ON handle_search_index (registry_launcher_id, handle)
+                ",
+            ),
+            // Databases written before the index existed.
+            Sql(
+                "
+                INSERT OR IGNORE INTO handle_search_index (registry_launcher_id, handle_hash, handle)
+                SELECT registry_launcher_id, handle_hash,
+                       json_extract(record_json, '$.current.handle')
+                FROM registration_records
+                WHERE json_extract(record_json, '$.current.handle') IS NOT NULL
+                  AND json_extract(record_json, '$.current.handle') != ''
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 4,
+        name: "handle_slot_launchers",
+        steps: &[
+            // Owner / resolved launcher of each slot's current state, kept in step
+            // by every handle-slot upsert/delete so rollback restores reach it too.
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS handle_slot_launchers (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    role TEXT NOT NULL,
+                    launcher_id BLOB NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, handle_hash, role)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_handle_slot_launchers_launcher
+                ON handle_slot_launchers (role, launcher_id)
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 5,
+        name: "handle_history",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS handle_history (
+                    registry_launcher_id BLOB NOT NULL,
+                    handle_hash BLOB NOT NULL,
+                    confirmation_height INTEGER NOT NULL,
+                    log_index INTEGER NOT NULL,
+                    entry_json TEXT NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, handle_hash, confirmation_height, log_index)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_handle_history_height
+                ON handle_history (confirmation_height)
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 6,
+        name: "catalog_listener",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS catalog_registry_records (
+                    launcher_id BLOB PRIMARY KEY,
+                    record_json TEXT NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS catalog_cat_records (
+                    registry_launcher_id BLOB NOT NULL,
+                    asset_id BLOB NOT NULL,
+                    record_json TEXT NOT NULL,
+                    PRIMARY KEY (registry_launcher_id, asset_id)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS catalog_registration_stats (
+                    registry_launcher_id BLOB PRIMARY KEY,
+                    state_json TEXT NOT NULL
+                )
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 7,
+        name: "db_network",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS db_network (
+                    id INTEGER PRIMARY KEY CHECK (id = 0),
+                    name TEXT NOT NULL,
+                    genesis_challenge BLOB NOT NULL
+                )
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 8,
+        name: "reward_distributor_listener",
+        steps: &[
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS reward_distributor_records (
+                    launcher_id BLOB PRIMARY KEY,
+                    record_json TEXT NOT NULL
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE TABLE IF NOT EXISTS reward_distributor_slots (
+                    launcher_id BLOB NOT NULL,
+                    value_hash BLOB NOT NULL,
+                    created_height INTEGER NOT NULL,
+                    kind TEXT NOT NULL,
+                    hint BLOB NOT NULL,
+                    spent_height INTEGER,
+                    slot_json TEXT NOT NULL,
+                    PRIMARY KEY (launcher_id, value_hash, created_height)
+                )
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_reward_distributor_slots_hint
+                ON reward_distributor_slots(launcher_id, hint)
+                ",
+            ),
+            Sql(
+                "
+                CREATE INDEX IF NOT EXISTS idx_reward_distributor_slots_kind
+                ON reward_distributor_slots(launcher_id, kind)
+                ",
+            ),
+        ],
+    },
+    Migration {
+        version: 9,
+        name: "registration_activity_deltas",
+        steps: &[Sql(
+            "
+            CREATE TABLE IF NOT EXISTS registration_activity_deltas (
+                registry_launcher_id BLOB NOT NULL,
+                registry_coin_id BLOB NOT NULL,
+                confirmation_height INTEGER NOT NULL,
+                delta_json TEXT NOT NULL,
+                PRIMARY KEY (registry_launcher_id, registry_coin_id)
+            )
+            ",
+        )],
+    },
+    Migration {
+        version: 10,
+        name: "backfill_handle_slot_launchers",
+        steps: &[BackfillHandleSlotLaunchers],
+    },
+];
+
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+/// Where a database stands relative to [`MIGRATIONS`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaReport {
+    pub version: u32,
+    pub latest: u32,
+    /// Tables from the latest schema that are missing, or lack some of its columns.
+    pub mismatches: Vec<String>,
+}
+
+impl SchemaReport {
+    pub fn is_current(&self) -> bool {
+        self.version == self.latest && self.mismatches.is_empty()
+    }
+}
+
+async fn table_columns(pool: &Pool<Sqlite>) -> Result<BTreeMap<String, Vec<String>>, CliError> {
+    let tables = sqlx::query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut columns = BTreeMap::new();
+    for table in tables {
+        let name: String = table.get("name");
+        let rows = sqlx::query(&format!("PRAGMA table_info({name})"))
+            .fetch_all(pool)
+            .await?;
+        columns.insert(
+            name,
+            rows.iter()
+                .map(|row| row.get::<String, _>("name"))
+                .collect(),
+        );
+    }
+    Ok(columns)
+}
+
+impl Db {
+    async fn ensure_schema_migrations_table(&self) -> Result<(), CliError> {
+        sqlx::query(
+            "
+            CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Highest applied migration; 0 for a new or pre-versioning database.
+    pub async fn schema_version(&self) -> Result<u32, CliError> {
+        let exists = sqlx::query(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .is_some();
+        if !exists {
+            return Ok(0);
+        }
+
+        let version: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM schema_migrations")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(version.unwrap_or(0) as u32)
+    }
+
+    /// Applies every pending migration in order and returns the ones it ran.
+    pub async fn migrate(&self) -> Result<Vec<&'static Migration>, CliError> {
+        let version = self.schema_version().await?;
+        let latest = latest_schema_version();
+        if version > latest {
+            return Err(CliError::Custom(format!(
+                "database schema v{version} is newer than this binary supports (v{latest})"
+            )));
+        }
+
+        self.ensure_schema_migrations_table().await?;
+        let mut applied = Vec::new();
+        for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+            self.apply_migration(migration).await?;
+            applied.push(migration);
+        }
+        Ok(applied)
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<(), CliError> {
+        let mut tx = self.pool.begin().await?;
+        for step in migration.steps {
+            match step {
+                Sql(sql) => {
+                    sqlx::query(sql).execute(&mut *tx).await?;
+                }
+                AddColumnIfMissing {
+                    table,
+                    column,
+                    definition,
+                } => {
+                    let present = sqlx::query(&format!("PRAGMA table_info({table})"))
+                        .fetch_all(&mut *tx)
+                        .await?
+                        .iter()
+                        .any(|row| row.get::<String, _>("name") == *column);
+                    if !present {
+                        sqlx::query(&format!(
+                            "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+                        ))
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+                }
+                BackfillHandleSlotLaunchers => backfill_handle_slot_launchers(&mut *tx).await?,
+            }
+        }
+        sqlx::query("INSERT INTO schema_migrations (version, name, applied_at) VALUES (?, ?, ?)")
+            .bind(migration.version as i64)
+            .bind(migration.name)
+            .bind(chrono::Utc::now().timestamp())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Compares the version and every table's columns against a scratch database
+    /// migrated to [`latest_schema_version`]. Never writes.
+    pub async fn check_schema(&self) -> Result<SchemaReport, CliError> {
+        let reference = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:")?)
+            .await?;
+        let reference = Db::from_pool(reference);
+        reference.migrate().await?;
+
+        let expected = table_columns(&reference.pool).await?;
+        let actual = table_columns(&self.pool).await?;
+        let mismatches = expected
+            .iter()
+            .filter_map(|(table, columns)| match actual.get(table) {
+                None => Some(format!("{table}: missing")),
+                Some(present) => {
+                    let missing: Vec<&str> = columns
+                        .iter()
+                        .filter(|column| !present.contains(column))
+                        .map(String::as_str)
+                        .collect();
+                    (!missing.is_empty())
+                        .then(|| format!("{table}: missing column(s) {}", missing.join(", ")))
+                }
+            })
+            .collect();
+
+        Ok(SchemaReport {
+            version: self.schema_version().await?,
+            latest: latest_schema_version(),
+            mismatches,
+        })
+    }
+}
+
+async fn backfill_handle_slot_launchers(conn: &mut SqliteConnection) -> Result<(), CliError> {
+    let rows = sqlx::query(
+        "
+        SELECT h.record_json AS record_json
+        FROM handle_slot_records h
+        WHERE NOT EXISTS (
+            SELECT 1 FROM handle_slot_launchers l
+            WHERE l.registry_launcher_id = h.registry_launcher_id
+              AND l.handle_hash = h.handle_hash
+        )
+        ",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    for row in rows {
+        let json = row.get::<String, _>("record_json");
+        let Ok(record) = serde_json::from_str::<HandleSlotRecord>(&json) else {
+            continue;
+        };
+        let Some(slot) = record.current else {
+            continue;
+        };
+        for (role, launcher_id) in [
+            ("owner", slot.owner_launcher_id),
+            ("resolved", slot.resolved_launcher_id),
+        ] {
+            sqlx::query(
+                "
+                INSERT INTO handle_slot_launchers (
+                    registry_launcher_id, handle_hash, role, launcher_id
+                )
+                VALUES (?1, ?2, ?3, ?4)
+                ",
+            )
+            .bind(record.registry_launcher_id.to_vec())
+            .bind(record.handle_hash.to_vec())
+            .bind(role)
+            .bind(launcher_id.to_vec())
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_protocol::Bytes32;
+
+    async fn memory_db() -> Db {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:").unwrap())
+            .await
+            .unwrap();
+        Db::from_pool(pool)
+    }
+
+    #[test]
+    fn versions_are_strictly_increasing_from_one() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1, "{}", migration.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn migrates_an_empty_database_once() {
+        let db = memory_db().await;
+        assert_eq!(db.schema_version().await.unwrap(), 0);
+
+        let applied = db.migrate().await.unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(db.schema_version().await.unwrap(), latest_schema_version());
+        assert!(db.check_schema().await.unwrap().is_current());
+
+        assert!(db.migrate().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn adopts_a_pre_versioning_database_and_backfills_expiration() {
+        let db = memory_db().await;
+        sqlx::query(
+            "
+            CREATE TABLE handle_slot_records (
+                registry_launcher_id BLOB NOT NULL,
+                handle_hash BLOB NOT NULL,
+                record_json TEXT NOT NULL,
+                PRIMARY KEY (registry_launcher_id, handle_hash)
+            )
+            ",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO handle_slot_records VALUES (x'01', x'02', ?)")
+            .bind(r#"{"current":{"expiration":1800000000}}"#)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let report = db.check_schema().await.unwrap();
+        assert_eq!(report.version, 0);
+        assert!(report
+            .mismatches
+            .contains(&"handle_slot_records: missing column(s) expiration".to_string()));
+
+        db.migrate().await.unwrap();
+        assert!(db.check_schema().await.unwrap().is_current());
+        let expiration: i64 = sqlx::query_scalar("SELECT expiration FROM handle_slot_records")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(expiration, 1_800_000_000);
+    }
+
+    #[tokio::test]
+    async fn backfills_handle_slot_launchers_once() {
+        let db = memory_db().await;
+        sqlx::query(
+            "
+            CREATE TABLE handle_slot_records (
+                registry_launcher_id BLOB NOT NULL,
+                handle_hash BLOB NOT NULL,
+                record_json TEXT NOT NULL,
+                PRIMARY KEY (registry_launcher_id, handle_hash)
+            )
+            ",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        let slot = crate::StoredHandleSlot {
+            registry_launcher_id: Bytes32::new([1; 32]),
+            handle_hash: Bytes32::new([2; 32]),
+            counter: 0,
+            neighbors_left: Bytes32::default(),
+            neighbors_right: Bytes32::default(),
+            expiration: 1_800_000_000,
+            owner_launcher_id: Bytes32::new([3; 32]),
+            resolved_launcher_id: Bytes32::new([4; 32]),
+            parent_coin_id: Bytes32::default(),
+            parent_parent_id: Bytes32::default(),
+            parent_inner_puzzle_hash: Bytes32::default(),
+            confirmation_height: 100,
+        };
+        let record = HandleSlotRecord {
+            registry_launcher_id: slot.registry_launcher_id,
+            handle_hash: slot.handle_hash,
+            current: Some(slot),
+            history: Vec::new(),
+        };
+        sqlx::query("INSERT INTO handle_slot_records VALUES (x'01', x'02', ?)")
+            .bind(serde_json::to_string(&record).unwrap())
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let applied = db.migrate().await.unwrap();
+        assert!(applied
+            .iter()
+            .any(|migration| migration.name == "backfill_handle_slot_launchers"));
+        let launchers: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT role, launcher_id FROM handle_slot_launchers ORDER BY role")
+                .fetch_all(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            launchers,
+            [
+                ("owner".to_string(), vec![3; 32]),
+                ("resolved".to_string(), vec![4; 32]),
+            ]
+        );
+        assert!(db.migrate().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn refuses_a_newer_schema() {
+        let db = memory_db().await;
+        db.migrate().await.unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?, 'future', 0)",
+        )
+        .bind(i64::from(latest_schema_version() + 1))
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        assert!(db.migrate().await.is_err());
+    }
+}
@@ -0,0 +1,554 @@
+//! Portable per-registry snapshots of the local cache.
+//!
+//! A snapshot holds every row a listener keeps for one registry, plus the header
+//! checkpoint window it was consistent with. Importing one into an empty database
+//! lets a new replica resume indexing from that height instead of from launch.
+
+use chia_protocol::Bytes32;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use super::Db;
+use crate::{
+    ChainSource, CliError, Network, CATALOG_CHECKPOINT_LISTENER, XCHANDLES_CHECKPOINT_LISTENER,
+};
+
+/// Bumped whenever the file layout changes; rows follow the schema version instead.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotRegistry {
+    Xchandles,
+    Catalog,
+}
+
+impl SnapshotRegistry {
+    /// `header_checkpoints.listener` key of the listener that indexes this registry.
+    pub fn listener(self) -> &'static str {
+        match self {
+            Self::Xchandles => XCHANDLES_CHECKPOINT_LISTENER,
+            Self::Catalog => CATALOG_CHECKPOINT_LISTENER,
+        }
+    }
+
+    fn tables(self) -> &'static [SnapshotTableSpec] {
+        match self {
+            Self::Xchandles => XCHANDLES_TABLES,
+            Self::Catalog => CATALOG_TABLES,
+        }
+    }
+}
+
+/// A table and the rows of it that belong to the registry bound as `?1`; `None`
+/// takes the whole table.
+struct SnapshotTableSpec {
+    name: &'static str,
+    filter: Option<&'static str>,
+}
+
+const XCHANDLES_TABLES: &[SnapshotTableSpec] = &[
+    SnapshotTableSpec {
+        name: "singleton_coins",
+        filter: Some("launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "slots",
+        filter: Some("singleton_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "xchandles_configurations",
+        filter: Some("launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "xchandles_indexed_slot_values",
+        filter: Some("launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "handle_slot_records",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "registration_records",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "registration_registry_stats",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "registration_activity_deltas",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "pending_update_records",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "handle_search_index",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "handle_slot_launchers",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "handle_history",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    // Owner and resolved singletons are shared between registries; take the ones
+    // this registry's handles point at.
+    SnapshotTableSpec {
+        name: "followed_singletons",
+        filter: Some(
+            "launcher_id IN (SELECT launcher_id FROM handle_slot_launchers WHERE registry_launcher_id = ?1)",
+        ),
+    },
+];
+
+const CATALOG_TABLES: &[SnapshotTableSpec] = &[
+    SnapshotTableSpec {
+        name: "singleton_coins",
+        filter: Some("launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "slots",
+        filter: Some("singleton_launcher_id = ?1"),
+    },
+    // There is one CATalog per network, so its slot index is not keyed by registry.
+    SnapshotTableSpec {
+        name: "catalog_indexed_slot_values",
+        filter: None,
+    },
+    SnapshotTableSpec {
+        name: "catalog_registry_records",
+        filter: Some("launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "catalog_cat_records",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+    SnapshotTableSpec {
+        name: "catalog_registration_stats",
+        filter: Some("registry_launcher_id = ?1"),
+    },
+];
+
+/// One SQLite value; blobs are hex so the file stays plain JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTable {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<SnapshotValue>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DbSnapshot {
+    pub format_version: u32,
+    pub network: String,
+    pub genesis_challenge: Bytes32,
+    pub schema_version: u32,
+    pub registry: SnapshotRegistry,
+    pub launcher_id: Bytes32,
+    /// Tip of `header_checkpoints`: the last peak the rows were indexed through.
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub header_checkpoints: Vec<(u32, Bytes32)>,
+    pub tables: Vec<SnapshotTable>,
+}
+
+impl Db {
+    /// Reads every row `registry` keeps for `launcher_id` inside one transaction, so
+    /// a listener writing meanwhile cannot leave the snapshot half a block ahead.
+    pub async fn export_snapshot(
+        &self,
+        network: &Network,
+        registry: SnapshotRegistry,
+        launcher_id: Bytes32,
+    ) -> Result<DbSnapshot, CliError> {
+        let schema_version = self.schema_version().await?;
+
+        self.begin_transaction().await?;
+        let result = self.read_snapshot_tables(registry, launcher_id).await;
+        // Read-only; nothing to keep.
+        self.rollback_transaction().await?;
+        let (header_checkpoints, tables) = result?;
+
+        let Some(&(height, header_hash)) = header_checkpoints.last() else {
+            return Err(CliError::Custom(format!(
+                "no header checkpoints for the {} listener; run it before exporting",
+                registry.listener()
+            )));
+        };
+        if !tables
+            .iter()
+            .any(|table| table.name == "singleton_coins" && !table.rows.is_empty())
+        {
+            return Err(CliError::Custom(format!(
+                "registry {} has not been indexed in this database",
+                hex::encode(launcher_id)
+            )));
+        }
+
+        Ok(DbSnapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            network: network.name.clone(),
+            genesis_challenge: network.constants.genesis_challenge,
+            schema_version,
+            registry,
+            launcher_id,
+            height,
+            header_hash,
+            header_checkpoints,
+            tables,
+        })
+    }
+
+    async fn read_snapshot_tables(
+        &self,
+        registry: SnapshotRegistry,
+        launcher_id: Bytes32,
+    ) -> Result<(Vec<(u32, Bytes32)>, Vec<SnapshotTable>), CliError> {
+        let header_checkpoints = self.header_checkpoints(registry.listener()).await?;
+
+        let mut tables = Vec::new();
+        for spec in registry.tables() {
+            let columns = self.table_columns(spec.name).await?;
+            let select = columns
+                .iter()
+                .map(|column| format!("typeof(\"{column}\"), \"{column}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = match spec.filter {
+                Some(filter) => format!("SELECT {select} FROM {} WHERE {filter}", spec.name),
+                None => format!("SELECT {select} FROM {}", spec.name),
+            };
+
+            let mut query = sqlx::query(&sql);
+            if spec.filter.is_some() {
+                query = query.bind(launcher_id.to_vec());
+            }
+            let rows = query.fetch_all(&mut *self.conn().await?).await?;
+
+            let mut values = Vec::with_capacity(rows.len());
+            for row in rows {
+                let mut row_values = Vec::with_capacity(columns.len());
+                for i in 0..columns.len() {
+                    let value = match row.get::<String, _>(2 * i).as_str() {
+                        "integer" => SnapshotValue::Integer(row.get(2 * i + 1)),
+                        "real" => SnapshotValue::Real(row.get(2 * i + 1)),
+                        "text" => SnapshotValue::Text(row.get(2 * i + 1)),
+                        "blob" => SnapshotValue::Blob(hex::encode(row.get::<&[u8], _>(2 * i + 1))),
+                        _ => SnapshotValue::Null,
+                    };
+                    row_values.push(value);
+                }
+                values.push(row_values);
+            }
+
+            tables.push(SnapshotTable {
+                name: spec.name.to_string(),
+                columns,
+                rows: values,
+            });
+        }
+
+        Ok((header_checkpoints, tables))
+    }
+
+    /// Loads `snapshot` into this database after checking it against `source`: its
+    /// header must still be canonical and the registry's tip coin must exist. The
+    /// listener then resumes from the snapshot height like after a restart.
+    pub async fn import_snapshot(
+        &self,
+        network: &Network,
+        snapshot: &DbSnapshot,
+        source: &dyn ChainSource,
+    ) -> Result<(), CliError> {
+        if snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(CliError::Custom(format!(
+                "snapshot format v{} is not supported (expected v{SNAPSHOT_FORMAT_VERSION})",
+                snapshot.format_version
+            )));
+        }
+        if snapshot.genesis_challenge != network.constants.genesis_challenge {
+            return Err(CliError::Custom(format!(
+                "snapshot was exported on network '{}', not '{}'",
+                snapshot.network, network.name
+            )));
+        }
+        let schema_version = self.schema_version().await?;
+        if snapshot.schema_version != schema_version {
+            return Err(CliError::Custom(format!(
+                "snapshot is at schema v{} but this database is at v{schema_version}; export and import with the same release",
+                snapshot.schema_version
+            )));
+        }
+        if self
+            .get_last_unspent_singleton_coin(snapshot.launcher_id)
+            .await?
+            .is_some()
+        {
+            return Err(CliError::Custom(format!(
+                "registry {} is already indexed in this database",
+                hex::encode(snapshot.launcher_id)
+            )));
+        }
+
+        let peak = source
+            .peak()
+            .await?
+            .ok_or_else(|| CliError::Custom("chain has no peak".to_string()))?;
+        if snapshot.height > peak.height {
+            return Err(CliError::Custom(format!(
+                "snapshot height {} is above the chain peak {}",
+                snapshot.height, peak.height
+            )));
+        }
+        let on_chain = source.block_record_at(snapshot.height).await?;
+        if on_chain.map(|record| record.header_hash) != Some(snapshot.header_hash) {
+            return Err(CliError::Custom(format!(
+                "snapshot header at height {} is no longer on the chain; export a newer one",
+                snapshot.height
+            )));
+        }
+
+        self.begin_transaction().await?;
+        let result = self.write_snapshot(snapshot, source).await;
+        match result {
+            Ok(()) => self.commit_transaction().await,
+            Err(err) => {
+                self.rollback_transaction().await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn write_snapshot(
+        &self,
+        snapshot: &DbSnapshot,
+        source: &dyn ChainSource,
+    ) -> Result<(), CliError> {
+        let specs = snapshot.registry.tables();
+        for table in &snapshot.tables {
+            // Table and column names end up in SQL text; only accept ones we know.
+            if !specs.iter().any(|spec| spec.name == table.name) {
+                return Err(CliError::Custom(format!(
+                    "snapshot table '{}' does not belong to a {:?} registry",
+                    table.name, snapshot.registry
+                )));
+            }
+            let known = self.table_columns(&table.name).await?;
+            if let Some(column) = table.columns.iter().find(|c| !known.contains(c)) {
+                return Err(CliError::Custom(format!(
+                    "snapshot column '{}.{column}' is not in this schema",
+                    table.name
+                )));
+            }
+
+            let sql = format!(
+                "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+                table.name,
+                table
+                    .columns
+                    .iter()
+                    .map(|column| format!("\"{column}\""))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                vec!["?"; table.columns.len()].join(", ")
+            );
+            for row in &table.rows {
+                if row.len() != table.columns.len() {
+                    return Err(CliError::Custom(format!(
+                        "snapshot row in '{}' has {} values for {} columns",
+                        table.name,
+                        row.len(),
+                        table.columns.len()
+                    )));
+                }
+                let mut query = sqlx::query(&sql);
+                for value in row {
+                    query = match value {
+                        SnapshotValue::Null => query.bind(None::<i64>),
+                        SnapshotValue::Integer(value) => query.bind(*value),
+                        SnapshotValue::Real(value) => query.bind(*value),
+                        SnapshotValue::Text(value) => query.bind(value.clone()),
+                        SnapshotValue::Blob(value) => query.bind(hex::decode(value)?),
+                    };
+                }
+                query.execute(&mut *self.conn().await?).await?;
+            }
+        }
+
+        // Other registries imported before share this listener's window; keep the
+        // older one so startup reorg detection covers every registry.
+        let listener = snapshot.registry.listener();
+        let existing_tip = self.header_checkpoints(listener).await?.last().copied();
+        if existing_tip.is_none_or(|(height, _)| height > snapshot.height) {
+            self.save_header_checkpoints(listener, &snapshot.header_checkpoints)
+                .await?;
+        }
+
+        let Some((tip_coin_id, _)) = self
+            .get_last_unspent_singleton_coin(snapshot.launcher_id)
+            .await?
+        else {
+            return Err(CliError::Custom(format!(
+                "snapshot has no unspent coin for registry {}",
+                hex::encode(snapshot.launcher_id)
+            )));
+        };
+        let tip = source
+            .coin_records_by_names(vec![tip_coin_id], true)
+            .await?
+            .into_iter()
+            .next();
+        match tip {
+            Some(record) if record.confirmed_block_index <= snapshot.height => Ok(()),
+            _ => Err(CliError::Custom(format!(
+                "registry tip coin {} is not on the chain at height {}",
+                hex::encode(tip_coin_id),
+                snapshot.height
+            ))),
+        }
+    }
+
+    async fn table_columns(&self, table: &str) -> Result<Vec<String>, CliError> {
+        let rows = sqlx::query("SELECT name FROM pragma_table_info(?1) ORDER BY cid")
+            .bind(table)
+            .fetch_all(&mut *self.conn().await?)
+            .await?;
+        Ok(rows.iter().map(|row| row.get("name")).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chia_protocol::Coin;
+    use chia_wallet_sdk::coinset::CoinRecord;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    use super::*;
+    use crate::MemoryChainSource;
+
+    async fn memory_db() -> Db {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:").unwrap())
+            .await
+            .unwrap();
+        let db = Db::from_pool(pool);
+        db.migrate().await.unwrap();
+        db
+    }
+
+    /// A chain two blocks high with the registry's tip coin, and a database that
+    /// indexed it through the second block.
+    async fn indexed_registry() -> (MemoryChainSource, Db, Bytes32) {
+        let launcher_id = Bytes32::new([1; 32]);
+        let tip = Coin::new(launcher_id, Bytes32::new([2; 32]), 1);
+
+        let chain = MemoryChainSource::new();
+        chain.push_block(None, vec![]);
+        chain.create_coin(tip, None, 0);
+        let block = chain.push_block(None, vec![]);
+
+        let db = memory_db().await;
+        db.save_singleton_coin(
+            launcher_id,
+            CoinRecord {
+                coin: tip,
+                coinbase: false,
+                confirmed_block_index: 0,
+                spent: false,
+                spent_block_index: 0,
+                timestamp: 0,
+            },
+        )
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO handle_search_index (registry_launcher_id, handle_hash, handle) VALUES (?1, ?2, 'alice')",
+        )
+        .bind(launcher_id.to_vec())
+        .bind(vec![3; 32])
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        db.save_header_checkpoints(
+            XCHANDLES_CHECKPOINT_LISTENER,
+            &[(block.height, block.header_hash)],
+        )
+        .await
+        .unwrap();
+
+        (chain, db, launcher_id)
+    }
+
+    #[tokio::test]
+    async fn round_trips_a_registry_into_an_empty_database() {
+        let network = Network::mainnet();
+        let (chain, db, launcher_id) = indexed_registry().await;
+
+        let snapshot = db
+            .export_snapshot(&network, SnapshotRegistry::Xchandles, launcher_id)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.height, 1);
+        let snapshot: DbSnapshot =
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let replica = memory_db().await;
+        replica
+            .import_snapshot(&network, &snapshot, &chain)
+            .await
+            .unwrap();
+        let reexported = replica
+            .export_snapshot(&network, SnapshotRegistry::Xchandles, launcher_id)
+            .await
+            .unwrap();
+        assert_eq!(reexported, snapshot);
+
+        assert!(replica
+            .import_snapshot(&network, &snapshot, &chain)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn refuses_a_snapshot_whose_header_was_reorged_out() {
+        let network = Network::mainnet();
+        let (chain, db, launcher_id) = indexed_registry().await;
+        let snapshot = db
+            .export_snapshot(&network, SnapshotRegistry::Xchandles, launcher_id)
+            .await
+            .unwrap();
+
+        chain.rewind_to(0);
+        chain.push_block(None, vec![]);
+
+        let replica = memory_db().await;
+        assert!(replica
+            .import_snapshot(&network, &snapshot, &chain)
+            .await
+            .is_err());
+        assert!(replica
+            .header_checkpoints(XCHANDLES_CHECKPOINT_LISTENER)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
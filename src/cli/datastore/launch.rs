@@ -9,9 +9,9 @@ use chia_wallet_sdk::{
 };
 
 use crate::{
-    assets_xch_only, build_root_hash, confirm_pushed_transaction, get_coinset_client,
-    get_constants, hex_string_to_pubkey, load_and_dedupe_csv, no_assets, parse_amount,
-    yes_no_prompt, CliError, SageClient,
+    assets_xch_only, build_root_hash, confirm_pushed_transaction, get_chain_client, get_constants,
+    hex_string_to_pubkey, load_and_dedupe_csv, no_assets, parse_amount, yes_no_prompt, CliError,
+    Network, SageClient,
 };
 
 use super::oracle_delegated_puzzles;
@@ -20,7 +20,7 @@ pub async fn datastore_launch(
     csv_path: String,
     label: Option<String>,
     description: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let records = load_and_dedupe_csv(&csv_path)?;
@@ -78,7 +78,7 @@ pub async fn datastore_launch(
         security_coin,
         launch_conditions,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )
     .map_err(CliError::Driver)?;
 
@@ -90,7 +90,7 @@ pub async fn datastore_launch(
     let spend_bundle = offer.take(SpendBundle::new(ctx.take(), security_coin_sig));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
@@ -1,13 +1,13 @@
 use chia_protocol::Bytes32;
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{Datastore, DatastoreMetadata, DelegatedPuzzle, SpendContext},
 };
 
-use crate::CliError;
+use crate::{ChainClient, CliError};
 
 pub async fn sync_datastore(
-    client: &CoinsetClient,
+    client: &ChainClient,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
     delegated_puzzles: &[DelegatedPuzzle],
@@ -10,9 +10,9 @@ use chia_wallet_sdk::{
 };
 
 use crate::{
-    assets_xch_only, build_root_hash, confirm_pushed_transaction, get_coinset_client,
-    get_constants, hex_string_to_pubkey, hex_string_to_signature, load_and_dedupe_csv, no_assets,
-    parse_amount, sync_datastore, validate_update_csvs, yes_no_prompt, CliError, SageClient,
+    assets_xch_only, build_root_hash, confirm_pushed_transaction, get_chain_client, get_constants,
+    hex_string_to_pubkey, hex_string_to_signature, load_and_dedupe_csv, no_assets, parse_amount,
+    sync_datastore, validate_update_csvs, yes_no_prompt, CliError, Network, SageClient,
 };
 
 use super::oracle_delegated_puzzles;
@@ -23,7 +23,7 @@ pub async fn datastore_update(
     new_csv_path: String,
     label: Option<String>,
     description: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = crate::hex_string_to_bytes32(&launcher_id_str)?;
@@ -34,7 +34,7 @@ pub async fn datastore_update(
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing datastore...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let mut ctx = SpendContext::new();
     let datastore =
         sync_datastore(&client, &mut ctx, launcher_id, &oracle_delegated_puzzles()).await?;
@@ -117,7 +117,7 @@ pub async fn datastore_update(
         security_coin,
         Conditions::new().assert_concurrent_spend(datastore_coin_id),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )
     .map_err(CliError::Driver)?;
 
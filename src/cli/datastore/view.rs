@@ -3,15 +3,17 @@ use chia_wallet_sdk::{
     utils::Address,
 };
 
-use crate::{get_coinset_client, get_prefix, hex_string_to_bytes32, sync_datastore, CliError};
+use crate::{
+    get_chain_client, get_prefix, hex_string_to_bytes32, sync_datastore, CliError, Network,
+};
 
 use super::oracle_delegated_puzzles;
 
-pub async fn datastore_view(launcher_id_str: String, testnet11: bool) -> Result<(), CliError> {
+pub async fn datastore_view(launcher_id_str: String, network: &Network) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     let mut ctx = SpendContext::new();
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
 
     print!("Syncing datastore... ");
     let datastore =
@@ -31,7 +33,7 @@ pub async fn datastore_view(launcher_id_str: String, testnet11: bool) -> Result<
     println!("  Coin amount: {}", datastore.coin.amount);
     println!(
         "  Owner address: {}",
-        Address::new(datastore.info.owner_puzzle_hash, get_prefix(testnet11)).encode()?
+        Address::new(datastore.info.owner_puzzle_hash, get_prefix(network)).encode()?
     );
 
     let metadata = &datastore.info.metadata;
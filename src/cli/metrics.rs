@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+/// Prometheus metrics for one listener process (`xchandles` or `catalog`).
+///
+/// Every series carries a constant `listener` label and the `slot_machine_` prefix.
+pub struct ListenerMetrics {
+    registry: Registry,
+    pub blocks_processed: IntCounter,
+    pub transitions_projected: IntCounter,
+    pub rollbacks: IntCounter,
+    pub websocket_reconnects: IntCounter,
+    rpc_duration: HistogramVec,
+    rpc_errors: IntCounterVec,
+    sqlite_write_duration: HistogramVec,
+    http_request_duration: HistogramVec,
+}
+
+impl ListenerMetrics {
+    pub fn new(listener: &str) -> Self {
+        let registry = Registry::new_custom(
+            Some("slot_machine".to_string()),
+            Some(HashMap::from([(
+                "listener".to_string(),
+                listener.to_string(),
+            )])),
+        )
+        .expect("valid metrics registry");
+
+        let blocks_processed = IntCounter::with_opts(Opts::new(
+            "blocks_processed_total",
+            "Blocks (or followed spends) applied to the index",
+        ))
+        .expect("valid counter");
+        let transitions_projected = IntCounter::with_opts(Opts::new(
+            "transitions_projected_total",
+            "Registry transitions projected into the index",
+        ))
+        .expect("valid counter");
+        let rollbacks = IntCounter::with_opts(Opts::new(
+            "rollbacks_total",
+            "Index rollbacks after a chain reorganization",
+        ))
+        .expect("valid counter");
+        let websocket_reconnects = IntCounter::with_opts(Opts::new(
+            "websocket_reconnects_total",
+            "Upstream peak websocket reconnects",
+        ))
+        .expect("valid counter");
+        let rpc_duration = HistogramVec::new(
+            HistogramOpts::new("rpc_duration_seconds", "Upstream RPC latency"),
+            &["method"],
+        )
+        .expect("valid histogram");
+        let rpc_errors = IntCounterVec::new(
+            Opts::new(
+                "rpc_errors_total",
+                "Upstream RPC calls that returned an error",
+            ),
+            &["method"],
+        )
+        .expect("valid counter");
+        let sqlite_write_duration = HistogramVec::new(
+            HistogramOpts::new("sqlite_write_duration_seconds", "SQLite write latency"),
+            &["op"],
+        )
+        .expect("valid histogram");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "Public API latency"),
+            &["route", "method", "status"],
+        )
+        .expect("valid histogram");
+
+        for collector in [
+            Box::new(blocks_processed.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(transitions_projected.clone()),
+            Box::new(rollbacks.clone()),
+            Box::new(websocket_reconnects.clone()),
+            Box::new(rpc_duration.clone()),
+            Box::new(rpc_errors.clone()),
+            Box::new(sqlite_write_duration.clone()),
+            Box::new(http_request_duration.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric registered once");
+        }
+
+        Self {
+            registry,
+            blocks_processed,
+            transitions_projected,
+            rollbacks,
+            websocket_reconnects,
+            rpc_duration,
+            rpc_errors,
+            sqlite_write_duration,
+            http_request_duration,
+        }
+    }
+
+    pub fn shared(listener: &str) -> Arc<Self> {
+        Arc::new(Self::new(listener))
+    }
+
+    /// Time one upstream call; errors are counted under the same `method`.
+    pub async fn observe_rpc<T, E>(
+        &self,
+        method: &str,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let started = Instant::now();
+        let result = call.await;
+        self.rpc_duration
+            .with_label_values(&[method])
+            .observe(started.elapsed().as_secs_f64());
+        if result.is_err() {
+            self.rpc_errors.with_label_values(&[method]).inc();
+        }
+        result
+    }
+
+    pub fn observe_sqlite_write(&self, op: &str, elapsed: Duration) {
+        self.sqlite_write_duration
+            .with_label_values(&[op])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Text exposition format for `GET /metrics`.
+    pub fn render(&self) -> String {
+        let mut buf = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buf) {
+            eprintln!("metrics encode error: {e}");
+        }
+        String::from_utf8(buf).unwrap_or_default()
+    }
+
+    pub fn response(&self) -> Response {
+        (
+            [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+            self.render(),
+        )
+            .into_response()
+    }
+}
+
+/// `route_layer` middleware: latency and status per matched route template.
+pub async fn record_http_metrics(
+    State(metrics): State<Arc<ListenerMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics
+        .http_request_duration
+        .with_label_values(&[route.as_str(), method.as_str(), response.status().as_str()])
+        .observe(started.elapsed().as_secs_f64());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn render_exposes_prefixed_series_with_listener_label() {
+        let metrics = ListenerMetrics::new("xchandles");
+        metrics.blocks_processed.inc();
+        let _ = metrics
+            .observe_rpc("get_blockchain_state", async { Err::<(), _>("down") })
+            .await;
+
+        let text = metrics.render();
+        assert!(text.contains("slot_machine_blocks_processed_total{listener=\"xchandles\"} 1"));
+        assert!(text.contains(
+            "slot_machine_rpc_errors_total{listener=\"xchandles\",method=\"get_blockchain_state\"} 1"
+        ));
+        assert!(text.contains("slot_machine_rpc_duration_seconds_count"));
+    }
+}
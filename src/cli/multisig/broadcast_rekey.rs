@@ -2,7 +2,7 @@ use chia_wallet_sdk::driver::MedievalVault;
 
 use crate::{
     get_constants, hex_string_to_pubkey, multisig_broadcast_thing_finish,
-    multisig_broadcast_thing_start, print_medieval_vault_configuration, CliError,
+    multisig_broadcast_thing_start, print_medieval_vault_configuration, CliError, Network,
 };
 
 pub async fn multisig_broadcast_rekey(
@@ -10,7 +10,7 @@ pub async fn multisig_broadcast_rekey(
     new_m: usize,
     signatures_str: String,
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let mut new_pubkeys = Vec::new();
@@ -25,7 +25,7 @@ pub async fn multisig_broadcast_rekey(
     }
 
     let (signature_from_signers, pubkeys, client, mut ctx, medieval_vault) =
-        multisig_broadcast_thing_start(signatures_str, launcher_id_str, testnet11).await?;
+        multisig_broadcast_thing_start(signatures_str, launcher_id_str, network).await?;
 
     println!("\nNew configuration:");
     print_medieval_vault_configuration(new_m, &new_pubkeys)?;
@@ -41,7 +41,7 @@ pub async fn multisig_broadcast_rekey(
         &mut ctx,
         &pubkeys,
         conditions,
-        get_constants(testnet11).genesis_challenge,
+        get_constants(network).genesis_challenge,
     )?;
 
     multisig_broadcast_thing_finish(
@@ -49,7 +49,7 @@ pub async fn multisig_broadcast_rekey(
         &mut ctx,
         signature_from_signers,
         fee_str,
-        testnet11,
+        network,
         medieval_vault_coin_id,
         None,
     )
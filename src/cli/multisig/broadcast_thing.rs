@@ -1,29 +1,29 @@
 use chia_bls::{PublicKey, Signature};
 use chia_protocol::{Bytes32, SpendBundle};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin, MedievalVault, Offer, SpendContext,
     },
-    types::{Conditions, MAINNET_CONSTANTS, TESTNET11_CONSTANTS},
+    types::Conditions,
 };
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, hex_string_to_bytes32,
-    hex_string_to_signature, no_assets, parse_amount, print_medieval_vault_configuration,
-    sync_multisig_singleton, yes_no_prompt, CliError, MultisigSingleton, SageClient,
-    StateSchedulerHintedState,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants,
+    hex_string_to_bytes32, hex_string_to_signature, no_assets, parse_amount,
+    print_medieval_vault_configuration, sync_multisig_singleton, yes_no_prompt, ChainClient,
+    CliError, MultisigSingleton, Network, SageClient, StateSchedulerHintedState,
 };
 
 pub async fn multisig_broadcast_thing_start(
     signatures_str: String,
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<
     (
         Signature,
         Vec<PublicKey>,
-        CoinsetClient,
+        ChainClient,
         SpendContext,
         MedievalVault,
     ),
@@ -34,7 +34,7 @@ pub async fn multisig_broadcast_thing_start(
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     println!("Syncing multisig...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let mut ctx = SpendContext::new();
     let (MultisigSingleton::Vault(medieval_vault), _state_scheduler_info) =
         sync_multisig_singleton::<StateSchedulerHintedState>(&client, &mut ctx, launcher_id, None)
@@ -76,11 +76,11 @@ pub async fn multisig_broadcast_thing_start(
 }
 
 pub async fn multisig_broadcast_thing_finish(
-    client: CoinsetClient,
+    client: ChainClient,
     ctx: &mut SpendContext,
     signature_from_signers: Signature,
     fee_str: String,
-    testnet11: bool,
+    network: &Network,
     medieval_vault_coin_id: Bytes32,
     additional_security_conditions: Option<Conditions>,
 ) -> Result<(), CliError> {
@@ -115,11 +115,7 @@ pub async fn multisig_broadcast_thing_finish(
         security_coin,
         conditions,
         &security_coin_sk,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(
@@ -10,14 +10,14 @@ use chia_wallet_sdk::{
 };
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_constants, no_assets,
-    parse_amount, print_medieval_vault_configuration, yes_no_prompt, CliError, SageClient,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants, no_assets,
+    parse_amount, print_medieval_vault_configuration, yes_no_prompt, CliError, Network, SageClient,
 };
 
 pub async fn multisig_launch(
     pubkeys_str: String,
     m: usize,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let mut pubkeys = Vec::new();
@@ -36,7 +36,7 @@ pub async fn multisig_launch(
 
     println!("You're about to create a new multisig with the following settings:");
     print_medieval_vault_configuration(m, &pubkeys)?;
-    println!("  Testnet: {}", testnet11);
+    println!("  Network: {}", network.name);
 
     println!("A one-sided offer offering 1 mojo and {} XCH ({} mojos) as fee will be generated and used to launch the multisig.", fee_str, fee);
     yes_no_prompt("Continue?")?;
@@ -77,13 +77,13 @@ pub async fn multisig_launch(
         security_coin,
         create_conditions,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(ctx.take(), security_coin_sig));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(sb).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
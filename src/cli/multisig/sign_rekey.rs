@@ -2,7 +2,7 @@ use chia_wallet_sdk::driver::MedievalVault;
 
 use crate::{
     get_constants, hex_string_to_pubkey, multisig_sign_thing_finish, multisig_sign_thing_start,
-    print_medieval_vault_configuration, CliError,
+    print_medieval_vault_configuration, CliError, Network,
 };
 
 pub async fn multisig_sign_rekey(
@@ -10,7 +10,7 @@ pub async fn multisig_sign_rekey(
     new_m: usize,
     my_pubkey_str: String,
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     debug: bool,
 ) -> Result<(), CliError> {
     let mut new_pubkeys = Vec::new();
@@ -25,7 +25,7 @@ pub async fn multisig_sign_rekey(
     }
 
     let (my_pubkey, mut ctx, _client, medieval_vault) =
-        multisig_sign_thing_start(my_pubkey_str, launcher_id_str, testnet11).await?;
+        multisig_sign_thing_start(my_pubkey_str, launcher_id_str, network).await?;
 
     println!("\nNew configuration:");
     print_medieval_vault_configuration(new_m, &new_pubkeys)?;
@@ -36,7 +36,7 @@ pub async fn multisig_sign_rekey(
         new_m,
         new_pubkeys,
         medieval_vault.coin.coin_id(),
-        get_constants(testnet11).genesis_challenge,
+        get_constants(network).genesis_challenge,
     )
     .map_err(CliError::Driver)?;
 
@@ -45,7 +45,7 @@ pub async fn multisig_sign_rekey(
         delegated_puzzle,
         &medieval_vault,
         my_pubkey,
-        testnet11,
+        network,
         debug,
     )
     .await
@@ -1,7 +1,6 @@
 use chia_bls::sign;
 use chia_bls::PublicKey;
 use chia_protocol::Bytes;
-use chia_wallet_sdk::coinset::CoinsetClient;
 use chia_wallet_sdk::driver::MedievalVault;
 use chia_wallet_sdk::driver::SpendContext;
 use chia_wallet_sdk::prelude::AggSig;
@@ -11,21 +10,22 @@ use chia_wallet_sdk::signer::RequiredBlsSignature;
 use clvmr::NodePtr;
 
 use crate::{
-    get_alias_map, get_coinset_client, get_constants, hex_string_to_bytes32, hex_string_to_pubkey,
+    get_alias_map, get_chain_client, get_constants, hex_string_to_bytes32, hex_string_to_pubkey,
     hex_string_to_secret_key, print_medieval_vault_configuration, prompt_for_value,
-    sync_multisig_singleton, yes_no_prompt, CliError, MultisigSingleton, StateSchedulerHintedState,
+    sync_multisig_singleton, yes_no_prompt, ChainClient, CliError, MultisigSingleton, Network,
+    StateSchedulerHintedState,
 };
 
 pub async fn multisig_sign_thing_start(
     my_pubkey_str: String,
     launcher_id_str: String,
-    testnet11: bool,
-) -> Result<(PublicKey, SpendContext, CoinsetClient, MedievalVault), CliError> {
+    network: &Network,
+) -> Result<(PublicKey, SpendContext, ChainClient, MedievalVault), CliError> {
     let my_pubkey = hex_string_to_pubkey(&my_pubkey_str)?;
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     println!("Syncing multisig...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let mut ctx = SpendContext::new();
     let (MultisigSingleton::Vault(medieval_vault), _state_scheduler_info) =
         sync_multisig_singleton::<StateSchedulerHintedState>(&client, &mut ctx, launcher_id, None)
@@ -59,7 +59,7 @@ pub async fn multisig_sign_thing_finish(
     delegated_puzzle: NodePtr,
     medieval_vault: &MedievalVault,
     my_pubkey: PublicKey,
-    testnet11: bool,
+    network: &Network,
     debug: bool,
 ) -> Result<(), CliError> {
     println!(
@@ -99,7 +99,7 @@ pub async fn multisig_sign_thing_finish(
                 my_pubkey,
                 Bytes::new(delegated_puzzle_hash.to_vec()),
             ),
-            &AggSigConstants::new(get_constants(testnet11).agg_sig_amount_additional_data),
+            &AggSigConstants::new(get_constants(network).agg_sig_amount_additional_data),
         );
 
         let signature = sign(&sk, required_signature.message());
@@ -8,14 +8,14 @@ use chia_wallet_sdk::driver::{
     MedievalVaultHint, MedievalVaultInfo, SingletonInfo, SpendContext, StateSchedulerInfo,
 };
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{MedievalVault, StateScheduler},
 };
 use clvm_traits::{FromClvm, ToClvm};
 use clvm_utils::ToTreeHash;
 use clvmr::{serde::node_from_bytes, Allocator, NodePtr};
 
-use crate::{get_alias_map, CliError};
+use crate::{get_alias_map, ChainClient, CliError};
 
 pub enum MultisigSingleton<S>
 where
@@ -48,7 +48,7 @@ pub fn print_medieval_vault_configuration(m: usize, pubkeys: &[PublicKey]) -> Re
 //  (i.e., the last coin is a vault)
 #[allow(clippy::type_complexity)]
 pub async fn sync_multisig_singleton<S>(
-    client: &CoinsetClient,
+    client: &ChainClient,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
     print_state_info: Option<fn(u64, &S) -> Result<(), CliError>>,
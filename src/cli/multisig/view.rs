@@ -2,8 +2,8 @@ use chia_wallet_sdk::driver::{CatalogRegistryState, SpendContext, XchandlesRegis
 use clvm_traits::{FromClvm, ToClvm};
 
 use crate::{
-    get_coinset_client, hex_string_to_bytes32, print_medieval_vault_configuration,
-    sync_multisig_singleton, CliError,
+    get_chain_client, hex_string_to_bytes32, print_medieval_vault_configuration,
+    sync_multisig_singleton, CliError, Network,
 };
 
 #[derive(ToClvm, FromClvm, Debug, Clone, PartialEq, Eq)]
@@ -13,10 +13,10 @@ pub enum StateSchedulerHintedState {
     Xchandles(XchandlesRegistryState),
 }
 
-pub async fn multisig_view(launcher_id_str: String, testnet11: bool) -> Result<(), CliError> {
+pub async fn multisig_view(launcher_id_str: String, network: &Network) -> Result<(), CliError> {
     let mut ctx = SpendContext::new();
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
 
     println!("Viewing vault...");
 
@@ -0,0 +1,392 @@
+//! Which chain a command talks to.
+//!
+//! `mainnet` and `testnet11` are built in. Anything else (e.g. a private simulator)
+//! is looked up by name in a JSON networks file, `networks.json` in the working
+//! directory unless `--network-config` points elsewhere:
+//!
+//! ```json
+//! {
+//!   "simnet": {
+//!     "base": "testnet11",
+//!     "address_prefix": "txch",
+//!     "genesis_challenge": "0x...",
+//!     "rpc_url": "https://localhost:8555",
+//!     "rpc_kind": "full_node",
+//!     "full_node_ssl_dir": "/root/.chia/simulator/main/config/ssl",
+//!     "xchandles_api_url": "http://localhost:8080",
+//!     "catalog_api_url": "http://localhost:3000",
+//!     "catalog_launcher_id": "0x...",
+//!     "xchandles_launcher_ids": ["0x..."],
+//!     "db_path": "simnet.db"
+//!   }
+//! }
+//! ```
+//!
+//! Fields that are left out fall back to the `base` network.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chia_consensus::consensus_constants::ConsensusConstants;
+use chia_protocol::Bytes32;
+use chia_wallet_sdk::{
+    chia::sha2::Sha256,
+    driver::CatalogRegistryConstants,
+    types::{MAINNET_CONSTANTS, TESTNET11_CONSTANTS},
+};
+use clap::Args;
+use serde::Deserialize;
+
+use crate::CliError;
+
+pub const DEFAULT_NETWORK_CONFIG_PATH: &str = "networks.json";
+/// SQLite file to use instead of the network's default; `--db-path` wins over it.
+pub const DB_PATH_ENV: &str = "SLOT_MACHINE_DB_PATH";
+
+/// Built-in network a custom one inherits its defaults from.
+///
+/// It also decides which launch CSVs and price schedules apply, and whether the
+/// mainnet-only deployment checks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkBase {
+    Mainnet,
+    Testnet11,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcKind {
+    /// Coinset-compatible HTTP API; peaks come from its websocket.
+    #[default]
+    Coinset,
+    /// Full node RPC over mutual TLS; peaks are polled.
+    FullNode,
+}
+
+/// One entry of the networks file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub base: NetworkBase,
+    pub address_prefix: Option<String>,
+    pub genesis_challenge: Option<Bytes32>,
+    /// Defaults to `genesis_challenge`, like the reference full node.
+    pub agg_sig_me_additional_data: Option<Bytes32>,
+    pub rpc_url: Option<String>,
+    #[serde(default)]
+    pub rpc_kind: RpcKind,
+    /// Coinset-style websocket; derived from `rpc_url` when absent.
+    pub websocket_url: Option<String>,
+    pub full_node_ssl_dir: Option<PathBuf>,
+    pub xchandles_api_url: Option<String>,
+    pub catalog_api_url: Option<String>,
+    pub catalog_launcher_id: Option<Bytes32>,
+    #[serde(default)]
+    pub xchandles_launcher_ids: Vec<Bytes32>,
+    pub db_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub name: String,
+    pub base: NetworkBase,
+    pub address_prefix: String,
+    pub constants: ConsensusConstants,
+    /// `None` uses the public Coinset endpoint for `base`.
+    pub rpc_url: Option<String>,
+    pub rpc_kind: RpcKind,
+    pub websocket_url: Option<String>,
+    pub full_node_ssl_dir: Option<PathBuf>,
+    pub xchandles_api_url: Option<String>,
+    pub catalog_api_url: Option<String>,
+    pub catalog_launcher_id: Option<Bytes32>,
+    pub xchandles_launcher_ids: Vec<Bytes32>,
+    /// `None` uses [`Network::default_db_path`].
+    pub db_path: Option<PathBuf>,
+}
+
+// Condition opcodes whose signatures are domain-separated from AGG_SIG_ME.
+const AGG_SIG_PARENT: u8 = 43;
+const AGG_SIG_PUZZLE: u8 = 44;
+const AGG_SIG_AMOUNT: u8 = 45;
+const AGG_SIG_PUZZLE_AMOUNT: u8 = 46;
+const AGG_SIG_PARENT_AMOUNT: u8 = 47;
+const AGG_SIG_PARENT_PUZZLE: u8 = 48;
+
+fn agg_sig_additional_data(agg_sig_me_additional_data: Bytes32, opcode: u8) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(agg_sig_me_additional_data);
+    hasher.update([opcode]);
+    Bytes32::from(hasher.finalize())
+}
+
+impl NetworkBase {
+    pub fn constants(self) -> &'static ConsensusConstants {
+        match self {
+            NetworkBase::Mainnet => &MAINNET_CONSTANTS,
+            NetworkBase::Testnet11 => &TESTNET11_CONSTANTS,
+        }
+    }
+
+    pub fn address_prefix(self) -> &'static str {
+        match self {
+            NetworkBase::Mainnet => "xch",
+            NetworkBase::Testnet11 => "txch",
+        }
+    }
+}
+
+impl Network {
+    fn from_base(name: &str, base: NetworkBase) -> Self {
+        Self {
+            name: name.to_string(),
+            base,
+            address_prefix: base.address_prefix().to_string(),
+            constants: base.constants().clone(),
+            rpc_url: None,
+            rpc_kind: RpcKind::Coinset,
+            websocket_url: None,
+            full_node_ssl_dir: None,
+            xchandles_api_url: None,
+            catalog_api_url: None,
+            catalog_launcher_id: None,
+            xchandles_launcher_ids: Vec::new(),
+            db_path: None,
+        }
+    }
+
+    pub fn mainnet() -> Self {
+        Self::from_base("mainnet", NetworkBase::Mainnet)
+    }
+
+    pub fn testnet11() -> Self {
+        Self::from_base("testnet11", NetworkBase::Testnet11)
+    }
+
+    pub fn from_config(name: &str, config: NetworkConfig) -> Self {
+        let mut network = Self::from_base(name, config.base);
+
+        if let Some(prefix) = config.address_prefix {
+            network.address_prefix = prefix;
+        }
+        if let Some(genesis_challenge) = config.genesis_challenge {
+            network.constants.genesis_challenge = genesis_challenge;
+        }
+        if let Some(data) = config
+            .agg_sig_me_additional_data
+            .or(config.genesis_challenge)
+        {
+            let constants = &mut network.constants;
+            constants.agg_sig_me_additional_data = data;
+            constants.agg_sig_parent_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_PARENT);
+            constants.agg_sig_puzzle_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_PUZZLE);
+            constants.agg_sig_amount_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_AMOUNT);
+            constants.agg_sig_puzzle_amount_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_PUZZLE_AMOUNT);
+            constants.agg_sig_parent_amount_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_PARENT_AMOUNT);
+            constants.agg_sig_parent_puzzle_additional_data =
+                agg_sig_additional_data(data, AGG_SIG_PARENT_PUZZLE);
+        }
+
+        network.rpc_url = config.rpc_url;
+        network.rpc_kind = config.rpc_kind;
+        network.websocket_url = config.websocket_url;
+        network.full_node_ssl_dir = config.full_node_ssl_dir;
+        network.xchandles_api_url = config.xchandles_api_url;
+        network.catalog_api_url = config.catalog_api_url;
+        network.catalog_launcher_id = config.catalog_launcher_id;
+        network.xchandles_launcher_ids = config.xchandles_launcher_ids;
+        network.db_path = config.db_path;
+        network
+    }
+
+    /// Resolves a built-in name, or reads `name` from the networks file at `config_path`.
+    pub fn load(name: &str, config_path: &Path) -> Result<Self, CliError> {
+        match name {
+            "mainnet" => return Ok(Self::mainnet()),
+            "testnet11" => return Ok(Self::testnet11()),
+            _ => {}
+        }
+
+        let contents = std::fs::read_to_string(config_path).map_err(|e| {
+            CliError::Custom(format!(
+                "network '{name}' is not built in and {} could not be read: {e}",
+                config_path.display()
+            ))
+        })?;
+        let mut configs: HashMap<String, NetworkConfig> = serde_json::from_str(&contents)?;
+        let config = configs
+            .remove(name)
+            .ok_or_else(|| CliError::UnknownNetwork(name.to_string()))?;
+
+        Ok(Self::from_config(name, config))
+    }
+
+    /// Testnet rules: testnet CSVs and price schedules, no mainnet-only checks.
+    pub fn is_testnet(&self) -> bool {
+        self.base == NetworkBase::Testnet11
+    }
+
+    /// Mainnet keeps the historical `data.db`; every other network gets its own file so
+    /// caches from different chains never share one.
+    pub fn default_db_path(&self) -> PathBuf {
+        match self.base {
+            NetworkBase::Mainnet if self.name == "mainnet" => PathBuf::from("data.db"),
+            _ => PathBuf::from(format!("data-{}.db", self.name)),
+        }
+    }
+
+    pub fn db_path(&self) -> PathBuf {
+        self.db_path
+            .clone()
+            .unwrap_or_else(|| self.default_db_path())
+    }
+
+    /// CATalog constants for `base`, with the launcher id swapped when configured.
+    pub fn catalog_constants(&self) -> CatalogRegistryConstants {
+        let constants = CatalogRegistryConstants::get(self.is_testnet());
+        match self.catalog_launcher_id {
+            Some(launcher_id) => constants.with_launcher_id(launcher_id),
+            None => constants,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct NetworkArgs {
+    /// Network: mainnet, testnet11, or a name from the networks config file
+    #[arg(long, global = true, default_value = "mainnet")]
+    pub network: String,
+
+    /// Use testnet11 (same as --network testnet11)
+    #[arg(long, global = true, default_value_t = false)]
+    pub testnet11: bool,
+
+    /// Networks config file used to resolve custom --network names
+    #[arg(long, global = true, default_value = DEFAULT_NETWORK_CONFIG_PATH)]
+    pub network_config: PathBuf,
+
+    /// SQLite database file (default: data.db on mainnet, data-<network>.db elsewhere)
+    #[arg(long, global = true)]
+    pub db_path: Option<PathBuf>,
+}
+
+impl NetworkArgs {
+    pub fn resolve(&self) -> Result<Network, CliError> {
+        let mut network = if self.testnet11 {
+            if self.network != "mainnet" && self.network != "testnet11" {
+                return Err(CliError::Custom(format!(
+                    "--testnet11 conflicts with --network {}",
+                    self.network
+                )));
+            }
+            Network::testnet11()
+        } else {
+            Network::load(&self.network, &self.network_config)?
+        };
+
+        if let Some(path) = self
+            .db_path
+            .clone()
+            .or_else(|| std::env::var_os(DB_PATH_ENV).map(PathBuf::from))
+        {
+            network.db_path = Some(path);
+        }
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_agg_sig_data_matches_builtin_constants() {
+        for base in [NetworkBase::Mainnet, NetworkBase::Testnet11] {
+            let builtin = base.constants();
+            let network = Network::from_config(
+                "copy",
+                NetworkConfig {
+                    base,
+                    address_prefix: None,
+                    genesis_challenge: Some(builtin.genesis_challenge),
+                    agg_sig_me_additional_data: Some(builtin.agg_sig_me_additional_data),
+                    rpc_url: None,
+                    rpc_kind: RpcKind::Coinset,
+                    websocket_url: None,
+                    full_node_ssl_dir: None,
+                    xchandles_api_url: None,
+                    catalog_api_url: None,
+                    catalog_launcher_id: None,
+                    xchandles_launcher_ids: Vec::new(),
+                    db_path: None,
+                },
+            );
+
+            assert_eq!(&network.constants, builtin);
+        }
+    }
+
+    #[test]
+    fn custom_network_overrides_base() {
+        let json = r#"{
+            "simnet": {
+                "base": "testnet11",
+                "address_prefix": "sim",
+                "genesis_challenge": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "rpc_url": "https://localhost:8555",
+                "rpc_kind": "full_node",
+                "xchandles_launcher_ids": [
+                    "0x2222222222222222222222222222222222222222222222222222222222222222"
+                ]
+            }
+        }"#;
+        let dir = std::env::temp_dir().join(format!("networks-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("networks.json");
+        std::fs::write(&path, json).unwrap();
+
+        let network = Network::load("simnet", &path).unwrap();
+        assert!(network.is_testnet());
+        assert_eq!(network.address_prefix, "sim");
+        assert_eq!(
+            network.constants.genesis_challenge,
+            Bytes32::new([0x11; 32])
+        );
+        assert_eq!(
+            network.constants.agg_sig_me_additional_data,
+            Bytes32::new([0x11; 32])
+        );
+        assert_ne!(
+            network.constants.agg_sig_amount_additional_data,
+            TESTNET11_CONSTANTS.agg_sig_amount_additional_data
+        );
+        assert_eq!(network.rpc_kind, RpcKind::FullNode);
+        assert_eq!(
+            network.xchandles_launcher_ids,
+            vec![Bytes32::new([0x22; 32])]
+        );
+        assert_eq!(network.db_path(), PathBuf::from("data-simnet.db"));
+
+        assert!(matches!(
+            Network::load("devnet", &path),
+            Err(CliError::UnknownNetwork(_))
+        ));
+        assert_eq!(
+            Network::load("mainnet", &path).unwrap().address_prefix,
+            "xch"
+        );
+        assert_eq!(Network::mainnet().db_path(), PathBuf::from("data.db"));
+        assert_eq!(
+            Network::testnet11().db_path(),
+            PathBuf::from("data-testnet11.db")
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -3,10 +3,16 @@ mod broadcast_entry_update;
 mod clawback_rewards;
 mod commit_available_rewards;
 mod commit_rewards;
+mod distributor_listener;
+mod entry;
+mod entry_updates;
 mod helpers;
 mod initiate_payout;
+mod keeper;
 mod launch;
+mod listen;
 mod new_epoch;
+mod payout_all;
 mod refresh;
 mod sign_entry_update;
 mod stake;
@@ -20,10 +26,16 @@ pub use broadcast_entry_update::*;
 pub use clawback_rewards::*;
 pub use commit_available_rewards::*;
 pub use commit_rewards::*;
+pub use distributor_listener::*;
+pub use entry::*;
+pub use entry_updates::*;
 pub use helpers::*;
 pub use initiate_payout::*;
+pub use keeper::*;
 pub use launch::*;
+pub use listen::*;
 pub use new_epoch::*;
+pub use payout_all::*;
 pub use refresh::*;
 pub use sign_entry_update::*;
 pub use stake::*;
@@ -10,15 +10,15 @@ use chia_wallet_sdk::{
 use clvmr::NodePtr;
 
 use crate::{
-    assets_xch_and_cat, confirm_pushed_transaction, get_coinset_client, get_constants,
+    assets_xch_and_cat, confirm_pushed_transaction, get_chain_client, get_constants,
     get_last_onchain_timestamp, hex_string_to_bytes32, no_assets, parse_amount, sync_distributor,
-    yes_no_prompt, CliError, Db, SageClient,
+    yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_add_rewards(
     launcher_id_str: String,
     reward_amount_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
@@ -26,8 +26,8 @@ pub async fn reward_distributor_add_rewards(
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -114,7 +114,7 @@ pub async fn reward_distributor_add_rewards(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -123,7 +123,7 @@ pub async fn reward_distributor_add_rewards(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
@@ -10,7 +10,7 @@ use clvmr::{Allocator, NodePtr};
 use crate::{
     find_entry_slots, get_constants, get_last_onchain_timestamp, hex_string_to_bytes32,
     multisig_broadcast_thing_finish, multisig_broadcast_thing_start, sync_distributor, CliError,
-    Db,
+    Db, Network,
 };
 
 pub async fn reward_distributor_broadcast_entry_update(
@@ -19,14 +19,14 @@ pub async fn reward_distributor_broadcast_entry_update(
     entry_shares: u64,
     signatures_str: String,
     remove_entry: bool,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let entry_payout_puzzle_hash = hex_string_to_bytes32(&entry_payout_puzzle_hash_str)?;
 
     println!("\nGetting distributor constants... ");
-    let db = Db::new(true).await?;
+    let db = Db::new(network, true).await?;
 
     let mut temp_allocator = Allocator::new();
     let distributor_constants = db
@@ -48,7 +48,7 @@ pub async fn reward_distributor_broadcast_entry_update(
     };
 
     let (signature_from_signers, pubkeys, client, mut ctx, medieval_vault) =
-        multisig_broadcast_thing_start(signatures_str, hex::encode(manager_launcher_id), testnet11)
+        multisig_broadcast_thing_start(signatures_str, hex::encode(manager_launcher_id), network)
             .await?;
 
     println!("\nSyncing reward distributor... ");
@@ -80,7 +80,7 @@ pub async fn reward_distributor_broadcast_entry_update(
     );
     println!("  Entry shares: {}", entry_shares);
 
-    let constants = get_constants(testnet11);
+    let constants = get_constants(network);
     let medieval_vault_coin_id = medieval_vault.coin.coin_id();
     let medieval_vault_inner_ph = medieval_vault.info.inner_puzzle_hash();
 
@@ -161,7 +161,7 @@ pub async fn reward_distributor_broadcast_entry_update(
         &mut ctx,
         signature_from_signers + &pending_sig,
         fee_str,
-        testnet11,
+        network,
         medieval_vault_coin_id,
         None,
     )
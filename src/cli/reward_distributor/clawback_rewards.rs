@@ -14,9 +14,9 @@ use clvmr::NodePtr;
 
 use crate::{
     assets_xch_only, confirm_pushed_transaction, find_commitment_slots, find_reward_slot,
-    get_coin_public_key, get_coinset_client, get_constants, hex_string_to_bytes32,
+    get_chain_client, get_coin_public_key, get_constants, hex_string_to_bytes32,
     hex_string_to_signature, no_assets, parse_amount, spend_to_coin_spend, sync_distributor,
-    yes_no_prompt, CliError, Db, SageClient,
+    yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_clawback_rewards(
@@ -24,7 +24,7 @@ pub async fn reward_distributor_clawback_rewards(
     clawback_address: String,
     epoch_start: Option<u64>,
     reward_amount_str: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
@@ -34,8 +34,8 @@ pub async fn reward_distributor_clawback_rewards(
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -97,7 +97,7 @@ pub async fn reward_distributor_clawback_rewards(
         security_coin,
         Conditions::new().create_coin(clawback_ph, 0, Memos::None),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     println!("Fetching clawback public key...");
@@ -131,7 +131,7 @@ pub async fn reward_distributor_clawback_rewards(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
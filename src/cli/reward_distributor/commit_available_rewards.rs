@@ -1,16 +1,16 @@
 use chia_protocol::{Bytes32, SpendBundle};
 use chia_puzzle_types::{cat::CatArgs, singleton::SingletonStruct, LineageProof};
 use chia_wallet_sdk::{
-    coinset::ChiaRpcClient,
+    coinset::{ChiaRpcClient, CoinRecord},
     driver::{
         create_security_coin, decode_offer, spend_security_coin, Cat, CatInfo, CatLayer, CatSpend,
-        Layer, Offer, Puzzle, RewardDistributor, RewardDistributorCommitIncentivesAction, Spend,
-        SpendContext,
+        Layer, Offer, Puzzle, RewardDistributor, RewardDistributorCommitIncentivesAction, Slot,
+        Spend, SpendContext,
     },
     types::{
         puzzles::{
             NonceWrapperArgs, P2NextRewardDistributorEpochArgs,
-            P2NextRewardDistributorEpochSolution,
+            P2NextRewardDistributorEpochSolution, RewardDistributorRewardSlotValue,
         },
         Conditions, Mod,
     },
@@ -19,16 +19,16 @@ use chia_wallet_sdk::{
 use clvm_utils::{ToTreeHash, TreeHash};
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, find_reward_slot, get_coinset_client,
-    get_constants, hex_string_to_bytes32, no_assets, parse_amount, sync_distributor, yes_no_prompt,
-    CliError, Db, SageClient,
+    assets_xch_only, confirm_pushed_transaction, find_reward_slot, get_chain_client, get_constants,
+    hex_string_to_bytes32, no_assets, parse_amount, sync_distributor, yes_no_prompt, ChainClient,
+    CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_commit_available_rewards(
     launcher_id_str: String,
     clawback_address: Option<String>,
     max_coins: usize,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     if max_coins == 0 || max_coins > 32 {
@@ -45,63 +45,24 @@ pub async fn reward_distributor_commit_available_rewards(
         .map_or_else(Bytes32::default, |address| address.puzzle_hash);
     let fee = parse_amount(&fee_str, false)?;
 
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let mut ctx = SpendContext::new();
-    let launcher_record = client
-        .get_coin_record_by_name(launcher_id)
-        .await?
-        .coin_record
-        .ok_or(CliError::CoinNotFound(launcher_id))?;
-    let launcher_spend = client
-        .get_puzzle_and_solution(launcher_id, Some(launcher_record.spent_block_index))
-        .await?
-        .coin_solution
-        .ok_or(CliError::CoinNotSpent(launcher_id))?;
-    let launcher_solution = ctx.alloc(&launcher_spend.solution)?;
-    let Some((constants, initial_state, _eve_coin)) = RewardDistributor::from_launcher_solution(
-        &mut ctx,
-        launcher_spend.coin,
-        launcher_solution,
-    )?
-    else {
-        return Err(CliError::Custom(
-            "Could not parse reward distributor launcher spend".to_string(),
-        ));
-    };
-    let first_epoch_start = initial_state.round_time_info.epoch_end;
-    let reward_asset_id = constants.reserve_asset_id;
-
-    let p2_args = P2NextRewardDistributorEpochArgs::new(
-        clawback_inner_puzzle_hash,
-        SingletonStruct::new(launcher_id).tree_hash(),
-        first_epoch_start,
-        constants.epoch_seconds,
-    );
-    let p2_inner_puzzle_hash: Bytes32 = p2_args.curry_tree_hash().into();
-    let full_puzzle_hash: Bytes32 =
-        CatArgs::curry_tree_hash(reward_asset_id, p2_inner_puzzle_hash.into()).into();
+    let deposit =
+        reward_deposit_puzzle(&mut ctx, &client, launcher_id, clawback_inner_puzzle_hash).await?;
 
     println!(
         "Reward CAT deposit address: {}",
-        Address::new(p2_inner_puzzle_hash, crate::get_prefix(testnet11)).encode()?
+        Address::new(deposit.p2_inner_puzzle_hash, crate::get_prefix(network)).encode()?
     );
     println!("Syncing reward distributor...");
-    let db = Db::new(false).await?;
+    let db = Db::new(network, false).await?;
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
     println!(
         "Scanning full Reward CAT puzzle hash {}...",
-        hex::encode(full_puzzle_hash)
+        hex::encode(deposit.full_puzzle_hash)
     );
-    let mut records = client
-        .get_coin_records_by_puzzle_hash(full_puzzle_hash, None, None, Some(false), None)
-        .await?
-        .coin_records
-        .unwrap_or_default();
-    records.retain(|record| !record.spent && record.coin.puzzle_hash == full_puzzle_hash);
-    records.sort_by_key(|record| record.coin.coin_id());
-    records.truncate(max_coins);
-
+    let records = available_reward_coins(&client, &deposit, max_coins).await?;
     if records.is_empty() {
         return Err(CliError::Custom(
             "No unspent Reward CAT coins are available to commit".to_string(),
@@ -109,7 +70,13 @@ pub async fn reward_distributor_commit_available_rewards(
     }
 
     let next_epoch_start = distributor.info.state.round_time_info.epoch_end;
-    let reward_slot = find_reward_slot(&mut ctx, &client, constants, next_epoch_start).await?;
+    let reward_slot = find_reward_slot(
+        &mut ctx,
+        &client,
+        distributor.info.constants,
+        next_epoch_start,
+    )
+    .await?;
     let total_rewards = records.iter().try_fold(0_u64, |total, record| {
         total
             .checked_add(record.coin.amount)
@@ -137,59 +104,185 @@ pub async fn reward_distributor_commit_available_rewards(
     let (security_coin_sk, security_coin) =
         create_security_coin(&mut ctx, offer.offered_coins().xch[0])?;
 
+    let p2_cat_spends = commit_reward_coins(
+        &mut ctx,
+        &client,
+        &mut distributor,
+        &deposit,
+        records,
+        reward_slot,
+    )
+    .await?;
+
+    let (_new_distributor, distributor_signature) =
+        distributor.finish_spend(&mut ctx, p2_cat_spends)?;
+    let security_signature = spend_security_coin(
+        &mut ctx,
+        security_coin,
+        Conditions::new(),
+        &security_coin_sk,
+        get_constants(network),
+    )?;
+    let spend_bundle = offer.take(SpendBundle::new(
+        ctx.take(),
+        security_signature + &distributor_signature,
+    ));
+
+    println!("Submitting transaction...");
+    let response = client.push_tx(spend_bundle).await?;
+    if confirm_pushed_transaction(&client, &response, security_coin.coin_id(), true).await? {
+        println!("Confirmed!");
+    }
+
+    Ok(())
+}
+
+/// The `P2NextRewardDistributorEpochArgs` puzzle that Reward CAT deposits for a
+/// distributor are sent to.
+pub struct RewardDepositPuzzle {
+    pub args: P2NextRewardDistributorEpochArgs,
+    pub reward_asset_id: Bytes32,
+    pub clawback_inner_puzzle_hash: Bytes32,
+    pub p2_inner_puzzle_hash: Bytes32,
+    pub full_puzzle_hash: Bytes32,
+}
+
+pub async fn reward_deposit_puzzle(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    launcher_id: Bytes32,
+    clawback_inner_puzzle_hash: Bytes32,
+) -> Result<RewardDepositPuzzle, CliError> {
+    let launcher_record = client
+        .get_coin_record_by_name(launcher_id)
+        .await?
+        .coin_record
+        .ok_or(CliError::CoinNotFound(launcher_id))?;
+    let launcher_spend = client
+        .get_puzzle_and_solution(launcher_id, Some(launcher_record.spent_block_index))
+        .await?
+        .coin_solution
+        .ok_or(CliError::CoinNotSpent(launcher_id))?;
+    let launcher_solution = ctx.alloc(&launcher_spend.solution)?;
+    let Some((constants, initial_state, _eve_coin)) =
+        RewardDistributor::from_launcher_solution(ctx, launcher_spend.coin, launcher_solution)?
+    else {
+        return Err(CliError::Custom(
+            "Could not parse reward distributor launcher spend".to_string(),
+        ));
+    };
+    let first_epoch_start = initial_state.round_time_info.epoch_end;
+    let reward_asset_id = constants.reserve_asset_id;
+
+    let args = P2NextRewardDistributorEpochArgs::new(
+        clawback_inner_puzzle_hash,
+        SingletonStruct::new(launcher_id).tree_hash(),
+        first_epoch_start,
+        constants.epoch_seconds,
+    );
+    let p2_inner_puzzle_hash: Bytes32 = args.curry_tree_hash().into();
+    let full_puzzle_hash: Bytes32 =
+        CatArgs::curry_tree_hash(reward_asset_id, p2_inner_puzzle_hash.into()).into();
+
+    Ok(RewardDepositPuzzle {
+        args,
+        reward_asset_id,
+        clawback_inner_puzzle_hash,
+        p2_inner_puzzle_hash,
+        full_puzzle_hash,
+    })
+}
+
+/// Up to `max_coins` unspent deposits, in coin id order.
+pub async fn available_reward_coins(
+    client: &ChainClient,
+    deposit: &RewardDepositPuzzle,
+    max_coins: usize,
+) -> Result<Vec<CoinRecord>, CliError> {
+    let mut records = client
+        .get_coin_records_by_puzzle_hash(deposit.full_puzzle_hash, None, None, Some(false), None)
+        .await?
+        .coin_records
+        .unwrap_or_default();
+    records.retain(|record| !record.spent && record.coin.puzzle_hash == deposit.full_puzzle_hash);
+    records.sort_by_key(|record| record.coin.coin_id());
+    records.truncate(max_coins);
+    Ok(records)
+}
+
+/// `record` as a Reward CAT, or `None` when its parent is not a CAT of the distributor's
+/// reward asset; anyone can send other coins to the deposit puzzle hash.
+pub async fn reward_deposit_cat(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    deposit: &RewardDepositPuzzle,
+    record: &CoinRecord,
+) -> Result<Option<Cat>, CliError> {
+    let parent_spend = client
+        .get_puzzle_and_solution(
+            record.coin.parent_coin_info,
+            Some(record.confirmed_block_index),
+        )
+        .await?
+        .coin_solution
+        .ok_or(CliError::CoinNotSpent(record.coin.parent_coin_info))?;
+    let parent_puzzle_ptr = ctx.alloc(&parent_spend.puzzle_reveal)?;
+    let parent_puzzle = Puzzle::parse(ctx, parent_puzzle_ptr);
+    let Some(parent_cat) = CatLayer::<clvmr::NodePtr>::parse_puzzle(ctx, parent_puzzle)? else {
+        return Ok(None);
+    };
+    if parent_cat.asset_id != deposit.reward_asset_id {
+        return Ok(None);
+    }
+
+    Ok(Some(Cat::new(
+        record.coin,
+        Some(LineageProof {
+            parent_parent_coin_info: parent_spend.coin.parent_coin_info,
+            parent_inner_puzzle_hash: ctx.tree_hash(parent_cat.inner_puzzle).into(),
+            parent_amount: parent_spend.coin.amount,
+        }),
+        CatInfo::new(deposit.reward_asset_id, None, deposit.p2_inner_puzzle_hash),
+    )))
+}
+
+/// Commit every deposit in `records` to the distributor's next epoch and return the
+/// deposit spends to pass to `finish_spend`.
+pub async fn commit_reward_coins(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    distributor: &mut RewardDistributor,
+    deposit: &RewardDepositPuzzle,
+    records: Vec<CoinRecord>,
+    reward_slot: Slot<RewardDistributorRewardSlotValue>,
+) -> Result<Vec<CatSpend>, CliError> {
+    let next_epoch_start = distributor.info.state.round_time_info.epoch_end;
     let mut p2_cat_spends = Vec::with_capacity(records.len());
     for record in records {
-        let parent_spend = client
-            .get_puzzle_and_solution(
-                record.coin.parent_coin_info,
-                Some(record.confirmed_block_index),
-            )
-            .await?
-            .coin_solution
-            .ok_or(CliError::CoinNotSpent(record.coin.parent_coin_info))?;
-        let parent_puzzle_ptr = ctx.alloc(&parent_spend.puzzle_reveal)?;
-        let parent_puzzle = Puzzle::parse(&ctx, parent_puzzle_ptr);
-        let Some(parent_cat) = CatLayer::<clvmr::NodePtr>::parse_puzzle(&ctx, parent_puzzle)?
-        else {
-            return Err(CliError::Custom(format!(
-                "Parent of coin {} is not a CAT",
-                hex::encode(record.coin.coin_id())
-            )));
-        };
-        if parent_cat.asset_id != reward_asset_id {
+        let Some(cat) = reward_deposit_cat(ctx, client, deposit, &record).await? else {
             return Err(CliError::Custom(format!(
                 "Coin {} is not the distributor Reward CAT",
                 hex::encode(record.coin.coin_id())
             )));
-        }
-
-        let cat = Cat::new(
-            record.coin,
-            Some(LineageProof {
-                parent_parent_coin_info: parent_spend.coin.parent_coin_info,
-                parent_inner_puzzle_hash: ctx.tree_hash(parent_cat.inner_puzzle).into(),
-                parent_amount: parent_spend.coin.amount,
-            }),
-            CatInfo::new(reward_asset_id, None, p2_inner_puzzle_hash),
-        );
+        };
         let clawback_ph = NonceWrapperArgs::<Bytes32, TreeHash> {
             nonce: cat.coin.coin_id(),
-            inner_puzzle: clawback_inner_puzzle_hash.into(),
+            inner_puzzle: deposit.clawback_inner_puzzle_hash.into(),
         }
         .curry_tree_hash();
 
         let _security_conditions = distributor
             .new_action::<RewardDistributorCommitIncentivesAction>()
             .spend(
-                &mut ctx,
-                &mut distributor,
+                ctx,
+                distributor,
                 reward_slot.clone(),
                 next_epoch_start,
                 clawback_ph.into(),
                 cat.coin.amount,
             )?;
 
-        let p2_inner_puzzle = ctx.curry(p2_args)?;
+        let p2_inner_puzzle = ctx.curry(deposit.args)?;
         let p2_inner_solution = ctx.alloc(&P2NextRewardDistributorEpochSolution {
             next_epoch_start,
             my_id: cat.coin.coin_id(),
@@ -201,26 +294,5 @@ pub async fn reward_distributor_commit_available_rewards(
             Spend::new(p2_inner_puzzle, p2_inner_solution),
         ));
     }
-
-    let (_new_distributor, distributor_signature) =
-        distributor.finish_spend(&mut ctx, p2_cat_spends)?;
-    let security_signature = spend_security_coin(
-        &mut ctx,
-        security_coin,
-        Conditions::new(),
-        &security_coin_sk,
-        get_constants(testnet11),
-    )?;
-    let spend_bundle = offer.take(SpendBundle::new(
-        ctx.take(),
-        security_signature + &distributor_signature,
-    ));
-
-    println!("Submitting transaction...");
-    let response = client.push_tx(spend_bundle).await?;
-    if confirm_pushed_transaction(&client, &response, security_coin.coin_id(), true).await? {
-        println!("Confirmed!");
-    }
-
-    Ok(())
+    Ok(p2_cat_spends)
 }
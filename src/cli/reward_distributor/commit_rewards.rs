@@ -11,9 +11,9 @@ use chia_wallet_sdk::{
 use clvmr::NodePtr;
 
 use crate::{
-    assets_xch_and_cat, confirm_pushed_transaction, find_reward_slot, get_coinset_client,
+    assets_xch_and_cat, confirm_pushed_transaction, find_reward_slot, get_chain_client,
     get_constants, hex_string_to_bytes32, no_assets, parse_amount, sync_distributor, yes_no_prompt,
-    CliError, Db, SageClient,
+    CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_commit_rewards(
@@ -21,7 +21,7 @@ pub async fn reward_distributor_commit_rewards(
     reward_amount_str: String,
     epoch_start: u64,
     clawback_address: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
@@ -29,8 +29,8 @@ pub async fn reward_distributor_commit_rewards(
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -102,7 +102,7 @@ pub async fn reward_distributor_commit_rewards(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -111,7 +111,7 @@ pub async fn reward_distributor_commit_rewards(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
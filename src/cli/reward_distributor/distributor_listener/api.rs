@@ -0,0 +1,505 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Path, Query, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chia_protocol::Bytes32;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tower_http::cors::{Any, CorsLayer};
+
+use super::slot_store::{DistributorSlotKind, DistributorSlotStore, DistributorSlotValue};
+use super::store::{DistributorRecord, DistributorStore, StoredDistributorState};
+use super::types::{
+    CommitmentItem, CommitmentScheduleResponse, DistributorQuery, DistributorStateResponse,
+    EntryRewardsResponse, EpochHistoryResponse, EpochItem, EpochStatus,
+};
+use crate::{
+    hex32, parse_launcher_id, record_http_metrics, ApiError, FreshnessState, ListenerMetrics,
+    ReadyResponse, RegistryStatusItem, RegistryTip, StatusResponse, MAX_PEAK_AGE_SECONDS,
+    MAX_PEAK_LAG,
+};
+
+#[derive(Clone)]
+pub struct DistributorApiState {
+    pub distributors: Arc<dyn DistributorStore>,
+    pub slots: Arc<dyn DistributorSlotStore>,
+    pub freshness: Arc<RwLock<FreshnessState>>,
+    /// Synced tip per distributor; the listener is not ready until every one is published.
+    pub distributor_tips: Arc<RwLock<HashMap<Bytes32, RegistryTip>>>,
+    /// Followed distributors in configuration order.
+    pub launcher_ids: Vec<Bytes32>,
+    /// Optional clock override for tests (unix seconds).
+    pub now_unix_override: Option<u64>,
+    /// Shared with the indexer and websocket loop; rendered on `/metrics`.
+    pub metrics: Arc<ListenerMetrics>,
+}
+
+impl DistributorApiState {
+    pub fn new(
+        distributors: Arc<dyn DistributorStore>,
+        slots: Arc<dyn DistributorSlotStore>,
+        freshness: FreshnessState,
+        launcher_ids: Vec<Bytes32>,
+    ) -> Self {
+        Self {
+            distributors,
+            slots,
+            freshness: Arc::new(RwLock::new(freshness)),
+            distributor_tips: Arc::new(RwLock::new(HashMap::new())),
+            launcher_ids,
+            now_unix_override: None,
+            metrics: ListenerMetrics::shared("reward_distributor"),
+        }
+    }
+
+    fn now_unix(&self) -> u64 {
+        self.now_unix_override.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// Public reward distributor router: CORS is credential-free for any origin on GET/HEAD.
+pub fn distributor_router(state: DistributorApiState) -> Router {
+    let metrics = Arc::clone(&state.metrics);
+    Router::new()
+        .route("/healthz", get(|| async { StatusCode::OK }))
+        .route("/readyz", get(get_readyz).head(head_readyz))
+        .route("/status", get(get_status))
+        .route("/state", get(get_state).head(head_state))
+        .route(
+            "/entry/{payout_puzzle_hash}",
+            get(get_entry).head(head_entry),
+        )
+        .route("/commitments", get(get_commitments).head(head_commitments))
+        .route("/epochs", get(get_epochs).head(head_epochs))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&metrics),
+            record_http_metrics,
+        ))
+        .route("/metrics", get(move || async move { metrics.response() }))
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods([Method::GET, Method::HEAD, Method::OPTIONS])
+                .allow_headers(Any),
+        )
+        .with_state(state)
+}
+
+/// `indexed_peak_height` when fresh; `index_stale` otherwise.
+pub async fn require_distributor_fresh(state: &DistributorApiState) -> Result<u32, ApiError> {
+    let freshness = state.freshness.read().await;
+    if !freshness.is_fresh(state.now_unix()) {
+        return Err(ApiError::index_stale(
+            freshness.indexed_peak_height,
+            freshness.upstream_peak_height,
+        ));
+    }
+    Ok(freshness.indexed_peak_height)
+}
+
+/// Requested distributor, or the first followed one when omitted.
+pub fn select_distributor(
+    state: &DistributorApiState,
+    launcher_id_param: Option<&str>,
+) -> Result<Bytes32, ApiError> {
+    match launcher_id_param {
+        None => state
+            .launcher_ids
+            .first()
+            .copied()
+            .ok_or_else(ApiError::distributor_not_followed),
+        Some(raw) => {
+            let id = parse_launcher_id(raw).ok_or_else(ApiError::invalid_launcher_id)?;
+            if state.launcher_ids.contains(&id) {
+                Ok(id)
+            } else {
+                Err(ApiError::distributor_not_followed())
+            }
+        }
+    }
+}
+
+/// `index_stale` naming the distributor, for reads that need its synced state.
+async fn unsynced_distributor(state: &DistributorApiState, launcher_id: Bytes32) -> ApiError {
+    let freshness = state.freshness.read().await;
+    ApiError::index_stale(
+        freshness.indexed_peak_height,
+        freshness.upstream_peak_height,
+    )
+    .with_details(json!({
+        "indexed_peak_height": freshness.indexed_peak_height,
+        "upstream_peak_height": freshness.upstream_peak_height,
+        "unsynced_distributor": hex32(launcher_id),
+    }))
+}
+
+/// Record with a current state, or `index_stale` until the first sync lands.
+async fn synced_record(
+    state: &DistributorApiState,
+    launcher_id: Bytes32,
+) -> Result<(DistributorRecord, StoredDistributorState), ApiError> {
+    match state.distributors.get(launcher_id).await {
+        Some(record) => match record.current.clone() {
+            Some(current) => Ok((record, current)),
+            None => Err(unsynced_distributor(state, launcher_id).await),
+        },
+        None => Err(unsynced_distributor(state, launcher_id).await),
+    }
+}
+
+/// Readiness fails closed: fresh index and a synced tip for every followed distributor.
+async fn require_ready(state: &DistributorApiState) -> Result<ReadyResponse, ApiError> {
+    let indexed_peak_height = require_distributor_fresh(state).await?;
+    let tips = state.distributor_tips.read().await;
+    if let Some(missing) = state
+        .launcher_ids
+        .iter()
+        .find(|launcher_id| !tips.contains_key(launcher_id))
+    {
+        let missing = *missing;
+        drop(tips);
+        return Err(unsynced_distributor(state, missing).await);
+    }
+    Ok(ReadyResponse {
+        ready: true,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_status(state: &DistributorApiState) -> StatusResponse {
+    let now = state.now_unix();
+    let freshness = state.freshness.read().await.clone();
+    let tips = state.distributor_tips.read().await.clone();
+    let stale_reasons = freshness
+        .stale_reasons(now)
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    StatusResponse {
+        ready: stale_reasons.is_empty()
+            && state
+                .launcher_ids
+                .iter()
+                .all(|launcher_id| tips.contains_key(launcher_id)),
+        stale_reasons,
+        indexed_peak_height: freshness.indexed_peak_height,
+        upstream_peak_height: freshness.upstream_peak_height,
+        peak_lag: freshness
+            .upstream_peak_height
+            .saturating_sub(freshness.indexed_peak_height),
+        max_peak_lag: MAX_PEAK_LAG,
+        last_successful_peak_unix: freshness.last_successful_peak_unix,
+        seconds_since_last_peak: now.saturating_sub(freshness.last_successful_peak_unix),
+        max_peak_age_seconds: MAX_PEAK_AGE_SECONDS,
+        confirmed_timestamp: freshness.confirmed_timestamp,
+        rolling_back: freshness.rolling_back,
+        resyncing: freshness.resyncing,
+        last_reorg_unix: freshness.last_reorg_unix,
+        last_reorg_height: freshness.last_reorg_height,
+        registries: state
+            .launcher_ids
+            .iter()
+            .map(|launcher_id| {
+                let tip = tips.get(launcher_id);
+                RegistryStatusItem {
+                    launcher_id: hex32(*launcher_id),
+                    tip_coin_id: tip.map(|t| hex32(t.coin_id)),
+                    tip_indexed_height: tip.map(|t| t.indexed_height),
+                }
+            })
+            .collect(),
+    }
+}
+
+async fn lookup_state(
+    state: &DistributorApiState,
+    query: &DistributorQuery,
+) -> Result<DistributorStateResponse, ApiError> {
+    let launcher_id = select_distributor(state, query.launcher_id.as_deref())?;
+    let indexed_peak_height = require_distributor_fresh(state).await?;
+    let (record, current) = synced_record(state, launcher_id).await?;
+    Ok(DistributorStateResponse {
+        launcher_id: hex32(launcher_id),
+        coin_id: hex32(current.coin_id),
+        reserve_asset_id: hex32(record.constants.reserve_asset_id),
+        active_shares: current.active_shares,
+        total_reserves: current.total_reserves,
+        cumulative_payout: current.cumulative_payout.to_string(),
+        remaining_rewards: current.remaining_rewards.to_string(),
+        last_update: current.last_update,
+        epoch_end: current.epoch_end,
+        epoch_seconds: record.constants.epoch_seconds,
+        precision: record.constants.precision,
+        fee_bps: record.constants.fee_bps,
+        payout_threshold: record.constants.payout_threshold,
+        confirmation_height: current.confirmation_height,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_entry(
+    state: &DistributorApiState,
+    raw_payout_puzzle_hash: &str,
+    query: &DistributorQuery,
+) -> Result<EntryRewardsResponse, ApiError> {
+    let payout_puzzle_hash =
+        parse_launcher_id(raw_payout_puzzle_hash).ok_or_else(ApiError::invalid_puzzle_hash)?;
+    let launcher_id = select_distributor(state, query.launcher_id.as_deref())?;
+    let indexed_peak_height = require_distributor_fresh(state).await?;
+    let (record, current) = synced_record(state, launcher_id).await?;
+
+    let (slot, initial_cumulative_payout, shares) = state
+        .slots
+        .slots_by_hint(launcher_id, payout_puzzle_hash)
+        .await
+        .into_iter()
+        .filter(|slot| slot.spent_height.is_none())
+        .filter_map(|slot| match slot.value {
+            DistributorSlotValue::Entry {
+                payout_puzzle_hash: ph,
+                initial_cumulative_payout,
+                shares,
+            } if ph == payout_puzzle_hash => Some((slot, initial_cumulative_payout, shares)),
+            _ => None,
+        })
+        .max_by_key(|(slot, _, _)| slot.created_height)
+        .ok_or_else(ApiError::entry_not_found)?;
+
+    let accrued = current
+        .cumulative_payout
+        .saturating_sub(initial_cumulative_payout)
+        .saturating_mul(u128::from(shares))
+        / u128::from(record.constants.precision.max(1));
+    Ok(EntryRewardsResponse {
+        launcher_id: hex32(launcher_id),
+        payout_puzzle_hash: hex32(payout_puzzle_hash),
+        shares,
+        initial_cumulative_payout: initial_cumulative_payout.to_string(),
+        accrued_rewards: u64::try_from(accrued).unwrap_or(u64::MAX),
+        last_update: current.last_update,
+        created_height: slot.created_height,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_commitments(
+    state: &DistributorApiState,
+    query: &DistributorQuery,
+) -> Result<CommitmentScheduleResponse, ApiError> {
+    let launcher_id = select_distributor(state, query.launcher_id.as_deref())?;
+    let indexed_peak_height = require_distributor_fresh(state).await?;
+    let _ = synced_record(state, launcher_id).await?;
+
+    let mut items = state
+        .slots
+        .slots(launcher_id, DistributorSlotKind::Commitment)
+        .await
+        .into_iter()
+        .filter(|slot| slot.spent_height.is_none())
+        .filter_map(|slot| match slot.value {
+            DistributorSlotValue::Commitment {
+                epoch_start,
+                clawback_puzzle_hash,
+                rewards,
+            } => Some(CommitmentItem {
+                epoch_start,
+                clawback_puzzle_hash: hex32(clawback_puzzle_hash),
+                rewards,
+                created_height: slot.created_height,
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    items.sort_by_key(|item| (item.epoch_start, item.created_height));
+    let total_committed = items
+        .iter()
+        .fold(0u64, |total, item| total.saturating_add(item.rewards));
+
+    Ok(CommitmentScheduleResponse {
+        launcher_id: hex32(launcher_id),
+        items,
+        total_committed,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_epochs(
+    state: &DistributorApiState,
+    query: &DistributorQuery,
+) -> Result<EpochHistoryResponse, ApiError> {
+    let launcher_id = select_distributor(state, query.launcher_id.as_deref())?;
+    let indexed_peak_height = require_distributor_fresh(state).await?;
+    let (record, current) = synced_record(state, launcher_id).await?;
+    let epoch_seconds = record.constants.epoch_seconds;
+    let current_epoch_start = current.epoch_end.saturating_sub(epoch_seconds);
+
+    let mut committed = HashMap::<u64, u64>::new();
+    for slot in state
+        .slots
+        .slots(launcher_id, DistributorSlotKind::Commitment)
+        .await
+    {
+        if let (
+            None,
+            DistributorSlotValue::Commitment {
+                epoch_start,
+                rewards,
+                ..
+            },
+        ) = (slot.spent_height, slot.value)
+        {
+            let total = committed.entry(epoch_start).or_default();
+            *total = total.saturating_add(rewards);
+        }
+    }
+
+    // Slots come oldest first, so the last write per epoch wins.
+    let mut epochs = BTreeMap::new();
+    for slot in state
+        .slots
+        .slots(launcher_id, DistributorSlotKind::Reward)
+        .await
+    {
+        if let DistributorSlotValue::Reward {
+            epoch_start,
+            next_epoch_initialized,
+            rewards,
+        } = slot.value
+        {
+            epochs.insert(
+                epoch_start,
+                (rewards, next_epoch_initialized, slot.created_height),
+            );
+        }
+    }
+
+    let items = epochs
+        .into_iter()
+        .map(
+            |(epoch_start, (rewards, next_epoch_initialized, updated_height))| EpochItem {
+                epoch_start,
+                epoch_end: epoch_start.saturating_add(epoch_seconds),
+                status: match epoch_start.cmp(&current_epoch_start) {
+                    std::cmp::Ordering::Less => EpochStatus::Past,
+                    std::cmp::Ordering::Equal => EpochStatus::Current,
+                    std::cmp::Ordering::Greater => EpochStatus::Future,
+                },
+                rewards,
+                next_epoch_initialized,
+                committed_rewards: committed.get(&epoch_start).copied().unwrap_or(0),
+                updated_height,
+            },
+        )
+        .collect();
+
+    Ok(EpochHistoryResponse {
+        launcher_id: hex32(launcher_id),
+        current_epoch_start,
+        items,
+        indexed_peak_height,
+    })
+}
+
+async fn get_readyz(
+    State(state): State<DistributorApiState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = require_ready(&state).await?;
+    Ok(Json(body))
+}
+
+async fn head_readyz(State(state): State<DistributorApiState>) -> Result<StatusCode, ApiError> {
+    let _ = require_ready(&state).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_status(State(state): State<DistributorApiState>) -> impl IntoResponse {
+    Json(lookup_status(&state).await)
+}
+
+async fn get_state(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_state(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_state(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_state(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_entry(
+    State(state): State<DistributorApiState>,
+    Path(payout_puzzle_hash): Path<String>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_entry(&state, &payout_puzzle_hash, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_entry(
+    State(state): State<DistributorApiState>,
+    Path(payout_puzzle_hash): Path<String>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_entry(&state, &payout_puzzle_hash, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_commitments(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_commitments(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_commitments(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_commitments(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_epochs(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_epochs(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_epochs(
+    State(state): State<DistributorApiState>,
+    Query(query): Query<DistributorQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_epochs(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Bind + serve helper used by production `reward-distributor listen` and the real-HTTP
+/// test fixture.
+pub async fn serve_distributor_listener(
+    state: DistributorApiState,
+    bind: std::net::SocketAddr,
+) -> Result<(), std::io::Error> {
+    let app = distributor_router(state);
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use tokio::sync::RwLock;
+
+use super::slot_store::{
+    DistributorSlotKind, DistributorSlotStore, DistributorSlotValue, StoredDistributorSlot,
+};
+use super::store::{
+    push_distributor_replacement, rollback_distributor_to_before, DistributorRecord,
+    DistributorStore, StoredDistributorConstants, StoredDistributorState,
+};
+use crate::{CliError, FreshnessState, ListenerMetrics};
+
+/// Projects confirmed reward distributor spends into the state and slot stores.
+pub struct DistributorIndexer {
+    pub distributors: Arc<dyn DistributorStore>,
+    pub slots: Arc<dyn DistributorSlotStore>,
+    pub freshness: Arc<RwLock<FreshnessState>>,
+    pub metrics: Arc<ListenerMetrics>,
+}
+
+impl DistributorIndexer {
+    pub fn new(
+        distributors: Arc<dyn DistributorStore>,
+        slots: Arc<dyn DistributorSlotStore>,
+        freshness: Arc<RwLock<FreshnessState>>,
+    ) -> Self {
+        Self {
+            distributors,
+            slots,
+            freshness,
+            metrics: ListenerMetrics::shared("reward_distributor"),
+        }
+    }
+
+    /// Report into the process-wide metrics served on `/metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<ListenerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub async fn note_peak(&self, indexed: u32, upstream: u32, now_unix: u64) {
+        let mut f = self.freshness.write().await;
+        f.indexed_peak_height = indexed;
+        f.upstream_peak_height = upstream;
+        f.last_successful_peak_unix = now_unix;
+        f.rolling_back = false;
+        f.resyncing = false;
+    }
+
+    pub async fn note_upstream_peak(&self, upstream: u32) {
+        self.freshness.write().await.upstream_peak_height = upstream;
+    }
+
+    pub async fn begin_resync(&self) {
+        self.freshness.write().await.resyncing = true;
+    }
+
+    /// Apply one confirmed distributor spend: the new state, the slots it consumed and
+    /// the slots it created.
+    pub async fn on_distributor_transition(
+        &self,
+        launcher_id: Bytes32,
+        constants: StoredDistributorConstants,
+        state: StoredDistributorState,
+        created: &[(Bytes32, DistributorSlotValue)],
+        spent: &[(Bytes32, DistributorSlotValue)],
+    ) -> Result<(), CliError> {
+        let height = state.confirmation_height;
+        let mut record = self
+            .distributors
+            .get(launcher_id)
+            .await
+            .unwrap_or(DistributorRecord {
+                launcher_id,
+                constants,
+                current: None,
+                history: Vec::new(),
+            });
+        // Replays after a restart see the spend that produced the current state again.
+        if record
+            .current
+            .as_ref()
+            .is_some_and(|current| current.coin_id == state.coin_id)
+        {
+            return Ok(());
+        }
+        push_distributor_replacement(&mut record, state, height);
+        self.distributors.upsert(record).await?;
+
+        for (value_hash, value) in spent {
+            let live = self
+                .slots
+                .slots_by_hint(launcher_id, value.hint())
+                .await
+                .into_iter()
+                .filter(|slot| slot.value_hash == *value_hash && slot.spent_height.is_none())
+                .max_by_key(|slot| slot.created_height);
+            if let Some(mut slot) = live {
+                slot.spent_height = Some(height);
+                self.slots.upsert(slot).await?;
+            }
+        }
+        for (value_hash, value) in created {
+            self.slots
+                .upsert(StoredDistributorSlot {
+                    launcher_id,
+                    value_hash: *value_hash,
+                    value: *value,
+                    created_height: height,
+                    spent_height: None,
+                })
+                .await?;
+        }
+
+        self.metrics.transitions_projected.inc();
+        Ok(())
+    }
+
+    /// Pre-final reorganization: drop states and slots created at or above `from_height`
+    /// and revive slots spent there.
+    pub async fn rollback(&self, from_height: u32) -> Result<(), CliError> {
+        {
+            let mut f = self.freshness.write().await;
+            f.rolling_back = true;
+            f.last_reorg_unix = Some(FreshnessState::now_unix());
+            f.last_reorg_height = Some(from_height);
+        }
+        self.metrics.rollbacks.inc();
+
+        for launcher_id in self.distributors.all_launcher_ids().await {
+            let Some(mut rec) = self.distributors.get(launcher_id).await else {
+                continue;
+            };
+            rollback_distributor_to_before(&mut rec, from_height);
+            if rec.current.is_none() && rec.history.is_empty() {
+                self.distributors.remove(launcher_id).await?;
+            } else {
+                self.distributors.upsert(rec).await?;
+            }
+
+            for mut slot in self.slots.touched_from(launcher_id, from_height).await {
+                if slot.created_height >= from_height {
+                    self.slots.remove(&slot).await?;
+                } else {
+                    slot.spent_height = None;
+                    self.slots.upsert(slot).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget entry and commitment slots spent below `cutoff`. Spent reward slots are
+    /// kept: the last one of each epoch is its history.
+    pub async fn prune_spent_slots(&self, cutoff: u32) -> Result<(), CliError> {
+        for kind in [DistributorSlotKind::Entry, DistributorSlotKind::Commitment] {
+            self.slots.prune_spent_before(kind, cutoff).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryDistributorSlotStore, MemoryDistributorStore};
+
+    fn b32(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn constants() -> StoredDistributorConstants {
+        StoredDistributorConstants {
+            reserve_asset_id: b32(0xcc),
+            epoch_seconds: 604_800,
+            precision: 1_000,
+            fee_bps: 0,
+            payout_threshold: 0,
+        }
+    }
+
+    fn state(tag: u8, height: u32, active_shares: u64) -> StoredDistributorState {
+        StoredDistributorState {
+            coin_id: b32(tag),
+            parent_coin_id: b32(tag.wrapping_sub(1)),
+            active_shares,
+            total_reserves: 0,
+            cumulative_payout: 0,
+            remaining_rewards: 0,
+            last_update: 0,
+            epoch_end: 604_800,
+            confirmation_height: height,
+        }
+    }
+
+    fn entry(shares: u64) -> (Bytes32, DistributorSlotValue) {
+        (
+            b32(shares as u8),
+            DistributorSlotValue::Entry {
+                payout_puzzle_hash: b32(0xee),
+                initial_cumulative_payout: 0,
+                shares,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn rollback_revives_spent_entry_and_restores_shares() {
+        let launcher_id = b32(0xaa);
+        let freshness = Arc::new(RwLock::new(FreshnessState::fresh_at(0, 0)));
+        let indexer = DistributorIndexer::new(
+            MemoryDistributorStore::shared(),
+            MemoryDistributorSlotStore::shared(),
+            Arc::clone(&freshness),
+        );
+
+        indexer
+            .on_distributor_transition(launcher_id, constants(), state(1, 100, 1), &[entry(1)], &[])
+            .await
+            .unwrap();
+        indexer
+            .on_distributor_transition(
+                launcher_id,
+                constants(),
+                state(2, 110, 3),
+                &[entry(3)],
+                &[entry(1)],
+            )
+            .await
+            .unwrap();
+        // A replay of the same spend is a no-op.
+        indexer
+            .on_distributor_transition(
+                launcher_id,
+                constants(),
+                state(2, 110, 3),
+                &[entry(3)],
+                &[entry(1)],
+            )
+            .await
+            .unwrap();
+
+        let live = |slots: Vec<StoredDistributorSlot>| {
+            slots
+                .into_iter()
+                .filter(|slot| slot.spent_height.is_none())
+                .map(|slot| slot.value)
+                .collect::<Vec<_>>()
+        };
+        let entries = indexer
+            .slots
+            .slots(launcher_id, DistributorSlotKind::Entry)
+            .await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(live(entries), vec![entry(3).1]);
+
+        indexer.rollback(105).await.unwrap();
+        assert!(freshness.read().await.rolling_back);
+        let entries = indexer
+            .slots
+            .slots(launcher_id, DistributorSlotKind::Entry)
+            .await;
+        assert_eq!(live(entries), vec![entry(1).1]);
+        let current = indexer.distributors.get(launcher_id).await.unwrap().current;
+        assert_eq!(current.map(|s| s.active_shares), Some(1));
+
+        indexer.prune_spent_slots(200).await.unwrap();
+        assert_eq!(
+            indexer
+                .slots
+                .slots(launcher_id, DistributorSlotKind::Entry)
+                .await
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Public reward distributor listener: distributor state, indexed slots, and HTTP reads.
+//!
+//! Shares freshness, error envelope, and metrics with the XCHandles listener.
+
+mod api;
+mod index;
+mod slot_store;
+mod store;
+mod types;
+
+pub use api::{
+    distributor_router, require_distributor_fresh, select_distributor, serve_distributor_listener,
+    DistributorApiState,
+};
+pub use index::DistributorIndexer;
+pub use slot_store::{
+    DbDistributorSlotStore, DistributorSlotKind, DistributorSlotStore, DistributorSlotValue,
+    MemoryDistributorSlotStore, StoredDistributorSlot,
+};
+pub use store::{
+    prune_distributor_history, push_distributor_replacement, rollback_distributor_to_before,
+    DbDistributorStore, DistributorRecord, DistributorStore, MemoryDistributorStore,
+    StoredDistributorConstants, StoredDistributorState,
+};
+pub use types::{
+    CommitmentItem, CommitmentScheduleResponse, DistributorQuery, DistributorStateResponse,
+    EntryRewardsResponse, EpochHistoryResponse, EpochItem, EpochStatus,
+};
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use chia_wallet_sdk::types::puzzles::{
+    RewardDistributorCommitmentSlotValue, RewardDistributorEntrySlotValue,
+    RewardDistributorRewardSlotValue,
+};
+use clvm_utils::ToTreeHash;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::CliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributorSlotKind {
+    Reward,
+    Commitment,
+    Entry,
+}
+
+impl DistributorSlotKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reward => "reward",
+            Self::Commitment => "commitment",
+            Self::Entry => "entry",
+        }
+    }
+}
+
+/// Decoded value of one distributor slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributorSlotValue {
+    /// Rewards committed to the epoch starting at `epoch_start`.
+    Reward {
+        epoch_start: u64,
+        next_epoch_initialized: bool,
+        rewards: u64,
+    },
+    /// Rewards a funder committed to a future epoch and may claw back.
+    Commitment {
+        epoch_start: u64,
+        clawback_puzzle_hash: Bytes32,
+        rewards: u64,
+    },
+    /// A staker's shares and the cumulative payout they were last paid at.
+    Entry {
+        payout_puzzle_hash: Bytes32,
+        initial_cumulative_payout: u128,
+        shares: u64,
+    },
+}
+
+impl DistributorSlotValue {
+    pub fn kind(&self) -> DistributorSlotKind {
+        match self {
+            Self::Reward { .. } => DistributorSlotKind::Reward,
+            Self::Commitment { .. } => DistributorSlotKind::Commitment,
+            Self::Entry { .. } => DistributorSlotKind::Entry,
+        }
+    }
+
+    /// The hint the distributor puts on the slot coin.
+    pub fn hint(&self) -> Bytes32 {
+        match self {
+            Self::Reward { epoch_start, .. } => epoch_start.tree_hash().into(),
+            Self::Commitment {
+                clawback_puzzle_hash,
+                ..
+            } => *clawback_puzzle_hash,
+            Self::Entry {
+                payout_puzzle_hash, ..
+            } => *payout_puzzle_hash,
+        }
+    }
+}
+
+impl From<RewardDistributorRewardSlotValue> for DistributorSlotValue {
+    fn from(value: RewardDistributorRewardSlotValue) -> Self {
+        Self::Reward {
+            epoch_start: value.epoch_start,
+            next_epoch_initialized: value.next_epoch_initialized,
+            rewards: value.rewards,
+        }
+    }
+}
+
+impl From<RewardDistributorCommitmentSlotValue> for DistributorSlotValue {
+    fn from(value: RewardDistributorCommitmentSlotValue) -> Self {
+        Self::Commitment {
+            epoch_start: value.epoch_start,
+            clawback_puzzle_hash: value.clawback_ph,
+            rewards: value.rewards,
+        }
+    }
+}
+
+impl From<RewardDistributorEntrySlotValue> for DistributorSlotValue {
+    fn from(value: RewardDistributorEntrySlotValue) -> Self {
+        Self::Entry {
+            payout_puzzle_hash: value.payout_puzzle_hash,
+            initial_cumulative_payout: value.initial_cumulative_payout,
+            shares: value.shares,
+        }
+    }
+}
+
+/// One slot coin a distributor created; spent slots stay until rolled back or pruned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredDistributorSlot {
+    pub launcher_id: Bytes32,
+    /// Tree hash of the on-chain value; with `created_height` it identifies the coin.
+    pub value_hash: Bytes32,
+    pub value: DistributorSlotValue,
+    pub created_height: u32,
+    pub spent_height: Option<u32>,
+}
+
+#[async_trait::async_trait]
+pub trait DistributorSlotStore: Send + Sync {
+    /// Every indexed slot of `kind`, live and spent.
+    async fn slots(
+        &self,
+        launcher_id: Bytes32,
+        kind: DistributorSlotKind,
+    ) -> Vec<StoredDistributorSlot>;
+    /// Every indexed slot hinted with `hint`, live and spent.
+    async fn slots_by_hint(
+        &self,
+        launcher_id: Bytes32,
+        hint: Bytes32,
+    ) -> Vec<StoredDistributorSlot>;
+    /// Slots created or spent at or above `height`.
+    async fn touched_from(&self, launcher_id: Bytes32, height: u32) -> Vec<StoredDistributorSlot>;
+    async fn upsert(&self, slot: StoredDistributorSlot) -> Result<(), CliError>;
+    async fn remove(&self, slot: &StoredDistributorSlot) -> Result<(), CliError>;
+    /// Forget slots of `kind` spent below `height`, across every distributor.
+    async fn prune_spent_before(
+        &self,
+        kind: DistributorSlotKind,
+        height: u32,
+    ) -> Result<(), CliError>;
+}
+
+#[derive(Default)]
+pub struct MemoryDistributorSlotStore {
+    inner: RwLock<HashMap<(Bytes32, Bytes32, u32), StoredDistributorSlot>>,
+}
+
+impl MemoryDistributorSlotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    async fn filtered(
+        &self,
+        keep: impl Fn(&StoredDistributorSlot) -> bool,
+    ) -> Vec<StoredDistributorSlot> {
+        let mut slots: Vec<_> = self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|slot| keep(slot))
+            .cloned()
+            .collect();
+        slots.sort_by_key(|slot| (slot.created_height, slot.value_hash));
+        slots
+    }
+}
+
+#[async_trait::async_trait]
+impl DistributorSlotStore for MemoryDistributorSlotStore {
+    async fn slots(
+        &self,
+        launcher_id: Bytes32,
+        kind: DistributorSlotKind,
+    ) -> Vec<StoredDistributorSlot> {
+        self.filtered(|slot| slot.launcher_id == launcher_id && slot.value.kind() == kind)
+            .await
+    }
+
+    async fn slots_by_hint(
+        &self,
+        launcher_id: Bytes32,
+        hint: Bytes32,
+    ) -> Vec<StoredDistributorSlot> {
+        self.filtered(|slot| slot.launcher_id == launcher_id && slot.value.hint() == hint)
+            .await
+    }
+
+    async fn touched_from(&self, launcher_id: Bytes32, height: u32) -> Vec<StoredDistributorSlot> {
+        self.filtered(|slot| {
+            slot.launcher_id == launcher_id
+                && (slot.created_height >= height || slot.spent_height >= Some(height))
+        })
+        .await
+    }
+
+    async fn upsert(&self, slot: StoredDistributorSlot) -> Result<(), CliError> {
+        self.inner.write().await.insert(
+            (slot.launcher_id, slot.value_hash, slot.created_height),
+            slot,
+        );
+        Ok(())
+    }
+
+    async fn remove(&self, slot: &StoredDistributorSlot) -> Result<(), CliError> {
+        self.inner
+            .write()
+            .await
+            .remove(&(slot.launcher_id, slot.value_hash, slot.created_height));
+        Ok(())
+    }
+
+    async fn prune_spent_before(
+        &self,
+        kind: DistributorSlotKind,
+        height: u32,
+    ) -> Result<(), CliError> {
+        self.inner.write().await.retain(|_, slot| {
+            slot.value.kind() != kind || slot.spent_height.is_none_or(|spent| spent >= height)
+        });
+        Ok(())
+    }
+}
+
+/// SQLite-backed store used by the production `reward-distributor listen` process.
+pub struct DbDistributorSlotStore {
+    db: Arc<futures::lock::Mutex<crate::Db>>,
+}
+
+impl DbDistributorSlotStore {
+    pub fn new(db: Arc<futures::lock::Mutex<crate::Db>>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+fn parse_slots(rows: Vec<String>) -> Vec<StoredDistributorSlot> {
+    rows.iter()
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl DistributorSlotStore for DbDistributorSlotStore {
+    async fn slots(
+        &self,
+        launcher_id: Bytes32,
+        kind: DistributorSlotKind,
+    ) -> Vec<StoredDistributorSlot> {
+        let db = self.db.lock().await;
+        parse_slots(
+            db.list_reward_distributor_slots_of_kind(launcher_id, kind.as_str())
+                .await
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn slots_by_hint(
+        &self,
+        launcher_id: Bytes32,
+        hint: Bytes32,
+    ) -> Vec<StoredDistributorSlot> {
+        let db = self.db.lock().await;
+        parse_slots(
+            db.list_reward_distributor_slots_by_hint(launcher_id, hint)
+                .await
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn touched_from(&self, launcher_id: Bytes32, height: u32) -> Vec<StoredDistributorSlot> {
+        let db = self.db.lock().await;
+        parse_slots(
+            db.list_reward_distributor_slots_touched_from(launcher_id, height)
+                .await
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn upsert(&self, slot: StoredDistributorSlot) -> Result<(), CliError> {
+        let json = serde_json::to_string(&slot)?;
+        let db = self.db.lock().await;
+        db.upsert_reward_distributor_slot_json(
+            slot.launcher_id,
+            slot.value_hash,
+            slot.created_height,
+            slot.value.kind().as_str(),
+            slot.value.hint(),
+            slot.spent_height,
+            &json,
+        )
+        .await
+    }
+
+    async fn remove(&self, slot: &StoredDistributorSlot) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_reward_distributor_slot(slot.launcher_id, slot.value_hash, slot.created_height)
+            .await
+    }
+
+    async fn prune_spent_before(
+        &self,
+        kind: DistributorSlotKind,
+        height: u32,
+    ) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_reward_distributor_slots_spent_before(kind.as_str(), height)
+            .await
+    }
+}
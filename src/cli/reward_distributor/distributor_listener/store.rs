@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use chia_wallet_sdk::driver::RewardDistributorConstants;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::CliError;
+
+/// Distributor singleton state after one confirmed spend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredDistributorState {
+    /// Unspent distributor coin created by the spend.
+    pub coin_id: Bytes32,
+    pub parent_coin_id: Bytes32,
+    pub active_shares: u64,
+    pub total_reserves: u64,
+    /// Scaled by the distributor's precision, like the on-chain value.
+    pub cumulative_payout: u128,
+    pub remaining_rewards: u128,
+    pub last_update: u64,
+    pub epoch_end: u64,
+    pub confirmation_height: u32,
+}
+
+/// Launch-time constants the API needs to read the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredDistributorConstants {
+    pub reserve_asset_id: Bytes32,
+    pub epoch_seconds: u64,
+    pub precision: u64,
+    pub fee_bps: u64,
+    pub payout_threshold: u64,
+}
+
+impl From<RewardDistributorConstants> for StoredDistributorConstants {
+    fn from(constants: RewardDistributorConstants) -> Self {
+        Self {
+            reserve_asset_id: constants.reserve_asset_id,
+            epoch_seconds: constants.epoch_seconds,
+            precision: constants.precision,
+            fee_bps: constants.fee_bps,
+            payout_threshold: constants.payout_threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributorRecord {
+    pub launcher_id: Bytes32,
+    pub constants: StoredDistributorConstants,
+    pub current: Option<StoredDistributorState>,
+    /// Prior states: every replacement within 32 blocks plus one older predecessor.
+    pub history: Vec<StoredDistributorState>,
+}
+
+#[async_trait::async_trait]
+pub trait DistributorStore: Send + Sync {
+    async fn get(&self, launcher_id: Bytes32) -> Option<DistributorRecord>;
+    async fn upsert(&self, record: DistributorRecord) -> Result<(), CliError>;
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError>;
+    async fn all_launcher_ids(&self) -> Vec<Bytes32>;
+}
+
+#[derive(Default)]
+pub struct MemoryDistributorStore {
+    inner: RwLock<HashMap<Bytes32, DistributorRecord>>,
+}
+
+impl MemoryDistributorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+}
+
+#[async_trait::async_trait]
+impl DistributorStore for MemoryDistributorStore {
+    async fn get(&self, launcher_id: Bytes32) -> Option<DistributorRecord> {
+        self.inner.read().await.get(&launcher_id).cloned()
+    }
+
+    async fn upsert(&self, record: DistributorRecord) -> Result<(), CliError> {
+        self.inner.write().await.insert(record.launcher_id, record);
+        Ok(())
+    }
+
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
+        self.inner.write().await.remove(&launcher_id);
+        Ok(())
+    }
+
+    async fn all_launcher_ids(&self) -> Vec<Bytes32> {
+        self.inner.read().await.keys().copied().collect()
+    }
+}
+
+/// Retain current state, every replaced state in the last 32 blocks, and one older predecessor.
+pub fn prune_distributor_history(history: &mut Vec<StoredDistributorState>, peak: u32) {
+    if history.is_empty() {
+        return;
+    }
+    let cutoff = peak.saturating_sub(32);
+    let mut keep_recent = Vec::new();
+    let mut older = None;
+    for state in history.drain(..) {
+        if state.confirmation_height >= cutoff {
+            keep_recent.push(state);
+        } else {
+            older = Some(state);
+        }
+    }
+    if let Some(pred) = older {
+        history.push(pred);
+    }
+    history.extend(keep_recent);
+}
+
+pub fn push_distributor_replacement(
+    record: &mut DistributorRecord,
+    new_state: StoredDistributorState,
+    peak: u32,
+) {
+    if let Some(prev) = record.current.take() {
+        record.history.push(prev);
+    }
+    record.current = Some(new_state);
+    prune_distributor_history(&mut record.history, peak);
+}
+
+/// Restore the distributor state confirmed before a pre-final reorganization at `from_height`.
+pub fn rollback_distributor_to_before(record: &mut DistributorRecord, from_height: u32) {
+    if let Some(cur) = &record.current {
+        if cur.confirmation_height >= from_height {
+            record.current = None;
+        }
+    }
+    let mut restored = None;
+    let mut kept = Vec::new();
+    for state in record.history.drain(..) {
+        if state.confirmation_height < from_height {
+            restored = Some(state.clone());
+            kept.push(state);
+        }
+    }
+    record.history = kept;
+    if record.current.is_none() {
+        record.current = restored;
+    }
+}
+
+/// SQLite-backed store used by the production `reward-distributor listen` process.
+pub struct DbDistributorStore {
+    db: Arc<futures::lock::Mutex<crate::Db>>,
+}
+
+impl DbDistributorStore {
+    pub fn new(db: Arc<futures::lock::Mutex<crate::Db>>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl DistributorStore for DbDistributorStore {
+    async fn get(&self, launcher_id: Bytes32) -> Option<DistributorRecord> {
+        let db = self.db.lock().await;
+        let json = db
+            .get_reward_distributor_record_json(launcher_id)
+            .await
+            .ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    async fn upsert(&self, record: DistributorRecord) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
+        let db = self.db.lock().await;
+        db.upsert_reward_distributor_record_json(record.launcher_id, &json)
+            .await
+    }
+
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_reward_distributor_record(launcher_id).await
+    }
+
+    async fn all_launcher_ids(&self) -> Vec<Bytes32> {
+        let db = self.db.lock().await;
+        db.all_reward_distributor_ids().await.unwrap_or_default()
+    }
+}
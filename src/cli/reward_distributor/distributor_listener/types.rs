@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DistributorQuery {
+    /// Followed distributor to read; omitted defaults to the first configured one.
+    pub launcher_id: Option<String>,
+}
+
+/// `GET /state`. 128-bit values are decimal strings, scaled by `precision`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistributorStateResponse {
+    pub launcher_id: String,
+    pub coin_id: String,
+    pub reserve_asset_id: String,
+    pub active_shares: u64,
+    pub total_reserves: u64,
+    pub cumulative_payout: String,
+    pub remaining_rewards: String,
+    pub last_update: u64,
+    pub epoch_end: u64,
+    pub epoch_seconds: u64,
+    pub precision: u64,
+    pub fee_bps: u64,
+    pub payout_threshold: u64,
+    pub confirmation_height: u32,
+    pub indexed_peak_height: u32,
+}
+
+/// `GET /entry/{payout_puzzle_hash}`: rewards accrued as of the state's `last_update`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryRewardsResponse {
+    pub launcher_id: String,
+    pub payout_puzzle_hash: String,
+    pub shares: u64,
+    pub initial_cumulative_payout: String,
+    /// Reserve CAT mojos the entry would be paid out now.
+    pub accrued_rewards: u64,
+    pub last_update: u64,
+    pub created_height: u32,
+    pub indexed_peak_height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentItem {
+    pub epoch_start: u64,
+    pub clawback_puzzle_hash: String,
+    pub rewards: u64,
+    pub created_height: u32,
+}
+
+/// `GET /commitments`: live commitment slots, earliest epoch first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentScheduleResponse {
+    pub launcher_id: String,
+    pub items: Vec<CommitmentItem>,
+    pub total_committed: u64,
+    pub indexed_peak_height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EpochStatus {
+    Past,
+    Current,
+    Future,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochItem {
+    pub epoch_start: u64,
+    pub epoch_end: u64,
+    pub status: EpochStatus,
+    pub rewards: u64,
+    pub next_epoch_initialized: bool,
+    /// Sum of live commitment slots for this epoch.
+    pub committed_rewards: u64,
+    /// Height of the spend that last wrote this epoch's reward slot.
+    pub updated_height: u32,
+}
+
+/// `GET /epochs`: one item per indexed reward slot epoch, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochHistoryResponse {
+    pub launcher_id: String,
+    pub current_epoch_start: u64,
+    pub items: Vec<EpochItem>,
+    pub indexed_peak_height: u32,
+}
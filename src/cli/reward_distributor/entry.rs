@@ -0,0 +1,557 @@
+use chia_bls::Signature;
+use chia_protocol::{Bytes32, Coin};
+use chia_puzzle_types::singleton::LauncherSolution;
+use chia_wallet_sdk::{
+    coinset::ChiaRpcClient,
+    driver::{
+        Cat, Nft, Puzzle, RewardDistributor, RewardDistributorConstants, RewardDistributorType,
+        SpendContext,
+    },
+    types::puzzles::RewardDistributorEntrySlotValue,
+    utils::Address,
+};
+use clvmr::NodePtr;
+
+use crate::{
+    find_entry_slots, find_locked_cats, find_locked_nfts, format_cat_mojos,
+    format_precision_amount, get_chain_client, get_last_onchain_timestamp, get_prefix,
+    hex_string_to_bytes32, locked_nft_p2_puzzle_hash, resolve_custody, sync_distributor,
+    ChainClient, ChainSource, CliError, Db, Network, SageClient,
+};
+
+/// Distributor fields that decide what an entry is owed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DistributorRewardState {
+    pub active_shares: u64,
+    /// Per-share payout so far, scaled by `precision`.
+    pub cumulative_payout: u128,
+    /// Undistributed rewards of the current epoch, scaled by `precision`.
+    pub remaining_rewards: u128,
+    pub last_update: u64,
+    pub epoch_end: u64,
+    pub precision: u64,
+    pub fee_bps: u64,
+    pub payout_threshold: u64,
+}
+
+impl From<&RewardDistributor> for DistributorRewardState {
+    fn from(distributor: &RewardDistributor) -> Self {
+        let state = &distributor.info.state;
+        let constants = &distributor.info.constants;
+        Self {
+            active_shares: state.active_shares,
+            cumulative_payout: state.round_reward_info.cumulative_payout,
+            remaining_rewards: state.round_reward_info.remaining_rewards,
+            last_update: state.round_time_info.last_update,
+            epoch_end: state.round_time_info.epoch_end,
+            precision: constants.precision,
+            fee_bps: constants.fee_bps,
+            payout_threshold: constants.payout_threshold,
+        }
+    }
+}
+
+impl DistributorRewardState {
+    /// State after a sync action at `update_time` (capped at the epoch end): the elapsed
+    /// fraction of the epoch's remaining rewards is split over the active shares.
+    pub fn synced_to(self, update_time: u64) -> Self {
+        let update_time = update_time.min(self.epoch_end);
+        if update_time <= self.last_update {
+            return self;
+        }
+        if self.active_shares == 0 {
+            return Self {
+                last_update: update_time,
+                ..self
+            };
+        }
+
+        let elapsed = u128::from(update_time - self.last_update);
+        let epoch_left = u128::from(self.epoch_end - self.last_update);
+        let distributed = self.remaining_rewards * elapsed / epoch_left;
+        Self {
+            cumulative_payout: self.cumulative_payout
+                + distributed / u128::from(self.active_shares),
+            remaining_rewards: self.remaining_rewards - distributed,
+            last_update: update_time,
+            ..self
+        }
+    }
+
+    /// Reserve CAT mojos an entry would be paid at this state.
+    pub fn entry_rewards(&self, entry: &RewardDistributorEntrySlotValue) -> u64 {
+        let accrued = self
+            .cumulative_payout
+            .saturating_sub(entry.initial_cumulative_payout)
+            * u128::from(entry.shares)
+            / u128::from(self.precision.max(1));
+        u64::try_from(accrued).unwrap_or(u64::MAX)
+    }
+}
+
+/// What one entry is owed now and after syncing the distributor to `update_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryRewardsEstimate {
+    pub update_time: u64,
+    /// Accrued as of the distributor's last on-chain sync.
+    pub synced_rewards: u64,
+    /// Accrued including the projected sync to `update_time`; this is the payout amount.
+    pub pending_rewards: u64,
+    /// Distributor fee withheld from the epoch rewards behind `pending_rewards`.
+    pub fee: u64,
+    pub meets_payout_threshold: bool,
+}
+
+/// Project `entry`'s claimable rewards to `update_time` with the sync action's math.
+pub fn estimate_entry_rewards(
+    state: DistributorRewardState,
+    entry: &RewardDistributorEntrySlotValue,
+    update_time: u64,
+) -> EntryRewardsEstimate {
+    let synced = state.synced_to(update_time);
+    let pending_rewards = synced.entry_rewards(entry);
+
+    // The fee is taken from each epoch's rewards when it starts, so what is left for
+    // stakers is (10000 - fee_bps) / 10000 of the gross.
+    let fee_bps = state.fee_bps.min(10_000);
+    let fee = if fee_bps == 10_000 {
+        0
+    } else {
+        let gross = u128::from(pending_rewards) * 10_000 / u128::from(10_000 - fee_bps);
+        u64::try_from(gross).unwrap_or(u64::MAX) - pending_rewards
+    };
+
+    EntryRewardsEstimate {
+        update_time: synced.last_update,
+        synced_rewards: state.entry_rewards(entry),
+        pending_rewards,
+        fee,
+        meets_payout_threshold: pending_rewards >= state.payout_threshold,
+    }
+}
+
+/// Custody of the entry that locked an asset paid to `locked_p2_puzzle_hash`, among the
+/// `entries` its staking spend created.
+///
+/// A locked CAT coin's amount is its share count; an NFT's shares are not known up front,
+/// so every count up to the entry's shares is tried.
+pub fn custody_of_locked_asset(
+    launcher_id: Bytes32,
+    locked_p2_puzzle_hash: Bytes32,
+    locked_shares: Option<u64>,
+    entries: &[RewardDistributorEntrySlotValue],
+) -> Option<Bytes32> {
+    entries
+        .iter()
+        .find(|entry| {
+            let locked_with = |shares| {
+                locked_nft_p2_puzzle_hash(entry.payout_puzzle_hash, launcher_id, shares)
+                    == locked_p2_puzzle_hash
+            };
+            match locked_shares {
+                Some(shares) => locked_with(shares),
+                None => (0..=entry.shares.max(1)).any(locked_with),
+            }
+        })
+        .map(|entry| entry.payout_puzzle_hash)
+}
+
+/// Current coin of an NFT, following its singleton from the launcher, and the height it
+/// was created at.
+async fn current_nft(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    nft_launcher_id: Bytes32,
+) -> Result<(Nft, u32), CliError> {
+    let launcher_spend = client
+        .get_puzzle_and_solution(nft_launcher_id, None)
+        .await?
+        .coin_solution
+        .ok_or(CliError::CoinNotSpent(nft_launcher_id))?;
+    let solution_ptr = ctx.alloc(&launcher_spend.solution)?;
+    let solution = ctx.extract::<LauncherSolution<NodePtr>>(solution_ptr)?;
+    let mut coin = Coin::new(
+        nft_launcher_id,
+        solution.singleton_puzzle_hash,
+        solution.amount,
+    );
+
+    let mut current = None;
+    loop {
+        let coin_record = client
+            .get_coin_record_by_name(coin.coin_id())
+            .await?
+            .coin_record
+            .ok_or(CliError::CoinNotFound(coin.coin_id()))?;
+        if !coin_record.spent {
+            // The eve coin was never spent, so it cannot have been locked.
+            return current
+                .map(|nft| (nft, coin_record.confirmed_block_index))
+                .ok_or(CliError::Custom(
+                    "NFT is not locked in a reward distributor".to_string(),
+                ));
+        }
+
+        let coin_spend = client
+            .get_puzzle_and_solution(coin.coin_id(), Some(coin_record.spent_block_index))
+            .await?
+            .coin_solution
+            .ok_or(CliError::CoinNotSpent(coin.coin_id()))?;
+        let puzzle_ptr = ctx.alloc(&coin_spend.puzzle_reveal)?;
+        let puzzle = Puzzle::parse(ctx, puzzle_ptr);
+        let solution_ptr = ctx.alloc(&coin_spend.solution)?;
+        let Some(nft) = Nft::parse_child(ctx, coin_spend.coin, puzzle, solution_ptr)? else {
+            return Err(CliError::Custom(
+                "Failed to parse NFT from on-chain data".to_string(),
+            ));
+        };
+        coin = nft.coin;
+        current = Some(nft);
+    }
+}
+
+/// A locked CAT coin and the height it was created at.
+async fn locked_cat(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    coin_id: Bytes32,
+) -> Result<(Cat, u32), CliError> {
+    let coin_record = client
+        .get_coin_record_by_name(coin_id)
+        .await?
+        .coin_record
+        .ok_or(CliError::CoinNotFound(coin_id))?;
+    let parent_coin_spend = client
+        .get_puzzle_and_solution(
+            coin_record.coin.parent_coin_info,
+            Some(coin_record.confirmed_block_index),
+        )
+        .await?
+        .coin_solution
+        .ok_or(CliError::CoinNotSpent(coin_record.coin.parent_coin_info))?;
+
+    let parent_puzzle_ptr = ctx.alloc(&parent_coin_spend.puzzle_reveal)?;
+    let parent_puzzle = Puzzle::parse(ctx, parent_puzzle_ptr);
+    let parent_solution_ptr = ctx.alloc(&parent_coin_spend.solution)?;
+    Cat::parse_children(
+        ctx,
+        parent_coin_spend.coin,
+        parent_puzzle,
+        parent_solution_ptr,
+    )?
+    .unwrap_or_default()
+    .into_iter()
+    .find(|cat| cat.coin == coin_record.coin)
+    .map(|cat| (cat, coin_record.confirmed_block_index))
+    .ok_or(CliError::Custom("Coin is not a CAT".to_string()))
+}
+
+/// Custody of the entry whose stake, confirmed at `height`, locked an asset paid to
+/// `locked_p2_puzzle_hash`.
+async fn locked_asset_custody(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    constants: RewardDistributorConstants,
+    height: u32,
+    locked_p2_puzzle_hash: Bytes32,
+    locked_shares: Option<u64>,
+) -> Result<Bytes32, CliError> {
+    let Some(block_record) = client.block_record_at(height).await? else {
+        return Err(CliError::Custom(format!(
+            "No block record at height {height}"
+        )));
+    };
+
+    // The stake spends the distributor in the block that creates the locked asset.
+    let mut entries = Vec::new();
+    for coin_spend in client.block_spends(block_record.header_hash).await? {
+        let Ok(Some(distributor)) =
+            RewardDistributor::from_spend(ctx, &coin_spend, None, constants, Signature::default())
+        else {
+            continue;
+        };
+        if distributor.info.constants.launcher_id == constants.launcher_id {
+            entries.extend(distributor.pending_spend.created_entry_slots);
+        }
+    }
+
+    custody_of_locked_asset(
+        constants.launcher_id,
+        locked_p2_puzzle_hash,
+        locked_shares,
+        &entries,
+    )
+    .ok_or(CliError::Custom(
+        "Asset is not locked in this reward distributor".to_string(),
+    ))
+}
+
+pub async fn reward_distributor_entry(
+    launcher_id_str: String,
+    custody_address: Option<String>,
+    nft_launcher_id_str: Option<String>,
+    stake_coin_id_str: Option<String>,
+    update_time: Option<u64>,
+    network: &Network,
+) -> Result<(), CliError> {
+    let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
+
+    println!("Syncing reward distributor...");
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
+    let mut ctx = SpendContext::new();
+    let distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
+
+    let update_time = match update_time {
+        Some(update_time) => update_time,
+        None => get_last_onchain_timestamp(&client).await?,
+    };
+
+    // Looking up someone else's entry does not need their wallet.
+    let custody_puzzle_hash = match (custody_address, nft_launcher_id_str, stake_coin_id_str) {
+        (Some(address), None, None) => Address::decode(&address)?.puzzle_hash,
+        (None, Some(nft_launcher_id_str), None) => {
+            println!("Finding the entry that locked the NFT...");
+            let nft_launcher_id = hex_string_to_bytes32(&nft_launcher_id_str)?;
+            let (nft, height) = current_nft(&mut ctx, &client, nft_launcher_id).await?;
+            locked_asset_custody(
+                &mut ctx,
+                &client,
+                distributor.info.constants,
+                height,
+                nft.info.p2_puzzle_hash,
+                None,
+            )
+            .await?
+        }
+        (None, None, Some(stake_coin_id_str)) => {
+            println!("Finding the entry that locked the stake coin...");
+            let stake_coin_id = hex_string_to_bytes32(&stake_coin_id_str)?;
+            let (cat, height) = locked_cat(&mut ctx, &client, stake_coin_id).await?;
+            locked_asset_custody(
+                &mut ctx,
+                &client,
+                distributor.info.constants,
+                height,
+                cat.info.p2_puzzle_hash,
+                Some(cat.coin.amount),
+            )
+            .await?
+        }
+        (None, None, None) => {
+            resolve_custody(&SageClient::new()?, None)
+                .await?
+                .puzzle_hash
+        }
+        _ => {
+            return Err(CliError::Custom(
+                "Use only one of --custody-address, --nft and --stake-coin".to_string(),
+            ))
+        }
+    };
+    println!(
+        "Using the following address as custody: {}",
+        Address::new(custody_puzzle_hash, get_prefix(network)).encode()?
+    );
+
+    println!("Finding entry slot...");
+    let entry = find_entry_slots(
+        &mut ctx,
+        &client,
+        distributor.info.constants,
+        custody_puzzle_hash,
+        None,
+        None,
+    )
+    .await?
+    .into_iter()
+    .next()
+    .ok_or(CliError::SlotNotFound("Entry"))?
+    .info
+    .value;
+
+    match distributor.info.constants.reward_distributor_type {
+        RewardDistributorType::NftCollection { .. } | RewardDistributorType::CuratedNft { .. } => {
+            let locked_nfts = find_locked_nfts(
+                &mut ctx,
+                &client,
+                launcher_id,
+                custody_puzzle_hash,
+                entry.shares,
+            )
+            .await?;
+            println!("Locked NFTs: {}", locked_nfts.len());
+        }
+        RewardDistributorType::Cat { asset_id, .. } => {
+            let locked_cats = find_locked_cats(
+                &mut ctx,
+                &client,
+                launcher_id,
+                custody_puzzle_hash,
+                asset_id,
+            )
+            .await?;
+            println!(
+                "Locked CAT: {} in {} coin(s)",
+                format_cat_mojos(locked_cats.iter().map(|(_, shares)| shares).sum::<u64>()),
+                locked_cats.len()
+            );
+        }
+        RewardDistributorType::Managed { .. } => {}
+    }
+
+    let state = DistributorRewardState::from(&distributor);
+    let estimate = estimate_entry_rewards(state, &entry, update_time);
+
+    println!("Entry:");
+    println!(
+        "  Shares: {} of {} active",
+        entry.shares, state.active_shares
+    );
+    println!(
+        "  Initial cumulative payout: {}",
+        format_precision_amount(entry.initial_cumulative_payout, state.precision)
+    );
+    println!(
+        "  Accrued at last sync ({}): {}",
+        state.last_update,
+        format_cat_mojos(estimate.synced_rewards)
+    );
+    println!(
+        "  Claimable at {}: {}",
+        estimate.update_time,
+        format_cat_mojos(estimate.pending_rewards)
+    );
+    println!(
+        "  Distributor fee ({} bps, already withheld): {}",
+        state.fee_bps,
+        format_cat_mojos(estimate.fee)
+    );
+    println!(
+        "  Payout threshold: {} ({})",
+        format_cat_mojos(state.payout_threshold),
+        if estimate.meets_payout_threshold {
+            "met"
+        } else {
+            "not met yet"
+        }
+    );
+    if update_time > state.epoch_end {
+        println!(
+            "The current epoch ended at {}; rewards stop accruing until a new epoch starts.",
+            state.epoch_end
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> DistributorRewardState {
+        DistributorRewardState {
+            active_shares: 4,
+            cumulative_payout: 0,
+            remaining_rewards: 8_000_000,
+            last_update: 1_000,
+            epoch_end: 2_000,
+            precision: 1_000,
+            fee_bps: 500,
+            payout_threshold: 1_000,
+        }
+    }
+
+    fn entry(shares: u64) -> RewardDistributorEntrySlotValue {
+        RewardDistributorEntrySlotValue {
+            payout_puzzle_hash: Bytes32::new([0xee; 32]),
+            initial_cumulative_payout: 0,
+            shares,
+        }
+    }
+
+    #[test]
+    fn sync_splits_elapsed_rewards_and_caps_at_epoch_end() {
+        let halfway = state().synced_to(1_500);
+        assert_eq!(halfway.cumulative_payout, 1_000_000);
+        assert_eq!(halfway.remaining_rewards, 4_000_000);
+        assert_eq!(halfway.entry_rewards(&entry(1)), 1_000);
+
+        let end = state().synced_to(5_000);
+        assert_eq!(end.last_update, 2_000);
+        assert_eq!(end.remaining_rewards, 0);
+        assert_eq!(end.entry_rewards(&entry(2)), 4_000);
+
+        let nobody = DistributorRewardState {
+            active_shares: 0,
+            ..state()
+        }
+        .synced_to(1_500);
+        assert_eq!(nobody.remaining_rewards, 8_000_000);
+        assert_eq!(nobody.last_update, 1_500);
+    }
+
+    #[test]
+    fn estimate_reports_fee_and_threshold() {
+        let estimate = estimate_entry_rewards(state(), &entry(1), 1_100);
+        assert_eq!(estimate.synced_rewards, 0);
+        assert_eq!(estimate.pending_rewards, 200);
+        assert_eq!(estimate.fee, 10);
+        assert!(!estimate.meets_payout_threshold);
+
+        let estimate = estimate_entry_rewards(state(), &entry(3), 2_000);
+        assert_eq!(estimate.pending_rewards, 6_000);
+        assert!(estimate.meets_payout_threshold);
+    }
+
+    #[test]
+    fn nft_lookup_finds_the_custody_that_locked_it() {
+        let launcher_id = Bytes32::new([0xd1; 32]);
+        let custody = Bytes32::new([0xc1; 32]);
+        let entries = [
+            RewardDistributorEntrySlotValue {
+                payout_puzzle_hash: Bytes32::new([0xc0; 32]),
+                initial_cumulative_payout: 0,
+                shares: 5,
+            },
+            RewardDistributorEntrySlotValue {
+                payout_puzzle_hash: custody,
+                initial_cumulative_payout: 0,
+                shares: 5,
+            },
+        ];
+
+        let locked = locked_nft_p2_puzzle_hash(custody, launcher_id, 3);
+        assert_eq!(
+            custody_of_locked_asset(launcher_id, locked, None, &entries),
+            Some(custody)
+        );
+        let elsewhere = locked_nft_p2_puzzle_hash(custody, Bytes32::new([0xd2; 32]), 3);
+        assert_eq!(
+            custody_of_locked_asset(launcher_id, elsewhere, None, &entries),
+            None
+        );
+    }
+
+    #[test]
+    fn stake_coin_lookup_matches_the_coin_amount_as_shares() {
+        let launcher_id = Bytes32::new([0xd1; 32]);
+        let custody = Bytes32::new([0xc1; 32]);
+        let entries = [RewardDistributorEntrySlotValue {
+            payout_puzzle_hash: custody,
+            initial_cumulative_payout: 0,
+            shares: 1_500,
+        }];
+
+        let locked = locked_nft_p2_puzzle_hash(custody, launcher_id, 1_000);
+        assert_eq!(
+            custody_of_locked_asset(launcher_id, locked, Some(1_000), &entries),
+            Some(custody)
+        );
+        assert_eq!(
+            custody_of_locked_asset(launcher_id, locked, Some(500), &entries),
+            None
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chia_protocol::{Bytes, Bytes32};
+use chia_puzzle_types::singleton::SingletonStruct;
+use chia_puzzles::SINGLETON_TOP_LAYER_V1_1_HASH;
+use chia_wallet_sdk::{
+    driver::{
+        MedievalVault, RewardDistributorAddEntryAction, RewardDistributorConstants,
+        RewardDistributorReceivedMessagePrefix, RewardDistributorRemoveEntryAction,
+        RewardDistributorSyncAction, RewardDistributorType, SingletonInfo, SpendContext,
+    },
+    types::puzzles::{
+        RewardDistributorEntrySlotValue, StateSchedulerLayerArgs, StateSchedulerLayerSolution,
+    },
+};
+use clvm_utils::ToTreeHash;
+use clvmr::{Allocator, NodePtr};
+
+use crate::{
+    find_entry_slots, get_constants, get_last_onchain_timestamp, hex_string_to_bytes32,
+    load_reward_distributor_entries_csv, multisig_broadcast_thing_finish,
+    multisig_broadcast_thing_start, multisig_sign_thing_finish, multisig_sign_thing_start,
+    sync_distributor, ChainClient, CliError, Db, DbDistributorSlotStore, DistributorSlotKind,
+    DistributorSlotStore, DistributorSlotValue, Network, RewardDistributorEntryRecord,
+};
+
+/// One add or remove the manager vault authorizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryUpdate {
+    Add {
+        payout_puzzle_hash: Bytes32,
+        shares: u64,
+    },
+    Remove {
+        payout_puzzle_hash: Bytes32,
+        shares: u64,
+    },
+}
+
+impl EntryUpdate {
+    pub fn message(&self) -> Bytes {
+        match *self {
+            Self::Add {
+                payout_puzzle_hash,
+                shares,
+            } => RewardDistributorReceivedMessagePrefix::add_entry(payout_puzzle_hash, shares),
+            Self::Remove {
+                payout_puzzle_hash,
+                shares,
+            } => RewardDistributorReceivedMessagePrefix::remove_entry(payout_puzzle_hash, shares),
+        }
+        .into()
+    }
+}
+
+/// Updates that turn the `current` entries into the `target` roster. A share change is a
+/// remove followed by an add; all removes come first so a payout puzzle hash is never
+/// listed twice at once.
+pub fn plan_entry_updates(
+    current: &[RewardDistributorEntrySlotValue],
+    target: &[RewardDistributorEntryRecord],
+) -> Result<Vec<EntryUpdate>, CliError> {
+    let mut wanted = BTreeMap::new();
+    for record in target {
+        if record.shares == 0 {
+            return Err(CliError::Custom(format!(
+                "Entry {} has zero shares; leave it out of the CSV to remove it",
+                hex::encode(record.payout_puzzle_hash)
+            )));
+        }
+        if wanted
+            .insert(record.payout_puzzle_hash, record.shares)
+            .is_some()
+        {
+            return Err(CliError::Custom(format!(
+                "Duplicate payout_puzzle_hash in CSV: {}",
+                hex::encode(record.payout_puzzle_hash)
+            )));
+        }
+    }
+
+    let existing = current
+        .iter()
+        .map(|entry| (entry.payout_puzzle_hash, entry.shares))
+        .collect::<BTreeMap<_, _>>();
+
+    let removes = existing
+        .iter()
+        .filter(|(payout_puzzle_hash, shares)| wanted.get(payout_puzzle_hash) != Some(shares))
+        .map(|(&payout_puzzle_hash, &shares)| EntryUpdate::Remove {
+            payout_puzzle_hash,
+            shares,
+        });
+    let adds = wanted
+        .iter()
+        .filter(|(payout_puzzle_hash, shares)| existing.get(payout_puzzle_hash) != Some(shares))
+        .map(|(&payout_puzzle_hash, &shares)| EntryUpdate::Add {
+            payout_puzzle_hash,
+            shares,
+        });
+
+    Ok(removes.chain(adds).collect())
+}
+
+/// Live entries from the listener's index (`reward-distributor listen`).
+pub async fn indexed_entries(
+    db: Db,
+    launcher_id: Bytes32,
+) -> Result<Vec<RewardDistributorEntrySlotValue>, CliError> {
+    let slot_store =
+        DbDistributorSlotStore::new(std::sync::Arc::new(futures::lock::Mutex::new(db)));
+    let mut entries = slot_store
+        .slots(launcher_id, DistributorSlotKind::Entry)
+        .await
+        .into_iter()
+        .filter(|slot| slot.spent_height.is_none())
+        .filter_map(|slot| match slot.value {
+            DistributorSlotValue::Entry {
+                payout_puzzle_hash,
+                initial_cumulative_payout,
+                shares,
+            } => Some(RewardDistributorEntrySlotValue {
+                payout_puzzle_hash,
+                initial_cumulative_payout,
+                shares,
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.payout_puzzle_hash);
+
+    let unique = entries
+        .iter()
+        .map(|entry| entry.payout_puzzle_hash)
+        .collect::<HashSet<_>>();
+    if unique.len() != entries.len() {
+        return Err(CliError::Custom(
+            "The index lists a payout puzzle hash twice - let the listener catch up first"
+                .to_string(),
+        ));
+    }
+
+    Ok(entries)
+}
+
+/// One delegated puzzle sending every update's message to the distributor: each message
+/// is a state scheduler layer around the next, with the vault's own conditions innermost.
+fn entry_updates_delegated_puzzle(
+    ctx: &mut SpendContext,
+    updates: &[EntryUpdate],
+    launcher_id: Bytes32,
+    medieval_vault: &MedievalVault,
+    network: &Network,
+) -> Result<NodePtr, CliError> {
+    let (last, rest) = updates
+        .split_last()
+        .ok_or(CliError::Custom("No entry updates to sign".to_string()))?;
+
+    let mut delegated_puzzle = MedievalVault::delegated_puzzle_for_flexible_send_message::<Bytes>(
+        ctx,
+        last.message(),
+        launcher_id,
+        medieval_vault.coin,
+        &medieval_vault.info,
+        get_constants(network).genesis_challenge,
+    )?;
+    let receiver_singleton_struct_hash: Bytes32 =
+        SingletonStruct::new(launcher_id).tree_hash().into();
+    for update in rest.iter().rev() {
+        delegated_puzzle = ctx.curry(StateSchedulerLayerArgs::<Bytes, NodePtr> {
+            singleton_mod_hash: SINGLETON_TOP_LAYER_V1_1_HASH.into(),
+            receiver_singleton_struct_hash,
+            message: update.message(),
+            inner_puzzle: delegated_puzzle,
+        })?;
+    }
+
+    Ok(delegated_puzzle)
+}
+
+/// Matching solution: every layer points its message at the same distributor spend.
+fn entry_updates_delegated_solution(
+    ctx: &mut SpendContext,
+    update_count: usize,
+    distributor_inner_puzzle_hash: Bytes32,
+) -> Result<NodePtr, CliError> {
+    let mut solution = NodePtr::NIL;
+    for _ in 0..update_count {
+        solution = ctx.alloc(&StateSchedulerLayerSolution {
+            other_singleton_inner_puzzle_hash: distributor_inner_puzzle_hash,
+            inner_solution: solution,
+        })?;
+    }
+
+    Ok(solution)
+}
+
+/// Constants of a managed distributor and the launcher id of the vault managing it.
+async fn managed_distributor(
+    db: &Db,
+    launcher_id: Bytes32,
+) -> Result<(RewardDistributorConstants, Bytes32), CliError> {
+    let mut temp_allocator = Allocator::new();
+    let distributor_constants = db
+        .get_reward_distributor_configuration(&mut temp_allocator, launcher_id)
+        .await?
+        .ok_or(CliError::Custom(
+            "Could not get reward distributor constants - try running another command to sync it first".to_string(),
+        ))?;
+
+    match distributor_constants.reward_distributor_type {
+        RewardDistributorType::Managed {
+            manager_singleton_launcher_id,
+        } => Ok((distributor_constants, manager_singleton_launcher_id)),
+        _ => Err(CliError::Custom(
+            "Entry updates require a managed reward distributor".to_string(),
+        )),
+    }
+}
+
+fn print_entry_updates(updates: &[EntryUpdate]) {
+    println!("\nThe roster change needs {} update(s):", updates.len());
+    for update in updates {
+        match update {
+            EntryUpdate::Add {
+                payout_puzzle_hash,
+                shares,
+            } => println!(
+                "  *ADD*    {} with {} shares",
+                hex::encode(payout_puzzle_hash),
+                shares
+            ),
+            EntryUpdate::Remove {
+                payout_puzzle_hash,
+                shares,
+            } => println!(
+                "  *REMOVE* {} with {} shares",
+                hex::encode(payout_puzzle_hash),
+                shares
+            ),
+        }
+    }
+}
+
+/// The plan comes from the listener's index, which may lag the chain: an add must find no
+/// live entry slot (besides one a planned remove spends) and a remove must find its slot.
+async fn verify_entry_updates_onchain(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    constants: RewardDistributorConstants,
+    updates: &[EntryUpdate],
+) -> Result<(), CliError> {
+    println!("\nChecking the planned updates against the chain... ");
+    for update in updates {
+        match *update {
+            EntryUpdate::Add {
+                payout_puzzle_hash, ..
+            } => {
+                let replaced = updates.iter().any(|other| match *other {
+                    EntryUpdate::Remove {
+                        payout_puzzle_hash: removed,
+                        ..
+                    } => removed == payout_puzzle_hash,
+                    EntryUpdate::Add { .. } => false,
+                });
+                let live = find_entry_slots(ctx, client, constants, payout_puzzle_hash, None, None)
+                    .await?
+                    .len();
+                if live != usize::from(replaced) {
+                    return Err(CliError::Custom(format!(
+                        "Entry {} already exists on chain - let the listener catch up first",
+                        hex::encode(payout_puzzle_hash)
+                    )));
+                }
+            }
+            EntryUpdate::Remove {
+                payout_puzzle_hash,
+                shares,
+            } => {
+                if find_entry_slots(
+                    ctx,
+                    client,
+                    constants,
+                    payout_puzzle_hash,
+                    None,
+                    Some(shares),
+                )
+                .await?
+                .is_empty()
+                {
+                    return Err(CliError::Custom(format!(
+                        "Entry {} with {} shares is not on chain - let the listener catch up first",
+                        hex::encode(payout_puzzle_hash),
+                        shares
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn plan_from_csv(
+    network: &Network,
+    launcher_id: Bytes32,
+    csv: &str,
+) -> Result<Vec<EntryUpdate>, CliError> {
+    let target = load_reward_distributor_entries_csv(csv)?;
+    let current = indexed_entries(Db::new(network, true).await?, launcher_id).await?;
+    let updates = plan_entry_updates(&current, &target)?;
+    if updates.is_empty() {
+        return Err(CliError::Custom(
+            "The CSV already matches the current entries".to_string(),
+        ));
+    }
+
+    Ok(updates)
+}
+
+pub async fn reward_distributor_sign_entry_updates(
+    launcher_id_str: String,
+    csv: String,
+    my_pubkey_str: String,
+    network: &Network,
+    debug: bool,
+) -> Result<(), CliError> {
+    let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
+
+    println!("\nGetting distributor constants... ");
+    let db = Db::new(network, true).await?;
+    let (constants, manager_launcher_id) = managed_distributor(&db, launcher_id).await?;
+
+    let updates = plan_from_csv(network, launcher_id, &csv).await?;
+    let (my_pubkey, mut ctx, client, medieval_vault) =
+        multisig_sign_thing_start(my_pubkey_str, hex::encode(manager_launcher_id), network).await?;
+
+    print_entry_updates(&updates);
+    verify_entry_updates_onchain(&mut ctx, &client, constants, &updates).await?;
+    let delegated_puzzle =
+        entry_updates_delegated_puzzle(&mut ctx, &updates, launcher_id, &medieval_vault, network)?;
+
+    multisig_sign_thing_finish(
+        &mut ctx,
+        delegated_puzzle,
+        &medieval_vault,
+        my_pubkey,
+        network,
+        debug,
+    )
+    .await
+}
+
+pub async fn reward_distributor_broadcast_entry_updates(
+    launcher_id_str: String,
+    csv: String,
+    signatures_str: String,
+    network: &Network,
+    fee_str: String,
+) -> Result<(), CliError> {
+    let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
+
+    println!("\nGetting distributor constants... ");
+    let db = Db::new(network, true).await?;
+    let (constants, manager_launcher_id) = managed_distributor(&db, launcher_id).await?;
+
+    let updates = plan_from_csv(network, launcher_id, &csv).await?;
+    let (signature_from_signers, pubkeys, client, mut ctx, medieval_vault) =
+        multisig_broadcast_thing_start(signatures_str, hex::encode(manager_launcher_id), network)
+            .await?;
+    verify_entry_updates_onchain(&mut ctx, &client, constants, &updates).await?;
+
+    println!("\nSyncing reward distributor... ");
+    let mut reward_distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
+
+    let update_time = get_last_onchain_timestamp(&client).await?;
+    if reward_distributor.info.state.round_time_info.last_update < update_time - 180 {
+        if update_time > reward_distributor.info.state.round_time_info.epoch_end {
+            return Err(CliError::Custom(
+                "You need to start a new epoch before you can broadcast entry updates".to_string(),
+            ));
+        }
+
+        println!("Will also sync reward distributor to {}", update_time);
+        let _conds = reward_distributor
+            .new_action::<RewardDistributorSyncAction>()
+            .spend(&mut ctx, &mut reward_distributor, update_time)?;
+    }
+
+    print_entry_updates(&updates);
+
+    let medieval_vault_coin_id = medieval_vault.coin.coin_id();
+    let medieval_vault_inner_ph = medieval_vault.info.inner_puzzle_hash();
+
+    let delegated_puzzle_ptr =
+        entry_updates_delegated_puzzle(&mut ctx, &updates, launcher_id, &medieval_vault, network)?;
+    let delegated_solution_ptr = entry_updates_delegated_solution(
+        &mut ctx,
+        updates.len(),
+        reward_distributor.info.inner_puzzle_hash().into(),
+    )?;
+
+    medieval_vault.spend_sunsafe(
+        &mut ctx,
+        &pubkeys,
+        delegated_puzzle_ptr,
+        delegated_solution_ptr,
+    )?;
+
+    for update in &updates {
+        match *update {
+            EntryUpdate::Remove {
+                payout_puzzle_hash,
+                shares,
+            } => {
+                let entry_slot = find_entry_slots(
+                    &mut ctx,
+                    &client,
+                    reward_distributor.info.constants,
+                    payout_puzzle_hash,
+                    None,
+                    Some(shares),
+                )
+                .await?
+                .into_iter()
+                .next()
+                .ok_or(CliError::SlotNotFound("Entry"))?;
+
+                let (_conds, last_payment_amount) = reward_distributor
+                    .new_action::<RewardDistributorRemoveEntryAction>()
+                    .spend(
+                        &mut ctx,
+                        &mut reward_distributor,
+                        entry_slot,
+                        medieval_vault_inner_ph.into(),
+                    )?;
+                println!(
+                    "Last payment to {}: {} CAT mojos",
+                    hex::encode(payout_puzzle_hash),
+                    last_payment_amount
+                );
+            }
+            EntryUpdate::Add {
+                payout_puzzle_hash,
+                shares,
+            } => {
+                let _conds = reward_distributor
+                    .new_action::<RewardDistributorAddEntryAction>()
+                    .spend(
+                        &mut ctx,
+                        &mut reward_distributor,
+                        payout_puzzle_hash,
+                        shares,
+                        medieval_vault_inner_ph.into(),
+                    )?;
+            }
+        }
+    }
+    let (_new_distributor, pending_sig) = reward_distributor.finish_spend(&mut ctx, vec![])?;
+
+    multisig_broadcast_thing_finish(
+        client,
+        &mut ctx,
+        signature_from_signers + &pending_sig,
+        fee_str,
+        network,
+        medieval_vault_coin_id,
+        None,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(byte: u8, shares: u64) -> RewardDistributorEntrySlotValue {
+        RewardDistributorEntrySlotValue {
+            payout_puzzle_hash: Bytes32::new([byte; 32]),
+            initial_cumulative_payout: 0,
+            shares,
+        }
+    }
+
+    fn record(byte: u8, shares: u64) -> RewardDistributorEntryRecord {
+        RewardDistributorEntryRecord {
+            payout_puzzle_hash: Bytes32::new([byte; 32]),
+            shares,
+        }
+    }
+
+    #[test]
+    fn plan_removes_before_adds_and_splits_share_changes() {
+        let current = [entry(1, 1), entry(2, 5), entry(3, 2)];
+        let target = [record(3, 2), record(2, 7), record(4, 1)];
+
+        assert_eq!(
+            plan_entry_updates(&current, &target).unwrap(),
+            vec![
+                EntryUpdate::Remove {
+                    payout_puzzle_hash: Bytes32::new([1; 32]),
+                    shares: 1,
+                },
+                EntryUpdate::Remove {
+                    payout_puzzle_hash: Bytes32::new([2; 32]),
+                    shares: 5,
+                },
+                EntryUpdate::Add {
+                    payout_puzzle_hash: Bytes32::new([2; 32]),
+                    shares: 7,
+                },
+                EntryUpdate::Add {
+                    payout_puzzle_hash: Bytes32::new([4; 32]),
+                    shares: 1,
+                },
+            ]
+        );
+        assert!(
+            plan_entry_updates(&current, &[record(1, 1), record(2, 5), record(3, 2)])
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn plan_rejects_duplicates_and_zero_shares() {
+        assert!(plan_entry_updates(&[], &[record(1, 1), record(1, 2)]).is_err());
+        assert!(plan_entry_updates(&[], &[record(1, 0)]).is_err());
+    }
+}
@@ -2,7 +2,7 @@ use chia_bls::PublicKey;
 use chia_protocol::{Bytes32, CoinSpend};
 use chia_puzzle_types::standard::StandardArgs;
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         Cat, Datastore, DatastoreMetadata, DelegatedPuzzle, Layer, Nft, OracleLayer, Puzzle,
         RewardDistributor, RewardDistributorStakeAction, Spend, SpendContext,
@@ -18,7 +18,7 @@ use clvm_utils::{CurriedProgram, ToTreeHash, TreeHash};
 
 use crate::{
     build_merkle_tree, build_root_hash, hex_string_to_pubkey, leaf_hash, load_and_dedupe_csv,
-    oracle_delegated_puzzles, CliError, DatastoreNftRecord, SageClient,
+    oracle_delegated_puzzles, ChainClient, CliError, DatastoreNftRecord, SageClient,
 };
 
 pub struct CustodyInfo {
@@ -245,7 +245,7 @@ pub fn locked_nft_p2_puzzle_hash(
 
 pub async fn find_locked_nfts(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     launcher_id: Bytes32,
     custody_puzzle_hash: Bytes32,
     entry_shares: u64,
@@ -298,7 +298,7 @@ pub async fn find_locked_nfts(
 
 pub async fn find_locked_cats(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     launcher_id: Bytes32,
     custody_puzzle_hash: Bytes32,
     asset_id: Bytes32,
@@ -15,24 +15,24 @@ use clvm_traits::clvm_quote;
 use clvmr::NodePtr;
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, find_entry_slots, get_coin_public_key,
-    get_coinset_client, get_constants, get_last_onchain_timestamp, get_prefix,
+    assets_xch_only, confirm_pushed_transaction, find_entry_slots, get_chain_client,
+    get_coin_public_key, get_constants, get_last_onchain_timestamp, get_prefix,
     hex_string_to_bytes32, hex_string_to_signature, no_assets, parse_amount, resolve_custody,
-    spend_to_coin_spend, sync_distributor, yes_no_prompt, CliError, Db, SageClient,
+    spend_to_coin_spend, sync_distributor, yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_initiate_payout(
     launcher_id_str: String,
     custody_address: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
 
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
@@ -56,7 +56,7 @@ pub async fn reward_distributor_initiate_payout(
     let custody = resolve_custody(&sage, custody_address).await?;
     println!(
         "Using the following address as custody: {}",
-        Address::new(custody.puzzle_hash, get_prefix(testnet11)).encode()?
+        Address::new(custody.puzzle_hash, get_prefix(network)).encode()?
     );
 
     println!("Finding entry slot...");
@@ -113,7 +113,7 @@ pub async fn reward_distributor_initiate_payout(
 
         let custody_pk = get_coin_public_key(
             &sage,
-            &Address::new(payout_puzzle_hash, get_prefix(testnet11)).encode()?,
+            &Address::new(payout_puzzle_hash, get_prefix(network)).encode()?,
             10000,
         )
         .await?;
@@ -150,7 +150,7 @@ pub async fn reward_distributor_initiate_payout(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -159,7 +159,7 @@ pub async fn reward_distributor_initiate_payout(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
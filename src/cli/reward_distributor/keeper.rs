@@ -0,0 +1,448 @@
+use std::{collections::HashMap, time::Duration};
+
+use chia_bls::{SecretKey, Signature};
+use chia_protocol::{Bytes32, SpendBundle};
+use chia_puzzle_types::{standard::StandardArgs, DeriveSynthetic, Memos};
+use chia_wallet_sdk::{
+    coinset::{ChiaRpcClient, CoinRecord},
+    driver::{
+        spend_security_coin, RewardDistributor, RewardDistributorNewEpochAction,
+        RewardDistributorSyncAction, SpendContext,
+    },
+    types::Conditions,
+    utils::Address,
+};
+
+use crate::{
+    available_reward_coins, commit_reward_coins, confirm_pushed_transaction, find_reward_slot,
+    get_chain_client, get_constants, get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32,
+    hex_string_to_secret_key, parse_amount, reward_deposit_cat, reward_deposit_puzzle,
+    sync_distributor, ChainClient, CliError, Db, Network, RewardDepositPuzzle,
+};
+
+/// Hex secret key of the keeper's hot wallet; its standard address pays the fees.
+pub const KEEPER_SECRET_KEY_ENV: &str = "SLOT_MACHINE_KEEPER_SECRET_KEY";
+
+/// How long the keeper waits for one of its transactions to confirm.
+pub const KEEPER_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Spend the keeper would make for a distributor on this tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeeperAction {
+    NewEpoch,
+    CommitRewards,
+    Sync { update_time: u64 },
+}
+
+/// Pick at most one spend: starting an overdue epoch first, then syncing once `last_update`
+/// gets within `sync_margin` of the offset, then sweeping deposits into the next epoch.
+pub fn next_keeper_action(
+    last_update: u64,
+    epoch_end: u64,
+    max_seconds_offset: u64,
+    now: u64,
+    has_deposits: bool,
+    sync_margin: u64,
+) -> Option<KeeperAction> {
+    if now >= epoch_end {
+        return Some(KeeperAction::NewEpoch);
+    }
+    if now > last_update && now - last_update + sync_margin >= max_seconds_offset {
+        return Some(KeeperAction::Sync { update_time: now });
+    }
+    if has_deposits {
+        return Some(KeeperAction::CommitRewards);
+    }
+
+    None
+}
+
+/// Standard-puzzle wallet the keeper signs fee coins with.
+struct HotWallet {
+    synthetic_key: SecretKey,
+    puzzle_hash: Bytes32,
+}
+
+impl HotWallet {
+    fn from_env() -> Result<Self, CliError> {
+        let hex = std::env::var(KEEPER_SECRET_KEY_ENV).map_err(|_| {
+            CliError::Custom(format!(
+                "{} must hold the keeper's hot wallet secret key",
+                KEEPER_SECRET_KEY_ENV
+            ))
+        })?;
+        let synthetic_key = hex_string_to_secret_key(hex.trim())?.derive_synthetic();
+        let puzzle_hash = StandardArgs::curry_tree_hash(synthetic_key.public_key()).into();
+
+        Ok(Self {
+            synthetic_key,
+            puzzle_hash,
+        })
+    }
+
+    /// Spend the largest hot coin covering `fee` next to the distributor spend already in
+    /// `ctx`, asserting `conditions` and returning the change to the hot wallet. The fee is
+    /// added to `fees_spent` as soon as the mempool accepts the bundle.
+    async fn submit(
+        &self,
+        ctx: &mut SpendContext,
+        client: &ChainClient,
+        network: &Network,
+        conditions: Conditions,
+        distributor_sig: Signature,
+        fee: u64,
+        fees_spent: &mut u64,
+    ) -> Result<(), CliError> {
+        let coin = client
+            .get_coin_records_by_puzzle_hash(self.puzzle_hash, None, None, Some(false), None)
+            .await?
+            .coin_records
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|record| !record.spent && record.coin.amount >= fee)
+            .max_by_key(|record| record.coin.amount)
+            .ok_or_else(|| {
+                CliError::Custom(format!(
+                    "Hot wallet has no unspent coin covering the {} mojo fee",
+                    fee
+                ))
+            })?
+            .coin;
+
+        let conditions = conditions.reserve_fee(fee).create_coin(
+            self.puzzle_hash,
+            coin.amount - fee,
+            Memos::None,
+        );
+        let hot_sig = spend_security_coin(
+            ctx,
+            coin,
+            conditions,
+            &self.synthetic_key,
+            get_constants(network),
+        )?;
+        let spend_bundle = SpendBundle::new(ctx.take(), hot_sig + &distributor_sig);
+
+        let resp = client.push_tx(spend_bundle).await?;
+        *fees_spent += fee;
+        tokio::time::timeout(
+            KEEPER_CONFIRMATION_TIMEOUT,
+            confirm_pushed_transaction(client, &resp, coin.coin_id(), true),
+        )
+        .await
+        .map_err(|_| {
+            CliError::Custom(format!(
+                "Transaction spending hot coin {} did not confirm within {}s",
+                hex::encode(coin.coin_id()),
+                KEEPER_CONFIRMATION_TIMEOUT.as_secs()
+            ))
+        })??;
+
+        Ok(())
+    }
+}
+
+/// Settings shared by every distributor a keeper watches.
+struct KeeperConfig {
+    clawback_inner_puzzle_hash: Bytes32,
+    max_coins: usize,
+    fee: u64,
+    sync_margin: u64,
+}
+
+/// Up to `max_coins` deposits whose parent is the distributor's Reward CAT; other coins
+/// at the deposit puzzle hash are skipped.
+async fn sweepable_deposits(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    launcher_id: Bytes32,
+    config: &KeeperConfig,
+) -> Result<(RewardDepositPuzzle, Vec<CoinRecord>), CliError> {
+    let deposit =
+        reward_deposit_puzzle(ctx, client, launcher_id, config.clawback_inner_puzzle_hash).await?;
+
+    let mut records = Vec::new();
+    for record in available_reward_coins(client, &deposit, usize::MAX).await? {
+        if records.len() == config.max_coins {
+            break;
+        }
+        if reward_deposit_cat(ctx, client, &deposit, &record)
+            .await?
+            .is_some()
+        {
+            records.push(record);
+        }
+    }
+
+    Ok((deposit, records))
+}
+
+/// Run one tick for `launcher_id`, returning the action it confirmed, if any. A failing
+/// deposit sweep is logged and skipped so it never holds up epochs and syncs.
+async fn keep_distributor(
+    client: &ChainClient,
+    db: &Db,
+    network: &Network,
+    hot: &HotWallet,
+    config: &KeeperConfig,
+    launcher_id: Bytes32,
+    fees_spent: &mut u64,
+) -> Result<Option<KeeperAction>, CliError> {
+    let mut ctx = SpendContext::new();
+    let mut distributor = sync_distributor(client, db, &mut ctx, launcher_id).await?;
+    let now = get_last_onchain_timestamp(client).await?;
+
+    let deposits = match sweepable_deposits(&mut ctx, client, launcher_id, config).await {
+        Ok((deposit, records)) if !records.is_empty() => Some((deposit, records)),
+        Ok(_) => None,
+        Err(err) => {
+            eprintln!(
+                "{}: skipping reward deposits: {}",
+                hex::encode(launcher_id),
+                err
+            );
+            None
+        }
+    };
+
+    let time_info = distributor.info.state.round_time_info;
+    let Some(action) = next_keeper_action(
+        time_info.last_update,
+        time_info.epoch_end,
+        distributor.info.constants.max_seconds_offset,
+        now,
+        deposits.is_some(),
+        config.sync_margin,
+    ) else {
+        return Ok(None);
+    };
+
+    let (conditions, distributor_sig) = match (action, deposits) {
+        (KeeperAction::NewEpoch, _) => new_epoch_spend(&mut ctx, client, &mut distributor).await?,
+        (KeeperAction::Sync { update_time }, _) => {
+            let conditions = distributor
+                .new_action::<RewardDistributorSyncAction>()
+                .spend(&mut ctx, &mut distributor, update_time)?;
+            let (_new_distributor, sig) = distributor.finish_spend(&mut ctx, vec![])?;
+            (conditions, sig)
+        }
+        (KeeperAction::CommitRewards, Some((deposit, records))) => {
+            let committed =
+                commit_rewards_spend(&mut ctx, client, &mut distributor, &deposit, records).await;
+            match committed {
+                Ok(spend) => spend,
+                Err(err) => {
+                    eprintln!(
+                        "{}: skipping reward deposits: {}",
+                        hex::encode(launcher_id),
+                        err
+                    );
+                    return Ok(None);
+                }
+            }
+        }
+        (KeeperAction::CommitRewards, None) => return Ok(None),
+    };
+
+    let submitted = hot
+        .submit(
+            &mut ctx,
+            client,
+            network,
+            conditions,
+            distributor_sig,
+            config.fee,
+            fees_spent,
+        )
+        .await;
+    match submitted {
+        Err(err) if action == KeeperAction::CommitRewards => {
+            eprintln!(
+                "{}: reward deposit sweep failed: {}",
+                hex::encode(launcher_id),
+                err
+            );
+            Ok(None)
+        }
+        submitted => submitted.map(|()| Some(action)),
+    }
+}
+
+/// Commit `records` to the epoch after the current one.
+async fn commit_rewards_spend(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    distributor: &mut RewardDistributor,
+    deposit: &RewardDepositPuzzle,
+    records: Vec<CoinRecord>,
+) -> Result<(Conditions, Signature), CliError> {
+    let epoch_end = distributor.info.state.round_time_info.epoch_end;
+    let reward_slot = find_reward_slot(ctx, client, distributor.info.constants, epoch_end).await?;
+    let cat_spends =
+        commit_reward_coins(ctx, client, distributor, deposit, records, reward_slot).await?;
+    let (_new_distributor, sig) = distributor.finish_spend(ctx, cat_spends)?;
+
+    Ok((Conditions::new(), sig))
+}
+
+/// Sync up to the epoch end if needed, then start the next epoch.
+async fn new_epoch_spend(
+    ctx: &mut SpendContext,
+    client: &ChainClient,
+    distributor: &mut RewardDistributor,
+) -> Result<(Conditions, Signature), CliError> {
+    let epoch_end = distributor.info.state.round_time_info.epoch_end;
+    if distributor.info.state.round_time_info.last_update < epoch_end {
+        // the new epoch action asserts the sync already happened
+        let _conds = distributor
+            .new_action::<RewardDistributorSyncAction>()
+            .spend(ctx, distributor, epoch_end)?;
+    }
+
+    let reward_slot = find_reward_slot(ctx, client, distributor.info.constants, epoch_end).await?;
+    let (conditions, epoch_fee) = distributor
+        .new_action::<RewardDistributorNewEpochAction>()
+        .spend(ctx, distributor, reward_slot)?;
+    println!("Fee for new epoch: {} CAT mojos", epoch_fee);
+    let (_new_distributor, sig) = distributor.finish_spend(ctx, vec![])?;
+
+    Ok((conditions, sig))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn reward_distributor_keep(
+    launcher_ids: String,
+    clawback_address: Option<String>,
+    max_coins: usize,
+    interval_seconds: u64,
+    sync_margin_seconds: u64,
+    max_retries: u32,
+    network: &Network,
+    fee_str: String,
+    fee_budget_str: String,
+) -> Result<(), CliError> {
+    if max_coins == 0 || max_coins > 32 {
+        return Err(CliError::Custom(
+            "max-coins must be between 1 and 32".to_string(),
+        ));
+    }
+
+    let mut active = launcher_ids
+        .split(',')
+        .map(hex_string_to_bytes32)
+        .collect::<Result<Vec<Bytes32>, CliError>>()?;
+    let config = KeeperConfig {
+        clawback_inner_puzzle_hash: clawback_address
+            .as_deref()
+            .map(Address::decode)
+            .transpose()?
+            .map_or_else(Bytes32::default, |address| address.puzzle_hash),
+        max_coins,
+        fee: parse_amount(&fee_str, false)?,
+        sync_margin: sync_margin_seconds,
+    };
+    let fee_budget = parse_amount(&fee_budget_str, false)?;
+
+    let hot = HotWallet::from_env()?;
+    println!(
+        "Keeper fees will be paid from {}",
+        Address::new(hot.puzzle_hash, get_prefix(network)).encode()?
+    );
+
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
+    let mut fees_spent = 0_u64;
+    let mut failures: HashMap<Bytes32, u32> = HashMap::new();
+
+    loop {
+        for launcher_id in active.clone() {
+            if fees_spent + config.fee > fee_budget {
+                return Err(CliError::Custom(format!(
+                    "Fee budget exhausted: {} of {} mojos spent",
+                    fees_spent, fee_budget
+                )));
+            }
+
+            let kept = keep_distributor(
+                &client,
+                &db,
+                network,
+                &hot,
+                &config,
+                launcher_id,
+                &mut fees_spent,
+            )
+            .await;
+            match kept {
+                Ok(Some(action)) => {
+                    failures.remove(&launcher_id);
+                    println!(
+                        "{}: {:?} confirmed ({} of {} fee mojos spent)",
+                        hex::encode(launcher_id),
+                        action,
+                        fees_spent,
+                        fee_budget
+                    );
+                }
+                Ok(None) => {
+                    failures.remove(&launcher_id);
+                }
+                Err(err) => {
+                    let count = failures.entry(launcher_id).or_default();
+                    *count += 1;
+                    eprintln!(
+                        "{}: attempt {} failed: {}",
+                        hex::encode(launcher_id),
+                        count,
+                        err
+                    );
+                    if *count > max_retries {
+                        eprintln!(
+                            "{}: retry budget exhausted; no longer keeping it",
+                            hex::encode(launcher_id)
+                        );
+                        active.retain(|id| *id != launcher_id);
+                    }
+                }
+            }
+        }
+
+        if active.is_empty() {
+            return Err(CliError::Custom(
+                "Every distributor exhausted its retry budget".to_string(),
+            ));
+        }
+        tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_one_action_in_priority_order() {
+        // last_update, epoch_end, max_seconds_offset, now, has_deposits, sync_margin
+        assert_eq!(
+            next_keeper_action(1_000, 2_000, 600, 2_000, true, 120),
+            Some(KeeperAction::NewEpoch)
+        );
+        assert_eq!(
+            next_keeper_action(1_000, 2_000, 600, 1_100, true, 120),
+            Some(KeeperAction::CommitRewards)
+        );
+        assert_eq!(
+            next_keeper_action(1_000, 2_000, 600, 1_400, false, 120),
+            None
+        );
+        assert_eq!(
+            next_keeper_action(1_000, 2_000, 600, 1_480, false, 120),
+            Some(KeeperAction::Sync { update_time: 1_480 })
+        );
+        // Pending deposits never hold up a due sync.
+        assert_eq!(
+            next_keeper_action(1_000, 2_000, 600, 1_480, true, 120),
+            Some(KeeperAction::Sync { update_time: 1_480 })
+        );
+    }
+}
@@ -9,8 +9,9 @@ use chia_wallet_sdk::{
 };
 
 use crate::{
-    assets_xch_and_cat, confirm_pushed_transaction, get_coinset_client, get_constants,
-    hex_string_to_bytes32, no_assets, parse_amount, yes_no_prompt, CliError, Db, SageClient,
+    assets_xch_and_cat, confirm_pushed_transaction, get_chain_client, get_constants,
+    hex_string_to_bytes32, no_assets, parse_amount, yes_no_prompt, CliError, Db, Network,
+    SageClient,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -30,7 +31,7 @@ pub async fn reward_distributor_launch(
     withdrawal_share_bps: u64,
     reserve_asset_id_str: String,
     comment_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let distributor_type = if let Some(manager_launcher_id_str) = manager_launcher_id_str {
@@ -113,7 +114,7 @@ pub async fn reward_distributor_launch(
             withdrawal_share_bps,
             reserve_asset_id,
         ),
-        get_constants(testnet11),
+        get_constants(network),
         &comment_str,
     )
     .map_err(CliError::Driver)?;
@@ -123,7 +124,7 @@ pub async fn reward_distributor_launch(
         hex::encode(reward_distributor.info.constants.launcher_id)
     );
 
-    let db = Db::new(false).await?;
+    let db = Db::new(network, false).await?;
     db.save_reward_distributor_configuration(
         &mut ctx,
         reward_distributor.info.constants.launcher_id,
@@ -134,7 +135,7 @@ pub async fn reward_distributor_launch(
     let spend_bundle = SpendBundle::new(ctx.take(), sig);
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(
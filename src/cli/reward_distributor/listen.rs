@@ -0,0 +1,422 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use chia_protocol::Bytes32;
+use chia_wallet_sdk::coinset::ChiaRpcClient;
+use chia_wallet_sdk::driver::{RewardDistributor, SpendContext};
+use futures_util::StreamExt;
+use tokio::sync::RwLock;
+
+use crate::{
+    checkpoint_fork_point, finish_transition, get_chain_client, hex_string_to_bytes32,
+    listener_bind_addr, reorg_rollback_from, serve_distributor_listener, sync_distributor_detailed,
+    ChainClient, ChainSource, CliError, Db, DbDistributorSlotStore, DbDistributorStore,
+    DbTransitionScope, DistributorApiState, DistributorIndexer, DistributorSlotStore,
+    DistributorStore, FreshnessState, ListenerMetrics, Network, RegistryTip,
+    StoredDistributorState, TransitionScope, HEADER_CHECKPOINT_WINDOW,
+};
+
+/// `header_checkpoints.listener` key for this listener's window.
+pub const REWARD_DISTRIBUTOR_CHECKPOINT_LISTENER: &str = "reward_distributor";
+
+/// Port `reward-distributor listen` serves on when `BIND_ADDR` is unset.
+pub const REWARD_DISTRIBUTOR_LISTEN_DEFAULT_PORT: u16 = 3001;
+
+pub async fn reward_distributor_listen(
+    launcher_ids: String,
+    network: &Network,
+) -> Result<(), CliError> {
+    let db = Db::new(network, false).await?;
+    // API reads get their own handle so they never see or wait on an open transition.
+    let api_db = Arc::new(futures::lock::Mutex::new(db.reader()));
+    let db = Arc::new(futures::lock::Mutex::new(db));
+    let launcher_ids = launcher_ids
+        .split(',')
+        .map(hex_string_to_bytes32)
+        .collect::<Result<Vec<Bytes32>, CliError>>()?;
+
+    let distributors: Arc<dyn DistributorStore> = DbDistributorStore::new(Arc::clone(&db));
+    let slots: Arc<dyn DistributorSlotStore> = DbDistributorSlotStore::new(Arc::clone(&db));
+    let freshness = Arc::new(RwLock::new(FreshnessState::fresh_at(
+        0,
+        FreshnessState::now_unix(),
+    )));
+    let metrics = ListenerMetrics::shared("reward_distributor");
+    let indexer = Arc::new(
+        DistributorIndexer::new(
+            Arc::clone(&distributors),
+            Arc::clone(&slots),
+            Arc::clone(&freshness),
+        )
+        .with_metrics(Arc::clone(&metrics)),
+    );
+    let distributor_tips = Arc::new(RwLock::new(HashMap::new()));
+
+    let api_state = DistributorApiState {
+        distributors: DbDistributorStore::new(Arc::clone(&api_db)),
+        slots: DbDistributorSlotStore::new(Arc::clone(&api_db)),
+        freshness,
+        distributor_tips: Arc::clone(&distributor_tips),
+        launcher_ids: launcher_ids.clone(),
+        now_unix_override: None,
+        metrics: Arc::clone(&metrics),
+    };
+
+    // Not fresh until the first peak after the initial sync.
+    indexer.begin_resync().await;
+
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    tokio::spawn(async move {
+        let addr = listener_bind_addr(REWARD_DISTRIBUTOR_LISTEN_DEFAULT_PORT);
+        println!("API server listening on {}", addr);
+        if let Err(e) = serve_distributor_listener(api_state, addr).await {
+            eprintln!("API server error: {}", e);
+        }
+    });
+
+    loop {
+        match connect_websocket(
+            network,
+            &launcher_ids,
+            Arc::clone(&db),
+            Arc::clone(&indexer),
+            Arc::clone(&distributor_tips),
+        )
+        .await
+        {
+            Ok(_resp) => (),
+            Err(e) => {
+                indexer.begin_resync().await;
+                metrics.websocket_reconnects.inc();
+                println!("WebSocket error: {}", e);
+                println!("Reconnecting in 5 seconds...");
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            }
+        }
+    }
+}
+
+/// Walk the distributor to its tip and index every spent transition at or above `from_height`.
+async fn sync_and_index_distributor(
+    client: &ChainClient,
+    db: &Arc<futures::lock::Mutex<Db>>,
+    indexer: &DistributorIndexer,
+    launcher_id: Bytes32,
+    from_height: u32,
+) -> Result<RewardDistributor, CliError> {
+    let synced = {
+        let mut ctx = SpendContext::new();
+        let db = db.lock().await;
+        sync_distributor_detailed(client, &db, &mut ctx, launcher_id).await?
+    };
+    for transition in &synced.spent_transitions {
+        if transition.height < from_height {
+            continue;
+        }
+        let distributor = &transition.distributor;
+        let state = &distributor.info.state;
+        indexer
+            .on_distributor_transition(
+                launcher_id,
+                synced.constants.into(),
+                StoredDistributorState {
+                    coin_id: distributor.coin.coin_id(),
+                    parent_coin_id: distributor.coin.parent_coin_info,
+                    active_shares: state.active_shares,
+                    total_reserves: state.total_reserves,
+                    cumulative_payout: state.round_reward_info.cumulative_payout,
+                    remaining_rewards: state.round_reward_info.remaining_rewards,
+                    last_update: state.round_time_info.last_update,
+                    epoch_end: state.round_time_info.epoch_end,
+                    confirmation_height: transition.height,
+                },
+                &transition.created_slots,
+                &transition.spent_slots,
+            )
+            .await?;
+    }
+    Ok(synced.distributor)
+}
+
+/// Pre-final reorganization: drop projections from `from_height`, rewind every saved
+/// distributor tip, then replay each one from the fork point in one commit.
+async fn resync_after_reorg(
+    client: &ChainClient,
+    db: &Arc<futures::lock::Mutex<Db>>,
+    scope: &dyn TransitionScope,
+    indexer: &DistributorIndexer,
+    launcher_ids: &[Bytes32],
+    from_height: u32,
+) -> Result<HashMap<Bytes32, RewardDistributor>, CliError> {
+    scope.begin().await?;
+    let result = async {
+        indexer.rollback(from_height).await?;
+        let mut tips = HashMap::new();
+        for &launcher_id in launcher_ids {
+            db.lock()
+                .await
+                .rewind_singleton_to_before(launcher_id, from_height)
+                .await?;
+            let distributor =
+                sync_and_index_distributor(client, db, indexer, launcher_id, from_height).await?;
+            tips.insert(launcher_id, distributor);
+        }
+        Ok(tips)
+    }
+    .await;
+    finish_transition(scope, result).await
+}
+
+async fn publish_distributor_tips(
+    distributor_tips: &RwLock<HashMap<Bytes32, RegistryTip>>,
+    distributors: &HashMap<Bytes32, RewardDistributor>,
+    indexed_height: u32,
+) {
+    let mut tips = distributor_tips.write().await;
+    for (launcher_id, distributor) in distributors {
+        tips.insert(
+            *launcher_id,
+            RegistryTip {
+                coin_id: distributor.coin.coin_id(),
+                indexed_height,
+            },
+        );
+    }
+}
+
+async fn connect_websocket(
+    network: &Network,
+    launcher_ids: &[Bytes32],
+    db: Arc<futures::lock::Mutex<Db>>,
+    indexer: Arc<DistributorIndexer>,
+    distributor_tips: Arc<RwLock<HashMap<Bytes32, RegistryTip>>>,
+) -> Result<(), CliError> {
+    println!("Syncing reward distributors (initial)...");
+    let client = get_chain_client(network)?;
+    let metrics = indexer.metrics.as_ref();
+    let scope = DbTransitionScope { db: &db, metrics };
+
+    // Checkpoints from the previous run are checked first so a reorg while we were
+    // down rolls the projections back instead of resuming stale tips.
+    let upstream_peak = metrics
+        .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+        .await?
+        .blockchain_state
+        .map(|s| s.peak.height)
+        .ok_or_else(|| CliError::Custom("no blockchain state at startup".to_string()))?;
+    indexer.note_upstream_peak(upstream_peak).await;
+    let mut checkpoints = db
+        .lock()
+        .await
+        .header_checkpoints(REWARD_DISTRIBUTOR_CHECKPOINT_LISTENER)
+        .await?;
+    let mut upstream_hashes = HashMap::new();
+    for &(height, _) in checkpoints.iter().filter(|(h, _)| *h <= upstream_peak) {
+        if let Some(rec) = metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                client.get_block_record_by_height(height),
+            )
+            .await?
+            .block_record
+        {
+            upstream_hashes.insert(height, rec.header_hash);
+        }
+    }
+    let fork = checkpoint_fork_point(&checkpoints, upstream_peak, |h| {
+        upstream_hashes.get(&h).copied()
+    });
+
+    let mut distributors = if let Some(from_height) = fork {
+        eprintln!(
+            "[reward-distributor-listen] chain reorg while offline: rolling back from height {from_height}"
+        );
+        let distributors = resync_after_reorg(
+            &client,
+            &db,
+            &scope,
+            indexer.as_ref(),
+            launcher_ids,
+            from_height,
+        )
+        .await?;
+        checkpoints.retain(|(h, _)| *h < from_height);
+        db.lock()
+            .await
+            .delete_header_checkpoints_from(REWARD_DISTRIBUTOR_CHECKPOINT_LISTENER, from_height)
+            .await?;
+        distributors
+    } else {
+        let mut distributors = HashMap::new();
+        for &launcher_id in launcher_ids {
+            scope.begin().await?;
+            let result =
+                sync_and_index_distributor(&client, &db, indexer.as_ref(), launcher_id, 0).await;
+            distributors.insert(launcher_id, finish_transition(&scope, result).await?);
+        }
+        distributors
+    };
+    publish_distributor_tips(&distributor_tips, &distributors, upstream_peak).await;
+
+    let mut recent_peaks: VecDeque<(u32, Bytes32)> = checkpoints
+        .into_iter()
+        .filter(|(h, _)| *h < upstream_peak)
+        .collect();
+    if let Some(rec) = metrics
+        .observe_rpc(
+            "get_block_record_by_height",
+            client.get_block_record_by_height(upstream_peak),
+        )
+        .await?
+        .block_record
+    {
+        recent_peaks.push_back((upstream_peak, rec.header_hash));
+    }
+    while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+        recent_peaks.pop_front();
+    }
+    db.lock()
+        .await
+        .save_header_checkpoints(
+            REWARD_DISTRIBUTOR_CHECKPOINT_LISTENER,
+            recent_peaks.make_contiguous(),
+        )
+        .await?;
+
+    let mut peaks = client.peak_notifications().await?;
+    let mut last_clear_time = SystemTime::now();
+
+    while peaks.next().await.is_some() {
+        let now = SystemTime::now();
+        let now_unix = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        println!("[{}] Received new peak", now_unix);
+
+        let Some(tip) = metrics
+            .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+            .await?
+            .blockchain_state
+            .map(|s| s.peak.height)
+        else {
+            continue;
+        };
+        indexer.note_upstream_peak(tip).await;
+
+        let tip_rec = metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                client.get_block_record_by_height(tip),
+            )
+            .await?
+            .block_record;
+        if let Some(rec) = &tip_rec {
+            let needs_hash_at = match recent_peaks.back() {
+                Some(&(last_height, last_hash)) => {
+                    tip != last_height.saturating_add(1) || rec.prev_hash != last_hash
+                }
+                None => false,
+            };
+            let mut hash_at_cache = HashMap::new();
+            if needs_hash_at {
+                for &(h, _) in &recent_peaks {
+                    if let Some(br) = metrics
+                        .observe_rpc(
+                            "get_block_record_by_height",
+                            client.get_block_record_by_height(h),
+                        )
+                        .await?
+                        .block_record
+                    {
+                        hash_at_cache.insert(h, br.header_hash);
+                    }
+                }
+            }
+            if let Some(from_height) =
+                reorg_rollback_from(recent_peaks.make_contiguous(), tip, rec.prev_hash, |h| {
+                    hash_at_cache.get(&h).copied()
+                })
+            {
+                eprintln!("chain reorg: rolling back from height {from_height}");
+                distributors = resync_after_reorg(
+                    &client,
+                    &db,
+                    &scope,
+                    indexer.as_ref(),
+                    launcher_ids,
+                    from_height,
+                )
+                .await?;
+                recent_peaks.retain(|(h, _)| *h < from_height);
+            }
+        }
+
+        for &launcher_id in launcher_ids {
+            let Some(distributor) = distributors.get(&launcher_id) else {
+                continue;
+            };
+            let coin_record = metrics
+                .observe_rpc(
+                    "get_coin_record_by_name",
+                    client.get_coin_record_by_name(distributor.coin.coin_id()),
+                )
+                .await?
+                .coin_record
+                .ok_or(CliError::Custom(
+                    "Weird - coin record not found after peak update.".to_string(),
+                ))?;
+            if coin_record.spent {
+                print!(
+                    "Reward distributor {} was spent at height {}... ",
+                    hex::encode(launcher_id),
+                    coin_record.spent_block_index
+                );
+                scope.begin().await?;
+                let result =
+                    sync_and_index_distributor(&client, &db, indexer.as_ref(), launcher_id, 0)
+                        .await;
+                distributors.insert(launcher_id, finish_transition(&scope, result).await?);
+                println!("synced :)")
+            }
+        }
+        metrics.blocks_processed.inc();
+
+        if let Some(rec) = &tip_rec {
+            recent_peaks.retain(|(h, _)| *h < tip);
+            recent_peaks.push_back((tip, rec.header_hash));
+            while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+                recent_peaks.pop_front();
+            }
+            let started = Instant::now();
+            db.lock()
+                .await
+                .save_header_checkpoints(
+                    REWARD_DISTRIBUTOR_CHECKPOINT_LISTENER,
+                    recent_peaks.make_contiguous(),
+                )
+                .await?;
+            metrics.observe_sqlite_write("header_checkpoints", started.elapsed());
+        }
+        publish_distributor_tips(&distributor_tips, &distributors, tip).await;
+        indexer.note_peak(tip, tip, now_unix).await;
+
+        if last_clear_time.elapsed().unwrap().as_secs() > 60 * 30 {
+            // 30 minutes in seconds
+            print!("Clearing cache (every 30m)... ");
+            let cutoff = tip.saturating_sub(128);
+            let started = Instant::now();
+            indexer.prune_spent_slots(cutoff).await?;
+            db.lock()
+                .await
+                .delete_singleton_coins_spent_before(cutoff)
+                .await?;
+            metrics.observe_sqlite_write("prune", started.elapsed());
+            println!("done :)");
+            last_clear_time = now;
+        }
+    }
+
+    Ok(())
+}
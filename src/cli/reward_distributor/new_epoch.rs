@@ -1,7 +1,7 @@
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, find_reward_slot, get_coinset_client,
-    get_constants, hex_string_to_bytes32, no_assets, parse_amount, sync_distributor, yes_no_prompt,
-    CliError, Db, SageClient,
+    assets_xch_only, confirm_pushed_transaction, find_reward_slot, get_chain_client, get_constants,
+    hex_string_to_bytes32, no_assets, parse_amount, sync_distributor, yes_no_prompt, CliError, Db,
+    Network, SageClient,
 };
 use chia_protocol::SpendBundle;
 use chia_wallet_sdk::{
@@ -14,15 +14,15 @@ use chia_wallet_sdk::{
 
 pub async fn reward_distributor_new_epoch(
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
 
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
@@ -79,7 +79,7 @@ pub async fn reward_distributor_new_epoch(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -88,7 +88,7 @@ pub async fn reward_distributor_new_epoch(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
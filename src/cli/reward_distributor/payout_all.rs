@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use chia_bls::Signature;
+use chia_consensus::spendbundle_conditions::get_conditions_from_spendbundle;
+use chia_protocol::{Bytes32, SpendBundle};
+use chia_wallet_sdk::{
+    coinset::ChiaRpcClient,
+    driver::{
+        create_security_coin, decode_offer, spend_security_coin, Offer, RewardDistributor,
+        RewardDistributorInitiatePayoutAction, RewardDistributorSyncAction, Slot, SpendContext,
+    },
+    types::{puzzles::RewardDistributorEntrySlotValue, Conditions},
+    utils::Address,
+};
+use clvmr::Allocator;
+
+use crate::{
+    assets_xch_only, confirm_pushed_transaction, find_entry_slots, get_chain_client, get_constants,
+    get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32, no_assets, parse_amount,
+    sync_distributor, yes_no_prompt, CliError, Db, DbDistributorSlotStore, DistributorRewardState,
+    DistributorSlotKind, DistributorSlotStore, DistributorSlotValue, Network, SageClient,
+};
+
+/// Default cost cap for the distributor side of one bundle; the mempool takes up to half
+/// of the 11B block cost per transaction, and the offer and security coin need the rest.
+pub const DEFAULT_PAYOUT_BUNDLE_MAX_COST: u64 = 5_000_000_000;
+
+fn spend_bundle_cost(spend_bundle: &SpendBundle, network: &Network) -> Result<u64, CliError> {
+    get_conditions_from_spendbundle(
+        &mut Allocator::new(),
+        spend_bundle,
+        u64::MAX,
+        100_000_000,
+        get_constants(network),
+    )
+    .map(|conds| conds.cost)
+    .map_err(|e| CliError::Custom(format!("Failed to analyze spend bundle cost: {e}")))
+}
+
+/// Sync (when needed) and pay out `slots` on `distributor`, returning the security coin
+/// conditions and the amount paid to each entry.
+fn spend_payouts(
+    ctx: &mut SpendContext,
+    distributor: &mut RewardDistributor,
+    update_time: Option<u64>,
+    slots: &[Slot<RewardDistributorEntrySlotValue>],
+) -> Result<(Conditions, Vec<(Bytes32, u64)>), CliError> {
+    let mut conds = match update_time {
+        Some(update_time) => distributor
+            .new_action::<RewardDistributorSyncAction>()
+            .spend(ctx, distributor, update_time)?,
+        None => Conditions::new(),
+    };
+
+    let mut payouts = Vec::with_capacity(slots.len());
+    for slot in slots {
+        let (payout_conds, amount) = distributor
+            .new_action::<RewardDistributorInitiatePayoutAction>()
+            .spend(ctx, distributor, *slot)?;
+        conds = conds.extend(payout_conds);
+        payouts.push((slot.info.value.payout_puzzle_hash, amount));
+    }
+
+    Ok((conds, payouts))
+}
+
+/// Largest prefix of `slots` whose distributor spend stays within `max_cost`.
+fn fit_payouts(
+    ctx: &mut SpendContext,
+    distributor: &RewardDistributor,
+    update_time: Option<u64>,
+    slots: &[Slot<RewardDistributorEntrySlotValue>],
+    max_cost: u64,
+    network: &Network,
+) -> Result<usize, CliError> {
+    let mut count = slots.len();
+    while count > 0 {
+        let mut trial = distributor.clone();
+        spend_payouts(ctx, &mut trial, update_time, &slots[..count])?;
+        let (_new_distributor, _sig) = trial.finish_spend(ctx, vec![])?;
+        let cost = spend_bundle_cost(&SpendBundle::new(ctx.take(), Signature::default()), network)?;
+        if cost <= max_cost {
+            return Ok(count);
+        }
+
+        // Payouts cost about the same each; shrink proportionally, with a little slack.
+        let scaled = count as u128 * u128::from(max_cost) * 95 / 100 / u128::from(cost);
+        count = usize::try_from(scaled).unwrap_or(0).min(count - 1);
+    }
+
+    Err(CliError::Custom(format!(
+        "A single payout does not fit within max cost {}",
+        max_cost
+    )))
+}
+
+pub async fn reward_distributor_payout_all(
+    launcher_id_str: String,
+    max_cost: u64,
+    network: &Network,
+    fee_str: String,
+) -> Result<(), CliError> {
+    let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
+    let fee = parse_amount(&fee_str, false)?;
+
+    println!("Syncing reward distributor...");
+    let client = get_chain_client(network)?;
+    let db = Arc::new(futures::lock::Mutex::new(Db::new(network, false).await?));
+    let mut ctx = SpendContext::new();
+    let distributor = sync_distributor(&client, &*db.lock().await, &mut ctx, launcher_id).await?;
+
+    if distributor.info.constants.require_payout_approval {
+        return Err(CliError::Custom(
+            "This distributor requires each custody to approve its payout; use initiate-payout"
+                .to_string(),
+        ));
+    }
+
+    let update_time = get_last_onchain_timestamp(&client).await?;
+    if update_time > distributor.info.state.round_time_info.epoch_end {
+        return Err(CliError::Custom(
+            "The current epoch has already ended - start a new epoch first".to_string(),
+        ));
+    }
+
+    // The listener's index is the only place that knows every entry.
+    let slot_store = DbDistributorSlotStore::new(Arc::clone(&db));
+    let mut payout_puzzle_hashes = slot_store
+        .slots(launcher_id, DistributorSlotKind::Entry)
+        .await
+        .into_iter()
+        .filter(|slot| slot.spent_height.is_none())
+        .filter_map(|slot| match slot.value {
+            DistributorSlotValue::Entry {
+                payout_puzzle_hash, ..
+            } => Some(payout_puzzle_hash),
+            _ => None,
+        })
+        .collect::<Vec<Bytes32>>();
+    payout_puzzle_hashes.sort();
+    payout_puzzle_hashes.dedup();
+    if payout_puzzle_hashes.is_empty() {
+        return Err(CliError::Custom(
+            "No indexed entries - run `reward-distributor listen` for this distributor first"
+                .to_string(),
+        ));
+    }
+
+    println!(
+        "Checking {} indexed entries against the payout threshold...",
+        payout_puzzle_hashes.len()
+    );
+    let projected = DistributorRewardState::from(&distributor).synced_to(update_time);
+    let payout_threshold = distributor.info.constants.payout_threshold;
+    let mut pending = Vec::new();
+    for payout_puzzle_hash in payout_puzzle_hashes {
+        let Some(slot) = find_entry_slots(
+            &mut ctx,
+            &client,
+            distributor.info.constants,
+            payout_puzzle_hash,
+            None,
+            None,
+        )
+        .await?
+        .into_iter()
+        .next() else {
+            continue;
+        };
+
+        if projected.entry_rewards(&slot.info.value) >= payout_threshold {
+            pending.push(slot);
+        }
+    }
+    if pending.is_empty() {
+        println!("No entry has reached the payout threshold yet.");
+        return Ok(());
+    }
+
+    println!(
+        "{} entries are above the payout threshold of {} CAT mojos.",
+        pending.len(),
+        payout_threshold
+    );
+    println!("Each bundle will use a one-sided offer containing:");
+    println!("  - 1 mojo");
+    println!("  - {} XCH ({} mojos) reserved as fees", fee_str, fee);
+    yes_no_prompt("Proceed?")?;
+
+    let sage = SageClient::new()?;
+    let mut bundle_index = 0;
+    while !pending.is_empty() {
+        bundle_index += 1;
+        let mut ctx = SpendContext::new();
+        let mut distributor =
+            sync_distributor(&client, &*db.lock().await, &mut ctx, launcher_id).await?;
+        let update_time = get_last_onchain_timestamp(&client).await?;
+        let sync_to = (distributor.info.state.round_time_info.last_update < update_time)
+            .then_some(update_time);
+
+        let count = fit_payouts(&mut ctx, &distributor, sync_to, &pending, max_cost, network)?;
+        let batch = pending.drain(..count).collect::<Vec<_>>();
+        println!(
+            "Bundle {}: {} payout(s), {} remaining after it.",
+            bundle_index,
+            batch.len(),
+            pending.len()
+        );
+
+        let offer_resp = sage
+            .make_offer(no_assets(), assets_xch_only(1), fee, None, None, false)
+            .await?;
+        println!("Offer with id {} generated.", offer_resp.offer_id);
+
+        let offer = Offer::from_spend_bundle(&mut ctx, &decode_offer(&offer_resp.offer)?)?;
+        let (security_coin_sk, security_coin) =
+            create_security_coin(&mut ctx, offer.offered_coins().xch[0])?;
+
+        let (sec_conds, payouts) = spend_payouts(&mut ctx, &mut distributor, sync_to, &batch)?;
+        let (_new_distributor, pending_sig) = distributor.finish_spend(&mut ctx, vec![])?;
+
+        let security_coin_sig = spend_security_coin(
+            &mut ctx,
+            security_coin,
+            sec_conds,
+            &security_coin_sk,
+            get_constants(network),
+        )?;
+        let spend_bundle = offer.take(SpendBundle::new(
+            ctx.take(),
+            security_coin_sig + &pending_sig,
+        ));
+
+        println!("Submitting transaction...");
+        let resp = client.push_tx(spend_bundle).await?;
+        if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
+            println!("Confirmed!");
+        }
+
+        for (payout_puzzle_hash, amount) in payouts {
+            println!(
+                "  {}: {} CAT mojos",
+                Address::new(payout_puzzle_hash, get_prefix(network)).encode()?,
+                amount
+            );
+        }
+    }
+
+    Ok(())
+}
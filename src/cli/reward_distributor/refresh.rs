@@ -12,25 +12,25 @@ use chia_wallet_sdk::{
 
 use crate::{
     assets_xch_only, confirm_pushed_transaction, curated_datastore_fields, delegated_puzzles,
-    ensure_epoch_open, find_entry_slots, find_locked_nfts, get_coinset_client, get_constants,
+    ensure_epoch_open, find_entry_slots, find_locked_nfts, get_chain_client, get_constants,
     get_last_onchain_timestamp, hex_string_to_bytes32, load_csv_matching_root,
     merkle_proof_for_nft, no_assets, parse_amount, resolve_custody, spend_datastore_oracle,
-    sync_datastore, sync_distributor, yes_no_prompt, CliError, Db, SageClient,
+    sync_datastore, sync_distributor, yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_refresh(
     launcher_id_str: String,
     csv_path: String,
     custody_address: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -73,7 +73,7 @@ pub async fn reward_distributor_refresh(
     let custody = resolve_custody(&sage, custody_address).await?;
     println!(
         "Using the following address as custody: {}",
-        Address::new(custody.puzzle_hash, crate::get_prefix(testnet11)).encode()?
+        Address::new(custody.puzzle_hash, crate::get_prefix(network)).encode()?
     );
 
     let entry_slot = find_entry_slots(
@@ -193,7 +193,7 @@ pub async fn reward_distributor_refresh(
         security_coin,
         sec_conds.reserve_fee(1),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
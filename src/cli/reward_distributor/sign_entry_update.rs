@@ -6,7 +6,7 @@ use clvmr::Allocator;
 
 use crate::{
     get_constants, hex_string_to_bytes32, multisig_sign_thing_finish, multisig_sign_thing_start,
-    CliError, Db,
+    CliError, Db, Network,
 };
 
 pub async fn reward_distributor_sign_entry_update(
@@ -15,14 +15,14 @@ pub async fn reward_distributor_sign_entry_update(
     entry_shares: u64,
     my_pubkey_str: String,
     remove_entry: bool,
-    testnet11: bool,
+    network: &Network,
     debug: bool,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let entry_payout_puzzle_hash = hex_string_to_bytes32(&entry_payout_puzzle_hash_str)?;
 
     println!("\nGetting distributor constants... ");
-    let db = Db::new(true).await?;
+    let db = Db::new(network, true).await?;
 
     let mut temp_allocator = Allocator::new();
     let distributor_constants = db
@@ -44,8 +44,7 @@ pub async fn reward_distributor_sign_entry_update(
     };
 
     let (my_pubkey, mut ctx, _client, medieval_vault) =
-        multisig_sign_thing_start(my_pubkey_str, hex::encode(manager_launcher_id), testnet11)
-            .await?;
+        multisig_sign_thing_start(my_pubkey_str, hex::encode(manager_launcher_id), network).await?;
 
     if remove_entry {
         println!("\nYou'll *REMOVE* the following entry from the reward list:");
@@ -75,7 +74,7 @@ pub async fn reward_distributor_sign_entry_update(
         launcher_id,
         medieval_vault.coin,
         &medieval_vault.info,
-        get_constants(testnet11).genesis_challenge,
+        get_constants(network).genesis_challenge,
     )
     .map_err(CliError::Driver)?;
 
@@ -84,7 +83,7 @@ pub async fn reward_distributor_sign_entry_update(
         delegated_puzzle,
         &medieval_vault,
         my_pubkey,
-        testnet11,
+        network,
         debug,
     )
     .await
@@ -2,7 +2,7 @@ use chia_bls::Signature;
 use chia_protocol::{Bytes32, Coin, SpendBundle};
 use chia_puzzle_types::{LineageProof, Memos};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin,
         spend_settlement_cats_with_payments, spend_settlement_nft_with_payment, HashedPtr, Layer,
@@ -24,11 +24,11 @@ use clvmr::NodePtr;
 
 use crate::{
     assets_xch_and_cat, assets_xch_and_nft, confirm_pushed_transaction, curated_datastore_fields,
-    delegated_puzzles, ensure_epoch_open, find_entry_slots, get_coin_public_key,
-    get_coinset_client, get_constants, get_last_onchain_timestamp, get_prefix,
-    hex_string_to_bytes32, hex_string_to_signature, load_csv_matching_root, merkle_proof_for_nft,
-    no_assets, parse_amount, resolve_custody, spend_datastore_oracle, spend_to_coin_spend,
-    sync_datastore, sync_distributor, yes_no_prompt, CliError, CustodyInfo, Db, SageClient,
+    delegated_puzzles, ensure_epoch_open, find_entry_slots, get_chain_client, get_coin_public_key,
+    get_constants, get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32,
+    hex_string_to_signature, load_csv_matching_root, merkle_proof_for_nft, no_assets, parse_amount,
+    resolve_custody, spend_datastore_oracle, spend_to_coin_spend, sync_datastore, sync_distributor,
+    yes_no_prompt, ChainClient, CliError, CustodyInfo, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_stake(
@@ -37,15 +37,15 @@ pub async fn reward_distributor_stake(
     stake_amount_str: Option<String>,
     csv_path: Option<String>,
     custody_address: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -71,7 +71,7 @@ pub async fn reward_distributor_stake(
     let custody = resolve_custody(&sage, custody_address).await?;
     println!(
         "Using the following address as custody: {}",
-        Address::new(custody.puzzle_hash, get_prefix(testnet11)).encode()?
+        Address::new(custody.puzzle_hash, get_prefix(network)).encode()?
     );
 
     let existing_slot = find_entry_slots(
@@ -109,7 +109,7 @@ pub async fn reward_distributor_stake(
                 latest_timestamp,
                 fee,
                 &fee_str,
-                testnet11,
+                network,
             )
             .await
         }
@@ -136,7 +136,7 @@ pub async fn reward_distributor_stake(
                 latest_timestamp,
                 fee,
                 &fee_str,
-                testnet11,
+                network,
             )
             .await
         }
@@ -159,7 +159,7 @@ pub async fn reward_distributor_stake(
                 latest_timestamp,
                 fee,
                 &fee_str,
-                testnet11,
+                network,
             )
             .await
         }
@@ -169,7 +169,7 @@ pub async fn reward_distributor_stake(
 
 #[allow(clippy::too_many_arguments)]
 async fn stake_nft_collection(
-    client: &CoinsetClient,
+    client: &ChainClient,
     sage: &SageClient,
     ctx: &mut SpendContext,
     distributor: RewardDistributor,
@@ -181,7 +181,7 @@ async fn stake_nft_collection(
     latest_timestamp: u64,
     fee: u64,
     fee_str: &str,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     let nft_launcher_id = Address::decode(nft_id_str)?.puzzle_hash;
 
@@ -271,14 +271,14 @@ async fn stake_nft_collection(
         existing_slot,
         also_sync,
         latest_timestamp,
-        testnet11,
+        network,
     )
     .await
 }
 
 #[allow(clippy::too_many_arguments)]
 async fn stake_curated_nft(
-    client: &CoinsetClient,
+    client: &ChainClient,
     sage: &SageClient,
     ctx: &mut SpendContext,
     mut distributor: RewardDistributor,
@@ -291,7 +291,7 @@ async fn stake_curated_nft(
     latest_timestamp: u64,
     fee: u64,
     fee_str: &str,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     let nft_launcher_id = Address::decode(nft_id_str)?.puzzle_hash;
 
@@ -382,7 +382,7 @@ async fn stake_curated_nft(
 
         let custody_pk = get_coin_public_key(
             sage,
-            &Address::new(custody_ph, get_prefix(testnet11)).encode()?,
+            &Address::new(custody_ph, get_prefix(network)).encode()?,
             10000,
         )
         .await?;
@@ -419,7 +419,7 @@ async fn stake_curated_nft(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -437,7 +437,7 @@ async fn stake_curated_nft(
 
 #[allow(clippy::too_many_arguments)]
 async fn stake_cat(
-    client: &CoinsetClient,
+    client: &ChainClient,
     sage: &SageClient,
     ctx: &mut SpendContext,
     mut distributor: RewardDistributor,
@@ -450,7 +450,7 @@ async fn stake_cat(
     latest_timestamp: u64,
     fee: u64,
     fee_str: &str,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     println!("A one-sided offer will be created. It will contain:");
     println!("  - {} stakeable CAT mojos (shares)", stake_amount_str);
@@ -525,7 +525,7 @@ async fn stake_cat(
 
         let custody_pk = get_coin_public_key(
             sage,
-            &Address::new(custody_ph, get_prefix(testnet11)).encode()?,
+            &Address::new(custody_ph, get_prefix(network)).encode()?,
             10000,
         )
         .await?;
@@ -563,7 +563,7 @@ async fn stake_cat(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -581,7 +581,7 @@ async fn stake_cat(
 
 #[allow(clippy::too_many_arguments)]
 async fn submit_nft_collection_stake(
-    client: &CoinsetClient,
+    client: &ChainClient,
     sage: &SageClient,
     ctx: &mut SpendContext,
     mut distributor: RewardDistributor,
@@ -592,7 +592,7 @@ async fn submit_nft_collection_stake(
     existing_slot: Option<Slot<RewardDistributorEntrySlotValue>>,
     also_sync: bool,
     latest_timestamp: u64,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     let offer = Offer::from_spend_bundle(ctx, &decode_offer(offer_str)?)?;
     let (security_coin_sk, security_coin) =
@@ -645,7 +645,7 @@ async fn submit_nft_collection_stake(
 
         let custody_pk = get_coin_public_key(
             sage,
-            &Address::new(custody_ph, get_prefix(testnet11)).encode()?,
+            &Address::new(custody_ph, get_prefix(network)).encode()?,
             10000,
         )
         .await?;
@@ -682,7 +682,7 @@ async fn submit_nft_collection_stake(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -8,23 +8,23 @@ use chia_wallet_sdk::{
 };
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_constants,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants,
     get_last_onchain_timestamp, hex_string_to_bytes32, no_assets, parse_amount, sync_distributor,
-    yes_no_prompt, CliError, Db, SageClient,
+    yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 pub async fn reward_distributor_sync(
     launcher_id_str: String,
     update_time: Option<u64>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
 
     let mut update_time = if let Some(update_time) = update_time {
@@ -75,7 +75,7 @@ pub async fn reward_distributor_sync(
         security_coin,
         sec_conds,
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let spend_bundle = offer.take(SpendBundle::new(
@@ -84,7 +84,7 @@ pub async fn reward_distributor_sync(
     ));
 
     println!("Submitting transaction...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
     let resp = client.push_tx(spend_bundle).await?;
 
     if confirm_pushed_transaction(&client, &resp, security_coin.coin_id(), true).await? {
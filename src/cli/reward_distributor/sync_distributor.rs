@@ -1,8 +1,10 @@
+use std::collections::HashMap;
+
 use chia_bls::Signature;
 use chia_protocol::Bytes32;
 use chia_puzzle_types::{cat::CatArgs, singleton::SingletonStruct, LineageProof};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         CatLayer, DriverError, HashedPtr, Layer, Puzzle, Reserve, RewardDistributor,
         RewardDistributorConstants, SingletonLayer, Slot, SpendContext,
@@ -16,54 +18,82 @@ use chia_wallet_sdk::{
 use clvm_utils::ToTreeHash;
 use clvmr::NodePtr;
 
-use crate::{CliError, Db};
+use crate::{ChainClient, CliError, Db, DistributorSlotValue};
+
+/// One spent distributor coin encountered while walking to the unspent tip.
+#[derive(Debug, Clone)]
+pub struct DistributorSpentTransition {
+    pub height: u32,
+    /// Distributor coin spent at `height`.
+    pub spent_coin_id: Bytes32,
+    /// Distributor after the spend (its coin is the one created at `height`).
+    pub distributor: RewardDistributor,
+    /// `(value hash, value)` of every slot the spend left behind.
+    pub created_slots: Vec<(Bytes32, DistributorSlotValue)>,
+    /// `(value hash, value)` of every pre-existing slot the spend consumed.
+    pub spent_slots: Vec<(Bytes32, DistributorSlotValue)>,
+}
 
-pub async fn sync_distributor(
-    client: &CoinsetClient,
+/// Result of a full distributor singleton walk, including each spend for indexer replay.
+#[derive(Debug, Clone)]
+pub struct DistributorSyncResult {
+    pub constants: RewardDistributorConstants,
+    /// Confirmed tip; unlike [`sync_distributor`], mempool spends are ignored.
+    pub distributor: RewardDistributor,
+    pub spent_transitions: Vec<DistributorSpentTransition>,
+}
+
+/// Cached constants, or the ones parsed from the launcher spend (then cached).
+async fn distributor_constants(
+    client: &ChainClient,
     db: &Db,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
-) -> Result<RewardDistributor, CliError> {
-    let mut constants_from_chain = false;
-    let constants = if let Some(cached_constants) = db
+) -> Result<RewardDistributorConstants, CliError> {
+    if let Some(cached_constants) = db
         .get_reward_distributor_configuration(ctx, launcher_id)
         .await?
     {
-        cached_constants
-    } else {
-        // configuration not in database, so we need to fetch the launcher
-        let launcher_coin_record = client
-            .get_coin_record_by_name(launcher_id)
-            .await?
-            .coin_record
-            .ok_or(CliError::CoinNotFound(launcher_id))?;
-        let launcher_coin_spend = client
-            .get_puzzle_and_solution(launcher_id, Some(launcher_coin_record.spent_block_index))
-            .await?
-            .coin_solution
-            .ok_or(CliError::CoinNotSpent(launcher_id))?;
+        return Ok(cached_constants);
+    }
 
-        let launcher_solution_ptr = ctx.alloc(&launcher_coin_spend.solution)?;
-        let Some((constants, _initial_state, _distributor_eve_coin)) =
-            RewardDistributor::from_launcher_solution(
-                ctx,
-                launcher_coin_spend.coin,
-                launcher_solution_ptr,
-            )?
-        else {
-            return Err(CliError::Custom(
-                "Could not parse launcher spend".to_string(),
-            ));
-        };
+    // configuration not in database, so we need to fetch the launcher
+    let launcher_coin_record = client
+        .get_coin_record_by_name(launcher_id)
+        .await?
+        .coin_record
+        .ok_or(CliError::CoinNotFound(launcher_id))?;
+    let launcher_coin_spend = client
+        .get_puzzle_and_solution(launcher_id, Some(launcher_coin_record.spent_block_index))
+        .await?
+        .coin_solution
+        .ok_or(CliError::CoinNotSpent(launcher_id))?;
 
-        constants_from_chain = true;
-        constants
+    let launcher_solution_ptr = ctx.alloc(&launcher_coin_spend.solution)?;
+    let Some((constants, _initial_state, _distributor_eve_coin)) =
+        RewardDistributor::from_launcher_solution(
+            ctx,
+            launcher_coin_spend.coin,
+            launcher_solution_ptr,
+        )?
+    else {
+        return Err(CliError::Custom(
+            "Could not parse launcher spend".to_string(),
+        ));
     };
 
-    if constants_from_chain {
-        db.save_reward_distributor_configuration(ctx, constants.launcher_id, constants)
-            .await?;
-    }
+    db.save_reward_distributor_configuration(ctx, constants.launcher_id, constants)
+        .await?;
+    Ok(constants)
+}
+
+pub async fn sync_distributor(
+    client: &ChainClient,
+    db: &Db,
+    ctx: &mut SpendContext,
+    launcher_id: Bytes32,
+) -> Result<RewardDistributor, CliError> {
+    let constants = distributor_constants(client, db, ctx, launcher_id).await?;
 
     let mut records = client
         .get_coin_records_by_hint(constants.launcher_id, None, None, Some(false), None)
@@ -112,6 +142,17 @@ pub async fn sync_distributor(
     }
 
     // Could not find distributor, so we're just after the eve spend and need to do special parsing
+    let (_eve_transition, new_distributor) =
+        distributor_after_eve(client, ctx, launcher_id).await?;
+    Ok(new_distributor)
+}
+
+/// Distributor right after its eve spend, and the reward slot the eve spend created.
+async fn distributor_after_eve(
+    client: &ChainClient,
+    ctx: &mut SpendContext,
+    launcher_id: Bytes32,
+) -> Result<(DistributorSpentTransition, RewardDistributor), CliError> {
     let launcher_coin_record = client
         .get_coin_record_by_name(launcher_id)
         .await?
@@ -145,6 +186,7 @@ pub async fn sync_distributor(
         .coin_solution
         .ok_or(CliError::CoinNotSpent(distributor_eve_coin.coin_id()))?;
 
+    // The first reserve may have been spent since launch.
     let reserve = find_reserve(
         ctx,
         client,
@@ -152,11 +194,11 @@ pub async fn sync_distributor(
         constants.reserve_asset_id,
         0,
         0,
-        false,
+        true,
     )
     .await?;
 
-    let (new_distributor, _slot) = RewardDistributor::from_eve_coin_spend(
+    let (distributor, reward_slot) = RewardDistributor::from_eve_coin_spend(
         ctx,
         constants,
         initial_state,
@@ -168,12 +210,166 @@ pub async fn sync_distributor(
         "Could not parse eve coin spend".to_string(),
     ))?;
 
-    Ok(new_distributor)
+    let reward_slot_value = reward_slot.info.value;
+    let transition = DistributorSpentTransition {
+        height: launcher_coin_record.spent_block_index,
+        spent_coin_id: distributor_eve_coin.coin_id(),
+        distributor: distributor.clone(),
+        created_slots: vec![(
+            reward_slot_value.tree_hash().into(),
+            reward_slot_value.into(),
+        )],
+        spent_slots: Vec::new(),
+    };
+    Ok((transition, distributor))
+}
+
+/// Values a spend created more often than it spent, and the reverse; a slot created
+/// and spent within the same spend never exists on chain.
+fn net_slot_values<T>(created: &[T], spent: &[T]) -> (Vec<(Bytes32, T)>, Vec<(Bytes32, T)>)
+where
+    T: ToTreeHash + Copy,
+{
+    let mut balance = HashMap::<Bytes32, i64>::new();
+    for value in created {
+        *balance.entry(value.tree_hash().into()).or_default() += 1;
+    }
+    for value in spent {
+        *balance.entry(value.tree_hash().into()).or_default() -= 1;
+    }
+
+    let mut net_created = Vec::new();
+    for value in created {
+        let hash: Bytes32 = value.tree_hash().into();
+        let count = balance.get_mut(&hash).expect("counted above");
+        if *count > 0 {
+            *count -= 1;
+            net_created.push((hash, *value));
+        }
+    }
+    let mut net_spent = Vec::new();
+    for value in spent {
+        let hash: Bytes32 = value.tree_hash().into();
+        let count = balance.get_mut(&hash).expect("counted above");
+        if *count < 0 {
+            *count += 1;
+            net_spent.push((hash, *value));
+        }
+    }
+    (net_created, net_spent)
+}
+
+/// Like [`sync_distributor`], but walks every spend from the last saved coin (or the
+/// launcher) and reports each one with the slots it created and spent.
+pub async fn sync_distributor_detailed(
+    client: &ChainClient,
+    db: &Db,
+    ctx: &mut SpendContext,
+    launcher_id: Bytes32,
+) -> Result<DistributorSyncResult, CliError> {
+    let constants = distributor_constants(client, db, ctx, launcher_id).await?;
+    let mut spent_transitions = Vec::new();
+
+    let (mut distributor, mut skip_save) = if let Some((_coin_id, parent_coin_id)) =
+        db.get_last_unspent_singleton_coin(launcher_id).await?
+    {
+        let parent_record = client
+            .get_coin_record_by_name(parent_coin_id)
+            .await?
+            .coin_record
+            .ok_or(CliError::CoinNotFound(parent_coin_id))?;
+        let parent_spend = client
+            .get_puzzle_and_solution(parent_coin_id, Some(parent_record.spent_block_index))
+            .await?
+            .coin_solution
+            .ok_or(CliError::CoinNotSpent(parent_coin_id))?;
+
+        (
+            RewardDistributor::from_parent_spend(ctx, &parent_spend, constants)?.ok_or(
+                CliError::Custom("Could not parse latest spent reward distributor".to_string()),
+            )?,
+            false,
+        )
+    } else {
+        // The eve child cannot be resumed from its parent spend, so it is only saved
+        // once spent - until then every walk starts from the launcher.
+        let (transition, distributor) = distributor_after_eve(client, ctx, launcher_id).await?;
+        spent_transitions.push(transition);
+        (distributor, true)
+    };
+
+    loop {
+        let coin_record = client
+            .get_coin_record_by_name(distributor.coin.coin_id())
+            .await?
+            .coin_record
+            .ok_or(CliError::CoinNotFound(distributor.coin.coin_id()))?;
+
+        if skip_save {
+            skip_save = false;
+        } else {
+            db.save_singleton_coin(launcher_id, coin_record).await?;
+        }
+
+        if !coin_record.spent {
+            break;
+        }
+
+        let coin_spend = client
+            .get_puzzle_and_solution(
+                coin_record.coin.coin_id(),
+                Some(coin_record.spent_block_index),
+            )
+            .await?
+            .coin_solution
+            .ok_or(CliError::CoinNotSpent(coin_record.coin.coin_id()))?;
+
+        let spent =
+            RewardDistributor::from_spend(ctx, &coin_spend, None, constants, Signature::default())?
+                .ok_or(CliError::Custom(
+                    "Could not parse reward distributor spend".to_string(),
+                ))?;
+        let pending = &spent.pending_spend;
+
+        let mut created_slots = Vec::new();
+        let mut spent_slots = Vec::new();
+        let (created, consumed) =
+            net_slot_values(&pending.created_reward_slots, &pending.spent_reward_slots);
+        created_slots.extend(created.into_iter().map(|(h, v)| (h, v.into())));
+        spent_slots.extend(consumed.into_iter().map(|(h, v)| (h, v.into())));
+        let (created, consumed) = net_slot_values(
+            &pending.created_commitment_slots,
+            &pending.spent_commitment_slots,
+        );
+        created_slots.extend(created.into_iter().map(|(h, v)| (h, v.into())));
+        spent_slots.extend(consumed.into_iter().map(|(h, v)| (h, v.into())));
+        let (created, consumed) =
+            net_slot_values(&pending.created_entry_slots, &pending.spent_entry_slots);
+        created_slots.extend(created.into_iter().map(|(h, v)| (h, v.into())));
+        spent_slots.extend(consumed.into_iter().map(|(h, v)| (h, v.into())));
+
+        distributor = RewardDistributor::from_parent_spend(ctx, &coin_spend, constants)?.ok_or(
+            CliError::Custom("Could not parse new reward distributor".to_string()),
+        )?;
+        spent_transitions.push(DistributorSpentTransition {
+            height: coin_record.spent_block_index,
+            spent_coin_id: coin_record.coin.coin_id(),
+            distributor: distributor.clone(),
+            created_slots,
+            spent_slots,
+        });
+    }
+
+    Ok(DistributorSyncResult {
+        constants,
+        distributor,
+        spent_transitions,
+    })
 }
 
 pub async fn find_reserve(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     launcher_id: Bytes32,
     asset_id: Bytes32,
     nonce: u64,
@@ -236,7 +432,7 @@ pub async fn find_reserve(
 
 pub async fn find_reward_slot(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     constants: RewardDistributorConstants,
     epoch_start: u64,
 ) -> Result<Slot<RewardDistributorRewardSlotValue>, CliError> {
@@ -336,7 +532,7 @@ pub async fn find_reward_slot(
 
 pub async fn find_commitment_slots(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     constants: RewardDistributorConstants,
     clawback_ph: Bytes32,
     epoch_start: Option<u64>,
@@ -413,7 +609,7 @@ pub async fn find_commitment_slots(
 
 pub async fn find_entry_slots(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     constants: RewardDistributorConstants,
     payout_puzzle_hash: Bytes32,
     initial_cumulative_payout: Option<u128>,
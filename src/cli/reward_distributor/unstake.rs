@@ -16,10 +16,10 @@ use chia_wallet_sdk::{
 
 use crate::{
     assets_xch_only, confirm_pushed_transaction, ensure_epoch_open, find_entry_slots,
-    find_locked_cats, find_locked_nfts, format_cat_mojos, get_coinset_client,
+    find_locked_cats, find_locked_nfts, format_cat_mojos, get_chain_client,
     get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32, hex_string_to_signature,
     no_assets, parse_amount, prompt_for_value, resolve_custody, spend_to_coin_spend,
-    sync_distributor, yes_no_prompt, CliError, Db, SageClient,
+    sync_distributor, yes_no_prompt, CliError, Db, Network, SageClient,
 };
 
 enum LockedAsset {
@@ -30,15 +30,15 @@ enum LockedAsset {
 pub async fn reward_distributor_unstake(
     launcher_id_str: String,
     custody_address: Option<String>,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let fee = parse_amount(&fee_str, false)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let mut distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -69,7 +69,7 @@ pub async fn reward_distributor_unstake(
     let custody = resolve_custody(&sage, custody_address).await?;
     println!(
         "Using the following address as custody: {}",
-        Address::new(custody.puzzle_hash, get_prefix(testnet11)).encode()?
+        Address::new(custody.puzzle_hash, get_prefix(network)).encode()?
     );
 
     println!("Getting entry slot...");
@@ -1,6 +1,6 @@
 use crate::{
-    format_cat_mojos, format_precision_amount, get_coinset_client, get_prefix,
-    hex_string_to_bytes32, sync_distributor, CliError, Db,
+    format_cat_mojos, format_precision_amount, get_chain_client, get_prefix, hex_string_to_bytes32,
+    sync_distributor, CliError, Db, Network,
 };
 use chia_wallet_sdk::{
     driver::{RewardDistributorType, SpendContext},
@@ -9,13 +9,13 @@ use chia_wallet_sdk::{
 
 pub async fn reward_distributor_view(
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     println!("Syncing reward distributor...");
-    let client = get_coinset_client(testnet11);
-    let db = Db::new(false).await?;
+    let client = get_chain_client(network)?;
+    let db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
     let distributor = sync_distributor(&client, &db, &mut ctx, launcher_id).await?;
 
@@ -92,7 +92,7 @@ pub async fn reward_distributor_view(
         "  Fee payout address: {}",
         Address::new(
             distributor.info.constants.fee_payout_puzzle_hash,
-            get_prefix(testnet11)
+            get_prefix(network)
         )
         .encode()?
     );
@@ -1,11 +1,10 @@
-use super::{ClientError, SageClient};
+use super::{ChainClient, ClientError, Network, SageClient};
 use chia_bls::{self, PublicKey, SecretKey, Signature};
 use chia_consensus::consensus_constants::ConsensusConstants;
 use chia_protocol::{Bytes, Bytes32, Coin, CoinSpend, Program, SpendBundle};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient, PushTxResponse},
+    coinset::{ChiaRpcClient, PushTxResponse},
     driver::{DriverError, Spend, SpendContext},
-    types::{MAINNET_CONSTANTS, TESTNET11_CONSTANTS},
     utils::Bech32Error,
 };
 use clvmr::error::EvalErr;
@@ -118,6 +117,9 @@ pub enum CliError {
 
     #[error("clvm eval: {0}")]
     ClvmEval(#[from] EvalErr),
+
+    #[error("unknown network: {0}")]
+    UnknownNetwork(String),
 }
 
 pub fn yes_no_prompt(prompt: &str) -> Result<(), CliError> {
@@ -179,28 +181,21 @@ pub fn parse_amount(amount: &str, is_cat: bool) -> Result<u64, CliError> {
     Ok(whole * fractional_per_whole + fractional)
 }
 
-pub fn get_prefix(testnet11: bool) -> String {
-    if testnet11 {
-        "txch".to_string()
-    } else {
-        "xch".to_string()
-    }
+/// Address a listener's HTTP API binds to: `BIND_ADDR` when it parses, else every
+/// interface on the listener's own `default_port`.
+pub fn listener_bind_addr(default_port: u16) -> std::net::SocketAddr {
+    std::env::var("BIND_ADDR")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| std::net::SocketAddr::from(([0, 0, 0, 0], default_port)))
 }
 
-pub fn get_constants(testnet11: bool) -> &'static ConsensusConstants {
-    if testnet11 {
-        &TESTNET11_CONSTANTS
-    } else {
-        &MAINNET_CONSTANTS
-    }
+pub fn get_prefix(network: &Network) -> String {
+    network.address_prefix.clone()
 }
 
-pub fn get_coinset_client(testnet11: bool) -> CoinsetClient {
-    if testnet11 {
-        CoinsetClient::testnet11()
-    } else {
-        CoinsetClient::mainnet()
-    }
+pub fn get_constants(network: &Network) -> &ConsensusConstants {
+    &network.constants
 }
 
 pub fn hex_string_to_bytes32(hex: &str) -> Result<Bytes32, CliError> {
@@ -235,7 +230,7 @@ pub fn print_spend_bundle_to_file(spend_bundle: &SpendBundle, path: &str) -> Res
 }
 
 pub async fn confirm_pushed_transaction(
-    client: &CoinsetClient,
+    client: &ChainClient,
     resp: &PushTxResponse,
     coin_id: Bytes32,
     also_wait_for_spent: bool,
@@ -261,7 +256,7 @@ pub async fn confirm_pushed_transaction(
 
 #[allow(clippy::nonminimal_bool)]
 pub async fn wait_for_coin(
-    client: &CoinsetClient,
+    client: &ChainClient,
     coin_id: Bytes32,
     also_wait_for_spent: bool,
 ) -> Result<(), CliError> {
@@ -304,7 +299,7 @@ pub async fn get_coin_public_key(
     ))
 }
 
-pub async fn get_last_onchain_timestamp(client: &CoinsetClient) -> Result<u64, CliError> {
+pub async fn get_last_onchain_timestamp(client: &ChainClient) -> Result<u64, CliError> {
     println!("Fetching latest transaction block timestamp...");
     let blockchain_state = client
         .get_blockchain_state()
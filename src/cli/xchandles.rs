@@ -11,6 +11,7 @@ mod mainnet_launch;
 mod premine_timing;
 mod quick_sync;
 mod register;
+mod register_builder;
 mod sign_state_update;
 mod sync;
 mod unroll_state_scheduler;
@@ -31,6 +32,7 @@ pub use mainnet_launch::*;
 pub use premine_timing::*;
 pub use quick_sync::*;
 pub use register::*;
+pub use register_builder::*;
 pub use sign_state_update::*;
 pub use sync::*;
 pub use unroll_state_scheduler::*;
@@ -10,7 +10,7 @@ use clvmr::NodePtr;
 
 use crate::{
     get_constants, multisig_broadcast_thing_finish, multisig_broadcast_thing_start,
-    sync_show_changes_and_compute_new_state, CliError,
+    sync_show_changes_and_compute_new_state, CliError, Network,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -24,15 +24,16 @@ pub async fn xchandles_broadcast_state_update(
     registration_period: Option<u64>,
     multisig_launcher_id_str: String,
     signatures_str: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let (signature_from_signers, pubkeys, client, mut ctx, medieval_vault) =
-        multisig_broadcast_thing_start(signatures_str, multisig_launcher_id_str, testnet11).await?;
+        multisig_broadcast_thing_start(signatures_str, multisig_launcher_id_str, network).await?;
 
     let (new_state, mut registry) = sync_show_changes_and_compute_new_state(
         &mut ctx,
         &client,
+        network,
         registry_launcher_id_str,
         new_payment_asset_id_str,
         new_payment_cat_base_price_str,
@@ -43,7 +44,7 @@ pub async fn xchandles_broadcast_state_update(
     )
     .await?;
 
-    let constants = get_constants(testnet11);
+    let constants = get_constants(network);
     let medieval_vault_coin_id = medieval_vault.coin.coin_id();
     let medieval_vault_inner_ph = medieval_vault.info.inner_puzzle_hash();
 
@@ -82,7 +83,7 @@ pub async fn xchandles_broadcast_state_update(
         &mut ctx,
         signature_from_signers + &pending_sig,
         fee_str,
-        testnet11,
+        network,
         medieval_vault_coin_id,
         None,
     )
@@ -11,7 +11,7 @@ use chia_puzzle_types::{
 };
 use chia_puzzles::SINGLETON_LAUNCHER_HASH;
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin, spend_settlement_cats, CatLayer,
         CatalogPrecommitValue, Launcher, Layer, Nft, NftInfo, Offer, PrecommitCoin, PrecommitLayer,
@@ -23,7 +23,7 @@ use chia_wallet_sdk::{
             HandleNftMetadata, XchandlesFactorPricingPuzzleArgs, XchandlesPricingSolution,
             ANY_METADATA_UPDATER_HASH,
         },
-        Conditions, Mod, MAINNET_CONSTANTS, TESTNET11_CONSTANTS,
+        Conditions, Mod,
     },
     utils::Address,
 };
@@ -33,9 +33,10 @@ use clvmr::{serde::node_from_bytes, Allocator, NodePtr};
 
 use crate::{
     assert_batch_csv_expirations, assets_xch_and_cat, assets_xch_only, confirm_pushed_transaction,
-    get_prefix, hex_string_to_bytes32, hex_string_to_pubkey, hex_string_to_signature,
-    load_xchandles_launch_csv, no_assets, parse_amount, sync_xchandles, unix_now_secs,
-    yes_no_prompt, CliError, Db, SageClient, XchandlesLaunchRecord,
+    get_chain_client, get_constants, get_prefix, hex_string_to_bytes32, hex_string_to_pubkey,
+    hex_string_to_signature, load_xchandles_launch_csv, no_assets, parse_amount, sync_xchandles,
+    unix_now_secs, yes_no_prompt, ChainClient, CliError, Db, Network, SageClient,
+    XchandlesLaunchRecord,
 };
 
 fn precommit_value_for_handle(
@@ -67,12 +68,8 @@ fn precommit_value_for_handle(
 }
 
 #[allow(clippy::cast_precision_loss)]
-fn print_spend_bundle_cost(sb: &SpendBundle, testnet11: bool) -> Result<(), CliError> {
-    let constants = if testnet11 {
-        &*TESTNET11_CONSTANTS
-    } else {
-        &*MAINNET_CONSTANTS
-    };
+fn print_spend_bundle_cost(sb: &SpendBundle, network: &Network) -> Result<(), CliError> {
+    let constants = get_constants(network);
     let conds = get_conditions_from_spendbundle(
         &mut Allocator::new(),
         sb,
@@ -106,7 +103,7 @@ pub fn metadata_for_handle_nft(handle_info: &XchandlesLaunchRecord) -> HandleNft
 #[allow(clippy::too_many_arguments)]
 async fn eve_nft_for_handle(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     registry_launcher_id: Bytes32,
     handle: &XchandlesLaunchRecord,
     royalty_puzzle_hash: Bytes32,
@@ -215,13 +212,13 @@ pub async fn xchandles_continue_launch(
     handles_per_spend: usize,
     premine: String,
     registration_period: u64,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
     yes: bool,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
     let royalty_puzzle_hash = Address::decode(&royalty_address)?.puzzle_hash;
-    if !testnet11 {
+    if !network.is_testnet() {
         if royalty_puzzle_hash != crate::ROYALTY_PUZZLE_HASH
             || royalty_basis_points != crate::ROYALTY_BASIS_POINTS
             || royalty_address != crate::ROYALTY_ADDRESS
@@ -250,14 +247,10 @@ pub async fn xchandles_continue_launch(
     );
 
     println!("Initializing Chia RPC client...");
-    let client = if testnet11 {
-        CoinsetClient::testnet11()
-    } else {
-        CoinsetClient::mainnet()
-    };
+    let client = get_chain_client(network)?;
 
     println!("Opening database...");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
 
     println!("Syncing XCHandles registry...");
@@ -350,7 +343,7 @@ pub async fn xchandles_continue_launch(
                 println!(
                     "  handle: {:}, recipient: {:}, expiration: {}, image_uris: {:?}",
                     handles_to_launch[j].handle,
-                    Address::new(handles_to_launch[j].recipient, get_prefix(testnet11)).encode()?,
+                    Address::new(handles_to_launch[j].recipient, get_prefix(network)).encode()?,
                     handles_to_launch[j].expiration,
                     handles_to_launch[j].image_uris.join("|")
                 );
@@ -536,11 +529,7 @@ pub async fn xchandles_continue_launch(
                 security_coin,
                 security_coin_conditions,
                 &security_coin_sk,
-                if testnet11 {
-                    &TESTNET11_CONSTANTS
-                } else {
-                    &MAINNET_CONSTANTS
-                },
+                get_constants(network),
             )?;
 
             // Spend CAT
@@ -562,7 +551,7 @@ pub async fn xchandles_continue_launch(
 
             // Build spend bundle
             let sb = offer.take(SpendBundle::new(ctx.take(), security_coin_sig));
-            print_spend_bundle_cost(&sb, testnet11)?;
+            print_spend_bundle_cost(&sb, network)?;
 
             println!("Submitting transaction...");
             let resp = client.push_tx(sb).await?;
@@ -599,7 +588,7 @@ pub async fn xchandles_continue_launch(
         println!(
             "  handle: {:}, recipient: {:}, expiration: {}, buy_time: {}, n: {}, image_uris: {:?}",
             handle.handle,
-            Address::new(handle.recipient, get_prefix(testnet11)).encode()?,
+            Address::new(handle.recipient, get_prefix(network)).encode()?,
             handle.expiration,
             buy_time,
             n,
@@ -932,18 +921,14 @@ pub async fn xchandles_continue_launch(
         security_coin,
         security_coin_conditions,
         &security_coin_sk,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(
         ctx.take(),
         security_coin_sig + &pending_sig + &nft_sig,
     ));
-    print_spend_bundle_cost(&sb, testnet11)?;
+    print_spend_bundle_cost(&sb, network)?;
 
     println!("Submitting transaction...");
     let resp = client.push_tx(sb).await?;
@@ -15,9 +15,9 @@ use clvm_utils::ToTreeHash;
 
 use crate::{
     assets_xch_only, confirm_pushed_transaction, fetch_nft_from_wallet, find_xchandles_update_slot,
-    get_coinset_client, get_constants, hex_string_to_bytes32, no_assets, parse_amount,
+    get_chain_client, get_constants, hex_string_to_bytes32, no_assets, parse_amount,
     quick_sync_xchandles, recreate_nft_in_wallet, sync_xchandles, yes_no_prompt, CliError, Db,
-    SageClient, XchandlesApiClient,
+    Network, SageClient, XchandlesApiClient,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -25,7 +25,7 @@ pub async fn xchandles_execute_update(
     launcher_id_str: String,
     handle: String,
     new_nft: String,
-    testnet11: bool,
+    network: &Network,
     local: bool,
     fee_str: String,
 ) -> Result<(), CliError> {
@@ -35,11 +35,11 @@ pub async fn xchandles_execute_update(
     let handle_hash = handle.tree_hash().into();
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let sage = SageClient::new()?;
 
     print!("First, let's sync the registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut registry = if local {
         sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?
     } else {
@@ -63,7 +63,7 @@ pub async fn xchandles_execute_update(
         .await?
         .ok_or(CliError::SlotNotFound("Handle"))?
     } else {
-        XchandlesApiClient::get(testnet11)
+        XchandlesApiClient::get(network)
             .get_slot_value(launcher_id, handle.tree_hash().into())
             .await?
     };
@@ -176,7 +176,7 @@ pub async fn xchandles_execute_update(
             .assert_concurrent_spend(current_owner_coin_id)
             .assert_concurrent_spend(new_owner_coin_id),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let (_new_registry, pending_sig) = registry.finish_spend(&mut ctx)?;
@@ -22,10 +22,10 @@ use clvm_utils::ToTreeHash;
 use clvmr::{serde::node_from_bytes, NodePtr};
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, fetch_nft_from_wallet, get_coinset_client,
+    assets_xch_only, confirm_pushed_transaction, fetch_nft_from_wallet, get_chain_client,
     get_constants, get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32, no_assets,
     parse_amount, quick_sync_xchandles, recreate_nft_in_wallet, sync_xchandles, wait_for_coin,
-    yes_no_prompt, CliError, Db, SageClient, XchandlesApiClient,
+    yes_no_prompt, CliError, Db, Network, SageClient, XchandlesApiClient,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -38,7 +38,7 @@ pub async fn xchandles_expire(
     secret: Option<String>,
     expire_time: Option<u64>,
     refund: bool,
-    testnet11: bool,
+    network: &Network,
     payment_asset_id_str: String,
     payment_cat_base_price_str: String,
     registration_period: u64,
@@ -56,11 +56,11 @@ pub async fn xchandles_expire(
     let fee = parse_amount(&fee_str, false)?;
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let sage = SageClient::new()?;
 
     print!("First, let's sync the registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut registry = if local {
         sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?
     } else {
@@ -96,7 +96,7 @@ pub async fn xchandles_expire(
         .await?
         .ok_or(CliError::SlotNotFound("Handle"))?
     } else {
-        let xchandles_api_client = XchandlesApiClient::get(testnet11);
+        let xchandles_api_client = XchandlesApiClient::get(network);
         xchandles_api_client
             .get_slot_value(launcher_id, handle.tree_hash().into())
             .await?
@@ -397,7 +397,7 @@ pub async fn xchandles_expire(
             security_coin,
             sec_conds,
             &security_coin_sk,
-            get_constants(testnet11),
+            get_constants(network),
         )?;
 
         let sb = offer.take(SpendBundle::new(
@@ -438,7 +438,7 @@ pub async fn xchandles_expire(
     yes_no_prompt("Continue with registration?")?;
 
     let precommit_coin_address =
-        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(testnet11)).encode()?;
+        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(network)).encode()?;
     let send_resp = sage
         .send_cat(
             hex::encode(payment_asset_id),
@@ -13,9 +13,9 @@ use chia_wallet_sdk::{
 use clvm_utils::ToTreeHash;
 
 use crate::{
-    assets_xch_and_cat, confirm_pushed_transaction, get_coinset_client, get_constants,
+    assets_xch_and_cat, confirm_pushed_transaction, get_chain_client, get_constants,
     get_last_onchain_timestamp, hex_string_to_bytes32, no_assets, parse_amount,
-    quick_sync_xchandles, sync_xchandles, yes_no_prompt, CliError, Db, SageClient,
+    quick_sync_xchandles, sync_xchandles, yes_no_prompt, CliError, Db, Network, SageClient,
     XchandlesApiClient,
 };
 
@@ -24,7 +24,7 @@ pub async fn xchandles_extend(
     launcher_id_str: String,
     handle: String,
     num_periods: u64,
-    testnet11: bool,
+    network: &Network,
     payment_asset_id_str: String,
     payment_cat_base_price_str: String,
     registration_period: u64,
@@ -37,11 +37,11 @@ pub async fn xchandles_extend(
     let fee = parse_amount(&fee_str, false)?;
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let sage = SageClient::new()?;
 
     print!("First, let's sync the registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut registry = if local {
         sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?
     } else {
@@ -89,7 +89,7 @@ pub async fn xchandles_extend(
         .await?
         .ok_or(CliError::SlotNotFound("Handle"))?
     } else {
-        let xchandles_api_client = XchandlesApiClient::get(testnet11);
+        let xchandles_api_client = XchandlesApiClient::get(network);
         xchandles_api_client
             .get_slot_value(launcher_id, handle.tree_hash().into())
             .await?
@@ -147,7 +147,7 @@ pub async fn xchandles_extend(
         security_coin,
         sec_conds.extend(payment_assertion),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(
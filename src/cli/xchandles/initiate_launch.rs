@@ -4,10 +4,10 @@ use crate::{
         utils::{yes_no_prompt, CliError},
         Db,
     },
-    confirm_pushed_transaction, controller_matches_configured, get_coinset_client, get_prefix,
-    load_xchandles_launch_csv, load_xchandles_state_schedule_csv, no_assets, parse_amount,
-    premine_buy_time, print_medieval_vault_configuration, schedule_records_match_configured,
-    unix_now_secs, SageClient, REGISTRATION_PERIOD,
+    confirm_pushed_transaction, controller_matches_configured, get_chain_client, get_constants,
+    get_prefix, load_xchandles_launch_csv, load_xchandles_state_schedule_csv, no_assets,
+    parse_amount, premine_buy_time, print_medieval_vault_configuration,
+    schedule_records_match_configured, unix_now_secs, Network, SageClient, REGISTRATION_PERIOD,
 };
 use chia_bls::PublicKey;
 use chia_protocol::{Bytes32, Coin, SpendBundle};
@@ -19,10 +19,7 @@ use chia_wallet_sdk::{
         MedievalVaultInfo, Offer, SingletonInfo, SpendContext, StateSchedulerInfo,
         XchandlesConstants, XchandlesRegistryState,
     },
-    types::{
-        conditions::RunCatTail, puzzles::XchandlesFactorPricingPuzzleArgs, Conditions,
-        MAINNET_CONSTANTS, TESTNET11_CONSTANTS,
-    },
+    types::{conditions::RunCatTail, puzzles::XchandlesFactorPricingPuzzleArgs, Conditions},
     utils::Address,
 };
 use clvm_utils::ToTreeHash;
@@ -116,7 +113,7 @@ pub async fn xchandles_initiate_launch(
     relative_block_height: u32,
     registration_period: u64,
     premine: String,
-    testnet11: bool,
+    network: &Network,
     fee_str: String,
 ) -> Result<(), CliError> {
     let payout_info = Address::decode(&payout_address)?;
@@ -135,14 +132,14 @@ pub async fn xchandles_initiate_launch(
         pubkeys.push(pubkey);
     }
 
-    if !testnet11 && !controller_matches_configured(m, &pubkeys)? {
+    if !network.is_testnet() && !controller_matches_configured(m, &pubkeys)? {
         return Err(CliError::Custom(
             "Mainnet post-schedule controller must be the ordered configured 6-of-10 validator key set"
                 .to_string(),
         ));
     }
 
-    if !testnet11 && registration_period != REGISTRATION_PERIOD {
+    if !network.is_testnet() && registration_period != REGISTRATION_PERIOD {
         return Err(CliError::Custom(format!(
             "Mainnet registration period must be exactly {REGISTRATION_PERIOD} seconds"
         )));
@@ -152,9 +149,9 @@ pub async fn xchandles_initiate_launch(
 
     println!("First things first, this multisig will have control over the price singleton once the state schedule is over:");
     print_medieval_vault_configuration(m, &pubkeys)?;
-    println!("  Testnet: {}", testnet11);
+    println!("  Network: {}", network.name);
 
-    let price_schedule_csv_filename = if testnet11 {
+    let price_schedule_csv_filename = if network.is_testnet() {
         "xchandles_price_schedule_testnet11.csv"
     } else {
         "xchandles_price_schedule_mainnet.csv"
@@ -165,7 +162,7 @@ pub async fn xchandles_initiate_launch(
     );
 
     let price_schedule = load_xchandles_state_schedule_csv(price_schedule_csv_filename)?;
-    if !testnet11 && !schedule_records_match_configured(&price_schedule) {
+    if !network.is_testnet() && !schedule_records_match_configured(&price_schedule) {
         return Err(CliError::Custom(
             "Mainnet price schedule CSV does not match typed launch configuration".to_string(),
         ));
@@ -191,7 +188,7 @@ pub async fn xchandles_initiate_launch(
         println!(
             "  handle: {:}, recipient: {:}, expiration: {}, buy_time: {}, n: {}, image_uris: {:?}",
             record.handle,
-            Address::new(record.recipient, get_prefix(testnet11)).encode()?,
+            Address::new(record.recipient, get_prefix(network)).encode()?,
             record.expiration,
             buy_time,
             n,
@@ -202,10 +199,10 @@ pub async fn xchandles_initiate_launch(
     yes_no_prompt("Is all the data above correct?")?;
 
     println!("Initializing RPC client...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
 
     println!("Opening database...");
-    let db = Db::new(false).await?;
+    let db = Db::new(network, false).await?;
 
     let constants = XchandlesConstants {
         launcher_id: Bytes32::default(),
@@ -214,7 +211,7 @@ pub async fn xchandles_initiate_launch(
         price_singleton_launcher_id: Bytes32::default(),
     };
 
-    let prefix = get_prefix(testnet11);
+    let prefix = get_prefix(network);
     if prefix != payout_info.prefix {
         return Err(CliError::Custom(format!(
             "Wrong prefix in payout address: expected {}, got {}",
@@ -283,11 +280,7 @@ pub async fn xchandles_initiate_launch(
         1,
         registration_period,
         get_additional_info_for_launch,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
         (
             constants,
             price_schedule
@@ -14,9 +14,9 @@ use chia_wallet_sdk::{
 use clvm_utils::ToTreeHash;
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, fetch_nft_from_wallet, get_coinset_client,
+    assets_xch_only, confirm_pushed_transaction, fetch_nft_from_wallet, get_chain_client,
     get_constants, hex_string_to_bytes32, no_assets, parse_amount, quick_sync_xchandles,
-    recreate_nft_in_wallet, sync_xchandles, yes_no_prompt, CliError, Db, SageClient,
+    recreate_nft_in_wallet, sync_xchandles, yes_no_prompt, CliError, Db, Network, SageClient,
     XchandlesApiClient,
 };
 
@@ -26,7 +26,7 @@ pub async fn xchandles_initiate_update(
     handle: String,
     new_nft: String,
     min_height: Option<u32>,
-    testnet11: bool,
+    network: &Network,
     local: bool,
     fee_str: String,
 ) -> Result<(), CliError> {
@@ -35,11 +35,11 @@ pub async fn xchandles_initiate_update(
     let fee = parse_amount(&fee_str, false)?;
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let sage = SageClient::new()?;
 
     print!("First, let's sync the registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut registry = if local {
         sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?
     } else {
@@ -63,7 +63,7 @@ pub async fn xchandles_initiate_update(
         .await?
         .ok_or(CliError::SlotNotFound("Handle"))?
     } else {
-        XchandlesApiClient::get(testnet11)
+        XchandlesApiClient::get(network)
             .get_slot_value(launcher_id, handle.tree_hash().into())
             .await?
     };
@@ -143,7 +143,7 @@ pub async fn xchandles_initiate_update(
         security_coin,
         Conditions::new().assert_concurrent_spend(nft_coin_id),
         &security_coin_sk,
-        get_constants(testnet11),
+        get_constants(network),
     )?;
 
     let sb = offer.take(SpendBundle::new(
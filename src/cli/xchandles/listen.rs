@@ -1,14 +1,18 @@
 use std::collections::VecDeque;
-use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use axum::debug_handler;
 use axum::extract::{Query, State};
 use axum::http::Method;
-use axum::{http::StatusCode, routing::get, Json, Router};
+use axum::middleware;
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
 use chia_protocol::{Bytes32, CoinSpend};
-use chia_wallet_sdk::coinset::{ChiaRpcClient, CoinsetClient};
+use chia_wallet_sdk::coinset::ChiaRpcClient;
 use chia_wallet_sdk::driver::{SpendContext, XchandlesActionLog, XchandlesRegistry};
 use chia_wallet_sdk::types::puzzles::{XchandlesHandleSlotValue, XchandlesSlotNonce};
 use clvm_utils::ToTreeHash;
@@ -16,30 +20,31 @@ use clvmr::Allocator;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use tower_http::cors::{Any, CorsLayer};
 
 use super::listener::{
     committed_base_from_pricing_puzzle, effective_base_at, generations_for_network,
-    listener_router, DbHandleSlotStore, DbPendingUpdateStore, DbRegistrationStore,
-    DbSingletonStore, FollowRecordStatus, FreshnessState, HandleSlotStore, ListenerApiState,
-    PendingUpdateStore, RegistrationStore, RegistryPricing, ScheduleGeneration, SingletonIndexer,
-    SingletonStore, SlotParentLineage,
+    listener_router, parse_launcher_id, require_fresh, ApiError, DbHandleHistoryStore,
+    DbHandleSlotStore, DbPendingUpdateStore, DbRegistrationStore, DbSingletonStore,
+    FollowRecordStatus, FreshnessState, HandleEventFeed, HandleHistoryStore, HandleSlotStore,
+    ListenerApiState, PendingUpdateStore, RegistrationStore, RegistryPricing, RegistryTip,
+    ScheduleGeneration, SingletonIndexer, SingletonStore, SlotParentLineage,
 };
+use super::register_builder::{post_register_precommit, post_register_spend};
 use crate::{
-    get_coinset_client, get_last_onchain_timestamp, hex_string_to_bytes32, sync_xchandles,
-    sync_xchandles_detailed, CliError, CoinsetWebSocketMessage, Db, XchandlesSpentTransition,
-    REGISTRATION_PERIOD,
+    get_chain_client, get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32,
+    listener_bind_addr, record_http_metrics, sync_xchandles_detailed, ChainClient, ChainSource,
+    CliError, Db, ListenerMetrics, Network, REGISTRATION_PERIOD,
 };
 
 /// Latest schedule row whose timestamp is `<= now`. Before the first row, launch price is 1.
-fn schedule_base_price_at(now: u64, testnet11: bool) -> u64 {
-    effective_base_at(&generations_for_network(testnet11), now)
+fn schedule_base_price_at(now: u64, network: &Network) -> u64 {
+    effective_base_at(&generations_for_network(network), now)
 }
 
-fn pricing_at(now: u64, testnet11: bool) -> RegistryPricing {
+fn pricing_at(now: u64, network: &Network) -> RegistryPricing {
     RegistryPricing {
-        base_price: schedule_base_price_at(now, testnet11),
+        base_price: schedule_base_price_at(now, network),
         registration_period: REGISTRATION_PERIOD,
     }
 }
@@ -51,9 +56,9 @@ async fn set_schedule_pricing(
     registries: &[chia_wallet_sdk::driver::XchandlesRegistry],
     schedule: &[ScheduleGeneration],
     now: u64,
-    testnet11: bool,
+    network: &Network,
 ) {
-    let pricing = pricing_at(now, testnet11);
+    let pricing = pricing_at(now, network);
     let effective = pricing.base_price;
     {
         let mut map = registry_pricing.write().await;
@@ -74,6 +79,29 @@ async fn set_schedule_pricing(
     }
 }
 
+/// Publish each registry's synced tip for `/readyz` and `/status`, and its
+/// state for the register spend builder.
+async fn set_registry_tips(
+    registry_tips: &RwLock<std::collections::HashMap<Bytes32, RegistryTip>>,
+    registry_snapshots: &RwLock<std::collections::HashMap<Bytes32, XchandlesRegistry>>,
+    launcher_ids: &[Bytes32],
+    registries: &[XchandlesRegistry],
+    indexed_height: u32,
+) {
+    let mut tips = registry_tips.write().await;
+    let mut snapshots = registry_snapshots.write().await;
+    for (id, registry) in launcher_ids.iter().zip(registries) {
+        tips.insert(
+            *id,
+            RegistryTip {
+                coin_id: registry.coin.coin_id(),
+                indexed_height,
+            },
+        );
+        snapshots.insert(*id, registry.clone());
+    }
+}
+
 fn registry_period_or_default(registry: &chia_wallet_sdk::driver::XchandlesRegistry) -> u64 {
     let _ = registry;
     REGISTRATION_PERIOD
@@ -111,76 +139,113 @@ pub struct XchandlesNeighborsResponse {
     #[serde(alias = "right_parent_parent_info")]
     pub right_parent_parent_id: String,
     pub right_parent_inner_puzzle_hash: String,
+
+    /// Peak the proofs were read at; absent from listeners that predate it.
+    #[serde(default)]
+    pub indexed_peak_height: Option<u32>,
 }
 
 #[derive(Clone)]
-struct AppState {
-    db: Arc<futures::lock::Mutex<Db>>,
+pub(crate) struct AppState {
+    pub(crate) db: Arc<futures::lock::Mutex<Db>>,
+    /// Freshness gate and follow set shared with the public listener routes.
+    pub(crate) api: ListenerApiState,
+    /// Unspent registry each register spend is built on, replaced with the tips.
+    pub(crate) registries: Arc<RwLock<std::collections::HashMap<Bytes32, XchandlesRegistry>>>,
 }
 
-fn bind_addr() -> SocketAddr {
-    std::env::var("BIND_ADDR")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 8080)))
-}
+/// Port `xchandles listen` serves on when `BIND_ADDR` is unset.
+pub const XCHANDLES_LISTEN_DEFAULT_PORT: u16 = 8080;
 
-pub async fn xchandles_listen(launcher_ids: String, testnet11: bool) -> Result<(), CliError> {
-    let db = Db::new(false).await?;
+pub async fn xchandles_listen(
+    launcher_ids: Option<String>,
+    network: &Network,
+) -> Result<(), CliError> {
+    let db = Db::new(network, false).await?;
+    // API reads get their own handle so they never see or wait on an open transition.
+    let api_db = Arc::new(futures::lock::Mutex::new(db.reader()));
     let db = Arc::new(futures::lock::Mutex::new(db));
 
-    let launcher_ids = launcher_ids
-        .split(',')
-        .map(hex_string_to_bytes32)
-        .collect::<Result<Vec<Bytes32>, CliError>>()?;
+    let launcher_ids = match launcher_ids {
+        Some(launcher_ids) => launcher_ids
+            .split(',')
+            .map(hex_string_to_bytes32)
+            .collect::<Result<Vec<Bytes32>, CliError>>()?,
+        None if !network.xchandles_launcher_ids.is_empty() => {
+            network.xchandles_launcher_ids.clone()
+        }
+        None => {
+            return Err(CliError::Custom(format!(
+                "--launcher-ids is required: network '{}' has no default XCHandles launcher ids",
+                network.name
+            )))
+        }
+    };
 
     let singleton_store: Arc<dyn SingletonStore> = DbSingletonStore::new(Arc::clone(&db));
     let handle_slots: Arc<dyn HandleSlotStore> = DbHandleSlotStore::new(Arc::clone(&db));
     let registrations: Arc<dyn RegistrationStore> = DbRegistrationStore::new(Arc::clone(&db));
     let pending_updates: Arc<dyn PendingUpdateStore> = DbPendingUpdateStore::new(Arc::clone(&db));
+    let handle_history: Arc<dyn HandleHistoryStore> = DbHandleHistoryStore::new(Arc::clone(&db));
     let freshness = Arc::new(RwLock::new(FreshnessState::fresh_at(
         0,
         FreshnessState::now_unix(),
     )));
-    let indexer = Arc::new(SingletonIndexer::new(
-        Arc::clone(&singleton_store),
-        Arc::clone(&handle_slots),
-        Arc::clone(&registrations),
-        Arc::clone(&pending_updates),
-        Arc::clone(&freshness),
-    ));
+    let metrics = ListenerMetrics::shared("xchandles");
+    let events = HandleEventFeed::shared();
+    let indexer = Arc::new(
+        SingletonIndexer::new(
+            Arc::clone(&singleton_store),
+            Arc::clone(&handle_slots),
+            Arc::clone(&registrations),
+            Arc::clone(&pending_updates),
+            Arc::clone(&freshness),
+        )
+        .with_metrics(Arc::clone(&metrics))
+        .with_events(Arc::clone(&events))
+        .with_handle_history(Arc::clone(&handle_history)),
+    );
 
     rustls::crypto::ring::default_provider()
         .install_default()
         .expect("Failed to install rustls crypto provider");
 
     let mut initial_pricing = std::collections::HashMap::new();
-    let startup_pricing = pricing_at(FreshnessState::now_unix(), testnet11);
+    let startup_pricing = pricing_at(FreshnessState::now_unix(), network);
     for id in &launcher_ids {
         initial_pricing.insert(*id, startup_pricing);
     }
     let registry_pricing = Arc::new(RwLock::new(initial_pricing));
-    let price_schedule = Arc::new(generations_for_network(testnet11));
+    let price_schedule = Arc::new(generations_for_network(network));
     let mut initial_committed = std::collections::HashMap::new();
     for id in &launcher_ids {
         initial_committed.insert(*id, startup_pricing.base_price);
     }
     let committed_base_price = Arc::new(RwLock::new(initial_committed));
+    let registry_tips = Arc::new(RwLock::new(std::collections::HashMap::new()));
+    let registry_snapshots = Arc::new(RwLock::new(std::collections::HashMap::new()));
 
     let api_state = ListenerApiState {
-        store: Arc::clone(&singleton_store),
-        handle_slots: Arc::clone(&handle_slots),
-        registrations: Arc::clone(&registrations),
-        pending_updates: Arc::clone(&pending_updates),
+        store: DbSingletonStore::new(Arc::clone(&api_db)),
+        handle_slots: DbHandleSlotStore::new(Arc::clone(&api_db)),
+        registrations: DbRegistrationStore::new(Arc::clone(&api_db)),
+        pending_updates: DbPendingUpdateStore::new(Arc::clone(&api_db)),
         freshness: Arc::clone(&freshness),
         registry_pricing: Arc::clone(&registry_pricing),
         price_schedule: Arc::clone(&price_schedule),
         committed_base_price: Arc::clone(&committed_base_price),
+        registry_tips: Arc::clone(&registry_tips),
         registry_launcher_ids: launcher_ids.clone(),
         now_unix_override: None,
+        metrics: Arc::clone(&metrics),
+        address_prefix: get_prefix(network),
+        events: Arc::clone(&events),
+        handle_history: DbHandleHistoryStore::new(Arc::clone(&api_db)),
     };
     let neighbors_state = AppState {
-        db: Arc::clone(&db),
+        db: api_db,
+        api: api_state.clone(),
+        registries: Arc::clone(&registry_snapshots),
     };
 
     // Mark the index as resyncing before the HTTP server binds so persisted slots
@@ -195,18 +260,22 @@ pub async fn xchandles_listen(launcher_ids: String, testnet11: bool) -> Result<(
 
     loop {
         match connect_websocket(
-            testnet11,
+            network,
             Arc::clone(&db),
             launcher_ids.clone(),
             Arc::clone(&indexer),
             Arc::clone(&registry_pricing),
             Arc::clone(&committed_base_price),
             Arc::clone(&price_schedule),
+            Arc::clone(&registry_tips),
+            Arc::clone(&registry_snapshots),
         )
         .await
         {
             Ok(_resp) => (),
             Err(e) => {
+                indexer.begin_resync().await;
+                metrics.websocket_reconnects.inc();
                 println!("WebSocket error: {}", e);
                 println!("Reconnecting in 5 seconds...");
                 tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
@@ -222,16 +291,22 @@ async fn start_api_server(
     let neighbors = Router::new()
         .route("/", get(health_check))
         .route("/neighbors", get(get_neighbors))
+        .route("/register/precommit", post(post_register_precommit))
+        .route("/register/spend", post(post_register_spend))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&listener_state.metrics),
+            record_http_metrics,
+        ))
         .with_state(neighbors_state);
 
     let app = listener_router(listener_state).merge(neighbors).layer(
         CorsLayer::new()
             .allow_origin(Any)
-            .allow_methods([Method::GET, Method::HEAD, Method::OPTIONS])
+            .allow_methods([Method::GET, Method::HEAD, Method::POST, Method::OPTIONS])
             .allow_headers(Any),
     );
 
-    let addr = bind_addr();
+    let addr = listener_bind_addr(XCHANDLES_LISTEN_DEFAULT_PORT);
     println!("API server listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
@@ -248,9 +323,15 @@ async fn health_check() -> StatusCode {
 async fn get_neighbors(
     Query(params): Query<XchandlesNeighborsQuery>,
     State(state): State<AppState>,
-) -> Result<Json<XchandlesNeighborsResponse>, CliError> {
-    let handle_hash = hex_string_to_bytes32(&params.handle_hash)?;
-    let launcher_id = hex_string_to_bytes32(&params.launcher_id)?;
+) -> Result<Json<XchandlesNeighborsResponse>, ApiError> {
+    let (indexed_peak_height, _) = require_fresh(&state.api).await?;
+    let launcher_id =
+        parse_launcher_id(&params.launcher_id).ok_or_else(ApiError::invalid_launcher_id)?;
+    if !state.api.registry_launcher_ids.contains(&launcher_id) {
+        return Err(ApiError::registry_not_followed());
+    }
+    let handle_hash =
+        parse_launcher_id(&params.handle_hash).ok_or_else(ApiError::invalid_handle_hash)?;
 
     let mut allocator = Allocator::new();
 
@@ -263,7 +344,8 @@ async fn get_neighbors(
             handle_hash,
         )
         .await
-    }?;
+    }
+    .map_err(|e| neighbors_lookup_error(e, ApiError::handle_not_found))?;
 
     let response = XchandlesNeighborsResponse {
         left_handle_hash: hex::encode(left.info.value.handle_hash.to_bytes()),
@@ -288,28 +370,50 @@ async fn get_neighbors(
         right_parent_inner_puzzle_hash: hex::encode(
             right.proof.parent_inner_puzzle_hash.to_bytes(),
         ),
+
+        indexed_peak_height: Some(indexed_peak_height),
     };
 
     Ok(Json(response))
 }
 
-async fn block_spends_at_height(
-    client: &CoinsetClient,
+/// No bracketing slot pair is a miss for the caller; anything else is a storage failure.
+pub(crate) fn neighbors_lookup_error(error: CliError, not_found: fn() -> ApiError) -> ApiError {
+    match error {
+        CliError::DbColumnNotFound() => not_found(),
+        other => {
+            eprintln!("neighbors lookup error: {other}");
+            ApiError::storage_error()
+        }
+    }
+}
+
+pub(crate) async fn block_spends_at_height(
+    chain: &dyn ChainSource,
+    metrics: &ListenerMetrics,
     height: u32,
 ) -> Result<Vec<CoinSpend>, CliError> {
-    let header_hash = client
-        .get_block_record_by_height(height)
+    Ok(block_spends_and_timestamp_at_height(chain, metrics, height)
         .await?
-        .block_record
-        .map(|r| r.header_hash);
-    if let Some(hh) = header_hash {
-        Ok(client
-            .get_block_spends(hh)
-            .await?
-            .block_spends
-            .unwrap_or_default())
+        .0)
+}
+
+/// Block spends plus the block's timestamp (set on transaction blocks only).
+pub(crate) async fn block_spends_and_timestamp_at_height(
+    chain: &dyn ChainSource,
+    metrics: &ListenerMetrics,
+    height: u32,
+) -> Result<(Vec<CoinSpend>, Option<u64>), CliError> {
+    let record = metrics
+        .observe_rpc("get_block_record_by_height", chain.block_record_at(height))
+        .await?;
+    if let Some(record) = record {
+        let spends = metrics
+            .observe_rpc("get_block_spends", chain.block_spends(record.header_hash))
+            .await?;
+        Ok((spends, record.timestamp))
     } else {
-        Ok(Vec::new())
+        Ok((Vec::new(), None))
     }
 }
 
@@ -352,9 +456,13 @@ async fn parent_lineage_by_created_slot_hash(
 
 #[derive(Debug, Clone)]
 struct RegistryIndexedTransition {
+    /// Spent registry coin; tells apart several spends confirmed in one block.
+    coin_id: Bytes32,
     height: u32,
     logs: Vec<XchandlesActionLog>,
     block_spends: Vec<CoinSpend>,
+    /// Block timestamp; `None` when the confirming block record carried none.
+    timestamp: Option<u64>,
     parent_by_value_hash: std::collections::HashMap<Bytes32, SlotParentLineage>,
 }
 
@@ -367,6 +475,8 @@ struct RegistrySyncBatch {
 #[async_trait::async_trait]
 trait RegistryChainSource: Send + Sync {
     async fn sync_registry(&self, launcher_id: Bytes32) -> Result<RegistrySyncBatch, CliError>;
+    /// Forget saved registry spends at or above `height` so the next sync walks them again.
+    async fn rewind_registry(&self, launcher_id: Bytes32, height: u32) -> Result<(), CliError>;
 }
 
 #[async_trait::async_trait]
@@ -377,30 +487,83 @@ trait BlockSpendSource: Send + Sync {
     ) -> Result<Option<(Bytes32, Vec<CoinSpend>)>, CliError>;
 }
 
-struct CoinsetBlockSource<'a> {
-    client: &'a CoinsetClient,
+/// Groups every write of one indexed transition into a single commit.
+#[async_trait::async_trait]
+pub(crate) trait TransitionScope: Send + Sync {
+    async fn begin(&self) -> Result<(), CliError>;
+    async fn commit(&self) -> Result<(), CliError>;
+    async fn abort(&self);
+}
+
+/// Production scope: one SQLite transaction on the shared `Db`, commit latency reported.
+pub(crate) struct DbTransitionScope<'a> {
+    pub(crate) db: &'a Arc<futures::lock::Mutex<Db>>,
+    pub(crate) metrics: &'a ListenerMetrics,
+}
+
+#[async_trait::async_trait]
+impl TransitionScope for DbTransitionScope<'_> {
+    async fn begin(&self) -> Result<(), CliError> {
+        self.db.lock().await.begin_transaction().await
+    }
+
+    async fn commit(&self) -> Result<(), CliError> {
+        let started = Instant::now();
+        let result = self.db.lock().await.commit_transaction().await;
+        self.metrics
+            .observe_sqlite_write("transition_commit", started.elapsed());
+        result
+    }
+
+    async fn abort(&self) {
+        if let Err(e) = self.db.lock().await.rollback_transaction().await {
+            eprintln!("transition rollback error: {e}");
+        }
+    }
+}
+
+/// Commit when `result` is Ok; otherwise discard every write since `begin`.
+pub(crate) async fn finish_transition<T>(
+    scope: &dyn TransitionScope,
+    result: Result<T, CliError>,
+) -> Result<T, CliError> {
+    match result {
+        Ok(value) => {
+            scope.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            scope.abort().await;
+            Err(e)
+        }
+    }
+}
+
+struct ChainBlockSource<'a> {
+    chain: &'a dyn ChainSource,
+    metrics: &'a ListenerMetrics,
 }
 
 #[async_trait::async_trait]
-impl BlockSpendSource for CoinsetBlockSource<'_> {
+impl BlockSpendSource for ChainBlockSource<'_> {
     async fn block_at_height(
         &self,
         height: u32,
     ) -> Result<Option<(Bytes32, Vec<CoinSpend>)>, CliError> {
         let Some(rec) = self
-            .client
-            .get_block_record_by_height(height)
+            .metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                self.chain.block_record_at(height),
+            )
             .await?
-            .block_record
         else {
             return Ok(None);
         };
         let spends = self
-            .client
-            .get_block_spends(rec.header_hash)
-            .await?
-            .block_spends
-            .unwrap_or_default();
+            .metrics
+            .observe_rpc("get_block_spends", self.chain.block_spends(rec.header_hash))
+            .await?;
         Ok(Some((rec.header_hash, spends)))
     }
 }
@@ -408,9 +571,10 @@ impl BlockSpendSource for CoinsetBlockSource<'_> {
 /// Follow spent current coins in every canonical block after `after_height` through `tip`.
 ///
 /// Returns `(height, header_hash)` for each height that had a block record, in order.
-/// Missing records are skipped so the next peak can retry them.
+/// Missing records are skipped so the next peak can retry them. Each block commits on its own.
 async fn follow_blocks_after(
     indexer: &SingletonIndexer,
+    scope: &dyn TransitionScope,
     source: &dyn BlockSpendSource,
     after_height: u32,
     tip: u32,
@@ -425,21 +589,22 @@ async fn follow_blocks_after(
             continue;
         };
         let mut allocator = Allocator::new();
-        if let Err(e) = indexer.on_block(&mut allocator, height, &spends).await {
-            eprintln!("singleton follow error: {e}");
-        }
+        scope.begin().await?;
+        let result = indexer.on_block(&mut allocator, height, &spends).await;
+        finish_transition(scope, result).await?;
         followed.push((height, header_hash));
     }
     Ok(followed)
 }
 
-struct CoinsetRegistryChainSource<'a> {
-    client: &'a CoinsetClient,
+struct RpcRegistryChainSource<'a> {
+    client: &'a ChainClient,
     db: &'a Arc<futures::lock::Mutex<Db>>,
+    metrics: &'a ListenerMetrics,
 }
 
 #[async_trait::async_trait]
-impl RegistryChainSource for CoinsetRegistryChainSource<'_> {
+impl RegistryChainSource for RpcRegistryChainSource<'_> {
     async fn sync_registry(&self, launcher_id: Bytes32) -> Result<RegistrySyncBatch, CliError> {
         let synced = {
             let mut ctx = SpendContext::new();
@@ -449,13 +614,17 @@ impl RegistryChainSource for CoinsetRegistryChainSource<'_> {
 
         let mut transitions = Vec::with_capacity(synced.spent_transitions.len());
         for transition in synced.spent_transitions {
-            let block_spends = block_spends_at_height(self.client, transition.height).await?;
+            let (block_spends, timestamp) =
+                block_spends_and_timestamp_at_height(self.client, self.metrics, transition.height)
+                    .await?;
             let parent_by_value_hash =
                 parent_lineage_by_created_slot_hash(self.db, launcher_id, &transition.logs).await;
             transitions.push(RegistryIndexedTransition {
+                coin_id: transition.coin_id,
                 height: transition.height,
                 logs: transition.logs,
                 block_spends,
+                timestamp,
                 parent_by_value_hash,
             });
         }
@@ -465,41 +634,120 @@ impl RegistryChainSource for CoinsetRegistryChainSource<'_> {
             transitions,
         })
     }
+
+    async fn rewind_registry(&self, launcher_id: Bytes32, height: u32) -> Result<(), CliError> {
+        self.db
+            .lock()
+            .await
+            .rewind_singleton_to_before(launcher_id, height)
+            .await
+    }
 }
 
+/// Same indexer path for live, catch-up and post-reorg transitions: discover NFTs in the block,
+/// project slots/registrations/pending, then follow any spent current coins.
 async fn apply_registry_transition(
     indexer: &SingletonIndexer,
     launcher_id: Bytes32,
-    height: u32,
-    logs: &[XchandlesActionLog],
-    block_spends: &[CoinSpend],
-    parent_by_value_hash: &std::collections::HashMap<Bytes32, SlotParentLineage>,
-) {
+    transition: &RegistryIndexedTransition,
+    first_log_index: u32,
+) -> Result<(), CliError> {
+    let RegistryIndexedTransition {
+        coin_id,
+        height,
+        ref logs,
+        ref block_spends,
+        timestamp,
+        ref parent_by_value_hash,
+    } = *transition;
     let mut allocator = Allocator::new();
-    if let Err(e) = indexer
+    indexer
         .on_registry_transition(&mut allocator, height, block_spends, logs)
-        .await
-    {
-        eprintln!("singleton discovery error: {e}");
-    }
+        .await?;
     indexer
         .project_handle_slots_from_logs(launcher_id, height, logs, |value_hash| {
             parent_by_value_hash.get(&value_hash).copied()
         })
-        .await;
+        .await?;
     indexer
         .project_registrations_from_logs(launcher_id, height, logs)
-        .await;
+        .await?;
+    indexer
+        .project_handle_history_from_logs(
+            launcher_id,
+            height,
+            first_log_index,
+            logs,
+            |value_hash| parent_by_value_hash.get(&value_hash).copied(),
+        )
+        .await?;
     indexer
         .project_pending_updates_from_logs(launcher_id, height, logs)
-        .await;
-    if let Err(e) = indexer.on_block(&mut allocator, height, block_spends).await {
-        eprintln!("singleton follow error: {e}");
+        .await?;
+    if let Some(timestamp) = timestamp {
+        indexer
+            .project_activity_from_logs(launcher_id, coin_id, height, timestamp, logs)
+            .await?;
+    }
+    indexer.on_block(&mut allocator, height, block_spends).await
+}
+
+/// Actions one registry transition indexed, held back from `/events` until its commit.
+struct PendingAnnouncement {
+    launcher_id: Bytes32,
+    height: u32,
+    logs: Vec<XchandlesActionLog>,
+}
+
+/// Publish what a committed transition indexed; aborted ones are simply dropped.
+async fn announce_committed(indexer: &SingletonIndexer, announcements: Vec<PendingAnnouncement>) {
+    for announcement in announcements {
+        indexer
+            .announce_actions_from_logs(
+                announcement.launcher_id,
+                announcement.height,
+                &announcement.logs,
+            )
+            .await;
+    }
+}
+
+/// Walk one registry to its tip and index every spent transition at or above `from_height`,
+/// in chain order, so no intermediate spend is skipped.
+async fn sync_and_index_registry(
+    source: &dyn RegistryChainSource,
+    indexer: &SingletonIndexer,
+    launcher_id: Bytes32,
+    from_height: u32,
+) -> Result<(XchandlesRegistry, Vec<PendingAnnouncement>), CliError> {
+    let synced = source.sync_registry(launcher_id).await?;
+    let mut announcements = Vec::new();
+    // Log positions run on across every spend confirmed in the same block.
+    let mut next_log_index = (0, 0);
+    for transition in synced.transitions {
+        if transition.height < from_height {
+            continue;
+        }
+        if next_log_index.0 != transition.height {
+            next_log_index = (transition.height, 0);
+        }
+        let first_log_index = next_log_index.1;
+        next_log_index.1 += transition.logs.len() as u32;
+        apply_registry_transition(indexer, launcher_id, &transition, first_log_index).await?;
+        announcements.push(PendingAnnouncement {
+            launcher_id,
+            height: transition.height,
+            logs: transition.logs,
+        });
     }
+    Ok((synced.registry, announcements))
 }
 
+/// Sync and index every spent registry; each registry's walk and projections commit together,
+/// so a failure leaves both the saved tip and `registries` at the previous state.
 async fn process_spent_registry_records(
     source: &dyn RegistryChainSource,
+    scope: &dyn TransitionScope,
     indexer: &SingletonIndexer,
     registries: &mut [XchandlesRegistry],
     coin_records: Vec<chia_wallet_sdk::coinset::CoinRecord>,
@@ -541,53 +789,59 @@ async fn process_spent_registry_records(
             "Latest registry #{} coin was spent at height {}... ",
             i, coin_record.spent_block_index
         );
-        let synced = source.sync_registry(launcher_id).await?;
-        for transition in &synced.transitions {
-            apply_registry_transition(
-                indexer,
-                launcher_id,
-                transition.height,
-                &transition.logs,
-                &transition.block_spends,
-                &transition.parent_by_value_hash,
-            )
-            .await;
-        }
-        *registry = synced.registry;
+        scope.begin().await?;
+        let result = sync_and_index_registry(source, indexer, launcher_id, 0).await;
+        let (synced, announcements) = finish_transition(scope, result).await?;
+        *registry = synced;
+        announce_committed(indexer, announcements).await;
         println!("synced :)");
     }
     Ok(())
 }
 
-/// Same indexer path as a live registry spend: discover NFTs in the block,
-/// project slots/registrations/pending, then follow any spent current coins.
-async fn index_registry_transition(
-    db: &Arc<futures::lock::Mutex<Db>>,
+/// Pre-final reorganization: restore projections confirmed before `from_height`, rewind each
+/// registry's saved tip, then replay every transition from the fork point in one commit.
+async fn resync_after_reorg(
+    source: &dyn RegistryChainSource,
+    scope: &dyn TransitionScope,
     indexer: &SingletonIndexer,
-    launcher_id: Bytes32,
-    height: u32,
-    logs: &[XchandlesActionLog],
-    block_spends: &[CoinSpend],
-) {
-    let parent_by_value_hash = parent_lineage_by_created_slot_hash(db, launcher_id, logs).await;
-    apply_registry_transition(
-        indexer,
-        launcher_id,
-        height,
-        logs,
-        block_spends,
-        &parent_by_value_hash,
-    )
+    launcher_ids: &[Bytes32],
+    from_height: u32,
+) -> Result<Vec<XchandlesRegistry>, CliError> {
+    scope.begin().await?;
+    let result = async {
+        indexer.rollback(from_height).await?;
+        let mut registries = Vec::with_capacity(launcher_ids.len());
+        let mut announcements = Vec::new();
+        for launcher_id in launcher_ids {
+            source.rewind_registry(*launcher_id, from_height).await?;
+            let (registry, replayed) =
+                sync_and_index_registry(source, indexer, *launcher_id, from_height).await?;
+            registries.push(registry);
+            announcements.extend(replayed);
+        }
+        Ok((registries, announcements))
+    }
     .await;
+    let (registries, announcements) = finish_transition(scope, result).await?;
+    announce_committed(indexer, announcements).await;
+    Ok(registries)
 }
 
+/// Number of recent `(height, header_hash)` pairs kept for reorg detection.
+pub(crate) const HEADER_CHECKPOINT_WINDOW: usize = 32;
+
+/// `header_checkpoints.listener` key for this listener's window.
+pub const XCHANDLES_CHECKPOINT_LISTENER: &str = "xchandles";
+
 /// Full-node `get_coin_records_by_names` request cap.
 const COINSET_NAMES_BATCH: usize = 500;
 
 /// Walk every followed NFT already in the store (including ones persisted
 /// before this restart) until a full pass finds no spent current coins.
 async fn catch_up_followed_nfts(
-    client: &CoinsetClient,
+    client: &ChainClient,
+    scope: &dyn TransitionScope,
     indexer: &SingletonIndexer,
 ) -> Result<(), CliError> {
     loop {
@@ -611,8 +865,12 @@ async fn catch_up_followed_nfts(
 
         let mut spent = Vec::new();
         for batch in coin_ids.chunks(COINSET_NAMES_BATCH) {
-            let Some(records) = client
-                .get_coin_records_by_names(batch.to_vec(), None, None, Some(true), None)
+            let Some(records) = indexer
+                .metrics
+                .observe_rpc(
+                    "get_coin_records_by_names",
+                    client.get_coin_records_by_names(batch.to_vec(), None, None, Some(true), None),
+                )
                 .await?
                 .coin_records
             else {
@@ -628,20 +886,23 @@ async fn catch_up_followed_nfts(
 
         for coin_record in &spent {
             let coin_id = coin_record.coin.coin_id();
-            let Some(spend) = client
-                .get_puzzle_and_solution(coin_id, Some(coin_record.spent_block_index))
+            let Some(spend) = indexer
+                .metrics
+                .observe_rpc(
+                    "get_puzzle_and_solution",
+                    client.get_puzzle_and_solution(coin_id, Some(coin_record.spent_block_index)),
+                )
                 .await?
                 .coin_solution
             else {
                 return Err(CliError::CoinNotSpent(coin_id));
             };
             let mut allocator = Allocator::new();
-            if let Err(e) = indexer
+            scope.begin().await?;
+            let result = indexer
                 .on_block(&mut allocator, coin_record.spent_block_index, &[spend])
-                .await
-            {
-                eprintln!("singleton follow error: {e}");
-            }
+                .await;
+            finish_transition(scope, result).await?;
         }
         eprintln!(
             "[xchandles-listen] followed {} subsequent NFT spend(s); rechecking unspent coins",
@@ -650,39 +911,100 @@ async fn catch_up_followed_nfts(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn connect_websocket(
-    testnet11: bool,
+    network: &Network,
     db: Arc<futures::lock::Mutex<Db>>,
     launcher_ids: Vec<Bytes32>,
     indexer: Arc<SingletonIndexer>,
     registry_pricing: Arc<RwLock<std::collections::HashMap<Bytes32, RegistryPricing>>>,
     committed_base_price: Arc<RwLock<std::collections::HashMap<Bytes32, u64>>>,
     price_schedule: Arc<Vec<ScheduleGeneration>>,
+    registry_tips: Arc<RwLock<std::collections::HashMap<Bytes32, RegistryTip>>>,
+    registry_snapshots: Arc<RwLock<std::collections::HashMap<Bytes32, XchandlesRegistry>>>,
 ) -> Result<(), CliError> {
     println!("Syncing XCHandles registries (initial)...");
-    let client = get_coinset_client(testnet11);
+    let client = get_chain_client(network)?;
 
-    let mut registries = Vec::<XchandlesRegistry>::new();
-    let mut spent_by_launcher: Vec<(Bytes32, Vec<XchandlesSpentTransition>)> = Vec::new();
-    for launcher_id in &launcher_ids {
-        eprintln!(
-            "[xchandles-listen] initial sync {}",
-            hex::encode(launcher_id)
-        );
-        let synced = {
-            let mut db = db.lock().await;
-            let mut ctx = SpendContext::new();
+    // Replay spent registry transitions (full history on first sync, only new
+    // spends when resuming a saved tip) with the same discover+project+follow
+    // path as live peaks. The walk and its replay commit together so a crash
+    // never leaves a saved tip ahead of its projections.
+    let metrics = indexer.metrics.as_ref();
+    let scope = DbTransitionScope { db: &db, metrics };
+    let source = RpcRegistryChainSource {
+        client: &client,
+        db: &db,
+        metrics,
+    };
 
-            sync_xchandles_detailed(&client, &mut db, &mut ctx, *launcher_id).await?
-        };
+    // Checkpoints persisted by the previous run are checked against upstream
+    // first: a reorg that happened while we were down rolls the index back to
+    // the fork point and replays from there instead of resuming a stale tip.
+    let upstream_peak = metrics
+        .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+        .await?
+        .blockchain_state
+        .map(|s| s.peak.height)
+        .ok_or_else(|| CliError::Custom("no blockchain state at startup".to_string()))?;
+    indexer.note_upstream_peak(upstream_peak).await;
+    let mut checkpoints = db
+        .lock()
+        .await
+        .header_checkpoints(XCHANDLES_CHECKPOINT_LISTENER)
+        .await?;
+    let mut upstream_hashes = std::collections::HashMap::new();
+    for &(height, _) in checkpoints.iter().filter(|(h, _)| *h <= upstream_peak) {
+        if let Some(rec) = metrics
+            .observe_rpc(
+                "get_block_record_by_height",
+                client.get_block_record_by_height(height),
+            )
+            .await?
+            .block_record
+        {
+            upstream_hashes.insert(height, rec.header_hash);
+        }
+    }
+    let fork = checkpoint_fork_point(&checkpoints, upstream_peak, |h| {
+        upstream_hashes.get(&h).copied()
+    });
+
+    let mut registries = Vec::<XchandlesRegistry>::new();
+    if let Some(from_height) = fork {
         eprintln!(
-            "[xchandles-listen] initial sync {} done, tip coin {}, {} spent transition(s)",
-            hex::encode(launcher_id),
-            hex::encode(synced.registry.coin.coin_id()),
-            synced.spent_transitions.len()
+            "[xchandles-listen] chain reorg while offline: rolling back from height {from_height}"
         );
-        spent_by_launcher.push((*launcher_id, synced.spent_transitions));
-        registries.push(synced.registry);
+        registries = resync_after_reorg(
+            &source,
+            &scope,
+            indexer.as_ref(),
+            &launcher_ids,
+            from_height,
+        )
+        .await?;
+        checkpoints.retain(|(h, _)| *h < from_height);
+        db.lock()
+            .await
+            .delete_header_checkpoints_from(XCHANDLES_CHECKPOINT_LISTENER, from_height)
+            .await?;
+    } else {
+        for launcher_id in &launcher_ids {
+            eprintln!(
+                "[xchandles-listen] initial sync {}",
+                hex::encode(launcher_id)
+            );
+            scope.begin().await?;
+            let result = sync_and_index_registry(&source, indexer.as_ref(), *launcher_id, 0).await;
+            let (registry, announcements) = finish_transition(&scope, result).await?;
+            announce_committed(indexer.as_ref(), announcements).await;
+            eprintln!(
+                "[xchandles-listen] initial sync {} done, tip coin {}",
+                hex::encode(launcher_id),
+                hex::encode(registry.coin.coin_id()),
+            );
+            registries.push(registry);
+        }
     }
 
     let onchain_ts = get_last_onchain_timestamp(&client).await?;
@@ -693,27 +1015,17 @@ async fn connect_websocket(
         &registries,
         &price_schedule,
         onchain_ts,
-        testnet11,
+        network,
+    )
+    .await;
+    set_registry_tips(
+        &registry_tips,
+        &registry_snapshots,
+        &launcher_ids,
+        &registries,
+        upstream_peak,
     )
     .await;
-
-    // Replay spent registry transitions (full history on first sync, only new
-    // spends when resuming a saved tip) with the same discover+project+follow
-    // path as live peaks.
-    for (launcher_id, transitions) in &spent_by_launcher {
-        for transition in transitions {
-            let block_spends = block_spends_at_height(&client, transition.height).await?;
-            index_registry_transition(
-                &db,
-                indexer.as_ref(),
-                *launcher_id,
-                transition.height,
-                &transition.logs,
-                &block_spends,
-            )
-            .await;
-        }
-    }
 
     // Fallback for indexed slots that were never in a replayed action log
     // (upgrade from a sync-only DB). Load slots first, then project -
@@ -758,20 +1070,26 @@ async fn connect_websocket(
         }
     }
     let mut fallback = 0usize;
-    for (launcher_id, handle_hash, value, parent) in persisted_slots {
-        let already = indexer
-            .handle_slots
-            .get(launcher_id, handle_hash)
-            .await
-            .and_then(|r| r.current);
-        if already.is_some() {
-            continue;
+    scope.begin().await?;
+    let result = async {
+        for (launcher_id, handle_hash, value, parent) in persisted_slots {
+            let already = indexer
+                .handle_slots
+                .get(launcher_id, handle_hash)
+                .await
+                .and_then(|r| r.current);
+            if already.is_some() {
+                continue;
+            }
+            fallback += 1;
+            indexer
+                .project_handle_slot(launcher_id, value, parent, 0)
+                .await?;
         }
-        fallback += 1;
-        indexer
-            .project_handle_slot(launcher_id, value, parent, 0)
-            .await;
+        Ok::<_, CliError>(())
     }
+    .await;
+    finish_transition(&scope, result).await?;
     if fallback > 0 {
         eprintln!("[xchandles-listen] projected {fallback} persisted handle slot(s) without logs");
     }
@@ -780,252 +1098,278 @@ async fn connect_websocket(
         "[xchandles-listen] catching up {} followed NFT singleton(s)",
         indexer.store.all_launcher_ids().await.len()
     );
-    catch_up_followed_nfts(&client, indexer.as_ref()).await?;
+    catch_up_followed_nfts(&client, &scope, indexer.as_ref()).await?;
 
     // Seed the live follow cursor at the current tip so reconnects walk only
     // new blocks (catch-up above already walked spent coins via coin records).
-    // recent_peaks is an in-memory window of (height, header_hash) used only
-    // to detect reorgs; it is not persisted.
-    let mut last_followed_height = client
-        .get_blockchain_state()
+    // recent_peaks is the (height, header_hash) window used to detect reorgs;
+    // it starts from the checkpoints validated above and is persisted after
+    // every peak so the next run can check it.
+    let mut last_followed_height = metrics
+        .observe_rpc("get_blockchain_state", client.get_blockchain_state())
         .await?
         .blockchain_state
         .map(|s| s.peak.height)
         .ok_or_else(|| CliError::Custom("no blockchain state after NFT catch-up".to_string()))?;
-    let mut recent_peaks: VecDeque<(u32, Bytes32)> = VecDeque::new();
-    if let Some(rec) = client
-        .get_block_record_by_height(last_followed_height)
+    let mut recent_peaks: VecDeque<(u32, Bytes32)> = checkpoints
+        .into_iter()
+        .filter(|(h, _)| *h < last_followed_height)
+        .collect();
+    if let Some(rec) = metrics
+        .observe_rpc(
+            "get_block_record_by_height",
+            client.get_block_record_by_height(last_followed_height),
+        )
         .await?
         .block_record
     {
         recent_peaks.push_back((last_followed_height, rec.header_hash));
     }
-
-    let ws_url = format!("{}/ws", client.base_url().replace("https://", "wss://"));
-    println!("Connecting to WebSocket at {}", ws_url);
-
-    let (ws_stream, _) = connect_async(ws_url)
+    while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+        recent_peaks.pop_front();
+    }
+    db.lock()
         .await
-        .map_err(|e| CliError::Custom(format!("Failed to connect: {}", e)))?;
-
-    println!("WebSocket connected");
+        .save_header_checkpoints(
+            XCHANDLES_CHECKPOINT_LISTENER,
+            recent_peaks.make_contiguous(),
+        )
+        .await?;
 
-    let (mut _write, mut read) = ws_stream.split();
+    let mut peaks = client.peak_notifications().await?;
     let mut last_clear_time = SystemTime::now();
 
-    while let Some(message) = read.next().await {
-        match message {
-            Ok(Message::Text(text)) => match serde_json::from_str::<CoinsetWebSocketMessage>(&text)
+    while peaks.next().await.is_some() {
+        let now = SystemTime::now();
+        let now_unix = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        println!("[{}] Received new peak", now_unix);
+
+        let blockchain_state = metrics
+            .observe_rpc("get_blockchain_state", client.get_blockchain_state())
+            .await?
+            .blockchain_state;
+        let upstream_peak = blockchain_state
+            .as_ref()
+            .map(|s| s.peak.height)
+            .unwrap_or(0);
+        let tip_height = blockchain_state.as_ref().map(|s| s.peak.height);
+        indexer.note_upstream_peak(upstream_peak).await;
+        let mut confirmed_timestamp = blockchain_state
+            .as_ref()
+            .and_then(|s| s.peak.timestamp)
+            .unwrap_or(0);
+        let mut tip_rec = None;
+
+        if let Some(tip) = tip_height {
+            if let Some(rec) = metrics
+                .observe_rpc(
+                    "get_block_record_by_height",
+                    client.get_block_record_by_height(tip),
+                )
+                .await?
+                .block_record
             {
-                Ok(msg) => {
-                    if msg.message_type() == "peak" {
-                        let now = SystemTime::now();
-                        let now_unix = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
-                        println!("[{}] Received new peak", now_unix);
-
-                        let blockchain_state =
-                            client.get_blockchain_state().await?.blockchain_state;
-                        let upstream_peak = blockchain_state
-                            .as_ref()
-                            .map(|s| s.peak.height)
-                            .unwrap_or(0);
-                        let tip_height = blockchain_state.as_ref().map(|s| s.peak.height);
-                        let mut confirmed_timestamp = blockchain_state
-                            .as_ref()
-                            .and_then(|s| s.peak.timestamp)
-                            .unwrap_or(0);
-                        let mut tip_rec = None;
-
-                        if let Some(tip) = tip_height {
-                            if let Some(rec) =
-                                client.get_block_record_by_height(tip).await?.block_record
-                            {
-                                if let Some(ts) = rec.timestamp {
-                                    confirmed_timestamp = ts;
-                                } else if confirmed_timestamp == 0 {
-                                    // Walk back like get_last_onchain_timestamp when tip is non-tx.
-                                    let mut height = tip.saturating_sub(1);
-                                    while height > 0 && confirmed_timestamp == 0 {
-                                        if let Some(br) = client
-                                            .get_block_record_by_height(height)
-                                            .await?
-                                            .block_record
-                                        {
-                                            if let Some(ts) = br.timestamp {
-                                                confirmed_timestamp = ts;
-                                                break;
-                                            }
-                                        }
-                                        height = height.saturating_sub(1);
-                                    }
-                                }
-
-                                let needs_hash_at = match recent_peaks.back() {
-                                    Some(&(last_height, last_hash)) => {
-                                        tip != last_height.saturating_add(1)
-                                            || rec.prev_hash != last_hash
-                                    }
-                                    None => false,
-                                };
-                                let mut hash_at_cache = std::collections::HashMap::new();
-                                if needs_hash_at {
-                                    for &(h, _) in &recent_peaks {
-                                        if hash_at_cache.contains_key(&h) {
-                                            continue;
-                                        }
-                                        if let Some(br) =
-                                            client.get_block_record_by_height(h).await?.block_record
-                                        {
-                                            hash_at_cache.insert(h, br.header_hash);
-                                        }
-                                    }
-                                }
-                                if let Some(from_height) = reorg_rollback_from(
-                                    recent_peaks.make_contiguous(),
-                                    tip,
-                                    rec.prev_hash,
-                                    |h| hash_at_cache.get(&h).copied(),
-                                ) {
-                                    eprintln!(
-                                        "chain reorg: rolling back from height {from_height}"
-                                    );
-                                    indexer.rollback(from_height).await;
-                                    recent_peaks.retain(|(h, _)| *h < from_height);
-                                    last_followed_height = recent_peaks
-                                        .back()
-                                        .map(|(h, _)| *h)
-                                        .unwrap_or(from_height.saturating_sub(1));
-                                    for (i, launcher_id) in launcher_ids.iter().enumerate() {
-                                        let registry = {
-                                            let mut ctx = SpendContext::new();
-                                            let mut db = db.lock().await;
-                                            sync_xchandles(&client, &mut db, &mut ctx, *launcher_id)
-                                                .await?
-                                        };
-                                        registries[i] = registry;
-                                    }
-                                }
-
-                                tip_rec = Some(rec);
+                if let Some(ts) = rec.timestamp {
+                    confirmed_timestamp = ts;
+                } else if confirmed_timestamp == 0 {
+                    // Walk back like get_last_onchain_timestamp when tip is non-tx.
+                    let mut height = tip.saturating_sub(1);
+                    while height > 0 && confirmed_timestamp == 0 {
+                        if let Some(br) = metrics
+                            .observe_rpc(
+                                "get_block_record_by_height",
+                                client.get_block_record_by_height(height),
+                            )
+                            .await?
+                            .block_record
+                        {
+                            if let Some(ts) = br.timestamp {
+                                confirmed_timestamp = ts;
+                                break;
                             }
                         }
+                        height = height.saturating_sub(1);
+                    }
+                }
 
-                        let coin_resp = client
-                            .get_coin_records_by_names(
-                                registries.iter().map(|r| r.coin.coin_id()).collect(),
-                                None,
-                                None,
-                                Some(true),
-                                None,
-                            )
-                            .await?;
-
-                        let coin_records = coin_resp.coin_records.ok_or(CliError::Custom(
-                            "Weird - coin records not found after peak update.".to_string(),
-                        ))?;
-                        let source = CoinsetRegistryChainSource {
-                            client: &client,
-                            db: &db,
-                        };
-                        process_spent_registry_records(
-                            &source,
-                            indexer.as_ref(),
-                            &mut registries,
-                            coin_records,
-                        )
-                        .await?;
-
-                        // Follow singleton spends in every block from the last
-                        // followed height through the current tip.
-                        if let Some(tip) = tip_height {
-                            let source = CoinsetBlockSource { client: &client };
-                            match follow_blocks_after(
-                                indexer.as_ref(),
-                                &source,
-                                last_followed_height,
-                                tip,
-                            )
-                            .await
-                            {
-                                Ok(followed) => {
-                                    for (height, header_hash) in followed {
-                                        recent_peaks.retain(|(h, _)| *h < height);
-                                        recent_peaks.push_back((height, header_hash));
-                                        last_followed_height = height;
-                                    }
-                                    while recent_peaks.len() > 32 {
-                                        recent_peaks.pop_front();
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("singleton follow error: {e}");
-                                }
-                            }
-                            if let Some(rec) = &tip_rec {
-                                if recent_peaks.back().map(|(h, _)| *h) != Some(tip) {
-                                    recent_peaks.retain(|(h, _)| *h < tip);
-                                    recent_peaks.push_back((tip, rec.header_hash));
-                                    while recent_peaks.len() > 32 {
-                                        recent_peaks.pop_front();
-                                    }
-                                }
-                            }
+                let needs_hash_at = match recent_peaks.back() {
+                    Some(&(last_height, last_hash)) => {
+                        tip != last_height.saturating_add(1) || rec.prev_hash != last_hash
+                    }
+                    None => false,
+                };
+                let mut hash_at_cache = std::collections::HashMap::new();
+                if needs_hash_at {
+                    for &(h, _) in &recent_peaks {
+                        if hash_at_cache.contains_key(&h) {
+                            continue;
                         }
-                        if let Some(tip) = tip_height {
-                            set_schedule_pricing(
-                                &registry_pricing,
-                                &committed_base_price,
-                                &launcher_ids,
-                                &registries,
-                                &price_schedule,
-                                confirmed_timestamp,
-                                testnet11,
+                        if let Some(br) = metrics
+                            .observe_rpc(
+                                "get_block_record_by_height",
+                                client.get_block_record_by_height(h),
                             )
-                            .await;
-                            indexer
-                                .note_peak(
-                                    tip,
-                                    upstream_peak.max(tip),
-                                    now_unix,
-                                    confirmed_timestamp,
-                                )
-                                .await;
-                        }
-
-                        if last_clear_time.elapsed().unwrap().as_secs() > 60 * 30 {
-                            if let Some(tip) = tip_height {
-                                print!("Clearing cache (every 30m)... ");
-                                let cutoff = tip.saturating_sub(128);
-                                {
-                                    let db = db.lock().await;
-                                    db.delete_slots_spent_before(cutoff).await?;
-                                    db.delete_singleton_coins_spent_before(cutoff).await?;
-                                }
-                                println!("done :)");
-                                last_clear_time = now;
-                            }
+                            .await?
+                            .block_record
+                        {
+                            hash_at_cache.insert(h, br.header_hash);
                         }
                     }
                 }
-                Err(e) => {
-                    println!("Failed to parse message: {}, text: {}", e, text);
+                if let Some(from_height) =
+                    reorg_rollback_from(recent_peaks.make_contiguous(), tip, rec.prev_hash, |h| {
+                        hash_at_cache.get(&h).copied()
+                    })
+                {
+                    eprintln!("chain reorg: rolling back from height {from_height}");
+                    registries = resync_after_reorg(
+                        &source,
+                        &scope,
+                        indexer.as_ref(),
+                        &launcher_ids,
+                        from_height,
+                    )
+                    .await?;
+                    recent_peaks.retain(|(h, _)| *h < from_height);
+                    last_followed_height = recent_peaks
+                        .back()
+                        .map(|(h, _)| *h)
+                        .unwrap_or(from_height.saturating_sub(1));
                 }
-            },
-            Ok(Message::Close(_)) => {
-                println!("WebSocket closed by server");
-                break;
+
+                tip_rec = Some(rec);
             }
-            Err(e) => {
-                println!("WebSocket error: {}", e);
-                break;
+        }
+
+        let coin_resp = client
+            .get_coin_records_by_names(
+                registries.iter().map(|r| r.coin.coin_id()).collect(),
+                None,
+                None,
+                Some(true),
+                None,
+            )
+            .await?;
+
+        let coin_records = coin_resp.coin_records.ok_or(CliError::Custom(
+            "Weird - coin records not found after peak update.".to_string(),
+        ))?;
+        process_spent_registry_records(
+            &source,
+            &scope,
+            indexer.as_ref(),
+            &mut registries,
+            coin_records,
+        )
+        .await?;
+
+        // Follow singleton spends in every block from the last
+        // followed height through the current tip.
+        if let Some(tip) = tip_height {
+            let source = ChainBlockSource {
+                chain: &client,
+                metrics,
+            };
+            let followed =
+                follow_blocks_after(indexer.as_ref(), &scope, &source, last_followed_height, tip)
+                    .await?;
+            for (height, header_hash) in followed {
+                recent_peaks.retain(|(h, _)| *h < height);
+                recent_peaks.push_back((height, header_hash));
+                last_followed_height = height;
+            }
+            while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+                recent_peaks.pop_front();
+            }
+            if let Some(rec) = &tip_rec {
+                if recent_peaks.back().map(|(h, _)| *h) != Some(tip) {
+                    recent_peaks.retain(|(h, _)| *h < tip);
+                    recent_peaks.push_back((tip, rec.header_hash));
+                    while recent_peaks.len() > HEADER_CHECKPOINT_WINDOW {
+                        recent_peaks.pop_front();
+                    }
+                }
+            }
+            let started = Instant::now();
+            db.lock()
+                .await
+                .save_header_checkpoints(
+                    XCHANDLES_CHECKPOINT_LISTENER,
+                    recent_peaks.make_contiguous(),
+                )
+                .await?;
+            metrics.observe_sqlite_write("header_checkpoints", started.elapsed());
+        }
+        if let Some(tip) = tip_height {
+            set_schedule_pricing(
+                &registry_pricing,
+                &committed_base_price,
+                &launcher_ids,
+                &registries,
+                &price_schedule,
+                confirmed_timestamp,
+                network,
+            )
+            .await;
+            set_registry_tips(
+                &registry_tips,
+                &registry_snapshots,
+                &launcher_ids,
+                &registries,
+                tip,
+            )
+            .await;
+            indexer
+                .note_peak(tip, upstream_peak.max(tip), now_unix, confirmed_timestamp)
+                .await;
+        }
+
+        if last_clear_time.elapsed().unwrap().as_secs() > 60 * 30 {
+            if let Some(tip) = tip_height {
+                print!("Clearing cache (every 30m)... ");
+                let cutoff = tip.saturating_sub(128);
+                let started = Instant::now();
+                {
+                    let db = db.lock().await;
+                    db.delete_slots_spent_before(cutoff).await?;
+                    db.delete_singleton_coins_spent_before(cutoff).await?;
+                }
+                metrics.observe_sqlite_write("prune", started.elapsed());
+                println!("done :)");
+                last_clear_time = now;
             }
-            _ => {}
         }
     }
 
     Ok(())
 }
 
+/// First persisted checkpoint that upstream no longer agrees with, checked
+/// once at startup before the index is served again.
+///
+/// - A checkpoint above `upstream_peak` means the chain rewound below it: `Some(that height)`.
+/// - A checkpoint whose upstream header differs: `Some(that height)`. When even the
+///   oldest checkpoint mismatches the real fork is older than the window; rolling
+///   back to the oldest one is the most we can recover.
+/// - `hash_at` returning `None` stops the scan, as in [`reorg_rollback_from`].
+pub(crate) fn checkpoint_fork_point(
+    stored: &[(u32, Bytes32)],
+    upstream_peak: u32,
+    hash_at: impl Fn(u32) -> Option<Bytes32>,
+) -> Option<u32> {
+    for &(height, hash) in stored {
+        if height > upstream_peak {
+            return Some(height);
+        }
+        match hash_at(height) {
+            Some(on_chain) if on_chain == hash => continue,
+            Some(_) => return Some(height),
+            None => break,
+        }
+    }
+    None
+}
+
 /// First height that is no longer canonical, for `indexer.rollback`.
 ///
 /// - Empty window or linear extend (`new_height == last+1` and `new_prev_hash == last.hash`): `None`.
@@ -1033,7 +1377,7 @@ async fn connect_websocket(
 /// - First stored height whose on-chain header no longer matches: `Some(that height)`.
 /// - `hash_at` returning `None` stops the scan (RPC miss); that is not treated as a reorg.
 /// - If every stored hash still matches, this is a skipped-peak gap, not a reorg.
-fn reorg_rollback_from(
+pub(crate) fn reorg_rollback_from(
     stored: &[(u32, Bytes32)],
     new_height: u32,
     new_prev_hash: Bytes32,
@@ -1076,9 +1420,10 @@ mod tests {
     use chia_wallet_sdk::types::puzzles::{XchandlesHandleSlotValue, XchandlesPricingSolution};
     use chia_wallet_sdk::types::Conditions;
 
-    #[derive(Clone)]
+    #[derive(Clone, Default)]
     struct FakeRegistryChainSource {
         syncs: HashMap<Bytes32, RegistrySyncBatch>,
+        rewinds: Arc<std::sync::Mutex<Vec<(Bytes32, u32)>>>,
     }
 
     #[derive(Clone, Default)]
@@ -1106,31 +1451,140 @@ mod tests {
                 ))
             })
         }
+
+        async fn rewind_registry(&self, launcher_id: Bytes32, height: u32) -> Result<(), CliError> {
+            self.rewinds.lock().unwrap().push((launcher_id, height));
+            Ok(())
+        }
     }
 
-    fn h(byte: u8) -> Bytes32 {
-        Bytes32::new([byte; 32])
+    /// Records transaction boundaries instead of touching SQLite.
+    #[derive(Default)]
+    struct RecordingScope {
+        events: std::sync::Mutex<Vec<&'static str>>,
     }
 
-    fn fake_registry(launcher_id: Bytes32, coin_tag: u8) -> XchandlesRegistry {
-        XchandlesRegistry::new(
-            Coin::new(h(coin_tag), h(coin_tag.wrapping_add(1)), 1),
-            Proof::Eve(EveProof {
-                parent_parent_coin_info: h(coin_tag.wrapping_add(2)),
-                parent_amount: 1,
-            }),
-            XchandlesRegistryInfo::new(
-                XchandlesRegistryState::from(h(0x91), 5_000, REGISTRATION_PERIOD),
-                chia_wallet_sdk::driver::XchandlesConstants::new(launcher_id, h(0x92), 32, h(0x93)),
-            ),
-        )
+    #[async_trait::async_trait]
+    impl TransitionScope for RecordingScope {
+        async fn begin(&self) -> Result<(), CliError> {
+            self.events.lock().unwrap().push("begin");
+            Ok(())
+        }
+
+        async fn commit(&self) -> Result<(), CliError> {
+            self.events.lock().unwrap().push("commit");
+            Ok(())
+        }
+
+        async fn abort(&self) {
+            self.events.lock().unwrap().push("abort");
+        }
     }
 
-    fn register_log(handle: &str, tag: u8) -> XchandlesActionLog {
-        let slot = |name: &str, counter: u64| {
-            XchandlesHandleSlotValue::new(
-                counter,
-                name.tree_hash().into(),
+    /// Scope whose commit fails after every projection succeeded.
+    struct FailingCommitScope;
+
+    #[async_trait::async_trait]
+    impl TransitionScope for FailingCommitScope {
+        async fn begin(&self) -> Result<(), CliError> {
+            Ok(())
+        }
+
+        async fn commit(&self) -> Result<(), CliError> {
+            Err(CliError::Custom("commit failed".to_string()))
+        }
+
+        async fn abort(&self) {}
+    }
+
+    /// Registration store whose writes fail, as a full disk or locked database would.
+    struct FailingRegistrationStore;
+
+    #[async_trait::async_trait]
+    impl RegistrationStore for FailingRegistrationStore {
+        async fn get(
+            &self,
+            _registry_launcher_id: Bytes32,
+            _handle_hash: Bytes32,
+        ) -> Option<crate::RegistrationRecord> {
+            None
+        }
+
+        async fn upsert(&self, _record: crate::RegistrationRecord) -> Result<(), CliError> {
+            Err(CliError::Custom("registration write failed".to_string()))
+        }
+
+        async fn remove(
+            &self,
+            _registry_launcher_id: Bytes32,
+            _handle_hash: Bytes32,
+        ) -> Result<(), CliError> {
+            Err(CliError::Custom("registration write failed".to_string()))
+        }
+
+        async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
+            Vec::new()
+        }
+
+        async fn get_stats(
+            &self,
+            _registry_launcher_id: Bytes32,
+        ) -> crate::RegistryRegistrationStats {
+            crate::RegistryRegistrationStats::default()
+        }
+
+        async fn set_stats(
+            &self,
+            _registry_launcher_id: Bytes32,
+            _stats: crate::RegistryRegistrationStats,
+        ) -> Result<(), CliError> {
+            Err(CliError::Custom("registration write failed".to_string()))
+        }
+
+        async fn all_stats_registry_ids(&self) -> Vec<Bytes32> {
+            Vec::new()
+        }
+
+        async fn record_activity_delta(
+            &self,
+            _registry_launcher_id: Bytes32,
+            _delta: crate::StoredActivityDelta,
+        ) -> Result<bool, CliError> {
+            Err(CliError::Custom("registration write failed".to_string()))
+        }
+
+        async fn take_activity_deltas_from(
+            &self,
+            _registry_launcher_id: Bytes32,
+            _from_height: u32,
+        ) -> Result<Vec<crate::StoredActivityDelta>, CliError> {
+            Err(CliError::Custom("registration write failed".to_string()))
+        }
+    }
+
+    fn h(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn fake_registry(launcher_id: Bytes32, coin_tag: u8) -> XchandlesRegistry {
+        XchandlesRegistry::new(
+            Coin::new(h(coin_tag), h(coin_tag.wrapping_add(1)), 1),
+            Proof::Eve(EveProof {
+                parent_parent_coin_info: h(coin_tag.wrapping_add(2)),
+                parent_amount: 1,
+            }),
+            XchandlesRegistryInfo::new(
+                XchandlesRegistryState::from(h(0x91), 5_000, REGISTRATION_PERIOD),
+                chia_wallet_sdk::driver::XchandlesConstants::new(launcher_id, h(0x92), 32, h(0x93)),
+            ),
+        )
+    }
+
+    fn register_log(handle: &str, tag: u8) -> XchandlesActionLog {
+        let slot = |name: &str, counter: u64| {
+            XchandlesHandleSlotValue::new(
+                counter,
+                name.tree_hash().into(),
                 Bytes32::default(),
                 Bytes32::new([0xff; 32]),
                 4_102_444_800,
@@ -1182,8 +1636,13 @@ mod tests {
             registry_pricing: Arc::new(RwLock::new(HashMap::new())),
             price_schedule: Arc::new(Vec::new()),
             committed_base_price: Arc::new(RwLock::new(HashMap::new())),
+            registry_tips: Arc::new(RwLock::new(HashMap::new())),
             registry_launcher_ids,
             now_unix_override: None,
+            metrics: Arc::clone(&indexer.metrics),
+            address_prefix: "xch".to_string(),
+            events: Arc::clone(&indexer.events),
+            handle_history: Arc::clone(&indexer.handle_history),
         };
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
@@ -1213,20 +1672,25 @@ mod tests {
                     registry: latest.clone(),
                     transitions: vec![
                         RegistryIndexedTransition {
+                            coin_id: h(0xca),
                             height: 10,
                             logs: vec![register_log("alice", 0xa1)],
                             block_spends: Vec::new(),
+                            timestamp: None,
                             parent_by_value_hash: HashMap::new(),
                         },
                         RegistryIndexedTransition {
+                            coin_id: h(0xcb),
                             height: 11,
                             logs: vec![register_log("bravo", 0xb1)],
                             block_spends: Vec::new(),
+                            timestamp: None,
                             parent_by_value_hash: HashMap::new(),
                         },
                     ],
                 },
             )]),
+            ..Default::default()
         };
         let indexer = SingletonIndexer::new(
             crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
@@ -1240,9 +1704,15 @@ mod tests {
         );
         let mut registries = vec![current];
 
-        process_spent_registry_records(&source, &indexer, &mut registries, vec![observed])
-            .await
-            .unwrap();
+        process_spent_registry_records(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap();
 
         let base = spawn_registration_api(vec![launcher_id], &indexer).await;
         let client = reqwest::Client::new();
@@ -1259,6 +1729,395 @@ mod tests {
         assert_eq!(registries[0].coin, latest.coin);
     }
 
+    #[tokio::test]
+    async fn reorg_resync_replays_every_transition_from_the_fork_point() {
+        let launcher_id = h(0xa0);
+        let current = fake_registry(launcher_id, 0x10);
+        let observed = CoinRecord {
+            coin: current.coin,
+            coinbase: false,
+            confirmed_block_index: 8,
+            spent: true,
+            spent_block_index: 10,
+            timestamp: 1_700_000_000,
+        };
+        // One registry spend per UTC day, so orphaned activity is visible per bucket.
+        let transition = |height: u32, handle: &str, tag: u8| RegistryIndexedTransition {
+            coin_id: h(tag),
+            height,
+            logs: vec![register_log(handle, tag)],
+            block_spends: Vec::new(),
+            timestamp: Some(1_700_000_000 + u64::from(height) * crate::SECONDS_PER_DAY),
+            parent_by_value_hash: HashMap::new(),
+        };
+        let before = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: fake_registry(launcher_id, 0x20),
+                    transitions: vec![transition(10, "alice", 0xa1), transition(11, "bravo", 0xb1)],
+                },
+            )]),
+            ..Default::default()
+        };
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            crate::MemoryRegistrationStore::shared() as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                11,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let mut registries = vec![current];
+        process_spent_registry_records(
+            &before,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap();
+
+        // The fork at 11 orphaned bravo; the outage then spanned three more registry spends.
+        let latest = fake_registry(launcher_id, 0x40);
+        let after = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: latest.clone(),
+                    transitions: vec![
+                        transition(10, "alice", 0xa1),
+                        transition(11, "charlie", 0xc1),
+                        transition(12, "delta", 0xd1),
+                        transition(13, "echo", 0xe1),
+                    ],
+                },
+            )]),
+            ..Default::default()
+        };
+        let scope = RecordingScope::default();
+        let registries = resync_after_reorg(&after, &scope, &indexer, &[launcher_id], 11)
+            .await
+            .unwrap();
+
+        assert_eq!(registries[0].coin, latest.coin);
+        assert_eq!(*after.rewinds.lock().unwrap(), vec![(launcher_id, 11)]);
+        assert_eq!(*scope.events.lock().unwrap(), vec!["begin", "commit"]);
+        assert_eq!(
+            indexer
+                .registrations
+                .get_stats(launcher_id)
+                .await
+                .total_registered,
+            4
+        );
+        let stats = indexer.registrations.get_stats(launcher_id).await;
+        let day = |height: u64| 1_700_000_000 / crate::SECONDS_PER_DAY + height;
+        assert_eq!(
+            stats.daily_activity.keys().copied().collect::<Vec<_>>(),
+            [day(10), day(11), day(12), day(13)]
+        );
+        // Alice's block was replayed from the fork point's sync but only counts once.
+        assert_eq!(stats.daily_activity[&day(10)].registrations, 1);
+        assert_eq!(stats.daily_activity[&day(11)].registrations, 1);
+        assert_eq!(stats.daily_activity[&day(11)].protocol_fees, 0xc1);
+
+        let now = FreshnessState::now_unix();
+        indexer.note_peak(13, 13, now, now).await;
+        let base = spawn_registration_api(vec![launcher_id], &indexer).await;
+        let client = reqwest::Client::new();
+        for (handle, height) in [("alice", 10), ("charlie", 11), ("delta", 12), ("echo", 13)] {
+            let response = client
+                .get(format!("{base}/registrations/{handle}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{handle}");
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["confirmation_height"], height);
+        }
+        let orphaned = client
+            .get(format!("{base}/registrations/bravo"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(orphaned.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn registry_spends_confirmed_in_one_block_each_count() {
+        let launcher_id = h(0xa0);
+        let current = fake_registry(launcher_id, 0x10);
+        let observed = CoinRecord {
+            coin: current.coin,
+            coinbase: false,
+            confirmed_block_index: 8,
+            spent: true,
+            spent_block_index: 10,
+            timestamp: 1_700_000_000,
+        };
+        let transition = |coin_id: Bytes32, handle: &str, tag: u8| RegistryIndexedTransition {
+            coin_id,
+            height: 10,
+            logs: vec![register_log(handle, tag)],
+            block_spends: Vec::new(),
+            timestamp: Some(1_700_000_000),
+            parent_by_value_hash: HashMap::new(),
+        };
+        let source = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: fake_registry(launcher_id, 0x20),
+                    transitions: vec![
+                        transition(current.coin.coin_id(), "alice", 0xa1),
+                        transition(h(0x15), "bravo", 0xb1),
+                    ],
+                },
+            )]),
+            ..Default::default()
+        };
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            crate::MemoryRegistrationStore::shared() as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                10,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let mut registries = vec![current];
+        process_spent_registry_records(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap();
+
+        let stats = indexer.registrations.get_stats(launcher_id).await;
+        let day = stats.daily_activity[&(1_700_000_000 / crate::SECONDS_PER_DAY)];
+        assert_eq!(day.registrations, 2);
+        assert_eq!(day.protocol_fees, 0xa1 + 0xb1);
+
+        // Replaying the block after a rollback still counts each spend exactly once.
+        let registries = resync_after_reorg(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &[launcher_id],
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(registries.len(), 1);
+        let stats = indexer.registrations.get_stats(launcher_id).await;
+        let day = stats.daily_activity[&(1_700_000_000 / crate::SECONDS_PER_DAY)];
+        assert_eq!(day.registrations, 2);
+    }
+
+    #[tokio::test]
+    async fn handle_history_keeps_every_spend_confirmed_in_one_block() {
+        let launcher_id = h(0xa0);
+        let current = fake_registry(launcher_id, 0x10);
+        let observed = CoinRecord {
+            coin: current.coin,
+            coinbase: false,
+            confirmed_block_index: 8,
+            spent: true,
+            spent_block_index: 10,
+            timestamp: 1_700_000_000,
+        };
+        let transition = |coin_id: Bytes32, tag: u8| RegistryIndexedTransition {
+            coin_id,
+            height: 10,
+            logs: vec![register_log("alice", tag)],
+            block_spends: Vec::new(),
+            timestamp: None,
+            parent_by_value_hash: HashMap::new(),
+        };
+        let source = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: fake_registry(launcher_id, 0x20),
+                    transitions: vec![
+                        transition(current.coin.coin_id(), 0xa1),
+                        transition(h(0x15), 0xa2),
+                    ],
+                },
+            )]),
+            ..Default::default()
+        };
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            crate::MemoryRegistrationStore::shared() as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                10,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let mut registries = vec![current];
+        process_spent_registry_records(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap();
+
+        let history = indexer
+            .handle_history
+            .list(launcher_id, "alice".tree_hash().into(), None, 10)
+            .await;
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| (entry.position(), entry.total_price))
+                .collect::<Vec<_>>(),
+            [((10, 1), Some(0xa2)), ((10, 0), Some(0xa1))]
+        );
+    }
+
+    #[tokio::test]
+    async fn store_failure_aborts_the_transition_and_keeps_the_registry_tip() {
+        let launcher_id = h(0xa0);
+        let current = fake_registry(launcher_id, 0x10);
+        let observed = CoinRecord {
+            coin: current.coin,
+            coinbase: false,
+            confirmed_block_index: 8,
+            spent: true,
+            spent_block_index: 10,
+            timestamp: 1_700_000_000,
+        };
+        let source = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: fake_registry(launcher_id, 0x20),
+                    transitions: vec![RegistryIndexedTransition {
+                        coin_id: h(0xca),
+                        height: 10,
+                        logs: vec![register_log("alice", 0xa1)],
+                        block_spends: Vec::new(),
+                        timestamp: None,
+                        parent_by_value_hash: HashMap::new(),
+                    }],
+                },
+            )]),
+            ..Default::default()
+        };
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            Arc::new(FailingRegistrationStore) as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                9,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let scope = RecordingScope::default();
+        let mut registries = vec![current.clone()];
+
+        let error = process_spent_registry_records(
+            &source,
+            &scope,
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("registration write failed"));
+        assert_eq!(*scope.events.lock().unwrap(), vec!["begin", "abort"]);
+        assert_eq!(registries[0].coin, current.coin);
+    }
+
+    #[tokio::test]
+    async fn failed_commit_announces_nothing_and_retry_announces_once() {
+        let launcher_id = h(0xa0);
+        let current = fake_registry(launcher_id, 0x10);
+        let observed = CoinRecord {
+            coin: current.coin,
+            coinbase: false,
+            confirmed_block_index: 8,
+            spent: true,
+            spent_block_index: 10,
+            timestamp: 1_700_000_000,
+        };
+        let source = FakeRegistryChainSource {
+            syncs: HashMap::from([(
+                launcher_id,
+                RegistrySyncBatch {
+                    registry: fake_registry(launcher_id, 0x20),
+                    transitions: vec![RegistryIndexedTransition {
+                        coin_id: h(0xca),
+                        height: 10,
+                        logs: vec![register_log("alice", 0xa1)],
+                        block_spends: Vec::new(),
+                        timestamp: None,
+                        parent_by_value_hash: HashMap::new(),
+                    }],
+                },
+            )]),
+            ..Default::default()
+        };
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            crate::MemoryRegistrationStore::shared() as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                9,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let standing = |indexer: &SingletonIndexer| {
+            indexer
+                .events
+                .subscribe(launcher_id, crate::HandleEventResume::FromHeight(0))
+                .replay
+                .len()
+        };
+        let mut registries = vec![current];
+
+        let error = process_spent_registry_records(
+            &source,
+            &FailingCommitScope,
+            &indexer,
+            &mut registries,
+            vec![observed.clone()],
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("commit failed"));
+        assert_eq!(standing(&indexer), 0);
+
+        process_spent_registry_records(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![observed],
+        )
+        .await
+        .unwrap();
+        assert_eq!(standing(&indexer), 1);
+    }
+
     #[tokio::test]
     async fn skipped_peaks_follow_singleton_spend_between_last_indexed_and_tip() {
         let mut sim = Simulator::new();
@@ -1330,7 +2189,8 @@ mod tests {
                 reference_count: 1,
                 dereference_height: None,
             })
-            .await;
+            .await
+            .unwrap();
         handle_slots
             .upsert(crate::HandleSlotRecord {
                 registry_launcher_id: registry,
@@ -1351,7 +2211,8 @@ mod tests {
                 }),
                 history: Vec::new(),
             })
-            .await;
+            .await
+            .unwrap();
         pending_updates
             .upsert(crate::PendingUpdateRecord {
                 registry_launcher_id: registry,
@@ -1367,7 +2228,8 @@ mod tests {
                 }),
                 history: Vec::new(),
             })
-            .await;
+            .await
+            .unwrap();
 
         let base = spawn_registration_api(vec![registry], &indexer).await;
         let client = reqwest::Client::new();
@@ -1384,7 +2246,7 @@ mod tests {
                 (12, (h(0x12), Vec::new())),
             ]),
         };
-        follow_blocks_after(&indexer, &source, 10, 12)
+        follow_blocks_after(&indexer, &RecordingScope::default(), &source, 10, 12)
             .await
             .unwrap();
 
@@ -1402,6 +2264,53 @@ mod tests {
         assert_eq!(after.status(), StatusCode::NO_CONTENT);
     }
 
+    #[tokio::test]
+    async fn memory_chain_block_source_follows_canonical_headers_after_a_rewind() {
+        let chain = crate::MemoryChainSource::new();
+        for ts in [100, 118, 136] {
+            chain.push_block(Some(ts), Vec::new());
+        }
+        let indexer = SingletonIndexer::new(
+            crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
+            crate::MemoryHandleSlotStore::shared() as Arc<dyn HandleSlotStore>,
+            crate::MemoryRegistrationStore::shared() as Arc<dyn RegistrationStore>,
+            crate::MemoryPendingUpdateStore::shared() as Arc<dyn PendingUpdateStore>,
+            Arc::new(RwLock::new(FreshnessState::fresh_at(
+                2,
+                FreshnessState::now_unix(),
+            ))),
+        );
+        let metrics = ListenerMetrics::shared("xchandles");
+        let source = ChainBlockSource {
+            chain: &chain,
+            metrics: &metrics,
+        };
+        let scope = RecordingScope::default();
+
+        let followed = follow_blocks_after(&indexer, &scope, &source, 0, 2)
+            .await
+            .unwrap();
+        let orphaned = chain.block_record_at(2).await.unwrap().unwrap();
+        assert_eq!(
+            followed,
+            vec![
+                (
+                    1,
+                    chain.block_record_at(1).await.unwrap().unwrap().header_hash
+                ),
+                (2, orphaned.header_hash),
+            ]
+        );
+
+        chain.rewind_to(1);
+        let replacement = chain.push_block(Some(140), Vec::new());
+        let refollowed = follow_blocks_after(&indexer, &scope, &source, 1, 3)
+            .await
+            .unwrap();
+        assert_eq!(refollowed, vec![(2, replacement.header_hash)]);
+        assert_ne!(replacement.header_hash, orphaned.header_hash);
+    }
+
     #[tokio::test]
     async fn reordered_coin_records_update_the_registry_with_the_matching_coin_id() {
         let launcher_a = h(0xa0);
@@ -1433,9 +2342,11 @@ mod tests {
                     RegistrySyncBatch {
                         registry: latest_a.clone(),
                         transitions: vec![RegistryIndexedTransition {
+                            coin_id: h(0xca),
                             height: 10,
                             logs: vec![register_log("alice", 0xa1)],
                             block_spends: Vec::new(),
+                            timestamp: None,
                             parent_by_value_hash: HashMap::new(),
                         }],
                     },
@@ -1445,14 +2356,17 @@ mod tests {
                     RegistrySyncBatch {
                         registry: latest_b,
                         transitions: vec![RegistryIndexedTransition {
+                            coin_id: h(0xca),
                             height: 10,
                             logs: vec![register_log("bravo", 0xb1)],
                             block_spends: Vec::new(),
+                            timestamp: None,
                             parent_by_value_hash: HashMap::new(),
                         }],
                     },
                 ),
             ]),
+            ..Default::default()
         };
         let indexer = SingletonIndexer::new(
             crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
@@ -1469,6 +2383,7 @@ mod tests {
         // Coinset does not promise request order; return B before A.
         process_spent_registry_records(
             &source,
+            &RecordingScope::default(),
             &indexer,
             &mut registries,
             vec![unspent_b, spent_a],
@@ -1513,13 +2428,16 @@ mod tests {
                 RegistrySyncBatch {
                     registry: latest_a,
                     transitions: vec![RegistryIndexedTransition {
+                        coin_id: h(0xca),
                         height: 10,
                         logs: vec![register_log("alice", 0xa1)],
                         block_spends: Vec::new(),
+                        timestamp: None,
                         parent_by_value_hash: HashMap::new(),
                     }],
                 },
             )]),
+            ..Default::default()
         };
         let indexer = SingletonIndexer::new(
             crate::MemorySingletonStore::shared() as Arc<dyn SingletonStore>,
@@ -1534,10 +2452,15 @@ mod tests {
         let original_a_coin = current_a.coin;
         let mut registries = vec![current_a, current_b];
 
-        let error =
-            process_spent_registry_records(&source, &indexer, &mut registries, vec![spent_a])
-                .await
-                .unwrap_err();
+        let error = process_spent_registry_records(
+            &source,
+            &RecordingScope::default(),
+            &indexer,
+            &mut registries,
+            vec![spent_a],
+        )
+        .await
+        .unwrap_err();
 
         assert!(error.to_string().contains("missing coin record"));
         assert_eq!(registries[0].coin, original_a_coin);
@@ -1617,6 +2540,89 @@ mod tests {
         assert_eq!(reorg_rollback_from(&stored, 12, h(99), |_| None), None);
     }
 
+    #[test]
+    fn checkpoint_fork_point_all_canonical() {
+        let stored = [(10, h(1)), (11, h(2))];
+        let hash_at = |height: u32| match height {
+            10 => Some(h(1)),
+            11 => Some(h(2)),
+            _ => None,
+        };
+        assert_eq!(checkpoint_fork_point(&stored, 20, hash_at), None);
+    }
+
+    #[test]
+    fn checkpoint_fork_point_header_replaced_while_offline() {
+        let stored = [(10, h(1)), (11, h(2)), (12, h(3))];
+        let hash_at = |height: u32| match height {
+            10 => Some(h(1)),
+            11 => Some(h(7)),
+            12 => Some(h(8)),
+            _ => None,
+        };
+        assert_eq!(checkpoint_fork_point(&stored, 20, hash_at), Some(11));
+    }
+
+    #[test]
+    fn checkpoint_fork_point_upstream_below_window() {
+        let stored = [(10, h(1)), (11, h(2)), (12, h(3))];
+        let hash_at = |height: u32| match height {
+            10 => Some(h(1)),
+            _ => panic!("heights above the upstream peak are not fetched"),
+        };
+        assert_eq!(checkpoint_fork_point(&stored, 10, hash_at), Some(11));
+    }
+
+    #[test]
+    fn checkpoint_fork_point_unverified_hash_is_not_a_reorg() {
+        let stored = [(10, h(1)), (11, h(2))];
+        assert_eq!(checkpoint_fork_point(&stored, 20, |_| None), None);
+    }
+
+    #[test]
+    fn neighbors_lookup_error_maps_missing_slots_to_not_found() {
+        let err = neighbors_lookup_error(CliError::DbColumnNotFound(), ApiError::handle_not_found);
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(err.code, "handle_not_found");
+
+        let err = neighbors_lookup_error(
+            CliError::Custom("disk I/O error".to_string()),
+            ApiError::handle_not_found,
+        );
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.code, "storage_error");
+    }
+
+    #[test]
+    fn neighbors_response_without_peak_still_parses() {
+        let mut body = serde_json::to_value(XchandlesNeighborsResponse {
+            left_handle_hash: String::new(),
+            right_handle_hash: String::new(),
+            left_left_handle_hash: String::new(),
+            left_expiration: 0,
+            left_counter: 0,
+            left_owner_launcher_id: String::new(),
+            left_resolved_launcher_id: String::new(),
+            left_parent_id: String::new(),
+            left_parent_parent_id: String::new(),
+            left_parent_inner_puzzle_hash: String::new(),
+            right_right_handle_hash: String::new(),
+            right_expiration: 0,
+            right_counter: 0,
+            right_owner_launcher_id: String::new(),
+            right_resolved_launcher_id: String::new(),
+            right_parent_id: String::new(),
+            right_parent_parent_id: String::new(),
+            right_parent_inner_puzzle_hash: String::new(),
+            indexed_peak_height: Some(116),
+        })
+        .unwrap();
+        assert_eq!(body["indexed_peak_height"], 116);
+        body.as_object_mut().unwrap().remove("indexed_peak_height");
+        let parsed: XchandlesNeighborsResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(parsed.indexed_peak_height, None);
+    }
+
     #[test]
     fn coinset_names_batches_cap_at_500() {
         assert_eq!(COINSET_NAMES_BATCH, 500);
@@ -1629,21 +2635,45 @@ mod tests {
 
     #[test]
     fn testnet11_schedule_price_follows_time() {
-        assert_eq!(schedule_base_price_at(0, true), 1);
-        assert_eq!(schedule_base_price_at(1_786_885_199, true), 1);
-        assert_eq!(schedule_base_price_at(1_786_885_200, true), 9);
-        assert_eq!(schedule_base_price_at(1_786_935_600, true), 6);
-        assert_eq!(schedule_base_price_at(1_786_953_600, true), 5);
-        assert_eq!(schedule_base_price_at(1_787_022_000, true), 1);
-        assert_eq!(schedule_base_price_at(u64::MAX, true), 1);
+        assert_eq!(schedule_base_price_at(0, &Network::testnet11()), 1);
+        assert_eq!(
+            schedule_base_price_at(1_786_885_199, &Network::testnet11()),
+            1
+        );
+        assert_eq!(
+            schedule_base_price_at(1_786_885_200, &Network::testnet11()),
+            9
+        );
+        assert_eq!(
+            schedule_base_price_at(1_786_935_600, &Network::testnet11()),
+            6
+        );
+        assert_eq!(
+            schedule_base_price_at(1_786_953_600, &Network::testnet11()),
+            5
+        );
+        assert_eq!(
+            schedule_base_price_at(1_787_022_000, &Network::testnet11()),
+            1
+        );
+        assert_eq!(schedule_base_price_at(u64::MAX, &Network::testnet11()), 1);
     }
 
     #[test]
     fn mainnet_schedule_price_follows_time() {
-        assert_eq!(schedule_base_price_at(0, false), 1);
-        assert_eq!(schedule_base_price_at(1_787_216_399, false), 1);
-        assert_eq!(schedule_base_price_at(1_787_216_400, false), 5_000_000);
-        assert_eq!(schedule_base_price_at(1_788_426_000, false), 5_000);
+        assert_eq!(schedule_base_price_at(0, &Network::mainnet()), 1);
+        assert_eq!(
+            schedule_base_price_at(1_787_216_399, &Network::mainnet()),
+            1
+        );
+        assert_eq!(
+            schedule_base_price_at(1_787_216_400, &Network::mainnet()),
+            5_000_000
+        );
+        assert_eq!(
+            schedule_base_price_at(1_788_426_000, &Network::mainnet()),
+            5_000
+        );
     }
 
     #[test]
@@ -1,38 +1,60 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use axum::extract::rejection::JsonRejection;
 use axum::extract::{Path, Query, State};
-use axum::http::{Method, StatusCode};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::middleware;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{Json, Router};
 use chia_protocol::Bytes32;
+use chia_wallet_sdk::utils::Address;
 use clvm_utils::ToTreeHash;
+use futures_util::{stream, Stream, StreamExt};
+use serde_json::json;
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 
 use super::auction_pricing::{
-    auction_premium, base_registration_fee, projected_pricing_timestamp, reaches_base_at,
-    AUCTION_DURATION_SECONDS, SOON_WINDOW_SECONDS,
+    auction_premium, base_registration_fee, period_registration_fee, projected_pricing_timestamp,
+    reaches_base_at, total_registration_fee, AUCTION_DURATION_SECONDS, SOON_WINDOW_SECONDS,
 };
 use super::error::ApiError;
-use super::freshness::FreshnessState;
-use super::handle_store::{HandleSlotStore, StoredHandleSlot};
+use super::events::{HandleEvent, HandleEventCursor, HandleEventFeed, HandleEventResume};
+use super::freshness::{FreshnessState, MAX_PEAK_AGE_SECONDS, MAX_PEAK_LAG};
+use super::handle_store::{HandleLauncherRole, HandleSlotStore, StoredHandleSlot};
+use super::history_store::{
+    HandleHistoryStore, MemoryHandleHistoryStore, StoredHandleHistoryEntry,
+};
 use super::pending_store::PendingUpdateStore;
 use super::price_schedule::{
     remaining_unroll_start, PriceQuery, PriceResponse, ScheduleGeneration, ScheduleResponse,
+    LAUNCH_BASE_PRICE,
+};
+use super::registration_store::{
+    RegistrationActionKind, RegistrationStore, RegistryActivity, StoredRegistration,
+    SECONDS_PER_DAY,
 };
-use super::registration_store::{RegistrationActionKind, RegistrationStore, StoredRegistration};
+use super::search::{is_search_term, HandleSearchFilter, HandleSearchMode, SEARCH_CANDIDATE_LIMIT};
+use super::stats::{bucket_activity, StatsBucket};
 use super::store::{FollowRecordStatus, SingletonStore, StoredSingletonState};
 use super::types::{
-    hex32, is_canonical_handle, parse_launcher_id, ExpiringActiveItem, ExpiringActiveResponse,
-    ExpiringQuery, ExpiringSoonItem, ExpiringSoonResponse, ExpiringView, HandleProofResponse,
-    HandleQuery, HandleSlotJson, PendingTransferQuery, PendingTransferResponse,
-    RecentRegistrationItem, RecentRegistrationsQuery, RecentRegistrationsResponse,
-    RegistrationQuery, RegistrationResponse, SingletonQuery, SingletonResponse, SlotNeighborsJson,
+    hex32, is_canonical_handle, parse_launcher_id, EventsQuery, ExpiringActiveItem,
+    ExpiringActiveResponse, ExpiringQuery, ExpiringSoonItem, ExpiringSoonResponse, ExpiringView,
+    HandleAddressQuery, HandleAddressResponse, HandleHistoryItem, HandleHistoryQuery,
+    HandleHistoryResponse, HandleHistoryUpdateJson, HandleLengthJson, HandleProofResponse,
+    HandleQuery, HandleSearchItem, HandleSearchResponse, HandleSlotJson, LauncherHandleItem,
+    LauncherHandlesQuery, LauncherHandlesResponse, PendingTransferQuery, PendingTransferResponse,
+    QuoteItem, QuoteRequest, QuoteResponse, ReadyResponse, RecentRegistrationItem,
+    RecentRegistrationsQuery, RecentRegistrationsResponse, RegistrationQuery, RegistrationResponse,
+    RegistryStatusItem, SearchQuery, SingletonQuery, SingletonResponse, SlotNeighborsJson,
+    StatsActivityJson, StatsBucketJson, StatsQuery, StatsResponse, StatusResponse,
 };
-use crate::{BASE_PRICE_AT_FACTOR_ONE, REGISTRATION_PERIOD};
+use crate::{record_http_metrics, ListenerMetrics, BASE_PRICE_AT_FACTOR_ONE, REGISTRATION_PERIOD};
 
 /// Confirmed pricing inputs for one followed registry.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,6 +73,13 @@ impl Default for RegistryPricing {
     }
 }
 
+/// Unspent registry coin the listener last synced to, and the peak it was seen at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistryTip {
+    pub coin_id: Bytes32,
+    pub indexed_height: u32,
+}
+
 #[derive(Clone)]
 pub struct ListenerApiState {
     pub store: Arc<dyn SingletonStore>,
@@ -64,10 +93,19 @@ pub struct ListenerApiState {
     pub price_schedule: Arc<Vec<ScheduleGeneration>>,
     /// Committed (pre-unroll) base price per registry.
     pub committed_base_price: Arc<RwLock<HashMap<Bytes32, u64>>>,
+    /// Synced tip per registry; a followed registry without one is not ready.
+    pub registry_tips: Arc<RwLock<HashMap<Bytes32, RegistryTip>>>,
     /// Configured registries in follow order; omission of `launcher_id` selects the first.
     pub registry_launcher_ids: Vec<Bytes32>,
     /// Optional clock override for tests (unix seconds).
     pub now_unix_override: Option<u64>,
+    /// Shared with the indexer and websocket loop; rendered on `/metrics`.
+    pub metrics: Arc<ListenerMetrics>,
+    /// Bech32m prefix for `/handle/{handle}/address` (`xch` or `txch`).
+    pub address_prefix: String,
+    /// Shared with the indexer, which announces into it; streamed on `/events`.
+    pub events: Arc<HandleEventFeed>,
+    pub handle_history: Arc<dyn HandleHistoryStore>,
 }
 
 impl ListenerApiState {
@@ -94,8 +132,13 @@ impl ListenerApiState {
             registry_pricing: Arc::new(RwLock::new(pricing)),
             price_schedule: Arc::new(Vec::new()),
             committed_base_price: Arc::new(RwLock::new(committed)),
+            registry_tips: Arc::new(RwLock::new(HashMap::new())),
             registry_launcher_ids,
             now_unix_override: None,
+            metrics: ListenerMetrics::shared("xchandles"),
+            address_prefix: "xch".to_string(),
+            events: HandleEventFeed::shared(),
+            handle_history: MemoryHandleHistoryStore::shared(),
         }
     }
 
@@ -109,10 +152,13 @@ impl ListenerApiState {
     }
 }
 
-/// Public listener router: CORS is credential-free for any origin on GET/HEAD.
+/// Public listener router: CORS is credential-free for any origin on GET/HEAD and `POST /quote`.
 pub fn listener_router(state: ListenerApiState) -> Router {
+    let metrics = Arc::clone(&state.metrics);
     Router::new()
         .route("/healthz", get(|| async { StatusCode::OK }))
+        .route("/readyz", get(get_readyz).head(head_readyz))
+        .route("/status", get(get_status))
         .route(
             "/singletons/{launcher_id}",
             get(get_singleton).head(head_singleton),
@@ -121,6 +167,14 @@ pub fn listener_router(state: ListenerApiState) -> Router {
             "/handle/{handle}/pending-transfer",
             get(get_pending_transfer).head(head_pending_transfer),
         )
+        .route(
+            "/handle/{handle}/history",
+            get(get_handle_history).head(head_handle_history),
+        )
+        .route(
+            "/handle/{handle}/address",
+            get(get_handle_address).head(head_handle_address),
+        )
         .route("/handle/{handle}", get(get_handle).head(head_handle))
         .route(
             "/recent-registrations",
@@ -131,18 +185,36 @@ pub fn listener_router(state: ListenerApiState) -> Router {
             get(get_registration).head(head_registration),
         )
         .route("/expiring", get(get_expiring).head(head_expiring))
+        .route("/search", get(get_search).head(head_search))
+        .route(
+            "/owners/{launcher_id}/handles",
+            get(get_owner_handles).head(head_owner_handles),
+        )
+        .route(
+            "/resolved/{launcher_id}/handles",
+            get(get_resolved_handles).head(head_resolved_handles),
+        )
+        .route("/stats", get(get_stats).head(head_stats))
+        .route("/events", get(get_events))
         .route("/price", get(get_price).head(head_price))
+        .route("/quote", post(post_quote))
         .route("/schedule", get(get_schedule).head(head_schedule))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&metrics),
+            record_http_metrics,
+        ))
+        .route("/metrics", get(move || async move { metrics.response() }))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
-                .allow_methods([Method::GET, Method::HEAD, Method::OPTIONS])
+                .allow_methods([Method::GET, Method::HEAD, Method::POST, Method::OPTIONS])
                 .allow_headers(Any),
         )
         .with_state(state)
 }
 
-async fn require_fresh(state: &ListenerApiState) -> Result<(u32, u64), ApiError> {
+/// `(indexed_peak_height, confirmed_timestamp)` when fresh; `index_stale` otherwise.
+pub async fn require_fresh(state: &ListenerApiState) -> Result<(u32, u64), ApiError> {
     let freshness = state.freshness.read().await;
     let now = state.now_unix();
     if !freshness.is_fresh(now) {
@@ -154,6 +226,74 @@ async fn require_fresh(state: &ListenerApiState) -> Result<(u32, u64), ApiError>
     Ok((freshness.indexed_peak_height, freshness.confirmed_timestamp))
 }
 
+/// Readiness fails closed: fresh index and a synced tip for every followed registry.
+async fn require_ready(state: &ListenerApiState) -> Result<ReadyResponse, ApiError> {
+    let (indexed_peak_height, _) = require_fresh(state).await?;
+    let tips = state.registry_tips.read().await;
+    if let Some(missing) = state
+        .registry_launcher_ids
+        .iter()
+        .find(|id| !tips.contains_key(*id))
+    {
+        let freshness = state.freshness.read().await;
+        return Err(ApiError::index_stale(
+            freshness.indexed_peak_height,
+            freshness.upstream_peak_height,
+        )
+        .with_details(json!({
+            "indexed_peak_height": freshness.indexed_peak_height,
+            "upstream_peak_height": freshness.upstream_peak_height,
+            "unsynced_registry": hex32(*missing),
+        })));
+    }
+    Ok(ReadyResponse {
+        ready: true,
+        indexed_peak_height,
+    })
+}
+
+async fn lookup_status(state: &ListenerApiState) -> StatusResponse {
+    let now = state.now_unix();
+    let freshness = state.freshness.read().await.clone();
+    let tips = state.registry_tips.read().await;
+    let registries = state
+        .registry_launcher_ids
+        .iter()
+        .map(|id| {
+            let tip = tips.get(id);
+            RegistryStatusItem {
+                launcher_id: hex32(*id),
+                tip_coin_id: tip.map(|t| hex32(t.coin_id)),
+                tip_indexed_height: tip.map(|t| t.indexed_height),
+            }
+        })
+        .collect::<Vec<_>>();
+    let stale_reasons = freshness
+        .stale_reasons(now)
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    StatusResponse {
+        ready: stale_reasons.is_empty() && registries.iter().all(|r| r.tip_coin_id.is_some()),
+        stale_reasons,
+        indexed_peak_height: freshness.indexed_peak_height,
+        upstream_peak_height: freshness.upstream_peak_height,
+        peak_lag: freshness
+            .upstream_peak_height
+            .saturating_sub(freshness.indexed_peak_height),
+        max_peak_lag: MAX_PEAK_LAG,
+        last_successful_peak_unix: freshness.last_successful_peak_unix,
+        seconds_since_last_peak: now.saturating_sub(freshness.last_successful_peak_unix),
+        max_peak_age_seconds: MAX_PEAK_AGE_SECONDS,
+        confirmed_timestamp: freshness.confirmed_timestamp,
+        rolling_back: freshness.rolling_back,
+        resyncing: freshness.resyncing,
+        last_reorg_unix: freshness.last_reorg_unix,
+        last_reorg_height: freshness.last_reorg_height,
+        registries,
+    }
+}
+
 /// Opaque cursor: `v1.{expiration}.{handle}` - stable for the canonical projection order.
 fn encode_cursor(expiration: u64, handle: &str) -> String {
     format!("v1.{expiration}.{handle}")
@@ -169,6 +309,16 @@ fn decode_cursor(cursor: &str) -> Option<(u64, String)> {
     Some((expiration, handle.to_string()))
 }
 
+/// Opaque history cursor: `v1.{confirmation_height}.{log_index}` of the last item served.
+fn encode_history_cursor(position: (u32, u32)) -> String {
+    format!("v1.{}.{}", position.0, position.1)
+}
+
+fn decode_history_cursor(cursor: &str) -> Option<(u32, u32)> {
+    let (height, log_index) = cursor.strip_prefix("v1.")?.split_once('.')?;
+    Some((height.parse().ok()?, log_index.parse().ok()?))
+}
+
 /// Inclusive `[min, max]` matching `now >= expiration` and `auction_premium(expiration, projected) != 0`.
 ///
 /// Membership is `expiration in (projected - 28d, min(now, projected)]`.
@@ -279,7 +429,8 @@ async fn lookup_singleton(
     }
 }
 
-fn select_registry(
+/// Requested registry, or the first followed one when omitted.
+pub fn select_registry(
     state: &ListenerApiState,
     launcher_id_param: Option<&str>,
 ) -> Result<Bytes32, ApiError> {
@@ -385,6 +536,129 @@ async fn lookup_handle_proof(
     })
 }
 
+async fn lookup_handle_address(
+    state: &ListenerApiState,
+    handle: &str,
+    query: &HandleAddressQuery,
+) -> Result<HandleAddressResponse, ApiError> {
+    if !is_canonical_handle(handle) {
+        return Err(ApiError::invalid_handle());
+    }
+
+    let registry = select_registry(state, query.launcher_id.as_deref())?;
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(state).await?;
+
+    let handle_hash: Bytes32 = handle.tree_hash().into();
+    let record = state
+        .handle_slots
+        .get(registry, handle_hash)
+        .await
+        .ok_or_else(ApiError::handle_not_found)?;
+    let slot = record
+        .current
+        .as_ref()
+        .ok_or_else(ApiError::handle_not_found)?;
+
+    // Same fail-closed expiration gate as the unified proof.
+    if state.now_unix() >= slot.expiration && !query.bypass_expiration_safety_check {
+        return Err(ApiError::handle_expired(slot.expiration));
+    }
+
+    let resolved = state
+        .store
+        .get(slot.resolved_launcher_id)
+        .await
+        .ok_or_else(ApiError::resolution_incomplete)?;
+    let current = match resolved.status {
+        FollowRecordStatus::Incomplete => return Err(ApiError::resolution_incomplete()),
+        FollowRecordStatus::Mismatch => return Err(ApiError::resolution_mismatch()),
+        FollowRecordStatus::Active => resolved
+            .current
+            .as_ref()
+            .ok_or_else(ApiError::resolution_incomplete)?,
+    };
+    // A melted NFT keeps its last p2 in history; paying it would burn funds.
+    let nft = current
+        .nft
+        .as_ref()
+        .filter(|_| !current.melted)
+        .ok_or_else(ApiError::resolution_not_addressable)?;
+    let address = Address::new(nft.p2_puzzle_hash, state.address_prefix.clone())
+        .encode()
+        .map_err(|_| ApiError::resolution_not_addressable())?;
+
+    Ok(HandleAddressResponse {
+        registry_launcher_id: hex32(registry),
+        handle: handle.to_string(),
+        expiration: slot.expiration,
+        resolved_launcher_id: hex32(slot.resolved_launcher_id),
+        p2_puzzle_hash: hex32(nft.p2_puzzle_hash),
+        address,
+        resolved_confirmation_height: current.confirmation_height,
+        indexed_peak_height,
+        confirmed_timestamp,
+    })
+}
+
+fn history_entry_to_item(entry: &StoredHandleHistoryEntry) -> HandleHistoryItem {
+    HandleHistoryItem {
+        action: entry.action.as_str().to_string(),
+        confirmation_height: entry.confirmation_height,
+        registry_coin_id: entry.registry_coin_id.map(hex32),
+        expiration: entry.expiration,
+        owner_launcher_id: hex32(entry.owner_launcher_id),
+        resolved_launcher_id: hex32(entry.resolved_launcher_id),
+        total_price: entry.total_price,
+        update: entry.update.map(|u| HandleHistoryUpdateJson {
+            new_owner_launcher_id: hex32(u.new_owner_launcher_id),
+            new_resolved_launcher_id: hex32(u.new_resolved_launcher_id),
+            minimum_execution_height: u.minimum_execution_height,
+            update_initiator_coin_id: hex32(u.update_initiator_coin_id),
+        }),
+    }
+}
+
+async fn lookup_handle_history(
+    state: &ListenerApiState,
+    handle: &str,
+    query: &HandleHistoryQuery,
+) -> Result<HandleHistoryResponse, ApiError> {
+    if !is_canonical_handle(handle) {
+        return Err(ApiError::invalid_handle());
+    }
+
+    let registry = select_registry(state, query.launcher_id.as_deref())?;
+    let (indexed_peak_height, _confirmed_timestamp) = require_fresh(state).await?;
+
+    // Malformed cursors restart from the newest entry, as on `/expiring`.
+    let before = query.cursor.as_deref().and_then(decode_history_cursor);
+    let limit = query.limit.unwrap_or(50).min(50) as usize;
+    let handle_hash: Bytes32 = handle.tree_hash().into();
+    let mut entries = state
+        .handle_history
+        .list(registry, handle_hash, before, limit.saturating_add(1))
+        .await;
+    if entries.is_empty() && before.is_none() {
+        return Err(ApiError::handle_not_found());
+    }
+
+    let next_cursor = if entries.len() > limit {
+        entries.truncate(limit);
+        entries.last().map(|e| encode_history_cursor(e.position()))
+    } else {
+        None
+    };
+
+    // Readable after Handle Expiration, like `/registrations/{handle}`.
+    Ok(HandleHistoryResponse {
+        registry_launcher_id: hex32(registry),
+        handle: handle.to_string(),
+        items: entries.iter().map(history_entry_to_item).collect(),
+        next_cursor,
+        indexed_peak_height,
+    })
+}
+
 async fn lookup_registration(
     state: &ListenerApiState,
     handle: &str,
@@ -440,6 +714,86 @@ async fn lookup_registrations_recent(
     })
 }
 
+fn activity_json(activity: &RegistryActivity) -> StatsActivityJson {
+    StatsActivityJson {
+        registrations: activity.registrations,
+        extensions: activity.extensions,
+        expires: activity.expires,
+        protocol_fees: activity.protocol_fees,
+        premium_revenue: activity.premium_revenue,
+    }
+}
+
+async fn lookup_stats(
+    state: &ListenerApiState,
+    query: &StatsQuery,
+) -> Result<StatsResponse, ApiError> {
+    let registry = select_registry(state, query.launcher_id.as_deref())?;
+    let bucket =
+        StatsBucket::parse(query.bucket.as_deref()).ok_or_else(ApiError::invalid_stats_bucket)?;
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(state).await?;
+
+    let to = query.to.unwrap_or(confirmed_timestamp);
+    let from = query
+        .from
+        .unwrap_or_else(|| to.saturating_sub(bucket.default_span_days() * SECONDS_PER_DAY));
+    if from > to {
+        return Err(ApiError::invalid_stats_range());
+    }
+
+    let stats = state.registrations.get_stats(registry).await;
+    let buckets = bucket_activity(
+        &stats.daily_activity,
+        bucket,
+        from / SECONDS_PER_DAY,
+        to / SECONDS_PER_DAY,
+    )
+    .ok_or_else(ApiError::invalid_stats_range)?;
+    let mut totals = RegistryActivity::default();
+    for (_, activity) in &buckets {
+        totals.add(activity);
+    }
+    let mut all_time = RegistryActivity::default();
+    for activity in stats.daily_activity.values() {
+        all_time.add(activity);
+    }
+
+    // Point-in-time counts use the same clock as the expiration gates.
+    let lengths = state
+        .handle_slots
+        .count_named_by_length(registry, state.now_unix(), state.registrations.as_ref())
+        .await;
+    let active_handles = lengths.values().map(|c| c.active).sum();
+    let expired_handles = lengths.values().map(|c| c.expired).sum();
+
+    Ok(StatsResponse {
+        registry_launcher_id: hex32(registry),
+        bucket: bucket.as_str().to_string(),
+        buckets: buckets
+            .iter()
+            .map(|(start_day, activity)| StatsBucketJson {
+                start: start_day * SECONDS_PER_DAY,
+                activity: activity_json(activity),
+            })
+            .collect(),
+        totals: activity_json(&totals),
+        all_time: activity_json(&all_time),
+        total_registered: stats.total_registered,
+        active_handles,
+        expired_handles,
+        handle_lengths: lengths
+            .into_iter()
+            .map(|(length, count)| HandleLengthJson {
+                length,
+                active: count.active,
+                expired: count.expired,
+            })
+            .collect(),
+        indexed_peak_height,
+        confirmed_timestamp,
+    })
+}
+
 /// Same payload as `GET /handle/{handle}/pending-transfer` 200, or `None` for 204.
 async fn performable_pending_transfer(
     state: &ListenerApiState,
@@ -633,6 +987,173 @@ async fn lookup_expiring_soon(
     })
 }
 
+async fn lookup_search(
+    state: &ListenerApiState,
+    query: &SearchQuery,
+) -> Result<HandleSearchResponse, ApiError> {
+    let term = query
+        .q
+        .as_deref()
+        .filter(|q| is_search_term(q))
+        .ok_or_else(ApiError::invalid_search_term)?;
+    let mode =
+        HandleSearchMode::parse(query.mode.as_deref()).ok_or_else(ApiError::invalid_search_mode)?;
+    let registries = match query.launcher_id.as_deref() {
+        None => state.registry_launcher_ids.clone(),
+        Some(raw) => vec![select_registry(state, Some(raw))?],
+    };
+    let (indexed_peak_height, _confirmed_timestamp) = require_fresh(state).await?;
+
+    // `available` narrows the same inclusive window the projection is indexed on.
+    let now = state.now_unix();
+    let mut min_expiration = query.min_expiration.unwrap_or(0);
+    let mut max_expiration = query.max_expiration.unwrap_or(u64::MAX);
+    match query.available {
+        Some(true) => max_expiration = max_expiration.min(now),
+        Some(false) => min_expiration = min_expiration.max(now.saturating_add(1)),
+        None => {}
+    }
+    let filter = HandleSearchFilter {
+        mode,
+        term: term.to_string(),
+        min_expiration,
+        max_expiration,
+    };
+
+    let limit = query.limit.unwrap_or(50).min(50) as usize;
+    let mut ranked = Vec::new();
+    if min_expiration <= max_expiration {
+        for registry in registries {
+            let named = state
+                .handle_slots
+                .search_named(
+                    registry,
+                    &filter,
+                    SEARCH_CANDIDATE_LIMIT,
+                    state.registrations.as_ref(),
+                )
+                .await;
+            for (handle, slot) in named {
+                if let Some(rank) = filter.rank(&handle) {
+                    ranked.push((rank, handle, registry, slot.expiration));
+                }
+            }
+        }
+    }
+    ranked.sort_by(|a, b| (a.0, &a.1, a.2.as_ref()).cmp(&(b.0, &b.1, b.2.as_ref())));
+    let items = ranked
+        .into_iter()
+        .take(limit)
+        .map(|(rank, handle, registry, expiration)| HandleSearchItem {
+            registry_launcher_id: hex32(registry),
+            handle,
+            expiration,
+            available: now >= expiration,
+            distance: (mode == HandleSearchMode::Fuzzy).then_some(rank as u32),
+        })
+        .collect();
+
+    Ok(HandleSearchResponse {
+        items,
+        indexed_peak_height,
+    })
+}
+
+async fn get_search(
+    State(state): State<ListenerApiState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_search(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_search(
+    State(state): State<ListenerApiState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_search(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn lookup_launcher_handles(
+    state: &ListenerApiState,
+    role: HandleLauncherRole,
+    raw_launcher_id: &str,
+    query: &LauncherHandlesQuery,
+) -> Result<LauncherHandlesResponse, ApiError> {
+    let launcher_id =
+        parse_launcher_id(raw_launcher_id).ok_or_else(ApiError::invalid_launcher_id)?;
+    let registries = match query.registry_launcher_id.as_deref() {
+        None => state.registry_launcher_ids.clone(),
+        Some(raw) => vec![select_registry(state, Some(raw))?],
+    };
+    let (indexed_peak_height, _confirmed_timestamp) = require_fresh(state).await?;
+
+    let now = state.now_unix();
+    let items = state
+        .handle_slots
+        .list_named_by_launcher(role, launcher_id, state.registrations.as_ref())
+        .await
+        .into_iter()
+        // Same suppression as the unified proof: expired Handles are no longer active.
+        .filter(|(_, slot)| {
+            registries.contains(&slot.registry_launcher_id) && now < slot.expiration
+        })
+        .map(|(handle, slot)| LauncherHandleItem {
+            registry_launcher_id: hex32(slot.registry_launcher_id),
+            handle,
+            slot: slot_to_json(&slot),
+            slot_confirmation_height: slot.confirmation_height,
+        })
+        .collect();
+
+    Ok(LauncherHandlesResponse {
+        launcher_id: hex32(launcher_id),
+        items,
+        indexed_peak_height,
+    })
+}
+
+async fn get_owner_handles(
+    State(state): State<ListenerApiState>,
+    Path(launcher_id): Path<String>,
+    Query(query): Query<LauncherHandlesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body =
+        lookup_launcher_handles(&state, HandleLauncherRole::Owner, &launcher_id, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_owner_handles(
+    State(state): State<ListenerApiState>,
+    Path(launcher_id): Path<String>,
+    Query(query): Query<LauncherHandlesQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ =
+        lookup_launcher_handles(&state, HandleLauncherRole::Owner, &launcher_id, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_resolved_handles(
+    State(state): State<ListenerApiState>,
+    Path(launcher_id): Path<String>,
+    Query(query): Query<LauncherHandlesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body =
+        lookup_launcher_handles(&state, HandleLauncherRole::Resolved, &launcher_id, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_resolved_handles(
+    State(state): State<ListenerApiState>,
+    Path(launcher_id): Path<String>,
+    Query(query): Query<LauncherHandlesQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ =
+        lookup_launcher_handles(&state, HandleLauncherRole::Resolved, &launcher_id, &query).await?;
+    Ok(StatusCode::OK)
+}
+
 async fn get_expiring(
     State(state): State<ListenerApiState>,
     Query(query): Query<ExpiringQuery>,
@@ -667,12 +1188,9 @@ async fn head_expiring(
     }
 }
 
-async fn lookup_price(
-    state: &ListenerApiState,
-    query: &PriceQuery,
-) -> Result<PriceResponse, ApiError> {
-    let registry = select_registry(state, query.launcher_id.as_deref())?;
-    let (indexed_peak_height, confirmed_timestamp) = require_fresh(state).await?;
+/// Base price the registry's Price Singleton has committed, else its configured pricing.
+/// Base price the registry's pricing puzzle commits to, falling back to the schedule.
+pub async fn committed_base_price(state: &ListenerApiState, registry: Bytes32) -> u64 {
     let committed = state
         .committed_base_price
         .read()
@@ -680,9 +1198,127 @@ async fn lookup_price(
         .get(&registry)
         .copied();
     let pricing = state.registry_pricing.read().await.get(&registry).copied();
-    let current_base_price = committed
+    committed
         .or_else(|| pricing.map(|p| p.base_price))
-        .unwrap_or(1);
+        .unwrap_or(LAUNCH_BASE_PRICE)
+}
+
+/// Cart size and period bounds accepted by `POST /quote`.
+const MAX_QUOTE_HANDLES: usize = 100;
+const MAX_QUOTE_PERIODS: u64 = 100;
+
+fn quote_item(
+    handle: &str,
+    slot: Option<&StoredHandleSlot>,
+    base_price: u64,
+    num_periods: u64,
+    now: u64,
+    projected: u64,
+) -> QuoteItem {
+    let mut item = QuoteItem {
+        handle: handle.to_string(),
+        canonical: is_canonical_handle(handle),
+        status: "invalid".to_string(),
+        expiration: slot.map(|s| s.expiration),
+        base_registration_fee: None,
+        current_premium: None,
+        total_registration_fee: None,
+    };
+    if !item.canonical {
+        return item;
+    }
+    // Same purchasable bound as `/expiring?view=active`: expired now and at the projection.
+    let expiration = slot.map(|s| s.expiration).unwrap_or(0);
+    let purchasable = slot.is_none() || expiration <= now.min(projected);
+    let premium = auction_premium(expiration, projected);
+    item.status = if !purchasable {
+        if expiration <= now.saturating_add(SOON_WINDOW_SECONDS) {
+            "expiring"
+        } else {
+            "registered"
+        }
+    } else if premium > 0 {
+        "in_auction"
+    } else {
+        "available"
+    }
+    .to_string();
+    if purchasable {
+        item.base_registration_fee = Some(period_registration_fee(base_price, handle, num_periods));
+        item.current_premium = Some(premium);
+        item.total_registration_fee = Some(total_registration_fee(
+            base_price,
+            handle,
+            num_periods,
+            expiration,
+            projected,
+        ));
+    }
+    item
+}
+
+async fn lookup_quote(
+    state: &ListenerApiState,
+    request: &QuoteRequest,
+) -> Result<QuoteResponse, ApiError> {
+    let num_periods = request.num_periods.unwrap_or(1);
+    if request.handles.is_empty()
+        || request.handles.len() > MAX_QUOTE_HANDLES
+        || !(1..=MAX_QUOTE_PERIODS).contains(&num_periods)
+    {
+        return Err(ApiError::invalid_quote_request());
+    }
+    let registry = select_registry(state, request.launcher_id.as_deref())?;
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(state).await?;
+    let now = state.now_unix();
+    let projected = projected_pricing_timestamp(confirmed_timestamp);
+    let base_price = committed_base_price(state, registry).await;
+
+    let mut items = Vec::with_capacity(request.handles.len());
+    for handle in &request.handles {
+        let slot = if is_canonical_handle(handle) {
+            let handle_hash: Bytes32 = handle.tree_hash().into();
+            state
+                .handle_slots
+                .get(registry, handle_hash)
+                .await
+                .and_then(|record| record.current)
+        } else {
+            None
+        };
+        items.push(quote_item(
+            handle,
+            slot.as_ref(),
+            base_price,
+            num_periods,
+            now,
+            projected,
+        ));
+    }
+    let total = items
+        .iter()
+        .filter_map(|item| item.total_registration_fee)
+        .fold(0u64, u64::saturating_add);
+
+    Ok(QuoteResponse {
+        registry_launcher_id: hex32(registry),
+        num_periods,
+        base_price,
+        projected_pricing_timestamp: projected,
+        items,
+        total,
+        indexed_peak_height,
+        confirmed_timestamp,
+    })
+}
+
+async fn lookup_price(
+    state: &ListenerApiState,
+    query: &PriceQuery,
+) -> Result<PriceResponse, ApiError> {
+    let registry = select_registry(state, query.launcher_id.as_deref())?;
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(state).await?;
+    let current_base_price = committed_base_price(state, registry).await;
     let start = remaining_unroll_start(
         &state.price_schedule,
         current_base_price,
@@ -723,6 +1359,15 @@ async fn head_price(
     Ok(StatusCode::OK)
 }
 
+async fn post_quote(
+    State(state): State<ListenerApiState>,
+    body: Result<Json<QuoteRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(request) = body.map_err(|_| ApiError::invalid_quote_request())?;
+    let body = lookup_quote(&state, &request).await?;
+    Ok(Json(body))
+}
+
 async fn get_schedule(
     State(state): State<ListenerApiState>,
     Query(query): Query<PriceQuery>,
@@ -739,6 +1384,20 @@ async fn head_schedule(
     Ok(StatusCode::OK)
 }
 
+async fn get_readyz(State(state): State<ListenerApiState>) -> Result<impl IntoResponse, ApiError> {
+    let body = require_ready(&state).await?;
+    Ok(Json(body))
+}
+
+async fn head_readyz(State(state): State<ListenerApiState>) -> Result<StatusCode, ApiError> {
+    let _ = require_ready(&state).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_status(State(state): State<ListenerApiState>) -> impl IntoResponse {
+    Json(lookup_status(&state).await)
+}
+
 async fn get_singleton(
     State(state): State<ListenerApiState>,
     Path(launcher_id_raw): Path<String>,
@@ -779,6 +1438,96 @@ async fn head_handle(
     Ok(StatusCode::OK)
 }
 
+async fn get_handle_history(
+    State(state): State<ListenerApiState>,
+    Path(handle): Path<String>,
+    Query(query): Query<HandleHistoryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_handle_history(&state, &handle, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_handle_history(
+    State(state): State<ListenerApiState>,
+    Path(handle): Path<String>,
+    Query(query): Query<HandleHistoryQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_handle_history(&state, &handle, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn get_handle_address(
+    State(state): State<ListenerApiState>,
+    Path(handle): Path<String>,
+    Query(query): Query<HandleAddressQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_handle_address(&state, &handle, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_handle_address(
+    State(state): State<ListenerApiState>,
+    Path(handle): Path<String>,
+    Query(query): Query<HandleAddressQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_handle_address(&state, &handle, &query).await?;
+    Ok(StatusCode::OK)
+}
+
+fn event_message(event: &HandleEvent) -> Event {
+    Event::default()
+        .id(event.cursor().to_string())
+        .event(event.kind.as_str())
+        .data(serde_json::to_string(&event.to_json()).unwrap_or_default())
+}
+
+/// SSE feed for one registry. No freshness gate: subscribers see retractions
+/// instead of stale reads, and an idle stream is kept open by comments.
+async fn get_events(
+    State(state): State<ListenerApiState>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let registry = select_registry(&state, query.launcher_id.as_deref())?;
+    let cursor = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .or(query.cursor.as_deref());
+    let resume = match (cursor, query.from_height) {
+        (Some(raw), _) => HandleEventResume::After(
+            HandleEventCursor::parse(raw).ok_or_else(ApiError::invalid_event_cursor)?,
+        ),
+        (None, Some(height)) => HandleEventResume::FromHeight(height),
+        (None, None) => HandleEventResume::Live,
+    };
+
+    let subscription = state.events.subscribe(registry, resume);
+    let mut prelude: Vec<Event> = Vec::new();
+    if let Some(from_height) = subscription.reset_from_height {
+        prelude.push(
+            Event::default()
+                .event("reset")
+                .data(json!({ "from_height": from_height }).to_string()),
+        );
+    }
+    prelude.extend(subscription.replay.iter().map(event_message));
+
+    let live = stream::unfold(subscription.live, move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if event.registry_launcher_id == registry => {
+                    return Some((event_message(&event), rx));
+                }
+                Ok(_) => continue,
+                // A lagged subscriber reconnects with `Last-Event-ID` and replays the gap.
+                Err(_) => return None,
+            }
+        }
+    });
+    let events = stream::iter(prelude).chain(live).map(Ok);
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 async fn get_registration(
     State(state): State<ListenerApiState>,
     Path(handle): Path<String>,
@@ -813,6 +1562,22 @@ async fn head_registrations_recent(
     Ok(StatusCode::OK)
 }
 
+async fn get_stats(
+    State(state): State<ListenerApiState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = lookup_stats(&state, &query).await?;
+    Ok(Json(body))
+}
+
+async fn head_stats(
+    State(state): State<ListenerApiState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<StatusCode, ApiError> {
+    let _ = lookup_stats(&state, &query).await?;
+    Ok(StatusCode::OK)
+}
+
 async fn get_pending_transfer(
     State(state): State<ListenerApiState>,
     Path(handle): Path<String>,
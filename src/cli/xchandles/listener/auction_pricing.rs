@@ -74,14 +74,23 @@ fn premium_after_elapsed(elapsed: u64) -> u64 {
     premium.saturating_sub(end_value)
 }
 
-/// Total registration fee = one-year base + current auction premium.
+/// Base fee for `num_periods` registration periods under the current confirmed base price.
+pub fn period_registration_fee(base_price: u64, handle: &str, num_periods: u64) -> u64 {
+    XchandlesFactorPricingPuzzleArgs::get_price(base_price, handle, num_periods)
+}
+
+/// Total registration fee = `num_periods` of base + current auction premium.
+///
+/// Never-registered Handles pass `expiration = 0`, which carries no premium.
 pub fn total_registration_fee(
     base_price: u64,
     handle: &str,
+    num_periods: u64,
     expiration: u64,
     buy_time: u64,
 ) -> u64 {
-    base_registration_fee(base_price, handle).saturating_add(auction_premium(expiration, buy_time))
+    period_registration_fee(base_price, handle, num_periods)
+        .saturating_add(auction_premium(expiration, buy_time))
 }
 
 #[cfg(test)]
@@ -195,10 +204,18 @@ mod tests {
         let premium = auction_premium(expiration, buy);
         let expected_base = base_registration_fee(base, handle);
         assert_eq!(
-            total_registration_fee(base, handle, expiration, buy),
+            total_registration_fee(base, handle, 1, expiration, buy),
             expected_base + premium
         );
         assert_eq!(expected_base, base * 16); // length 5 letters → factor 16
+        assert_eq!(
+            total_registration_fee(base, handle, 3, expiration, buy),
+            3 * expected_base + premium
+        );
+        assert_eq!(
+            total_registration_fee(base, handle, 2, 0, buy),
+            2 * expected_base
+        );
     }
 
     #[test]
@@ -120,6 +120,14 @@ impl ApiError {
         )
     }
 
+    pub fn resolution_not_addressable() -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            "resolution_not_addressable",
+            "Resolved Singleton is melted or is not an NFT with a p2 puzzle hash",
+        )
+    }
+
     pub fn handle_expired(expiration: u64) -> Self {
         Self::new(
             StatusCode::GONE,
@@ -129,6 +137,46 @@ impl ApiError {
         .with_details(json!({ "expiration": expiration }))
     }
 
+    pub fn invalid_handle_hash() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_handle_hash",
+            "Handle hash must be 32-byte lowercase hex without a required 0x prefix",
+        )
+    }
+
+    pub fn invalid_asset_id() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_asset_id",
+            "Asset ID must be 32-byte lowercase hex without a required 0x prefix",
+        )
+    }
+
+    pub fn cat_not_found() -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "cat_not_found",
+            "No live CATalog slot brackets this asset ID",
+        )
+    }
+
+    pub fn cat_not_registered() -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "cat_not_registered",
+            "No CATalog registration is indexed for this asset ID",
+        )
+    }
+
+    pub fn storage_error() -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "storage_error",
+            "Listener storage read failed",
+        )
+    }
+
     pub fn invalid_view() -> Self {
         Self::new(
             StatusCode::BAD_REQUEST,
@@ -136,6 +184,127 @@ impl ApiError {
             "Expiring view must be exactly active or soon",
         )
     }
+
+    pub fn invalid_search_term() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_search_term",
+            "Search term must be 1-63 lowercase ASCII alphanumeric characters",
+        )
+    }
+
+    pub fn invalid_search_mode() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_search_mode",
+            "Search mode must be exactly prefix, substring, or fuzzy",
+        )
+    }
+
+    pub fn invalid_quote_request() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_quote_request",
+            "Quote body must be JSON with 1-100 handles and num_periods between 1 and 100",
+        )
+    }
+
+    pub fn invalid_register_request() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_register_request",
+            "Register body must be JSON with a canonical handle, 32-byte hex launcher ids and secret, a refund address on this network, and num_periods between 1 and 100",
+        )
+    }
+
+    pub fn registry_unsynced() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "registry_unsynced",
+            "Registry state has not been synced since the listener started",
+        )
+    }
+
+    pub fn payment_not_accepted(base_price: u64) -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            "payment_not_accepted",
+            "Registry does not price this payment asset at its committed base price",
+        )
+        .with_details(json!({ "base_price": base_price }))
+    }
+
+    pub fn handle_not_available() -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            "handle_not_available",
+            "Handle already has a live slot or no bracketing neighbor slots are indexed",
+        )
+    }
+
+    pub fn nft_inner_puzzle_unknown() -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            "nft_inner_puzzle_unknown",
+            "NFT inner puzzle hash was not supplied and the NFT is not followed as a live singleton",
+        )
+    }
+
+    pub fn register_build_failed() -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "register_build_failed",
+            "Register spend could not be built from the indexed registry state",
+        )
+    }
+
+    pub fn invalid_stats_bucket() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_stats_bucket",
+            "Stats bucket must be exactly day, week, or month",
+        )
+    }
+
+    pub fn invalid_stats_range() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_stats_range",
+            "Stats range must have from <= to and span at most 400 buckets",
+        )
+    }
+
+    pub fn invalid_event_cursor() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_event_cursor",
+            "Event cursor must be {height}-{seq} as sent in the SSE id field",
+        )
+    }
+
+    pub fn distributor_not_followed() -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "distributor_not_followed",
+            "Reward distributor launcher is not in the listener's configured follow set",
+        )
+    }
+
+    pub fn invalid_puzzle_hash() -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_puzzle_hash",
+            "Puzzle hash must be 32-byte lowercase hex without a required 0x prefix",
+        )
+    }
+
+    pub fn entry_not_found() -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "entry_not_found",
+            "No live entry slot is indexed for this payout puzzle hash",
+        )
+    }
 }
 
 impl IntoResponse for ApiError {
@@ -0,0 +1,343 @@
+//! Push feed behind `GET /events`: typed per-registry Handle events.
+//!
+//! The indexer announces each projected action once. A rollback announces one
+//! `retracted` event per earlier announcement at or above the rollback height, so
+//! subscribers never have to diff REST reads to notice a reorg.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use chia_protocol::Bytes32;
+use tokio::sync::broadcast;
+
+use super::types::{hex32, HandleEventJson};
+
+/// Announcements and retractions retained for cursor replay.
+pub const EVENT_BUFFER_LEN: usize = 10_000;
+
+/// Live events a slow subscriber may fall behind before its stream is closed.
+const EVENT_CHANNEL_CAPACITY: usize = 1_024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleEventKind {
+    Registered,
+    Expired,
+    Extended,
+    UpdateInitiated,
+    UpdateExecuted,
+    Retracted,
+}
+
+impl HandleEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Registered => "registered",
+            Self::Expired => "expired",
+            Self::Extended => "extended",
+            Self::UpdateInitiated => "update_initiated",
+            Self::UpdateExecuted => "update_executed",
+            Self::Retracted => "retracted",
+        }
+    }
+}
+
+/// Resume point rendered as `{height}-{seq}` in the SSE `id` field.
+///
+/// `seq` is process-local; `height` lets a cursor outlive a restart or eviction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandleEventCursor {
+    pub height: u32,
+    pub seq: u64,
+}
+
+impl HandleEventCursor {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (height, seq) = raw.split_once('-')?;
+        Some(Self {
+            height: height.parse().ok()?,
+            seq: seq.parse().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for HandleEventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.height, self.seq)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandleEvent {
+    /// Assigned by [`HandleEventFeed::publish`]; ignored on input.
+    pub seq: u64,
+    pub registry_launcher_id: Bytes32,
+    pub height: u32,
+    pub kind: HandleEventKind,
+    /// `None` when no registration fact names the hash yet.
+    pub handle: Option<String>,
+    pub handle_hash: Bytes32,
+    pub expiration: Option<u64>,
+    pub owner_launcher_id: Option<Bytes32>,
+    pub resolved_launcher_id: Option<Bytes32>,
+    pub minimum_execution_height: Option<u32>,
+    /// Announcement withdrawn by a `retracted` event.
+    pub retracts: Option<HandleEventCursor>,
+}
+
+impl HandleEvent {
+    pub fn new(
+        registry_launcher_id: Bytes32,
+        height: u32,
+        kind: HandleEventKind,
+        handle_hash: Bytes32,
+    ) -> Self {
+        Self {
+            seq: 0,
+            registry_launcher_id,
+            height,
+            kind,
+            handle: None,
+            handle_hash,
+            expiration: None,
+            owner_launcher_id: None,
+            resolved_launcher_id: None,
+            minimum_execution_height: None,
+            retracts: None,
+        }
+    }
+
+    pub fn cursor(&self) -> HandleEventCursor {
+        HandleEventCursor {
+            height: self.height,
+            seq: self.seq,
+        }
+    }
+
+    pub fn to_json(&self) -> HandleEventJson {
+        HandleEventJson {
+            cursor: self.cursor().to_string(),
+            registry_launcher_id: hex32(self.registry_launcher_id),
+            height: self.height,
+            kind: self.kind.as_str().to_string(),
+            handle: self.handle.clone(),
+            handle_hash: hex32(self.handle_hash),
+            expiration: self.expiration,
+            owner_launcher_id: self.owner_launcher_id.map(hex32),
+            resolved_launcher_id: self.resolved_launcher_id.map(hex32),
+            minimum_execution_height: self.minimum_execution_height,
+            retracts: self.retracts.map(|c| c.to_string()),
+        }
+    }
+}
+
+/// Where a new subscriber starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleEventResume {
+    /// Only events published after subscribing.
+    Live,
+    /// Still-standing announcements confirmed at or above the height, then live.
+    FromHeight(u32),
+    /// Everything after a previously delivered event, retractions included, then live.
+    After(HandleEventCursor),
+}
+
+pub struct HandleEventSubscription {
+    pub replay: Vec<HandleEvent>,
+    pub live: broadcast::Receiver<HandleEvent>,
+    /// Set when an `After` cursor was no longer retained and replay fell back to
+    /// height; the subscriber must re-read anything it learned above that height.
+    pub reset_from_height: Option<u32>,
+}
+
+struct FeedLog {
+    next_seq: u64,
+    events: VecDeque<HandleEvent>,
+    retracted: HashSet<u64>,
+}
+
+pub struct HandleEventFeed {
+    log: Mutex<FeedLog>,
+    sender: broadcast::Sender<HandleEvent>,
+}
+
+impl Default for HandleEventFeed {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            log: Mutex::new(FeedLog {
+                next_seq: 1,
+                events: VecDeque::new(),
+                retracted: HashSet::new(),
+            }),
+            sender,
+        }
+    }
+}
+
+impl HandleEventFeed {
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Sequence, retain and broadcast one event; returns it with `seq` assigned.
+    pub fn publish(&self, event: HandleEvent) -> HandleEvent {
+        let mut log = self.log.lock().expect("event feed lock");
+        self.push(&mut log, event)
+    }
+
+    /// Retract every standing announcement confirmed at or above `from_height`, newest first.
+    pub fn retract_from(&self, from_height: u32) -> usize {
+        let mut log = self.log.lock().expect("event feed lock");
+        let standing: Vec<HandleEvent> = log
+            .events
+            .iter()
+            .rev()
+            .filter(|e| {
+                e.kind != HandleEventKind::Retracted
+                    && e.height >= from_height
+                    && !log.retracted.contains(&e.seq)
+            })
+            .cloned()
+            .collect();
+        for announced in &standing {
+            log.retracted.insert(announced.seq);
+            self.push(
+                &mut log,
+                HandleEvent {
+                    height: from_height,
+                    kind: HandleEventKind::Retracted,
+                    retracts: Some(announced.cursor()),
+                    ..announced.clone()
+                },
+            );
+        }
+        standing.len()
+    }
+
+    // Sending under the log lock keeps broadcast order identical to `seq` order.
+    fn push(&self, log: &mut FeedLog, mut event: HandleEvent) -> HandleEvent {
+        event.seq = log.next_seq;
+        log.next_seq += 1;
+        log.events.push_back(event.clone());
+        while log.events.len() > EVENT_BUFFER_LEN {
+            if let Some(evicted) = log.events.pop_front() {
+                log.retracted.remove(&evicted.seq);
+            }
+        }
+        let _ = self.sender.send(event.clone());
+        event
+    }
+
+    pub fn subscribe(
+        &self,
+        registry_launcher_id: Bytes32,
+        resume: HandleEventResume,
+    ) -> HandleEventSubscription {
+        let log = self.log.lock().expect("event feed lock");
+        let live = self.sender.subscribe();
+        let for_registry = |e: &&HandleEvent| e.registry_launcher_id == registry_launcher_id;
+        let standing_from = |height: u32| -> Vec<HandleEvent> {
+            log.events
+                .iter()
+                .filter(for_registry)
+                .filter(|e| {
+                    e.kind != HandleEventKind::Retracted
+                        && e.height >= height
+                        && !log.retracted.contains(&e.seq)
+                })
+                .cloned()
+                .collect()
+        };
+
+        let (replay, reset_from_height) = match resume {
+            HandleEventResume::Live => (Vec::new(), None),
+            HandleEventResume::FromHeight(height) => (standing_from(height), None),
+            HandleEventResume::After(cursor) => {
+                let retained = log
+                    .events
+                    .iter()
+                    .any(|e| e.seq == cursor.seq && e.height == cursor.height);
+                if retained {
+                    let replay = log
+                        .events
+                        .iter()
+                        .filter(for_registry)
+                        .filter(|e| e.seq > cursor.seq)
+                        .cloned()
+                        .collect();
+                    (replay, None)
+                } else {
+                    let height = cursor.height.saturating_add(1);
+                    (standing_from(height), Some(height))
+                }
+            }
+        };
+        HandleEventSubscription {
+            replay,
+            live,
+            reset_from_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn b32(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn registered(registry: Bytes32, height: u32, tag: u8) -> HandleEvent {
+        HandleEvent::new(registry, height, HandleEventKind::Registered, b32(tag))
+    }
+
+    #[test]
+    fn rollback_retracts_newest_first_and_hides_from_height_replay() {
+        let feed = HandleEventFeed::default();
+        let first = feed.publish(registered(b32(0xaa), 100, 0x01));
+        let second = feed.publish(registered(b32(0xaa), 110, 0x02));
+        let third = feed.publish(registered(b32(0xaa), 120, 0x03));
+
+        assert_eq!(feed.retract_from(105), 2);
+        // Already-retracted announcements are not retracted twice.
+        assert_eq!(feed.retract_from(105), 0);
+
+        let exact = feed.subscribe(b32(0xaa), HandleEventResume::After(first.cursor()));
+        let retracts: Vec<_> = exact.replay.iter().map(|e| e.retracts).collect();
+        assert_eq!(
+            retracts,
+            [None, None, Some(third.cursor()), Some(second.cursor())]
+        );
+        assert!(exact.replay[2..].iter().all(|e| e.height == 105));
+
+        let standing = feed.subscribe(b32(0xaa), HandleEventResume::FromHeight(0));
+        assert_eq!(standing.replay, vec![first]);
+        assert_eq!(standing.reset_from_height, None);
+    }
+
+    #[test]
+    fn unknown_cursor_falls_back_to_height_and_filters_registry() {
+        let feed = HandleEventFeed::default();
+        feed.publish(registered(b32(0xaa), 100, 0x01));
+        feed.publish(registered(b32(0xbb), 110, 0x02));
+        let later = feed.publish(registered(b32(0xaa), 120, 0x03));
+
+        // A cursor from a previous process: same seq range, different height.
+        let stale = HandleEventCursor {
+            height: 105,
+            seq: 1,
+        };
+        let sub = feed.subscribe(b32(0xaa), HandleEventResume::After(stale));
+        assert_eq!(sub.reset_from_height, Some(106));
+        assert_eq!(sub.replay, vec![later.clone()]);
+
+        assert_eq!(
+            HandleEventCursor::parse(&later.cursor().to_string()),
+            Some(later.cursor())
+        );
+        assert_eq!(HandleEventCursor::parse("120"), None);
+        assert_eq!(HandleEventCursor::parse("a-1"), None);
+    }
+}
@@ -1,5 +1,11 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Blocks the index may trail the upstream peak before reads are refused.
+pub const MAX_PEAK_LAG: u32 = 16;
+
+/// Seconds since the last successful peak before reads are refused.
+pub const MAX_PEAK_AGE_SECONDS: u64 = 300;
+
 /// Freshness gate for every chain-dependent public read.
 #[derive(Debug, Clone)]
 pub struct FreshnessState {
@@ -10,6 +16,9 @@ pub struct FreshnessState {
     pub confirmed_timestamp: u64,
     pub rolling_back: bool,
     pub resyncing: bool,
+    /// Wall-clock time and fork height of the most recent rollback.
+    pub last_reorg_unix: Option<u64>,
+    pub last_reorg_height: Option<u32>,
 }
 
 impl FreshnessState {
@@ -22,6 +31,8 @@ impl FreshnessState {
             confirmed_timestamp: now_unix,
             rolling_back: false,
             resyncing: false,
+            last_reorg_unix: None,
+            last_reorg_height: None,
         }
     }
 
@@ -37,21 +48,30 @@ impl FreshnessState {
             .unwrap_or(0)
     }
 
-    pub fn is_fresh(&self, now_unix: u64) -> bool {
-        if self.rolling_back || self.resyncing {
-            return false;
+    /// Every reason the index is not fresh at `now_unix`; empty when fresh.
+    pub fn stale_reasons(&self, now_unix: u64) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if self.rolling_back {
+            reasons.push("rolling_back");
+        }
+        if self.resyncing {
+            reasons.push("resyncing");
         }
         if self
             .upstream_peak_height
             .saturating_sub(self.indexed_peak_height)
-            > 16
+            > MAX_PEAK_LAG
         {
-            return false;
+            reasons.push("peak_lag");
         }
-        if now_unix.saturating_sub(self.last_successful_peak_unix) > 300 {
-            return false;
+        if now_unix.saturating_sub(self.last_successful_peak_unix) > MAX_PEAK_AGE_SECONDS {
+            reasons.push("peak_age");
         }
-        true
+        reasons
+    }
+
+    pub fn is_fresh(&self, now_unix: u64) -> bool {
+        self.stale_reasons(now_unix).is_empty()
     }
 }
 
@@ -72,4 +92,18 @@ mod tests {
         assert!(!state.resyncing);
         assert!(state.is_fresh(1_000));
     }
+
+    #[test]
+    fn stale_reasons_lists_every_failed_check() {
+        let mut state = FreshnessState::fresh_at(100, 1_000);
+        state.upstream_peak_height = 117;
+        state.rolling_back = true;
+        assert_eq!(
+            state.stale_reasons(1_301),
+            vec!["rolling_back", "peak_lag", "peak_age"]
+        );
+        state.upstream_peak_height = 116;
+        state.rolling_back = false;
+        assert!(state.stale_reasons(1_300).is_empty());
+    }
 }
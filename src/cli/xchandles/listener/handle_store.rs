@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 use chia_protocol::Bytes32;
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use super::registration_store::RegistrationStore;
+use super::search::HandleSearchFilter;
+use crate::CliError;
 
 /// Parent coin id plus compact lineage proof needed to spend a Handle slot.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -43,6 +45,36 @@ pub struct HandleSlotRecord {
     pub history: Vec<StoredHandleSlot>,
 }
 
+/// Named slots of one handle length, split at `expiration > now`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HandleLengthCount {
+    pub active: u64,
+    pub expired: u64,
+}
+
+/// Which launcher a reverse lookup matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandleLauncherRole {
+    Owner,
+    Resolved,
+}
+
+impl HandleLauncherRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    pub fn launcher_of(self, slot: &StoredHandleSlot) -> Bytes32 {
+        match self {
+            Self::Owner => slot.owner_launcher_id,
+            Self::Resolved => slot.resolved_launcher_id,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait HandleSlotStore: Send + Sync {
     async fn get(
@@ -50,8 +82,12 @@ pub trait HandleSlotStore: Send + Sync {
         registry_launcher_id: Bytes32,
         handle_hash: Bytes32,
     ) -> Option<HandleSlotRecord>;
-    async fn upsert(&self, record: HandleSlotRecord);
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32);
+    async fn upsert(&self, record: HandleSlotRecord) -> Result<(), CliError>;
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError>;
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)>;
     /// Named slots with `current.expiration` in `[min_expiration, max_expiration]`,
     /// ordered by `(expiration, handle)`, strictly after `after` when set, at most `limit`.
@@ -65,15 +101,86 @@ pub trait HandleSlotStore: Send + Sync {
         limit: usize,
         registrations: &dyn RegistrationStore,
     ) -> Vec<(String, StoredHandleSlot)>;
+    /// Named slots in `registry` matching `filter`, ordered by handle, at most `limit`.
+    /// Memory uses `registrations` for handle strings; SQLite reads `handle_search_index`.
+    async fn search_named(
+        &self,
+        registry: Bytes32,
+        filter: &HandleSearchFilter,
+        limit: usize,
+        registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)>;
+    /// Named slots in any registry whose current `role` launcher is `launcher_id`,
+    /// ordered by `(handle, registry)`. Served from a secondary index that every
+    /// `upsert`/`remove` (including rollback restores) keeps in step with `current`.
+    /// Memory uses `registrations` for handle strings; SQLite reads `handle_search_index`.
+    async fn list_named_by_launcher(
+        &self,
+        role: HandleLauncherRole,
+        launcher_id: Bytes32,
+        registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)>;
+    /// Named slots in `registry` counted per handle length (in characters).
+    /// Memory uses `registrations` for handle strings; SQLite groups `handle_search_index`.
+    async fn count_named_by_length(
+        &self,
+        registry: Bytes32,
+        now: u64,
+        registrations: &dyn RegistrationStore,
+    ) -> BTreeMap<usize, HandleLengthCount>;
+}
+
+fn count_into(counts: &mut HandleLengthCount, expiration: u64, now: u64) {
+    if expiration > now {
+        counts.active += 1;
+    } else {
+        counts.expired += 1;
+    }
 }
 
 fn key(registry: Bytes32, handle_hash: Bytes32) -> (Bytes32, Bytes32) {
     (registry, handle_hash)
 }
 
+type LauncherIndex = HashMap<(HandleLauncherRole, Bytes32), HashSet<(Bytes32, Bytes32)>>;
+
 #[derive(Default)]
 pub struct MemoryHandleSlotStore {
     inner: RwLock<HashMap<(Bytes32, Bytes32), HandleSlotRecord>>,
+    /// `(role, launcher_id)` -> slot keys whose current state names that launcher.
+    by_launcher: RwLock<LauncherIndex>,
+}
+
+const LAUNCHER_ROLES: [HandleLauncherRole; 2] =
+    [HandleLauncherRole::Owner, HandleLauncherRole::Resolved];
+
+fn unindex_launchers(index: &mut LauncherIndex, record: &HandleSlotRecord) {
+    let Some(slot) = record.current.as_ref() else {
+        return;
+    };
+    let slot_key = key(record.registry_launcher_id, record.handle_hash);
+    for role in LAUNCHER_ROLES {
+        let index_key = (role, role.launcher_of(slot));
+        if let Some(keys) = index.get_mut(&index_key) {
+            keys.remove(&slot_key);
+            if keys.is_empty() {
+                index.remove(&index_key);
+            }
+        }
+    }
+}
+
+fn index_launchers(index: &mut LauncherIndex, record: &HandleSlotRecord) {
+    let Some(slot) = record.current.as_ref() else {
+        return;
+    };
+    let slot_key = key(record.registry_launcher_id, record.handle_hash);
+    for role in LAUNCHER_ROLES {
+        index
+            .entry((role, role.launcher_of(slot)))
+            .or_default()
+            .insert(slot_key);
+    }
 }
 
 impl MemoryHandleSlotStore {
@@ -100,18 +207,28 @@ impl HandleSlotStore for MemoryHandleSlotStore {
             .cloned()
     }
 
-    async fn upsert(&self, record: HandleSlotRecord) {
-        self.inner
-            .write()
-            .await
-            .insert(key(record.registry_launcher_id, record.handle_hash), record);
+    async fn upsert(&self, record: HandleSlotRecord) -> Result<(), CliError> {
+        let slot_key = key(record.registry_launcher_id, record.handle_hash);
+        let mut inner = self.inner.write().await;
+        let mut by_launcher = self.by_launcher.write().await;
+        if let Some(previous) = inner.get(&slot_key) {
+            unindex_launchers(&mut by_launcher, previous);
+        }
+        index_launchers(&mut by_launcher, &record);
+        inner.insert(slot_key, record);
+        Ok(())
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
-        self.inner
-            .write()
-            .await
-            .remove(&key(registry_launcher_id, handle_hash));
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
+        let mut inner = self.inner.write().await;
+        if let Some(previous) = inner.remove(&key(registry_launcher_id, handle_hash)) {
+            unindex_launchers(&mut *self.by_launcher.write().await, &previous);
+        }
+        Ok(())
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -175,6 +292,117 @@ impl HandleSlotStore for MemoryHandleSlotStore {
         out.truncate(limit);
         out
     }
+
+    async fn search_named(
+        &self,
+        registry: Bytes32,
+        filter: &HandleSearchFilter,
+        limit: usize,
+        registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)> {
+        let candidates: Vec<StoredHandleSlot> = self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|record| record.registry_launcher_id == registry)
+            .filter_map(|record| record.current.clone())
+            .collect();
+
+        let mut out = Vec::new();
+        for slot in candidates {
+            let Some(reg_cur) = registrations
+                .get(registry, slot.handle_hash)
+                .await
+                .and_then(|rec| rec.current)
+            else {
+                continue;
+            };
+            if reg_cur.handle.is_empty() || !filter.matches(&reg_cur.handle, slot.expiration) {
+                continue;
+            }
+            out.push((reg_cur.handle, slot));
+        }
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out.truncate(limit);
+        out
+    }
+
+    async fn list_named_by_launcher(
+        &self,
+        role: HandleLauncherRole,
+        launcher_id: Bytes32,
+        registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)> {
+        let slots: Vec<StoredHandleSlot> = {
+            let inner = self.inner.read().await;
+            let by_launcher = self.by_launcher.read().await;
+            by_launcher
+                .get(&(role, launcher_id))
+                .into_iter()
+                .flatten()
+                .filter_map(|slot_key| inner.get(slot_key)?.current.clone())
+                .collect()
+        };
+
+        let mut out = Vec::new();
+        for slot in slots {
+            let Some(reg_cur) = registrations
+                .get(slot.registry_launcher_id, slot.handle_hash)
+                .await
+                .and_then(|rec| rec.current)
+            else {
+                continue;
+            };
+            if reg_cur.handle.is_empty() {
+                continue;
+            }
+            out.push((reg_cur.handle, slot));
+        }
+        out.sort_by(|a, b| {
+            a.0.cmp(&b.0).then_with(|| {
+                a.1.registry_launcher_id
+                    .as_ref()
+                    .cmp(b.1.registry_launcher_id.as_ref())
+            })
+        });
+        out
+    }
+
+    async fn count_named_by_length(
+        &self,
+        registry: Bytes32,
+        now: u64,
+        registrations: &dyn RegistrationStore,
+    ) -> BTreeMap<usize, HandleLengthCount> {
+        let slots: Vec<StoredHandleSlot> = self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|record| record.registry_launcher_id == registry)
+            .filter_map(|record| record.current.clone())
+            .collect();
+        let mut out = BTreeMap::<usize, HandleLengthCount>::new();
+        for slot in slots {
+            let Some(reg_cur) = registrations
+                .get(registry, slot.handle_hash)
+                .await
+                .and_then(|rec| rec.current)
+            else {
+                continue;
+            };
+            if reg_cur.handle.is_empty() {
+                continue;
+            }
+            count_into(
+                out.entry(reg_cur.handle.chars().count()).or_default(),
+                slot.expiration,
+                now,
+            );
+        }
+        out
+    }
 }
 
 /// Retain current state, every replaced state in the last 32 blocks, and one older predecessor.
@@ -257,38 +485,36 @@ impl HandleSlotStore for DbHandleSlotStore {
         serde_json::from_str(&json).ok()
     }
 
-    async fn upsert(&self, record: HandleSlotRecord) {
-        let json = match serde_json::to_string(&record) {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!("handle slot serialize error: {e}");
-                return;
-            }
-        };
+    async fn upsert(&self, record: HandleSlotRecord) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
         let expiration = record
             .current
             .as_ref()
             .map(|slot| slot.expiration)
             .unwrap_or(0);
+        let launchers = record
+            .current
+            .as_ref()
+            .map(|slot| (slot.owner_launcher_id, slot.resolved_launcher_id));
         let db = self.db.lock().await;
-        if let Err(e) = db
-            .upsert_handle_slot_record_json(
-                record.registry_launcher_id,
-                record.handle_hash,
-                &json,
-                expiration,
-            )
-            .await
-        {
-            eprintln!("handle slot upsert error: {e}");
-        }
+        db.upsert_handle_slot_record_json(
+            record.registry_launcher_id,
+            record.handle_hash,
+            &json,
+            expiration,
+            launchers,
+        )
+        .await
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
         let db = self.db.lock().await;
-        let _ = db
-            .delete_handle_slot_record(registry_launcher_id, handle_hash)
-            .await;
+        db.delete_handle_slot_record(registry_launcher_id, handle_hash)
+            .await
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -331,6 +557,70 @@ impl HandleSlotStore for DbHandleSlotStore {
         }
         out
     }
+
+    async fn search_named(
+        &self,
+        registry: Bytes32,
+        filter: &HandleSearchFilter,
+        limit: usize,
+        _registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)> {
+        let db = self.db.lock().await;
+        let rows = db
+            .search_named_handle_slots(registry, filter, limit)
+            .await
+            .unwrap_or_default();
+        let mut out = Vec::new();
+        for (json, handle) in rows {
+            let Ok(record) = serde_json::from_str::<HandleSlotRecord>(&json) else {
+                continue;
+            };
+            let Some(slot) = record.current else {
+                continue;
+            };
+            // SQLite only narrows fuzzy candidates by length.
+            if !filter.matches(&handle, slot.expiration) {
+                continue;
+            }
+            out.push((handle, slot));
+        }
+        out.truncate(limit);
+        out
+    }
+
+    async fn list_named_by_launcher(
+        &self,
+        role: HandleLauncherRole,
+        launcher_id: Bytes32,
+        _registrations: &dyn RegistrationStore,
+    ) -> Vec<(String, StoredHandleSlot)> {
+        let db = self.db.lock().await;
+        let rows = db
+            .list_named_handle_slots_by_launcher(role.as_str(), launcher_id)
+            .await
+            .unwrap_or_default();
+        rows.into_iter()
+            .filter_map(|(json, handle)| {
+                let record = serde_json::from_str::<HandleSlotRecord>(&json).ok()?;
+                Some((handle, record.current?))
+            })
+            .collect()
+    }
+
+    async fn count_named_by_length(
+        &self,
+        registry: Bytes32,
+        now: u64,
+        _registrations: &dyn RegistrationStore,
+    ) -> BTreeMap<usize, HandleLengthCount> {
+        let db = self.db.lock().await;
+        db.count_named_handle_slots_by_length(registry, now)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(length, active, expired)| (length, HandleLengthCount { active, expired }))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -389,7 +679,8 @@ mod tests {
                 current: Some(slot),
                 history: vec![],
             })
-            .await;
+            .await
+            .unwrap();
         let reg = named_reg(registry, handle);
         regs.upsert(RegistrationRecord {
             registry_launcher_id: registry,
@@ -397,7 +688,8 @@ mod tests {
             current: Some(reg),
             history: vec![],
         })
-        .await;
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -421,7 +713,8 @@ mod tests {
                 current: Some(unnamed),
                 history: vec![],
             })
-            .await;
+            .await
+            .unwrap();
 
         let page = slots
             .list_named_in_expiration_window(registry, 10, 30, None, 2, &regs)
@@ -450,4 +743,70 @@ mod tests {
             vec![("carol", 30)]
         );
     }
+
+    async fn names_by_launcher(
+        slots: &MemoryHandleSlotStore,
+        regs: &MemoryRegistrationStore,
+        role: HandleLauncherRole,
+        launcher_id: Bytes32,
+    ) -> Vec<String> {
+        slots
+            .list_named_by_launcher(role, launcher_id, regs)
+            .await
+            .into_iter()
+            .map(|(handle, _)| handle)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn launcher_index_follows_replacement_rollback_and_remove() {
+        let registry = b32(0xaa);
+        let slots = MemoryHandleSlotStore::new();
+        let regs = MemoryRegistrationStore::new();
+        upsert_named(&slots, &regs, registry, "alice", 10).await;
+        let handle_hash: Bytes32 = "alice".tree_hash().into();
+
+        assert_eq!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Owner, b32(0x11)).await,
+            ["alice"]
+        );
+
+        // Transfer at height 120 moves both launchers to 0x12.
+        let mut record = slots.get(registry, handle_hash).await.unwrap();
+        let mut transferred = named_slot(registry, "alice", 10);
+        transferred.owner_launcher_id = b32(0x12);
+        transferred.resolved_launcher_id = b32(0x12);
+        transferred.confirmation_height = 120;
+        push_handle_replacement(&mut record, transferred, 120);
+        slots.upsert(record).await.unwrap();
+        assert!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Owner, b32(0x11))
+                .await
+                .is_empty()
+        );
+        assert_eq!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Resolved, b32(0x12)).await,
+            ["alice"]
+        );
+
+        let mut record = slots.get(registry, handle_hash).await.unwrap();
+        rollback_handle_to_before(&mut record, 120);
+        slots.upsert(record).await.unwrap();
+        assert_eq!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Owner, b32(0x11)).await,
+            ["alice"]
+        );
+        assert!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Resolved, b32(0x12))
+                .await
+                .is_empty()
+        );
+
+        slots.remove(registry, handle_hash).await.unwrap();
+        assert!(
+            names_by_launcher(&slots, &regs, HandleLauncherRole::Owner, b32(0x11))
+                .await
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chia_protocol::Bytes32;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::CliError;
+
+/// Registry action recorded in a Handle's timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleHistoryAction {
+    Register,
+    Expire,
+    Extend,
+    InitiateUpdate,
+    ExecuteUpdate,
+}
+
+impl HandleHistoryAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Register => "register",
+            Self::Expire => "expire",
+            Self::Extend => "extend",
+            Self::InitiateUpdate => "initiate_update",
+            Self::ExecuteUpdate => "execute_update",
+        }
+    }
+}
+
+/// Owner / Resolved change announced by an InitiateUpdate, executable from `minimum_execution_height`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredHandleHistoryUpdate {
+    pub new_owner_launcher_id: Bytes32,
+    pub new_resolved_launcher_id: Bytes32,
+    pub minimum_execution_height: u32,
+    pub update_initiator_coin_id: Bytes32,
+}
+
+/// One confirmed action against a Handle, with the slot state it left behind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredHandleHistoryEntry {
+    pub registry_launcher_id: Bytes32,
+    pub handle_hash: Bytes32,
+    pub confirmation_height: u32,
+    /// Position of the action log within its block, counted across every registry spend
+    /// confirmed there; orders same-height entries.
+    pub log_index: u32,
+    pub action: HandleHistoryAction,
+    /// Registry coin whose spend emitted the action, when its slot lineage was known.
+    pub registry_coin_id: Option<Bytes32>,
+    pub expiration: u64,
+    pub owner_launcher_id: Bytes32,
+    pub resolved_launcher_id: Bytes32,
+    /// Total price paid (base fee plus any premium); `None` for actions without a payment.
+    pub total_price: Option<u64>,
+    pub update: Option<StoredHandleHistoryUpdate>,
+}
+
+impl StoredHandleHistoryEntry {
+    pub fn position(&self) -> (u32, u32) {
+        (self.confirmation_height, self.log_index)
+    }
+}
+
+/// Permanent per-Handle timeline. Entries are only ever removed when a
+/// pre-final reorganization orphans the block that confirmed them.
+#[async_trait::async_trait]
+pub trait HandleHistoryStore: Send + Sync {
+    /// Idempotent per `(registry, handle_hash, confirmation_height, log_index)`.
+    async fn append(&self, entry: StoredHandleHistoryEntry) -> Result<(), CliError>;
+    /// Newest first, strictly before `before` (a `(height, log_index)` position) when given.
+    async fn list(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        before: Option<(u32, u32)>,
+        limit: usize,
+    ) -> Vec<StoredHandleHistoryEntry>;
+    async fn rollback_from(&self, from_height: u32) -> Result<(), CliError>;
+}
+
+type Timeline = BTreeMap<(u32, u32), StoredHandleHistoryEntry>;
+
+#[derive(Default)]
+pub struct MemoryHandleHistoryStore {
+    inner: RwLock<HashMap<(Bytes32, Bytes32), Timeline>>,
+}
+
+impl MemoryHandleHistoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+}
+
+#[async_trait::async_trait]
+impl HandleHistoryStore for MemoryHandleHistoryStore {
+    async fn append(&self, entry: StoredHandleHistoryEntry) -> Result<(), CliError> {
+        self.inner
+            .write()
+            .await
+            .entry((entry.registry_launcher_id, entry.handle_hash))
+            .or_default()
+            .insert(entry.position(), entry);
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        before: Option<(u32, u32)>,
+        limit: usize,
+    ) -> Vec<StoredHandleHistoryEntry> {
+        let inner = self.inner.read().await;
+        let Some(timeline) = inner.get(&(registry_launcher_id, handle_hash)) else {
+            return Vec::new();
+        };
+        timeline
+            .values()
+            .rev()
+            .filter(|e| before.is_none_or(|b| e.position() < b))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    async fn rollback_from(&self, from_height: u32) -> Result<(), CliError> {
+        let mut inner = self.inner.write().await;
+        for timeline in inner.values_mut() {
+            timeline.retain(|(height, _), _| *height < from_height);
+        }
+        inner.retain(|_, timeline| !timeline.is_empty());
+        Ok(())
+    }
+}
+
+/// SQLite-backed Handle timelines used by the production `listen` process.
+pub struct DbHandleHistoryStore {
+    db: Arc<futures::lock::Mutex<crate::Db>>,
+}
+
+impl DbHandleHistoryStore {
+    pub fn new(db: Arc<futures::lock::Mutex<crate::Db>>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl HandleHistoryStore for DbHandleHistoryStore {
+    async fn append(&self, entry: StoredHandleHistoryEntry) -> Result<(), CliError> {
+        let json = serde_json::to_string(&entry)?;
+        let db = self.db.lock().await;
+        db.upsert_handle_history_entry_json(
+            entry.registry_launcher_id,
+            entry.handle_hash,
+            entry.confirmation_height,
+            entry.log_index,
+            &json,
+        )
+        .await
+    }
+
+    async fn list(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+        before: Option<(u32, u32)>,
+        limit: usize,
+    ) -> Vec<StoredHandleHistoryEntry> {
+        let db = self.db.lock().await;
+        db.list_handle_history_json(registry_launcher_id, handle_hash, before, limit)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect()
+    }
+
+    async fn rollback_from(&self, from_height: u32) -> Result<(), CliError> {
+        let db = self.db.lock().await;
+        db.delete_handle_history_from(from_height).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(handle: u8, height: u32, log_index: u32) -> StoredHandleHistoryEntry {
+        StoredHandleHistoryEntry {
+            registry_launcher_id: Bytes32::new([0xaa; 32]),
+            handle_hash: Bytes32::new([handle; 32]),
+            confirmation_height: height,
+            log_index,
+            action: HandleHistoryAction::Extend,
+            registry_coin_id: None,
+            expiration: 1_000,
+            owner_launcher_id: Bytes32::new([0x11; 32]),
+            resolved_launcher_id: Bytes32::new([0x11; 32]),
+            total_price: Some(1),
+            update: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_newest_first_pages_by_position_and_rolls_back() {
+        let store = MemoryHandleHistoryStore::new();
+        for (height, log_index) in [(100, 0), (110, 0), (110, 1), (120, 0)] {
+            store.append(entry(0x01, height, log_index)).await.unwrap();
+        }
+        // Re-applying a transition is idempotent.
+        store.append(entry(0x01, 110, 1)).await.unwrap();
+        store.append(entry(0x02, 105, 0)).await.unwrap();
+
+        let registry = Bytes32::new([0xaa; 32]);
+        let handle = Bytes32::new([0x01; 32]);
+        let positions = |entries: Vec<StoredHandleHistoryEntry>| -> Vec<(u32, u32)> {
+            entries
+                .iter()
+                .map(StoredHandleHistoryEntry::position)
+                .collect()
+        };
+        let first = store.list(registry, handle, None, 2).await;
+        assert_eq!(positions(first), [(120, 0), (110, 1)]);
+        let second = store.list(registry, handle, Some((110, 1)), 2).await;
+        assert_eq!(positions(second), [(110, 0), (100, 0)]);
+
+        store.rollback_from(110).await.unwrap();
+        assert_eq!(
+            positions(store.list(registry, handle, None, 10).await),
+            [(100, 0)]
+        );
+        assert_eq!(
+            store
+                .list(registry, Bytes32::new([0x02; 32]), None, 10)
+                .await
+                .len(),
+            1
+        );
+    }
+}
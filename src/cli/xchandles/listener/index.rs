@@ -5,23 +5,30 @@ use clvm_utils::ToTreeHash;
 use clvmr::Allocator;
 use tokio::sync::RwLock;
 
+use super::auction_pricing::auction_premium;
 use super::discovery::{
     discover_singleton_in_block, follow_singleton_spend, DiscoveryResult, FollowSpendResult,
 };
+use super::events::{HandleEvent, HandleEventFeed, HandleEventKind};
 use super::freshness::FreshnessState;
 use super::handle_store::{
     push_handle_replacement, rollback_handle_to_before, HandleSlotRecord, HandleSlotStore,
     SlotParentLineage, StoredHandleSlot,
 };
+use super::history_store::{
+    HandleHistoryAction, HandleHistoryStore, MemoryHandleHistoryStore, StoredHandleHistoryEntry,
+    StoredHandleHistoryUpdate,
+};
 use super::pending_store::{
     clear_pending_current, push_pending_replacement, rollback_pending_to_before,
     PendingUpdateRecord, PendingUpdateStore, StoredPendingUpdate,
 };
 use super::refs::{dereferenced_launchers, references_from_action_log, SingletonReference};
 use super::registration_store::{
-    push_registration_event, push_registration_replacement, rollback_registration_to_before,
-    rollback_stats_to_before, RegistrationActionKind, RegistrationRecord, RegistrationStore,
-    StoredRegistration, StoredRegistrationEvent,
+    pop_activity_delta, push_activity_delta, push_registration_event,
+    push_registration_replacement, rollback_registration_to_before, rollback_stats_to_before,
+    RegistrationActionKind, RegistrationRecord, RegistrationStore, RegistryActivity,
+    StoredActivityDelta, StoredRegistration, StoredRegistrationEvent, SECONDS_PER_DAY,
 };
 use super::store::{
     push_replacement, rollback_to_before, FollowRecordStatus, FollowedSingleton, SingletonStore,
@@ -30,6 +37,8 @@ use super::store::{
 use chia_wallet_sdk::driver::XchandlesActionLog;
 use chia_wallet_sdk::types::puzzles::XchandlesHandleSlotValue;
 
+use crate::{CliError, ListenerMetrics};
+
 /// Applies registry-transition singleton discovery and subsequent lineage follows.
 pub struct SingletonIndexer {
     pub store: Arc<dyn SingletonStore>,
@@ -37,6 +46,9 @@ pub struct SingletonIndexer {
     pub registrations: Arc<dyn RegistrationStore>,
     pub pending_updates: Arc<dyn PendingUpdateStore>,
     pub freshness: Arc<RwLock<FreshnessState>>,
+    pub metrics: Arc<ListenerMetrics>,
+    pub events: Arc<HandleEventFeed>,
+    pub handle_history: Arc<dyn HandleHistoryStore>,
 }
 
 impl SingletonIndexer {
@@ -53,9 +65,30 @@ impl SingletonIndexer {
             registrations,
             pending_updates,
             freshness,
+            metrics: ListenerMetrics::shared("xchandles"),
+            events: HandleEventFeed::shared(),
+            handle_history: MemoryHandleHistoryStore::shared(),
         }
     }
 
+    /// Report into the process-wide metrics served on `/metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<ListenerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Announce projected actions on the feed served by `/events`.
+    pub fn with_events(mut self, events: Arc<HandleEventFeed>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Persist Handle timelines somewhere other than the default in-memory store.
+    pub fn with_handle_history(mut self, handle_history: Arc<dyn HandleHistoryStore>) -> Self {
+        self.handle_history = handle_history;
+        self
+    }
+
     pub async fn note_peak(
         &self,
         indexed: u32,
@@ -76,6 +109,11 @@ impl SingletonIndexer {
         self.freshness.write().await.rolling_back = true;
     }
 
+    /// Record upstream's peak before it is indexed so `/status` shows the lag.
+    pub async fn note_upstream_peak(&self, upstream: u32) {
+        self.freshness.write().await.upstream_peak_height = upstream;
+    }
+
     pub async fn begin_resync(&self) {
         self.freshness.write().await.resyncing = true;
     }
@@ -87,14 +125,15 @@ impl SingletonIndexer {
         height: u32,
         block_spends: &[CoinSpend],
         logs: &[XchandlesActionLog],
-    ) -> Result<(), String> {
+    ) -> Result<(), CliError> {
         for log in logs {
             for reference in references_from_action_log(log) {
                 self.discover_reference(allocator, height, block_spends, reference)
                     .await?;
             }
-            self.track_slot_reference_changes(height, log).await;
+            self.track_slot_reference_changes(height, log).await?;
         }
+        self.metrics.transitions_projected.inc();
         Ok(())
     }
 
@@ -105,7 +144,7 @@ impl SingletonIndexer {
         value: XchandlesHandleSlotValue,
         parent: SlotParentLineage,
         confirmation_height: u32,
-    ) {
+    ) -> Result<(), CliError> {
         let stored = StoredHandleSlot {
             registry_launcher_id,
             handle_hash: value.handle_hash,
@@ -131,7 +170,8 @@ impl SingletonIndexer {
                 history: Vec::new(),
             });
         push_handle_replacement(&mut record, stored, confirmation_height);
-        self.handle_slots.upsert(record).await;
+        self.handle_slots.upsert(record).await?;
+        Ok(())
     }
 
     /// Project every created Handle slot from action logs.
@@ -141,7 +181,7 @@ impl SingletonIndexer {
         height: u32,
         logs: &[XchandlesActionLog],
         parent_for: impl Fn(Bytes32) -> Option<SlotParentLineage>,
-    ) {
+    ) -> Result<(), CliError> {
         let mut created = Vec::new();
         for log in logs {
             log.extend_created_handle_slots(&mut created);
@@ -150,8 +190,84 @@ impl SingletonIndexer {
             let value_hash: Bytes32 = value.tree_hash().into();
             let parent = parent_for(value_hash).unwrap_or_default();
             self.project_handle_slot(registry_launcher_id, value, parent, height)
-                .await;
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Append every Handle-changing action to its permanent timeline.
+    ///
+    /// `first_log_index` is where this spend's logs start within the block, so several
+    /// registry spends confirmed at one height never share a history position.
+    pub async fn project_handle_history_from_logs(
+        &self,
+        registry_launcher_id: Bytes32,
+        height: u32,
+        first_log_index: u32,
+        logs: &[XchandlesActionLog],
+        parent_for: impl Fn(Bytes32) -> Option<SlotParentLineage>,
+    ) -> Result<(), CliError> {
+        for (log_index, log) in logs.iter().enumerate() {
+            let (action, slot, total_price, update) = match log {
+                XchandlesActionLog::Register(e) => (
+                    HandleHistoryAction::Register,
+                    e.created_handle_slot,
+                    Some(e.total_price),
+                    None,
+                ),
+                XchandlesActionLog::Expire(e) => (
+                    HandleHistoryAction::Expire,
+                    e.created_slot,
+                    Some(e.total_price),
+                    None,
+                ),
+                XchandlesActionLog::Extend(e) => (
+                    HandleHistoryAction::Extend,
+                    e.created_slot,
+                    Some(e.total_price),
+                    None,
+                ),
+                XchandlesActionLog::InitiateUpdate(e) => (
+                    HandleHistoryAction::InitiateUpdate,
+                    e.created_handle_slot,
+                    None,
+                    Some(StoredHandleHistoryUpdate {
+                        new_owner_launcher_id: e.created_update_slot.new_owner_launcher_id,
+                        new_resolved_launcher_id: e.created_update_slot.new_resolved_launcher_id,
+                        minimum_execution_height: e.created_update_slot.min_height,
+                        update_initiator_coin_id: e.created_update_slot.update_initiator_coin_id,
+                    }),
+                ),
+                XchandlesActionLog::ExecuteUpdate(e) => (
+                    HandleHistoryAction::ExecuteUpdate,
+                    e.created_slot,
+                    None,
+                    None,
+                ),
+                XchandlesActionLog::Oracle(_)
+                | XchandlesActionLog::Refund(_)
+                | XchandlesActionLog::DelegatedState(_) => continue,
+            };
+
+            // The created slot's parent is the registry coin spent at this height.
+            let slot_hash: Bytes32 = slot.tree_hash().into();
+            self.handle_history
+                .append(StoredHandleHistoryEntry {
+                    registry_launcher_id,
+                    handle_hash: slot.handle_hash,
+                    confirmation_height: height,
+                    log_index: first_log_index + log_index as u32,
+                    action,
+                    registry_coin_id: parent_for(slot_hash).map(|p| p.parent_coin_id),
+                    expiration: slot.expiration,
+                    owner_launcher_id: slot.owner_launcher_id,
+                    resolved_launcher_id: slot.resolved_launcher_id,
+                    total_price,
+                    update,
+                })
+                .await?;
         }
+        Ok(())
     }
 
     /// Project register/expire registration facts and recent-event feed from action logs.
@@ -160,7 +276,7 @@ impl SingletonIndexer {
         registry_launcher_id: Bytes32,
         height: u32,
         logs: &[XchandlesActionLog],
-    ) {
+    ) -> Result<(), CliError> {
         for log in logs {
             let (action_kind, handle, secret, protocol_fee, handle_hash) = match log {
                 XchandlesActionLog::Register(e) => (
@@ -202,7 +318,7 @@ impl SingletonIndexer {
                     history: Vec::new(),
                 });
             push_registration_replacement(&mut record, stored, height);
-            self.registrations.upsert(record).await;
+            self.registrations.upsert(record).await?;
 
             let mut stats = self.registrations.get_stats(registry_launcher_id).await;
             push_registration_event(
@@ -218,8 +334,66 @@ impl SingletonIndexer {
             }
             self.registrations
                 .set_stats(registry_launcher_id, stats)
-                .await;
+                .await?;
         }
+        Ok(())
+    }
+
+    /// Fold register/extend/expire counts and payments into the block's UTC day, once per
+    /// spent registry coin so replaying a spend already indexed leaves the counts alone.
+    ///
+    /// Expire premiums are priced at the block timestamp; the committed buy time is at or
+    /// before it, so the recorded premium never overstates what was paid.
+    pub async fn project_activity_from_logs(
+        &self,
+        registry_launcher_id: Bytes32,
+        registry_coin_id: Bytes32,
+        height: u32,
+        timestamp: u64,
+        logs: &[XchandlesActionLog],
+    ) -> Result<(), CliError> {
+        let mut activity = RegistryActivity::default();
+        for log in logs {
+            match log {
+                XchandlesActionLog::Register(e) => {
+                    activity.registrations += 1;
+                    activity.protocol_fees = activity.protocol_fees.saturating_add(e.total_price);
+                }
+                XchandlesActionLog::Extend(e) => {
+                    activity.extensions += 1;
+                    activity.protocol_fees = activity.protocol_fees.saturating_add(e.total_price);
+                }
+                XchandlesActionLog::Expire(e) => {
+                    activity.expires += 1;
+                    activity.protocol_fees = activity.protocol_fees.saturating_add(e.total_price);
+                    let premium =
+                        auction_premium(e.spent_slot.expiration, timestamp).min(e.total_price);
+                    activity.premium_revenue = activity.premium_revenue.saturating_add(premium);
+                }
+                _ => {}
+            }
+        }
+        if activity.is_empty() {
+            return Ok(());
+        }
+        let delta = StoredActivityDelta {
+            registry_coin_id,
+            confirmation_height: height,
+            day: timestamp / SECONDS_PER_DAY,
+            activity,
+        };
+        if !self
+            .registrations
+            .record_activity_delta(registry_launcher_id, delta.clone())
+            .await?
+        {
+            return Ok(());
+        }
+        let mut stats = self.registrations.get_stats(registry_launcher_id).await;
+        push_activity_delta(&mut stats, &delta);
+        self.registrations
+            .set_stats(registry_launcher_id, stats)
+            .await
     }
 
     /// Project InitiateUpdate creations and clear pending on execute/invalidate.
@@ -228,7 +402,7 @@ impl SingletonIndexer {
         registry_launcher_id: Bytes32,
         height: u32,
         logs: &[XchandlesActionLog],
-    ) {
+    ) -> Result<(), CliError> {
         for log in logs {
             match log {
                 XchandlesActionLog::InitiateUpdate(e) => {
@@ -253,7 +427,7 @@ impl SingletonIndexer {
                             history: Vec::new(),
                         });
                     push_pending_replacement(&mut record, stored, height);
-                    self.pending_updates.upsert(record).await;
+                    self.pending_updates.upsert(record).await?;
                 }
                 XchandlesActionLog::ExecuteUpdate(e) => {
                     self.clear_pending_for_handle(
@@ -261,7 +435,7 @@ impl SingletonIndexer {
                         e.spent_update_slot.handle_hash,
                         height,
                     )
-                    .await;
+                    .await?;
                 }
                 // Any other action that spends this Handle slot without creating a
                 // replacement update invalidates the pending executor path.
@@ -271,7 +445,7 @@ impl SingletonIndexer {
                         e.spent_slot.handle_hash,
                         height,
                     )
-                    .await;
+                    .await?;
                 }
                 XchandlesActionLog::Oracle(e) => {
                     self.clear_pending_for_handle(
@@ -279,7 +453,7 @@ impl SingletonIndexer {
                         e.spent_slot.handle_hash,
                         height,
                     )
-                    .await;
+                    .await?;
                 }
                 XchandlesActionLog::Expire(e) => {
                     self.clear_pending_for_handle(
@@ -287,7 +461,7 @@ impl SingletonIndexer {
                         e.spent_slot.handle_hash,
                         height,
                     )
-                    .await;
+                    .await?;
                 }
                 XchandlesActionLog::Refund(e) => {
                     if let Some(spent) = e.spent_slot {
@@ -296,12 +470,66 @@ impl SingletonIndexer {
                             spent.handle_hash,
                             height,
                         )
-                        .await;
+                        .await?;
                     }
                 }
                 XchandlesActionLog::Register(_) | XchandlesActionLog::DelegatedState(_) => {}
             }
         }
+        Ok(())
+    }
+
+    /// Announce registry actions projected at `height`; call after the projections so
+    /// Handle names registered in the same block are already known.
+    pub async fn announce_actions_from_logs(
+        &self,
+        registry_launcher_id: Bytes32,
+        height: u32,
+        logs: &[XchandlesActionLog],
+    ) {
+        for log in logs {
+            let (kind, slot, update) = match log {
+                XchandlesActionLog::Register(e) => {
+                    (HandleEventKind::Registered, e.created_handle_slot, None)
+                }
+                XchandlesActionLog::Expire(e) => (HandleEventKind::Expired, e.created_slot, None),
+                XchandlesActionLog::Extend(e) => (HandleEventKind::Extended, e.created_slot, None),
+                XchandlesActionLog::InitiateUpdate(e) => (
+                    HandleEventKind::UpdateInitiated,
+                    e.created_handle_slot,
+                    Some(e.created_update_slot),
+                ),
+                XchandlesActionLog::ExecuteUpdate(e) => {
+                    (HandleEventKind::UpdateExecuted, e.created_slot, None)
+                }
+                // Oracle and refund spends leave the Handle's public state unchanged.
+                XchandlesActionLog::Oracle(_)
+                | XchandlesActionLog::Refund(_)
+                | XchandlesActionLog::DelegatedState(_) => continue,
+            };
+
+            let mut event = HandleEvent::new(registry_launcher_id, height, kind, slot.handle_hash);
+            event.handle = self
+                .registrations
+                .get(registry_launcher_id, slot.handle_hash)
+                .await
+                .and_then(|r| r.current)
+                .map(|r| r.handle);
+            event.expiration = Some(slot.expiration);
+            match update {
+                // A pending update announces the launchers it will install, not the current ones.
+                Some(update) => {
+                    event.owner_launcher_id = Some(update.new_owner_launcher_id);
+                    event.resolved_launcher_id = Some(update.new_resolved_launcher_id);
+                    event.minimum_execution_height = Some(update.min_height);
+                }
+                None => {
+                    event.owner_launcher_id = Some(slot.owner_launcher_id);
+                    event.resolved_launcher_id = Some(slot.resolved_launcher_id);
+                }
+            }
+            self.events.publish(event);
+        }
     }
 
     async fn clear_pending_for_handle(
@@ -309,25 +537,26 @@ impl SingletonIndexer {
         registry_launcher_id: Bytes32,
         handle_hash: Bytes32,
         height: u32,
-    ) {
+    ) -> Result<(), CliError> {
         let Some(mut record) = self
             .pending_updates
             .get(registry_launcher_id, handle_hash)
             .await
         else {
-            return;
+            return Ok(());
         };
         if record.current.is_none() {
-            return;
+            return Ok(());
         }
         clear_pending_current(&mut record, height);
         if record.current.is_none() && record.history.is_empty() {
             self.pending_updates
                 .remove(registry_launcher_id, handle_hash)
-                .await;
+                .await?;
         } else {
-            self.pending_updates.upsert(record).await;
+            self.pending_updates.upsert(record).await?;
         }
+        Ok(())
     }
 
     async fn discover_reference(
@@ -336,12 +565,12 @@ impl SingletonIndexer {
         height: u32,
         block_spends: &[CoinSpend],
         reference: SingletonReference,
-    ) -> Result<(), String> {
+    ) -> Result<(), CliError> {
         // Re-reference after cleanup: rediscover from this block.
         let existing = self.store.get(reference.launcher_id).await;
         if let Some(rec) = &existing {
             if rec.status == FollowRecordStatus::Active && rec.current.is_some() {
-                self.store.bump_reference(reference.launcher_id).await;
+                self.store.bump_reference(reference.launcher_id).await?;
                 return Ok(());
             }
         }
@@ -352,8 +581,7 @@ impl SingletonIndexer {
             reference.launcher_id,
             reference.expected_full_puzzle_hash,
             reference.expected_inner_puzzle_hash,
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
 
         let (status, current) = match result {
             DiscoveryResult::Incomplete => (FollowRecordStatus::Incomplete, None),
@@ -392,11 +620,15 @@ impl SingletonIndexer {
                 reference_count,
                 dereference_height: None,
             })
-            .await;
+            .await?;
         Ok(())
     }
 
-    async fn track_slot_reference_changes(&self, height: u32, log: &XchandlesActionLog) {
+    async fn track_slot_reference_changes(
+        &self,
+        height: u32,
+        log: &XchandlesActionLog,
+    ) -> Result<(), CliError> {
         let pairs: Vec<(XchandlesHandleSlotValue, XchandlesHandleSlotValue)> = match log {
             XchandlesActionLog::Extend(e) => vec![(e.spent_slot, e.created_slot)],
             XchandlesActionLog::Oracle(e) => vec![(e.spent_slot, e.created_slot)],
@@ -423,16 +655,17 @@ impl SingletonIndexer {
 
         for (spent, created) in pairs {
             for launcher in dereferenced_launchers(&spent, &created) {
-                self.store.drop_reference(launcher, height).await;
+                self.store.drop_reference(launcher, height).await?;
             }
             for launcher in [created.owner_launcher_id, created.resolved_launcher_id] {
                 if let Some(rec) = self.store.get(launcher).await {
                     if rec.reference_count == 0 || rec.dereference_height.is_some() {
-                        self.store.bump_reference(launcher).await;
+                        self.store.bump_reference(launcher).await?;
                     }
                 }
             }
         }
+        Ok(())
     }
 
     /// Retry incomplete discoveries and follow live coins that spent in this block.
@@ -441,7 +674,7 @@ impl SingletonIndexer {
         allocator: &mut Allocator,
         height: u32,
         block_spends: &[CoinSpend],
-    ) -> Result<(), String> {
+    ) -> Result<(), CliError> {
         let ids = self.store.all_launcher_ids().await;
         for launcher_id in ids {
             let Some(mut record) = self.store.get(launcher_id).await else {
@@ -455,8 +688,7 @@ impl SingletonIndexer {
                     record.launcher_id,
                     record.expected_full_puzzle_hash,
                     record.expected_inner_puzzle_hash,
-                )
-                .map_err(|e| e.to_string())?;
+                )?;
                 match result {
                     DiscoveryResult::Found(found) => {
                         let melted = found.melted;
@@ -470,11 +702,11 @@ impl SingletonIndexer {
                             if melted { Some(height) } else { None },
                             found.nft,
                         ));
-                        self.store.upsert(record).await;
+                        self.store.upsert(record).await?;
                     }
                     DiscoveryResult::Mismatch => {
                         record.status = FollowRecordStatus::Mismatch;
-                        self.store.upsert(record).await;
+                        self.store.upsert(record).await?;
                     }
                     DiscoveryResult::Incomplete => {}
                 }
@@ -498,9 +730,7 @@ impl SingletonIndexer {
                 continue;
             };
 
-            match follow_singleton_spend(allocator, spend, launcher_id)
-                .map_err(|e| e.to_string())?
-            {
+            match follow_singleton_spend(allocator, spend, launcher_id)? {
                 FollowSpendResult::Next(next) => {
                     let new_state = StoredSingletonState::from_coin(
                         launcher_id,
@@ -512,7 +742,7 @@ impl SingletonIndexer {
                         next.nft,
                     );
                     push_replacement(&mut record, new_state, height);
-                    self.store.upsert(record).await;
+                    self.store.upsert(record).await?;
                 }
                 FollowSpendResult::Melted {
                     last_coin,
@@ -529,16 +759,17 @@ impl SingletonIndexer {
                         nft,
                     );
                     push_replacement(&mut record, new_state, height);
-                    self.store.upsert(record).await;
+                    self.store.upsert(record).await?;
                 }
             }
         }
 
-        self.cleanup_finalized(height).await;
+        self.cleanup_finalized(height).await?;
+        self.metrics.blocks_processed.inc();
         Ok(())
     }
 
-    async fn cleanup_finalized(&self, peak: u32) {
+    async fn cleanup_finalized(&self, peak: u32) -> Result<(), CliError> {
         for launcher_id in self.store.all_launcher_ids().await {
             let Some(rec) = self.store.get(launcher_id).await else {
                 continue;
@@ -546,22 +777,29 @@ impl SingletonIndexer {
             if rec.reference_count == 0 {
                 if let Some(deref_h) = rec.dereference_height {
                     if peak >= deref_h.saturating_add(32) {
-                        self.store.remove(launcher_id).await;
+                        self.store.remove(launcher_id).await?;
                     }
                 }
             }
         }
+        Ok(())
     }
 
     /// Pre-final reorganization: restore lineage and Handle-slot state confirmed before `from_height`.
-    pub async fn rollback(&self, from_height: u32) {
-        self.begin_rollback().await;
+    pub async fn rollback(&self, from_height: u32) -> Result<(), CliError> {
+        {
+            let mut f = self.freshness.write().await;
+            f.rolling_back = true;
+            f.last_reorg_unix = Some(FreshnessState::now_unix());
+            f.last_reorg_height = Some(from_height);
+        }
+        self.metrics.rollbacks.inc();
         for launcher_id in self.store.all_launcher_ids().await {
             let Some(mut rec) = self.store.get(launcher_id).await else {
                 continue;
             };
             if rec.discovery_height >= from_height {
-                self.store.remove(launcher_id).await;
+                self.store.remove(launcher_id).await?;
                 continue;
             }
             rollback_to_before(&mut rec, from_height);
@@ -573,7 +811,7 @@ impl SingletonIndexer {
                     }
                 }
             }
-            self.store.upsert(rec).await;
+            self.store.upsert(rec).await?;
         }
 
         for (registry, handle_hash) in self.handle_slots.all_keys().await {
@@ -582,9 +820,9 @@ impl SingletonIndexer {
             };
             rollback_handle_to_before(&mut rec, from_height);
             if rec.current.is_none() && rec.history.is_empty() {
-                self.handle_slots.remove(registry, handle_hash).await;
+                self.handle_slots.remove(registry, handle_hash).await?;
             } else {
-                self.handle_slots.upsert(rec).await;
+                self.handle_slots.upsert(rec).await?;
             }
         }
 
@@ -601,15 +839,22 @@ impl SingletonIndexer {
             };
             rollback_registration_to_before(&mut rec, from_height);
             if rec.current.is_none() && rec.history.is_empty() {
-                self.registrations.remove(registry, handle_hash).await;
+                self.registrations.remove(registry, handle_hash).await?;
             } else {
-                self.registrations.upsert(rec).await;
+                self.registrations.upsert(rec).await?;
             }
         }
         for registry in touched_registries {
             let mut stats = self.registrations.get_stats(registry).await;
             rollback_stats_to_before(&mut stats, from_height);
-            self.registrations.set_stats(registry, stats).await;
+            for delta in self
+                .registrations
+                .take_activity_deltas_from(registry, from_height)
+                .await?
+            {
+                pop_activity_delta(&mut stats, &delta);
+            }
+            self.registrations.set_stats(registry, stats).await?;
         }
 
         for (registry, handle_hash) in self.pending_updates.all_keys().await {
@@ -618,10 +863,15 @@ impl SingletonIndexer {
             };
             rollback_pending_to_before(&mut rec, from_height);
             if rec.current.is_none() && rec.history.is_empty() {
-                self.pending_updates.remove(registry, handle_hash).await;
+                self.pending_updates.remove(registry, handle_hash).await?;
             } else {
-                self.pending_updates.upsert(rec).await;
+                self.pending_updates.upsert(rec).await?;
             }
         }
+        self.handle_history.rollback_from(from_height).await?;
+
+        // Retract only once the stores no longer serve what was announced.
+        self.events.retract_from(from_height);
+        Ok(())
     }
 }
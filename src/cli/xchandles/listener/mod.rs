@@ -6,31 +6,47 @@ mod api;
 mod auction_pricing;
 mod discovery;
 mod error;
+mod events;
 mod freshness;
 mod handle_store;
+mod history_store;
 mod index;
 mod pending_store;
 mod price_schedule;
 mod refs;
 mod registration_store;
+mod search;
+mod stats;
 mod store;
 mod types;
 
-pub use api::{listener_router, serve_listener, ListenerApiState, RegistryPricing};
+pub use api::{
+    committed_base_price, listener_router, require_fresh, select_registry, serve_listener,
+    ListenerApiState, RegistryPricing, RegistryTip,
+};
 pub use auction_pricing::{
-    auction_premium, base_registration_fee, projected_pricing_timestamp, reaches_base_at,
-    total_registration_fee, AUCTION_DURATION_SECONDS, PRICING_PROJECTION_OFFSET_SECONDS,
-    SOON_WINDOW_SECONDS,
+    auction_premium, base_registration_fee, period_registration_fee, projected_pricing_timestamp,
+    reaches_base_at, total_registration_fee, AUCTION_DURATION_SECONDS,
+    PRICING_PROJECTION_OFFSET_SECONDS, SOON_WINDOW_SECONDS,
 };
 pub use discovery::{
     discover_singleton_in_block, follow_singleton_spend, DiscoveredSingleton, DiscoveryResult,
     FollowSpendResult, ParsedNftState,
 };
 pub use error::ApiError;
-pub use freshness::FreshnessState;
+pub use events::{
+    HandleEvent, HandleEventCursor, HandleEventFeed, HandleEventKind, HandleEventResume,
+    HandleEventSubscription, EVENT_BUFFER_LEN,
+};
+pub use freshness::{FreshnessState, MAX_PEAK_AGE_SECONDS, MAX_PEAK_LAG};
 pub use handle_store::{
     prune_handle_history, push_handle_replacement, rollback_handle_to_before, DbHandleSlotStore,
-    HandleSlotRecord, HandleSlotStore, MemoryHandleSlotStore, SlotParentLineage, StoredHandleSlot,
+    HandleLauncherRole, HandleLengthCount, HandleSlotRecord, HandleSlotStore,
+    MemoryHandleSlotStore, SlotParentLineage, StoredHandleSlot,
+};
+pub use history_store::{
+    DbHandleHistoryStore, HandleHistoryAction, HandleHistoryStore, MemoryHandleHistoryStore,
+    StoredHandleHistoryEntry, StoredHandleHistoryUpdate,
 };
 pub use index::SingletonIndexer;
 pub use pending_store::{
@@ -40,25 +56,36 @@ pub use pending_store::{
 };
 pub use price_schedule::{
     committed_base_from_pricing_puzzle, effective_base_at, generations_for_network,
-    remaining_unroll_start, PriceQuery, PriceResponse, ScheduleGeneration, ScheduleResponse,
-    LAUNCH_BASE_PRICE, TESTNET11_PRICE_SCHEDULE,
+    is_schedule_base_price, remaining_unroll_start, PriceQuery, PriceResponse, ScheduleGeneration,
+    ScheduleResponse, LAUNCH_BASE_PRICE, TESTNET11_PRICE_SCHEDULE,
 };
 pub use refs::{dereferenced_launchers, references_from_action_log, SingletonReference};
 pub use registration_store::{
-    prune_registration_history, push_registration_replacement, rollback_registration_to_before,
-    rollback_stats_to_before, DbRegistrationStore, MemoryRegistrationStore, RegistrationActionKind,
-    RegistrationRecord, RegistrationStore, RegistryRegistrationStats, StoredRegistration,
-    StoredRegistrationEvent,
+    pop_activity_delta, prune_registration_history, push_activity_delta,
+    push_registration_replacement, rollback_registration_to_before, rollback_stats_to_before,
+    DbRegistrationStore, MemoryRegistrationStore, RegistrationActionKind, RegistrationRecord,
+    RegistrationStore, RegistryActivity, RegistryRegistrationStats, StoredActivityDelta,
+    StoredRegistration, StoredRegistrationEvent, SECONDS_PER_DAY,
+};
+pub use search::{
+    edit_distance_within, fuzzy_max_distance, is_search_term, HandleSearchFilter, HandleSearchMode,
+    SEARCH_CANDIDATE_LIMIT,
 };
+pub use stats::{bucket_activity, StatsBucket, MAX_STATS_BUCKETS};
 pub use store::{
     prune_history, push_replacement, rollback_to_before, DbSingletonStore, FollowRecordStatus,
     FollowedSingleton, MemorySingletonStore, SingletonStore, StoredSingletonState,
 };
 pub use types::{
-    hex32, is_canonical_handle, parse_launcher_id, ApiErrorBody, ExpiringActiveItem,
+    hex32, is_canonical_handle, parse_launcher_id, ApiErrorBody, EventsQuery, ExpiringActiveItem,
     ExpiringActiveResponse, ExpiringQuery, ExpiringSoonItem, ExpiringSoonResponse, ExpiringView,
-    HandleProofResponse, HandleQuery, HandleSlotJson, PendingTransferQuery,
-    PendingTransferResponse, RecentRegistrationItem, RecentRegistrationsQuery,
-    RecentRegistrationsResponse, RegistrationQuery, RegistrationResponse, SingletonNftDetails,
-    SingletonQuery, SingletonResponse, SlotNeighborsJson,
+    HandleAddressQuery, HandleAddressResponse, HandleEventJson, HandleHistoryItem,
+    HandleHistoryQuery, HandleHistoryResponse, HandleHistoryUpdateJson, HandleLengthJson,
+    HandleProofResponse, HandleQuery, HandleSearchItem, HandleSearchResponse, HandleSlotJson,
+    LauncherHandleItem, LauncherHandlesQuery, LauncherHandlesResponse, PendingTransferQuery,
+    PendingTransferResponse, QuoteItem, QuoteRequest, QuoteResponse, ReadyResponse,
+    RecentRegistrationItem, RecentRegistrationsQuery, RecentRegistrationsResponse,
+    RegistrationQuery, RegistrationResponse, RegistryStatusItem, SearchQuery, SingletonNftDetails,
+    SingletonQuery, SingletonResponse, SlotNeighborsJson, StatsActivityJson, StatsBucketJson,
+    StatsQuery, StatsResponse, StatusResponse,
 };
@@ -5,6 +5,8 @@ use chia_protocol::Bytes32;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use crate::CliError;
+
 /// Canonical pending-update projection for one Handle (from an InitiateUpdate).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoredPendingUpdate {
@@ -33,8 +35,12 @@ pub trait PendingUpdateStore: Send + Sync {
         registry_launcher_id: Bytes32,
         handle_hash: Bytes32,
     ) -> Option<PendingUpdateRecord>;
-    async fn upsert(&self, record: PendingUpdateRecord);
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32);
+    async fn upsert(&self, record: PendingUpdateRecord) -> Result<(), CliError>;
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError>;
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)>;
 }
 
@@ -71,18 +77,24 @@ impl PendingUpdateStore for MemoryPendingUpdateStore {
             .cloned()
     }
 
-    async fn upsert(&self, record: PendingUpdateRecord) {
+    async fn upsert(&self, record: PendingUpdateRecord) -> Result<(), CliError> {
         self.inner
             .write()
             .await
             .insert(key(record.registry_launcher_id, record.handle_hash), record);
+        Ok(())
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
         self.inner
             .write()
             .await
             .remove(&key(registry_launcher_id, handle_hash));
+        Ok(())
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -178,32 +190,21 @@ impl PendingUpdateStore for DbPendingUpdateStore {
         serde_json::from_str(&json).ok()
     }
 
-    async fn upsert(&self, record: PendingUpdateRecord) {
-        let json = match serde_json::to_string(&record) {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!("pending update serialize error: {e}");
-                return;
-            }
-        };
+    async fn upsert(&self, record: PendingUpdateRecord) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
         let db = self.db.lock().await;
-        if let Err(e) = db
-            .upsert_pending_update_record_json(
-                record.registry_launcher_id,
-                record.handle_hash,
-                &json,
-            )
+        db.upsert_pending_update_record_json(record.registry_launcher_id, record.handle_hash, &json)
             .await
-        {
-            eprintln!("pending update upsert error: {e}");
-        }
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
         let db = self.db.lock().await;
-        let _ = db
-            .delete_pending_update_record(registry_launcher_id, handle_hash)
-            .await;
+        db.delete_pending_update_record(registry_launcher_id, handle_hash)
+            .await
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -4,7 +4,7 @@ use chia_wallet_sdk::prelude::Mod;
 use chia_wallet_sdk::types::puzzles::XchandlesFactorPricingPuzzleArgs;
 use serde::{Deserialize, Serialize};
 
-use crate::PRICE_SCHEDULE;
+use crate::{Network, PRICE_SCHEDULE};
 
 /// Base price before the first generation row (launch / premine).
 pub const LAUNCH_BASE_PRICE: u64 = 1;
@@ -70,8 +70,8 @@ pub fn mainnet_generations() -> Vec<ScheduleGeneration> {
         .collect()
 }
 
-pub fn generations_for_network(testnet11: bool) -> Vec<ScheduleGeneration> {
-    if testnet11 {
+pub fn generations_for_network(network: &Network) -> Vec<ScheduleGeneration> {
+    if network.is_testnet() {
         testnet11_generations()
     } else {
         mainnet_generations()
@@ -118,6 +118,11 @@ pub fn remaining_unroll_start(
         .unwrap_or(0)
 }
 
+/// Whether `base_price` is launch 1 or the price of some schedule row.
+pub fn is_schedule_base_price(schedule: &[ScheduleGeneration], base_price: u64) -> bool {
+    base_price == LAUNCH_BASE_PRICE || schedule.iter().any(|row| row.base_price == base_price)
+}
+
 /// Match a registry pricing-puzzle hash against launch 1 and every schedule row.
 pub fn committed_base_from_pricing_puzzle(
     pricing_puzzle_hash: chia_protocol::Bytes32,
@@ -164,6 +169,15 @@ mod tests {
         assert_eq!(remaining_unroll_start(&schedule, 6, confirmed), 4);
     }
 
+    #[test]
+    fn schedule_base_prices_are_launch_and_rows() {
+        let schedule = testnet11_generations();
+        assert!(is_schedule_base_price(&schedule, 1));
+        assert!(is_schedule_base_price(&schedule, 9));
+        assert!(!is_schedule_base_price(&schedule, 10));
+        assert!(!is_schedule_base_price(&[], 9));
+    }
+
     #[test]
     fn remaining_is_full_schedule_when_committed_lags_at_launch_one() {
         let schedule = testnet11_generations();
@@ -1,10 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use chia_protocol::Bytes32;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use crate::CliError;
+
 /// Confirmed registration or expiry-auction purchase fact.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -42,11 +44,62 @@ pub struct StoredRegistrationEvent {
     pub confirmation_height: u32,
 }
 
+/// Width of one activity bucket; days are UTC (`timestamp / SECONDS_PER_DAY`).
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Registry activity counters; fees and premiums are in the payment CAT's mojos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryActivity {
+    pub registrations: u64,
+    pub extensions: u64,
+    pub expires: u64,
+    /// Total price paid across register, expire and extend actions.
+    pub protocol_fees: u64,
+    /// Expiration-auction premium portion of `protocol_fees`.
+    pub premium_revenue: u64,
+}
+
+impl RegistryActivity {
+    pub fn add(&mut self, other: &Self) {
+        self.registrations = self.registrations.saturating_add(other.registrations);
+        self.extensions = self.extensions.saturating_add(other.extensions);
+        self.expires = self.expires.saturating_add(other.expires);
+        self.protocol_fees = self.protocol_fees.saturating_add(other.protocol_fees);
+        self.premium_revenue = self.premium_revenue.saturating_add(other.premium_revenue);
+    }
+
+    pub fn subtract(&mut self, other: &Self) {
+        self.registrations = self.registrations.saturating_sub(other.registrations);
+        self.extensions = self.extensions.saturating_sub(other.extensions);
+        self.expires = self.expires.saturating_sub(other.expires);
+        self.protocol_fees = self.protocol_fees.saturating_sub(other.protocol_fees);
+        self.premium_revenue = self.premium_revenue.saturating_sub(other.premium_revenue);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// One registry spend's contribution to a day, kept per spent registry coin so a replayed
+/// spend is never counted twice and a reorganization of any depth can subtract it again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredActivityDelta {
+    pub registry_coin_id: Bytes32,
+    pub confirmation_height: u32,
+    /// UTC day number of the confirming block.
+    pub day: u64,
+    pub activity: RegistryActivity,
+}
+
 /// Per-registry cumulative register count and confirmed event log.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RegistryRegistrationStats {
     pub total_registered: u64,
     pub events: Vec<StoredRegistrationEvent>,
+    /// Activity per UTC day number.
+    #[serde(default)]
+    pub daily_activity: BTreeMap<u64, RegistryActivity>,
 }
 
 #[async_trait::async_trait]
@@ -56,13 +109,34 @@ pub trait RegistrationStore: Send + Sync {
         registry_launcher_id: Bytes32,
         handle_hash: Bytes32,
     ) -> Option<RegistrationRecord>;
-    async fn upsert(&self, record: RegistrationRecord);
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32);
+    async fn upsert(&self, record: RegistrationRecord) -> Result<(), CliError>;
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError>;
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)>;
 
     async fn get_stats(&self, registry_launcher_id: Bytes32) -> RegistryRegistrationStats;
-    async fn set_stats(&self, registry_launcher_id: Bytes32, stats: RegistryRegistrationStats);
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: RegistryRegistrationStats,
+    ) -> Result<(), CliError>;
     async fn all_stats_registry_ids(&self) -> Vec<Bytes32>;
+
+    /// Records `delta` unless its registry coin already has one; `true` when new.
+    async fn record_activity_delta(
+        &self,
+        registry_launcher_id: Bytes32,
+        delta: StoredActivityDelta,
+    ) -> Result<bool, CliError>;
+    /// Removes and returns the registry's deltas confirmed at or after `from_height`.
+    async fn take_activity_deltas_from(
+        &self,
+        registry_launcher_id: Bytes32,
+        from_height: u32,
+    ) -> Result<Vec<StoredActivityDelta>, CliError>;
 }
 
 fn key(registry: Bytes32, handle_hash: Bytes32) -> (Bytes32, Bytes32) {
@@ -73,6 +147,7 @@ fn key(registry: Bytes32, handle_hash: Bytes32) -> (Bytes32, Bytes32) {
 pub struct MemoryRegistrationStore {
     records: RwLock<HashMap<(Bytes32, Bytes32), RegistrationRecord>>,
     stats: RwLock<HashMap<Bytes32, RegistryRegistrationStats>>,
+    activity: RwLock<HashMap<Bytes32, BTreeMap<(u32, Bytes32), StoredActivityDelta>>>,
 }
 
 impl MemoryRegistrationStore {
@@ -99,18 +174,24 @@ impl RegistrationStore for MemoryRegistrationStore {
             .cloned()
     }
 
-    async fn upsert(&self, record: RegistrationRecord) {
+    async fn upsert(&self, record: RegistrationRecord) -> Result<(), CliError> {
         self.records
             .write()
             .await
             .insert(key(record.registry_launcher_id, record.handle_hash), record);
+        Ok(())
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
         self.records
             .write()
             .await
             .remove(&key(registry_launcher_id, handle_hash));
+        Ok(())
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -126,13 +207,48 @@ impl RegistrationStore for MemoryRegistrationStore {
             .unwrap_or_default()
     }
 
-    async fn set_stats(&self, registry_launcher_id: Bytes32, stats: RegistryRegistrationStats) {
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: RegistryRegistrationStats,
+    ) -> Result<(), CliError> {
         self.stats.write().await.insert(registry_launcher_id, stats);
+        Ok(())
     }
 
     async fn all_stats_registry_ids(&self) -> Vec<Bytes32> {
         self.stats.read().await.keys().copied().collect()
     }
+
+    async fn record_activity_delta(
+        &self,
+        registry_launcher_id: Bytes32,
+        delta: StoredActivityDelta,
+    ) -> Result<bool, CliError> {
+        let mut activity = self.activity.write().await;
+        let deltas = activity.entry(registry_launcher_id).or_default();
+        let key = (delta.confirmation_height, delta.registry_coin_id);
+        if deltas.contains_key(&key) {
+            return Ok(false);
+        }
+        deltas.insert(key, delta);
+        Ok(true)
+    }
+
+    async fn take_activity_deltas_from(
+        &self,
+        registry_launcher_id: Bytes32,
+        from_height: u32,
+    ) -> Result<Vec<StoredActivityDelta>, CliError> {
+        let mut activity = self.activity.write().await;
+        let Some(deltas) = activity.get_mut(&registry_launcher_id) else {
+            return Ok(Vec::new());
+        };
+        Ok(deltas
+            .split_off(&(from_height, Bytes32::default()))
+            .into_values()
+            .collect())
+    }
 }
 
 /// Retain current fact, every replaced fact in the last 32 blocks, and one older predecessor.
@@ -200,6 +316,25 @@ pub fn push_registration_event(stats: &mut RegistryRegistrationStats, ev: Stored
     }
 }
 
+/// Fold a newly recorded block's activity into its day.
+pub fn push_activity_delta(stats: &mut RegistryRegistrationStats, delta: &StoredActivityDelta) {
+    stats
+        .daily_activity
+        .entry(delta.day)
+        .or_default()
+        .add(&delta.activity);
+}
+
+/// Subtract an orphaned block's activity from its day.
+pub fn pop_activity_delta(stats: &mut RegistryRegistrationStats, delta: &StoredActivityDelta) {
+    if let Some(day) = stats.daily_activity.get_mut(&delta.day) {
+        day.subtract(&delta.activity);
+        if day.is_empty() {
+            stats.daily_activity.remove(&delta.day);
+        }
+    }
+}
+
 /// Drop orphaned recent events and reverse register counts for a reorganization.
 pub fn rollback_stats_to_before(stats: &mut RegistryRegistrationStats, from_height: u32) {
     while let Some(ev) = stats.events.last() {
@@ -239,28 +374,21 @@ impl RegistrationStore for DbRegistrationStore {
         serde_json::from_str(&json).ok()
     }
 
-    async fn upsert(&self, record: RegistrationRecord) {
-        let json = match serde_json::to_string(&record) {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!("registration serialize error: {e}");
-                return;
-            }
-        };
+    async fn upsert(&self, record: RegistrationRecord) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
         let db = self.db.lock().await;
-        if let Err(e) = db
-            .upsert_registration_record_json(record.registry_launcher_id, record.handle_hash, &json)
+        db.upsert_registration_record_json(record.registry_launcher_id, record.handle_hash, &json)
             .await
-        {
-            eprintln!("registration upsert error: {e}");
-        }
     }
 
-    async fn remove(&self, registry_launcher_id: Bytes32, handle_hash: Bytes32) {
+    async fn remove(
+        &self,
+        registry_launcher_id: Bytes32,
+        handle_hash: Bytes32,
+    ) -> Result<(), CliError> {
         let db = self.db.lock().await;
-        let _ = db
-            .delete_registration_record(registry_launcher_id, handle_hash)
-            .await;
+        db.delete_registration_record(registry_launcher_id, handle_hash)
+            .await
     }
 
     async fn all_keys(&self) -> Vec<(Bytes32, Bytes32)> {
@@ -276,21 +404,15 @@ impl RegistrationStore for DbRegistrationStore {
         serde_json::from_str(&json).unwrap_or_default()
     }
 
-    async fn set_stats(&self, registry_launcher_id: Bytes32, stats: RegistryRegistrationStats) {
-        let json = match serde_json::to_string(&stats) {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!("registration stats serialize error: {e}");
-                return;
-            }
-        };
+    async fn set_stats(
+        &self,
+        registry_launcher_id: Bytes32,
+        stats: RegistryRegistrationStats,
+    ) -> Result<(), CliError> {
+        let json = serde_json::to_string(&stats)?;
         let db = self.db.lock().await;
-        if let Err(e) = db
-            .upsert_registration_stats_json(registry_launcher_id, &json)
+        db.upsert_registration_stats_json(registry_launcher_id, &json)
             .await
-        {
-            eprintln!("registration stats upsert error: {e}");
-        }
     }
 
     async fn all_stats_registry_ids(&self) -> Vec<Bytes32> {
@@ -299,6 +421,35 @@ impl RegistrationStore for DbRegistrationStore {
             .await
             .unwrap_or_default()
     }
+
+    async fn record_activity_delta(
+        &self,
+        registry_launcher_id: Bytes32,
+        delta: StoredActivityDelta,
+    ) -> Result<bool, CliError> {
+        let json = serde_json::to_string(&delta)?;
+        let db = self.db.lock().await;
+        db.insert_registration_activity_delta_json(
+            registry_launcher_id,
+            delta.registry_coin_id,
+            delta.confirmation_height,
+            &json,
+        )
+        .await
+    }
+
+    async fn take_activity_deltas_from(
+        &self,
+        registry_launcher_id: Bytes32,
+        from_height: u32,
+    ) -> Result<Vec<StoredActivityDelta>, CliError> {
+        let db = self.db.lock().await;
+        db.take_registration_activity_deltas_json(registry_launcher_id, from_height)
+            .await?
+            .iter()
+            .map(|json| serde_json::from_str(json).map_err(CliError::from))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -310,6 +461,7 @@ mod tests {
         let mut stats = RegistryRegistrationStats {
             total_registered: 100,
             events: Vec::new(),
+            ..Default::default()
         };
         for i in 0..=RECENT_REGISTRATION_EVENTS_MAX {
             push_registration_event(
@@ -329,4 +481,65 @@ mod tests {
         );
         assert_eq!(stats.total_registered, 100);
     }
+
+    #[tokio::test]
+    async fn activity_counts_once_per_spend_and_rolls_back_at_any_depth() {
+        let store = MemoryRegistrationStore::new();
+        let registry = Bytes32::new([1; 32]);
+        let registered = RegistryActivity {
+            registrations: 1,
+            protocol_fees: 1_000,
+            ..Default::default()
+        };
+        let expired = RegistryActivity {
+            expires: 1,
+            protocol_fees: 5_000,
+            premium_revenue: 4_000,
+            ..Default::default()
+        };
+        let delta = |coin: u8, confirmation_height, day, activity| StoredActivityDelta {
+            registry_coin_id: Bytes32::new([coin; 32]),
+            confirmation_height,
+            day,
+            activity,
+        };
+
+        let mut stats = RegistryRegistrationStats::default();
+        for delta in [
+            delta(1, 100, 20_000, registered),
+            delta(2, 110, 20_000, expired),
+            delta(1, 100, 20_000, registered),
+            // A second registry spend confirmed in the same block.
+            delta(3, 100, 20_000, registered),
+            delta(4, 10_000, 20_001, registered),
+        ] {
+            if store
+                .record_activity_delta(registry, delta.clone())
+                .await
+                .unwrap()
+            {
+                push_activity_delta(&mut stats, &delta);
+            }
+        }
+        let mut same_block = registered;
+        same_block.add(&registered);
+        let mut day = same_block;
+        day.add(&expired);
+        assert_eq!(stats.daily_activity[&20_000], day);
+        assert_eq!(stats.daily_activity[&20_001], registered);
+
+        for delta in store
+            .take_activity_deltas_from(registry, 105)
+            .await
+            .unwrap()
+        {
+            pop_activity_delta(&mut stats, &delta);
+        }
+        assert_eq!(stats.daily_activity.len(), 1);
+        assert_eq!(stats.daily_activity[&20_000], same_block);
+        assert!(store
+            .record_activity_delta(registry, delta(2, 110, 20_000, expired))
+            .await
+            .unwrap());
+    }
 }
@@ -0,0 +1,163 @@
+//! Handle-name matching for `GET /search`.
+//!
+//! Stores only narrow candidates (registry, expiration window, and a coarse text
+//! filter); matching and ranking happen here so Memory and SQLite agree.
+
+/// Upper bound on candidates a store returns per registry for one search.
+///
+/// Prefix results are handle-ordered so the bound is exact; substring and fuzzy
+/// rank within the first `SEARCH_CANDIDATE_LIMIT` handle-ordered matches.
+pub const SEARCH_CANDIDATE_LIMIT: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleSearchMode {
+    Prefix,
+    Substring,
+    Fuzzy,
+}
+
+impl HandleSearchMode {
+    /// Omission selects `prefix`.
+    pub fn parse(raw: Option<&str>) -> Option<Self> {
+        match raw {
+            None | Some("prefix") => Some(Self::Prefix),
+            Some("substring") => Some(Self::Substring),
+            Some("fuzzy") => Some(Self::Fuzzy),
+            _ => None,
+        }
+    }
+}
+
+/// Search terms share the handle alphabet but may be shorter than a handle.
+pub fn is_search_term(term: &str) -> bool {
+    let len = term.len();
+    (1..=63).contains(&len)
+        && term
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+/// Edit budget for fuzzy matching: one typo for short terms, two otherwise.
+pub fn fuzzy_max_distance(term: &str) -> usize {
+    if term.len() <= 4 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` once it exceeds `max`.
+pub fn edit_distance_within(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            row[j + 1] = substitution.min(prev[j + 1] + 1).min(row[j] + 1);
+            row_min = row_min.min(row[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    Some(prev[b.len()]).filter(|d| *d <= max)
+}
+
+/// Text and expiration constraints for one search; expirations are inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandleSearchFilter {
+    pub mode: HandleSearchMode,
+    pub term: String,
+    pub min_expiration: u64,
+    pub max_expiration: u64,
+}
+
+impl HandleSearchFilter {
+    /// Sort key for a matching handle (lower ranks first), or `None` when it does not match.
+    ///
+    /// Prefix ranks every match equally; substring by match position; fuzzy by edit distance.
+    pub fn rank(&self, handle: &str) -> Option<usize> {
+        match self.mode {
+            HandleSearchMode::Prefix => handle.starts_with(&self.term).then_some(0),
+            HandleSearchMode::Substring => handle.find(&self.term),
+            HandleSearchMode::Fuzzy => {
+                edit_distance_within(handle, &self.term, fuzzy_max_distance(&self.term))
+            }
+        }
+    }
+
+    pub fn matches(&self, handle: &str, expiration: u64) -> bool {
+        expiration >= self.min_expiration
+            && expiration <= self.max_expiration
+            && self.rank(handle).is_some()
+    }
+
+    /// Inclusive handle-length bounds any match must satisfy; lets SQLite skip most rows.
+    pub fn length_bounds(&self) -> (usize, usize) {
+        match self.mode {
+            HandleSearchMode::Prefix | HandleSearchMode::Substring => (self.term.len(), 63),
+            HandleSearchMode::Fuzzy => {
+                let d = fuzzy_max_distance(&self.term);
+                (self.term.len().saturating_sub(d), self.term.len() + d)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(mode: HandleSearchMode, term: &str) -> HandleSearchFilter {
+        HandleSearchFilter {
+            mode,
+            term: term.to_string(),
+            min_expiration: 0,
+            max_expiration: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn ranks_prefix_substring_and_fuzzy_matches() {
+        let prefix = filter(HandleSearchMode::Prefix, "ali");
+        assert_eq!(prefix.rank("alice"), Some(0));
+        assert_eq!(prefix.rank("malik"), None);
+
+        let substring = filter(HandleSearchMode::Substring, "ali");
+        assert_eq!(substring.rank("alice"), Some(0));
+        assert_eq!(substring.rank("malik"), Some(1));
+        assert_eq!(substring.rank("bob"), None);
+
+        let fuzzy = filter(HandleSearchMode::Fuzzy, "alice");
+        assert_eq!(fuzzy.rank("alice"), Some(0));
+        assert_eq!(fuzzy.rank("alicia"), Some(2));
+        assert_eq!(fuzzy.rank("alcie"), Some(2));
+        assert_eq!(fuzzy.rank("bob"), None);
+        assert_eq!(filter(HandleSearchMode::Fuzzy, "bob").rank("bobby"), None);
+    }
+
+    #[test]
+    fn search_terms_and_modes_parse_strictly() {
+        assert!(is_search_term("a"));
+        assert!(!is_search_term(""));
+        assert!(!is_search_term("Alice"));
+        assert!(!is_search_term("ali%"));
+        assert_eq!(
+            HandleSearchMode::parse(None),
+            Some(HandleSearchMode::Prefix)
+        );
+        assert_eq!(
+            HandleSearchMode::parse(Some("fuzzy")),
+            Some(HandleSearchMode::Fuzzy)
+        );
+        assert_eq!(HandleSearchMode::parse(Some("regex")), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Calendar bucketing for `GET /stats`.
+//!
+//! The indexer keeps one [`RegistryActivity`] per UTC day; weeks (Monday-aligned)
+//! and months are folded from those days at read time.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use super::registration_store::RegistryActivity;
+
+/// Upper bound on buckets returned by one `/stats` read.
+pub const MAX_STATS_BUCKETS: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsBucket {
+    /// Omission selects `day`.
+    pub fn parse(raw: Option<&str>) -> Option<Self> {
+        match raw {
+            None | Some("day") => Some(Self::Day),
+            Some("week") => Some(Self::Week),
+            Some("month") => Some(Self::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// Lookback used when `from` is omitted.
+    pub fn default_span_days(self) -> u64 {
+        match self {
+            Self::Day => 30,
+            Self::Week => 26 * 7,
+            Self::Month => 365,
+        }
+    }
+
+    /// First day number of the bucket containing `day`.
+    pub fn start_of(self, day: u64) -> Option<u64> {
+        match self {
+            Self::Day => Some(day),
+            // 1970-01-01 was a Thursday; the epoch's own week starts at day 0.
+            Self::Week => Some(day.saturating_sub((day + 3) % 7)),
+            Self::Month => day_number(date_of(day)?.with_day(1)?),
+        }
+    }
+
+    /// First day number of the bucket after the one starting at `start`.
+    pub fn next_start(self, start: u64) -> Option<u64> {
+        match self {
+            Self::Day => start.checked_add(1),
+            // A clamped epoch week still ends on the first Monday (day 4).
+            Self::Week => Some(start.checked_add(7)? - (start + 3) % 7),
+            Self::Month => day_number(date_of(start)?.checked_add_months(Months::new(1))?),
+        }
+    }
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("epoch is a valid date")
+}
+
+fn date_of(day: u64) -> Option<NaiveDate> {
+    epoch().checked_add_days(Days::new(day))
+}
+
+fn day_number(date: NaiveDate) -> Option<u64> {
+    u64::try_from((date - epoch()).num_days()).ok()
+}
+
+/// Zero-filled `(start_day, activity)` for every bucket from the one containing
+/// `from_day` through the one containing `to_day`; `None` when that exceeds
+/// [`MAX_STATS_BUCKETS`] or leaves the calendar.
+pub fn bucket_activity(
+    daily: &BTreeMap<u64, RegistryActivity>,
+    bucket: StatsBucket,
+    from_day: u64,
+    to_day: u64,
+) -> Option<Vec<(u64, RegistryActivity)>> {
+    let mut out = Vec::new();
+    let mut start = bucket.start_of(from_day)?;
+    while start <= to_day {
+        if out.len() == MAX_STATS_BUCKETS {
+            return None;
+        }
+        let end = bucket.next_start(start)?;
+        let mut activity = RegistryActivity::default();
+        for day in daily.range(start..end).map(|(_, a)| a) {
+            activity.add(day);
+        }
+        out.push((start, activity));
+        start = end;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> u64 {
+        day_number(NaiveDate::from_ymd_opt(y, m, d).unwrap()).unwrap()
+    }
+
+    fn registrations(n: u64) -> RegistryActivity {
+        RegistryActivity {
+            registrations: n,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn weeks_start_monday_and_months_follow_the_calendar() {
+        // 2024-02-29 was a Thursday.
+        let leap = day(2024, 2, 29);
+        assert_eq!(StatsBucket::Week.start_of(leap), Some(day(2024, 2, 26)));
+        assert_eq!(
+            StatsBucket::Week.next_start(day(2024, 2, 26)),
+            Some(day(2024, 3, 4))
+        );
+        assert_eq!(StatsBucket::Month.start_of(leap), Some(day(2024, 2, 1)));
+        assert_eq!(
+            StatsBucket::Month.next_start(day(2024, 2, 1)),
+            Some(day(2024, 3, 1))
+        );
+        assert_eq!(StatsBucket::Week.start_of(1), Some(0));
+        assert_eq!(StatsBucket::Week.next_start(0), Some(4));
+        assert_eq!(StatsBucket::parse(Some("year")), None);
+    }
+
+    #[test]
+    fn buckets_are_zero_filled_and_capped() {
+        let daily = BTreeMap::from([
+            (day(2024, 1, 31), registrations(2)),
+            (day(2024, 3, 1), registrations(5)),
+            (day(2024, 3, 31), registrations(1)),
+        ]);
+        let months = bucket_activity(
+            &daily,
+            StatsBucket::Month,
+            day(2024, 1, 15),
+            day(2024, 3, 2),
+        )
+        .unwrap();
+        assert_eq!(
+            months,
+            vec![
+                (day(2024, 1, 1), registrations(2)),
+                (day(2024, 2, 1), RegistryActivity::default()),
+                (day(2024, 3, 1), registrations(6)),
+            ]
+        );
+
+        assert!(bucket_activity(&daily, StatsBucket::Day, 0, MAX_STATS_BUCKETS as u64).is_none());
+        assert_eq!(
+            bucket_activity(&daily, StatsBucket::Day, 0, MAX_STATS_BUCKETS as u64 - 1)
+                .unwrap()
+                .len(),
+            MAX_STATS_BUCKETS
+        );
+    }
+}
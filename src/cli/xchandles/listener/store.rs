@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use super::discovery::ParsedNftState;
+use crate::CliError;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FollowRecordStatus {
@@ -75,11 +76,11 @@ pub struct FollowedSingleton {
 #[async_trait::async_trait]
 pub trait SingletonStore: Send + Sync {
     async fn get(&self, launcher_id: Bytes32) -> Option<FollowedSingleton>;
-    async fn upsert(&self, record: FollowedSingleton);
-    async fn remove(&self, launcher_id: Bytes32);
+    async fn upsert(&self, record: FollowedSingleton) -> Result<(), CliError>;
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError>;
     async fn all_launcher_ids(&self) -> Vec<Bytes32>;
-    async fn bump_reference(&self, launcher_id: Bytes32);
-    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32);
+    async fn bump_reference(&self, launcher_id: Bytes32) -> Result<(), CliError>;
+    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32) -> Result<(), CliError>;
 }
 
 #[derive(Default)]
@@ -103,27 +104,30 @@ impl SingletonStore for MemorySingletonStore {
         self.inner.read().await.get(&launcher_id).cloned()
     }
 
-    async fn upsert(&self, record: FollowedSingleton) {
+    async fn upsert(&self, record: FollowedSingleton) -> Result<(), CliError> {
         self.inner.write().await.insert(record.launcher_id, record);
+        Ok(())
     }
 
-    async fn remove(&self, launcher_id: Bytes32) {
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
         self.inner.write().await.remove(&launcher_id);
+        Ok(())
     }
 
     async fn all_launcher_ids(&self) -> Vec<Bytes32> {
         self.inner.read().await.keys().copied().collect()
     }
 
-    async fn bump_reference(&self, launcher_id: Bytes32) {
+    async fn bump_reference(&self, launcher_id: Bytes32) -> Result<(), CliError> {
         let mut guard = self.inner.write().await;
         if let Some(rec) = guard.get_mut(&launcher_id) {
             rec.reference_count = rec.reference_count.saturating_add(1);
             rec.dereference_height = None;
         }
+        Ok(())
     }
 
-    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32) {
+    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32) -> Result<(), CliError> {
         let mut guard = self.inner.write().await;
         if let Some(rec) = guard.get_mut(&launcher_id) {
             rec.reference_count = rec.reference_count.saturating_sub(1);
@@ -131,6 +135,7 @@ impl SingletonStore for MemorySingletonStore {
                 rec.dereference_height = Some(at_height);
             }
         }
+        Ok(())
     }
 }
 
@@ -207,26 +212,16 @@ impl SingletonStore for DbSingletonStore {
         serde_json::from_str(&json).ok()
     }
 
-    async fn upsert(&self, record: FollowedSingleton) {
-        let json = match serde_json::to_string(&record) {
-            Ok(j) => j,
-            Err(e) => {
-                eprintln!("followed singleton serialize error: {e}");
-                return;
-            }
-        };
+    async fn upsert(&self, record: FollowedSingleton) -> Result<(), CliError> {
+        let json = serde_json::to_string(&record)?;
         let db = self.db.lock().await;
-        if let Err(e) = db
-            .upsert_followed_singleton_json(record.launcher_id, &json)
+        db.upsert_followed_singleton_json(record.launcher_id, &json)
             .await
-        {
-            eprintln!("followed singleton upsert error: {e}");
-        }
     }
 
-    async fn remove(&self, launcher_id: Bytes32) {
+    async fn remove(&self, launcher_id: Bytes32) -> Result<(), CliError> {
         let db = self.db.lock().await;
-        let _ = db.delete_followed_singleton(launcher_id).await;
+        db.delete_followed_singleton(launcher_id).await
     }
 
     async fn all_launcher_ids(&self) -> Vec<Bytes32> {
@@ -234,21 +229,23 @@ impl SingletonStore for DbSingletonStore {
         db.all_followed_singleton_ids().await.unwrap_or_default()
     }
 
-    async fn bump_reference(&self, launcher_id: Bytes32) {
+    async fn bump_reference(&self, launcher_id: Bytes32) -> Result<(), CliError> {
         if let Some(mut rec) = self.get(launcher_id).await {
             rec.reference_count = rec.reference_count.saturating_add(1);
             rec.dereference_height = None;
-            self.upsert(rec).await;
+            self.upsert(rec).await?;
         }
+        Ok(())
     }
 
-    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32) {
+    async fn drop_reference(&self, launcher_id: Bytes32, at_height: u32) -> Result<(), CliError> {
         if let Some(mut rec) = self.get(launcher_id).await {
             rec.reference_count = rec.reference_count.saturating_sub(1);
             if rec.reference_count == 0 {
                 rec.dereference_height = Some(at_height);
             }
-            self.upsert(rec).await;
+            self.upsert(rec).await?;
         }
+        Ok(())
     }
 }
@@ -78,6 +78,30 @@ pub struct HandleProofResponse {
     pub pending_transfer: Option<PendingTransferResponse>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HandleAddressQuery {
+    /// Explicit registry selection; omission selects the first configured registry.
+    pub launcher_id: Option<String>,
+    #[serde(default)]
+    pub bypass_expiration_safety_check: bool,
+}
+
+/// Payable destination for a Handle, returned by `GET /handle/{handle}/address`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleAddressResponse {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    pub expiration: u64,
+    pub resolved_launcher_id: String,
+    /// Current p2 puzzle hash of the Resolved NFT.
+    pub p2_puzzle_hash: String,
+    /// Bech32m encoding of `p2_puzzle_hash` for the listener's network.
+    pub address: String,
+    pub resolved_confirmation_height: u32,
+    pub indexed_peak_height: u32,
+    pub confirmed_timestamp: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ApiErrorBody {
     pub code: String,
@@ -209,6 +233,266 @@ pub struct ExpiringSoonResponse {
     pub confirmed_timestamp: u64,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchQuery {
+    /// Lowercase alphanumeric search term.
+    pub q: Option<String>,
+    /// `prefix` (default), `substring`, or `fuzzy`.
+    pub mode: Option<String>,
+    /// Restrict to one registry; omission searches every followed registry.
+    pub launcher_id: Option<String>,
+    /// Inclusive expiration bounds (unix seconds).
+    pub min_expiration: Option<u64>,
+    pub max_expiration: Option<u64>,
+    /// `true` keeps only expired Handles (registrable now); `false` only unexpired ones.
+    pub available: Option<bool>,
+    /// Page size; capped at 50. Omitted defaults to 50.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleSearchItem {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    pub expiration: u64,
+    pub available: bool,
+    /// Edit distance from the term; fuzzy searches only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<u32>,
+}
+
+/// `GET /search` results, best match first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleSearchResponse {
+    pub items: Vec<HandleSearchItem>,
+    pub indexed_peak_height: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LauncherHandlesQuery {
+    /// Restrict to one registry; omission lists across every followed registry.
+    pub registry_launcher_id: Option<String>,
+}
+
+/// One unexpired Handle whose current slot names the requested launcher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LauncherHandleItem {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    pub slot: HandleSlotJson,
+    pub slot_confirmation_height: u32,
+}
+
+/// `GET /owners/{launcher_id}/handles` and `GET /resolved/{launcher_id}/handles`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LauncherHandlesResponse {
+    pub launcher_id: String,
+    pub items: Vec<LauncherHandleItem>,
+    pub indexed_peak_height: u32,
+}
+
+/// `/readyz` success body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadyResponse {
+    pub ready: bool,
+    pub indexed_peak_height: u32,
+}
+
+/// One followed registry in `/status`; `tip_coin_id` is absent until its first sync finishes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryStatusItem {
+    pub launcher_id: String,
+    pub tip_coin_id: Option<String>,
+    pub tip_indexed_height: Option<u32>,
+}
+
+/// Listener health as the listener itself sees it; never freshness-gated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusResponse {
+    pub ready: bool,
+    pub stale_reasons: Vec<String>,
+    pub indexed_peak_height: u32,
+    pub upstream_peak_height: u32,
+    pub peak_lag: u32,
+    pub max_peak_lag: u32,
+    pub last_successful_peak_unix: u64,
+    pub seconds_since_last_peak: u64,
+    pub max_peak_age_seconds: u64,
+    pub confirmed_timestamp: u64,
+    pub rolling_back: bool,
+    pub resyncing: bool,
+    pub last_reorg_unix: Option<u64>,
+    pub last_reorg_height: Option<u32>,
+    pub registries: Vec<RegistryStatusItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HandleHistoryQuery {
+    /// Explicit registry selection; omission selects the first configured registry.
+    pub launcher_id: Option<String>,
+    pub cursor: Option<String>,
+    /// Page size; capped at 50. Omitted defaults to 50.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleHistoryUpdateJson {
+    pub new_owner_launcher_id: String,
+    pub new_resolved_launcher_id: String,
+    pub minimum_execution_height: u32,
+    pub update_initiator_coin_id: String,
+}
+
+/// One timeline entry; slot fields are the Handle's state after the action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleHistoryItem {
+    pub action: String,
+    pub confirmation_height: u32,
+    pub registry_coin_id: Option<String>,
+    pub expiration: u64,
+    pub owner_launcher_id: String,
+    pub resolved_launcher_id: String,
+    /// Total price paid for register, expire and extend; `null` otherwise.
+    pub total_price: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update: Option<HandleHistoryUpdateJson>,
+}
+
+/// Newest-first page of `GET /handle/{handle}/history`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleHistoryResponse {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    pub items: Vec<HandleHistoryItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    pub indexed_peak_height: u32,
+}
+
+/// Body of `POST /quote`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QuoteRequest {
+    /// Explicit registry selection; omission selects the first configured registry.
+    pub launcher_id: Option<String>,
+    pub handles: Vec<String>,
+    /// Registration periods to price; omitted defaults to 1.
+    pub num_periods: Option<u64>,
+}
+
+/// One cart line. Fees are set only when the Handle can be bought right now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteItem {
+    pub handle: String,
+    pub canonical: bool,
+    /// `invalid`, `available`, `in_auction`, `expiring` or `registered`.
+    pub status: String,
+    pub expiration: Option<u64>,
+    pub base_registration_fee: Option<u64>,
+    pub current_premium: Option<u64>,
+    pub total_registration_fee: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteResponse {
+    pub registry_launcher_id: String,
+    pub num_periods: u64,
+    pub base_price: u64,
+    pub projected_pricing_timestamp: u64,
+    /// In request order, duplicates included.
+    pub items: Vec<QuoteItem>,
+    /// Sum of `total_registration_fee` over purchasable items.
+    pub total: u64,
+    pub indexed_peak_height: u32,
+    pub confirmed_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StatsQuery {
+    /// Explicit registry selection; omission selects the first configured registry.
+    pub launcher_id: Option<String>,
+    /// `day` (default), `week` (Monday-aligned) or `month`; all buckets are UTC.
+    pub bucket: Option<String>,
+    /// Unix seconds; the bucket containing `from` is the first bucket returned.
+    pub from: Option<u64>,
+    /// Unix seconds; defaults to the latest confirmed block timestamp.
+    pub to: Option<u64>,
+}
+
+/// Activity counters; fees and premiums are payment-CAT mojos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsActivityJson {
+    pub registrations: u64,
+    pub extensions: u64,
+    pub expires: u64,
+    pub protocol_fees: u64,
+    pub premium_revenue: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsBucketJson {
+    /// Unix seconds at the start of the bucket.
+    pub start: u64,
+    #[serde(flatten)]
+    pub activity: StatsActivityJson,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleLengthJson {
+    pub length: usize,
+    pub active: u64,
+    pub expired: u64,
+}
+
+/// `GET /stats`: bucketed activity plus point-in-time Handle counts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsResponse {
+    pub registry_launcher_id: String,
+    pub bucket: String,
+    /// Oldest first and zero-filled, so consecutive buckets are always adjacent.
+    pub buckets: Vec<StatsBucketJson>,
+    /// Sum over `buckets`.
+    pub totals: StatsActivityJson,
+    pub all_time: StatsActivityJson,
+    pub total_registered: u64,
+    pub active_handles: u64,
+    pub expired_handles: u64,
+    pub handle_lengths: Vec<HandleLengthJson>,
+    pub indexed_peak_height: u32,
+    pub confirmed_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Explicit registry selection; omission selects the first configured registry.
+    pub launcher_id: Option<String>,
+    /// Resume after this `{height}-{seq}` event id; `Last-Event-ID` takes precedence.
+    pub cursor: Option<String>,
+    /// Without a cursor, replay still-standing events confirmed at or above this height.
+    pub from_height: Option<u32>,
+}
+
+/// `data` payload of one `GET /events` SSE message; the SSE `event` field repeats `kind`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandleEventJson {
+    pub cursor: String,
+    pub registry_launcher_id: String,
+    pub height: u32,
+    pub kind: String,
+    pub handle: Option<String>,
+    pub handle_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_launcher_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_launcher_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_execution_height: Option<u32>,
+    /// Cursor of the announcement this `retracted` event withdraws.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retracts: Option<String>,
+}
+
 /// Canonical Handle grammar: 3-63 lowercase ASCII alphanumeric, no normalization.
 pub fn is_canonical_handle(handle: &str) -> bool {
     let len = handle.len();
@@ -1,13 +1,13 @@
 use chia_protocol::{Bytes32, CoinSpend};
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{SpendContext, XchandlesRegistry},
 };
 
-use crate::{CliError, Db};
+use crate::{ChainClient, CliError, Db};
 
 pub async fn quick_sync_xchandles(
-    client: &CoinsetClient,
+    client: &ChainClient,
     db: &mut Db,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
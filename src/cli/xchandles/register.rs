@@ -8,7 +8,7 @@ use chia_puzzle_types::{
     LineageProof,
 };
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         create_security_coin, decode_offer, spend_security_coin, Asset, CatLayer, DriverError,
         Layer, Nft, Offer, PrecommitCoin, PrecommitLayer, Puzzle, SingletonInfo, Slot,
@@ -29,17 +29,17 @@ use clvm_utils::ToTreeHash;
 use clvmr::{serde::node_from_bytes, NodePtr};
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_constants,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants,
     get_last_onchain_timestamp, get_prefix, hex_string_to_bytes32, hex_string_to_pubkey,
     hex_string_to_signature, no_assets, parse_amount, print_spend_bundle_to_file,
-    quick_sync_xchandles, sync_xchandles, wait_for_coin, yes_no_prompt, CliError, Db, SageClient,
-    XchandlesApiClient,
+    quick_sync_xchandles, sync_xchandles, wait_for_coin, yes_no_prompt, ChainClient, CliError, Db,
+    Network, SageClient, XchandlesApiClient,
 };
 
 pub async fn fetch_nft_from_wallet(
     ctx: &mut SpendContext,
     sage: &SageClient,
-    cli: &CoinsetClient,
+    cli: &ChainClient,
     nft: String,
 ) -> Result<(Nft, StandardLayer), CliError> {
     println!("Fetching your NFT's info...");
@@ -191,7 +191,7 @@ pub async fn xchandles_register(
     secret: Option<String>,
     start_time: Option<u64>,
     refund: bool,
-    testnet11: bool,
+    network: &Network,
     payment_asset_id_str: String,
     payment_cat_base_price_str: String,
     registration_period: u64,
@@ -211,7 +211,7 @@ pub async fn xchandles_register(
     }
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
     let sage = SageClient::new()?;
 
     let fee = parse_amount(&fee_str, false)?;
@@ -219,7 +219,7 @@ pub async fn xchandles_register(
     let payment_asset_id = hex_string_to_bytes32(&payment_asset_id_str)?;
 
     print!("First, let's sync the registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let mut registry = if local {
         sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?
     } else {
@@ -479,7 +479,7 @@ pub async fn xchandles_register(
                         .unwrap(),
                     )
                 } else {
-                    let xchandles_api_client = XchandlesApiClient::get(testnet11);
+                    let xchandles_api_client = XchandlesApiClient::get(network);
                     Some(
                         xchandles_api_client
                             .get_slot_value(launcher_id, handle.tree_hash().into())
@@ -510,7 +510,7 @@ pub async fn xchandles_register(
                 db.get_xchandles_neighbors(&mut ctx, launcher_id, handle.tree_hash().into())
                     .await?
             } else {
-                let xchandles_api_client = XchandlesApiClient::get(testnet11);
+                let xchandles_api_client = XchandlesApiClient::get(network);
                 xchandles_api_client
                     .get_neighbors(launcher_id, handle.tree_hash().into())
                     .await?
@@ -550,7 +550,7 @@ pub async fn xchandles_register(
             security_coin,
             sec_conds,
             &security_coin_sk,
-            get_constants(testnet11),
+            get_constants(network),
         )?;
 
         let sb = offer.take(SpendBundle::new(
@@ -594,7 +594,7 @@ pub async fn xchandles_register(
     yes_no_prompt("Continue with registration?")?;
 
     let precommit_coin_address =
-        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(testnet11)).encode()?;
+        Address::new(precommit_inner_puzzle_hash.into(), get_prefix(network)).encode()?;
     let send_resp = sage
         .send_cat(
             hex::encode(payment_asset_id),
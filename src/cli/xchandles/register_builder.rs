@@ -0,0 +1,631 @@
+//! Unsigned `xchandles register` for wallets that cannot run the CLI.
+//!
+//! `POST /register/precommit` returns the CAT puzzle the payment must be sent to;
+//! once that coin has aged `relative_block_height` blocks, `POST /register/spend`
+//! returns the registry, slot and precommit spends built from the indexed neighbor
+//! proofs, plus the conditions the wallet's own coin and NFT spends must emit.
+//! Both steps re-derive the precommit value from the same request fields, so the
+//! wallet only needs to keep its secret, start time and base price in between.
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::State;
+use axum::Json;
+use chia_bls::Signature;
+use chia_protocol::{Bytes32, CoinSpend, SpendBundle};
+use chia_puzzle_types::{cat::CatArgs, singleton::SingletonStruct, LineageProof};
+use chia_wallet_sdk::{
+    driver::{
+        PrecommitCoin, PrecommitLayer, Slot, SpendContext, XchandlesExpirePricingPuzzle,
+        XchandlesPrecommitValue, XchandlesRegisterAction, XchandlesRegistry,
+    },
+    types::{
+        puzzles::{
+            DefaultCatMakerArgs, XchandlesFactorPricingPuzzleArgs, XchandlesHandleSlotValue,
+            XchandlesPricingSolution,
+        },
+        Conditions,
+    },
+    utils::Address,
+};
+use clvm_utils::ToTreeHash;
+use clvmr::serde::node_to_bytes;
+use serde::{Deserialize, Serialize};
+
+use super::listen::{neighbors_lookup_error, AppState};
+use super::listener::{
+    committed_base_price, hex32, is_canonical_handle, is_schedule_base_price, parse_launcher_id,
+    require_fresh, select_registry, ApiError, FollowRecordStatus,
+};
+use crate::{CliError, REGISTRATION_PERIOD};
+
+/// Period bound accepted by both register steps, matching `POST /quote`.
+pub const MAX_REGISTER_PERIODS: u64 = 100;
+
+/// Seconds per block the CLI assumes when it picks a default start time.
+const START_TIME_SECONDS_PER_BLOCK: u64 = 18;
+
+/// Everything the precommit value commits to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterParams {
+    pub handle: String,
+    pub owner_launcher_id: Bytes32,
+    pub resolved_launcher_id: Bytes32,
+    pub num_periods: u64,
+    pub refund_puzzle_hash: Bytes32,
+    pub secret: Bytes32,
+    pub start_time: u64,
+    pub payment_asset_id: Bytes32,
+    pub base_price: u64,
+    pub registration_period: u64,
+}
+
+impl RegisterParams {
+    fn pricing_args(&self) -> XchandlesFactorPricingPuzzleArgs {
+        XchandlesFactorPricingPuzzleArgs {
+            base_price: self.base_price,
+            registration_period: self.registration_period,
+        }
+    }
+
+    pub fn payment_amount(&self) -> u64 {
+        XchandlesFactorPricingPuzzleArgs::get_price(self.base_price, &self.handle, self.num_periods)
+    }
+
+    pub fn precommit_value(&self) -> XchandlesPrecommitValue {
+        XchandlesPrecommitValue::for_normal_registration(
+            self.payment_asset_id.tree_hash(),
+            self.pricing_args().curry_tree_hash(),
+            &XchandlesPricingSolution {
+                buy_time: self.start_time,
+                current_expiration: 0,
+                handle: self.handle.clone(),
+                num_periods: self.num_periods,
+            },
+            self.handle.clone(),
+            self.secret,
+            self.owner_launcher_id,
+            self.resolved_launcher_id,
+        )
+    }
+}
+
+/// Where the payment CAT has to be sent for [`RegisterParams`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterPrecommit {
+    pub value_hash: Bytes32,
+    pub inner_puzzle_hash: Bytes32,
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+}
+
+/// Register spends with only the registry's own signature aggregated.
+#[derive(Debug, Clone)]
+pub struct UnsignedRegisterSpend {
+    pub coin_spends: Vec<CoinSpend>,
+    pub registry_signature: Signature,
+    pub security_conditions: Conditions,
+    pub owner_nft_conditions: Conditions,
+    pub resolved_nft_conditions: Option<Conditions>,
+    pub precommit_coin_id: Bytes32,
+    pub new_registry_coin_id: Bytes32,
+}
+
+/// Same check `xchandles register` prompts on: the registry must price this
+/// asset with factor pricing at `base_price`, or the register action fails.
+pub fn registry_accepts_payment(
+    registry: &XchandlesRegistry,
+    payment_asset_id: Bytes32,
+    base_price: u64,
+    registration_period: u64,
+) -> bool {
+    let state = &registry.info.state;
+    DefaultCatMakerArgs::new(payment_asset_id.tree_hash().into()).curry_tree_hash()
+        == state.cat_maker_puzzle_hash.into()
+        && state.pricing_puzzle_hash
+            == XchandlesFactorPricingPuzzleArgs {
+                base_price,
+                registration_period,
+            }
+            .curry_tree_hash()
+            .into()
+        && state.expired_handle_pricing_puzzle_hash
+            == XchandlesExpirePricingPuzzle::curry_tree_hash(base_price, registration_period).into()
+}
+
+pub fn register_precommit(
+    ctx: &mut SpendContext,
+    registry: &XchandlesRegistry,
+    params: &RegisterParams,
+) -> Result<RegisterPrecommit, CliError> {
+    let value_ptr = ctx.alloc(&params.precommit_value())?;
+    let value_hash = ctx.tree_hash(value_ptr);
+    let inner_puzzle_hash = PrecommitLayer::<()>::puzzle_hash(
+        SingletonStruct::new(registry.info.constants.launcher_id)
+            .tree_hash()
+            .into(),
+        registry.info.constants.relative_block_height,
+        registry.info.constants.precommit_payout_puzzle_hash,
+        params.refund_puzzle_hash,
+        value_hash,
+    );
+
+    Ok(RegisterPrecommit {
+        value_hash: value_hash.into(),
+        inner_puzzle_hash: inner_puzzle_hash.into(),
+        puzzle_hash: CatArgs::curry_tree_hash(params.payment_asset_id, inner_puzzle_hash).into(),
+        amount: params.payment_amount(),
+    })
+}
+
+/// Spends `precommit_parent_id`'s precommit child into `registry` between the
+/// two neighbor slots. `registry` must be the current unspent registry coin.
+#[allow(clippy::too_many_arguments)]
+pub fn build_register_spend(
+    ctx: &mut SpendContext,
+    mut registry: XchandlesRegistry,
+    left_slot: Slot<XchandlesHandleSlotValue>,
+    right_slot: Slot<XchandlesHandleSlotValue>,
+    params: &RegisterParams,
+    precommit_parent_id: Bytes32,
+    precommit_lineage_proof: LineageProof,
+    owner_nft_inner_puzzle_hash: Bytes32,
+    resolved_nft_inner_puzzle_hash: Bytes32,
+) -> Result<UnsignedRegisterSpend, CliError> {
+    let precommit_coin = PrecommitCoin::new(
+        ctx,
+        precommit_parent_id,
+        precommit_lineage_proof,
+        params.payment_asset_id,
+        SingletonStruct::new(registry.info.constants.launcher_id)
+            .tree_hash()
+            .into(),
+        registry.info.constants.relative_block_height,
+        registry.info.constants.precommit_payout_puzzle_hash,
+        params.refund_puzzle_hash,
+        params.precommit_value(),
+        params.payment_amount(),
+    )?;
+    let precommit_coin_id = precommit_coin.coin.coin_id();
+
+    let (security_conditions, owner_nft_conditions, resolved_nft_conditions) =
+        registry.new_action::<XchandlesRegisterAction>().spend(
+            ctx,
+            &mut registry,
+            left_slot,
+            right_slot,
+            &precommit_coin,
+            params.base_price,
+            params.registration_period,
+            params.start_time,
+            owner_nft_inner_puzzle_hash,
+            resolved_nft_inner_puzzle_hash,
+        )?;
+    let (new_registry, registry_signature) = registry.finish_spend(ctx)?;
+
+    Ok(UnsignedRegisterSpend {
+        coin_spends: ctx.take(),
+        registry_signature,
+        security_conditions,
+        owner_nft_conditions,
+        resolved_nft_conditions,
+        precommit_coin_id,
+        new_registry_coin_id: new_registry.coin.coin_id(),
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegisterPrecommitRequest {
+    #[serde(default)]
+    pub launcher_id: Option<String>,
+    pub handle: String,
+    pub owner_launcher_id: String,
+    /// Defaults to `owner_launcher_id`.
+    #[serde(default)]
+    pub resolved_launcher_id: Option<String>,
+    #[serde(default)]
+    pub num_periods: Option<u64>,
+    /// Bech32 address on the listener's network; the precommit refunds here.
+    pub refund_address: String,
+    pub secret: String,
+    pub payment_asset_id: String,
+    /// Defaults to the confirmed timestamp plus `relative_block_height` blocks.
+    #[serde(default)]
+    pub start_time: Option<u64>,
+    /// Defaults to the registry's committed base price; `/register/spend` needs the one
+    /// the precommit response returned.
+    #[serde(default)]
+    pub base_price: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterPrecommitResponse {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    pub num_periods: u64,
+    pub base_price: u64,
+    pub start_time: u64,
+    pub payment_asset_id: String,
+    pub payment_amount: u64,
+    pub precommit_value_hash: String,
+    pub precommit_inner_puzzle_hash: String,
+    pub precommit_puzzle_hash: String,
+    /// Address for the precommit inner puzzle hash; CAT sends to it hint the coin.
+    pub precommit_address: String,
+    pub refund_puzzle_hash: String,
+    pub relative_block_height: u32,
+    pub indexed_peak_height: u32,
+    pub confirmed_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageProofJson {
+    pub parent_parent_coin_info: String,
+    pub parent_inner_puzzle_hash: String,
+    pub parent_amount: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegisterSpendRequest {
+    #[serde(flatten)]
+    pub registration: RegisterPrecommitRequest,
+    /// Parent of the precommit CAT coin and that parent's CAT lineage.
+    pub precommit_parent_coin_id: String,
+    pub precommit_lineage_proof: LineageProofJson,
+    /// NFT inner puzzle hashes the register action messages; read from the
+    /// followed singleton when omitted.
+    #[serde(default)]
+    pub owner_nft_inner_puzzle_hash: Option<String>,
+    #[serde(default)]
+    pub resolved_nft_inner_puzzle_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterSpendResponse {
+    pub registry_launcher_id: String,
+    pub handle: String,
+    /// Registry, slot and precommit spends; only the registry's signature is aggregated.
+    pub spend_bundle: SpendBundle,
+    /// Serialized CLVM conditions a wallet-controlled coin must output in the same bundle.
+    pub security_conditions: String,
+    /// Serialized CLVM conditions the owner NFT's inner puzzle must output.
+    pub owner_nft_conditions: String,
+    /// Present when the resolved NFT differs from the owner and must also be spent.
+    pub resolved_nft_conditions: Option<String>,
+    pub precommit_coin_id: String,
+    pub new_registry_coin_id: String,
+    pub indexed_peak_height: u32,
+}
+
+fn parse_refund_address(raw: &str, prefix: &str) -> Option<Bytes32> {
+    Address::decode(raw)
+        .ok()
+        .filter(|address| address.prefix == prefix)
+        .map(|address| address.puzzle_hash)
+}
+
+/// Validated params plus the registry snapshot they were priced against.
+async fn register_params(
+    state: &AppState,
+    request: &RegisterPrecommitRequest,
+    confirmed_timestamp: u64,
+) -> Result<(Bytes32, XchandlesRegistry, RegisterParams), ApiError> {
+    let num_periods = request.num_periods.unwrap_or(1);
+    if !is_canonical_handle(&request.handle) || !(1..=MAX_REGISTER_PERIODS).contains(&num_periods) {
+        return Err(ApiError::invalid_register_request());
+    }
+    let owner_launcher_id = parse_launcher_id(&request.owner_launcher_id)
+        .ok_or_else(ApiError::invalid_register_request)?;
+    let resolved_launcher_id = match request.resolved_launcher_id.as_deref() {
+        Some(raw) => parse_launcher_id(raw).ok_or_else(ApiError::invalid_register_request)?,
+        None => owner_launcher_id,
+    };
+    let secret =
+        parse_launcher_id(&request.secret).ok_or_else(ApiError::invalid_register_request)?;
+    let payment_asset_id =
+        parse_launcher_id(&request.payment_asset_id).ok_or_else(ApiError::invalid_asset_id)?;
+    let refund_puzzle_hash =
+        parse_refund_address(&request.refund_address, &state.api.address_prefix)
+            .ok_or_else(ApiError::invalid_register_request)?;
+
+    let launcher_id = select_registry(&state.api, request.launcher_id.as_deref())?;
+    let registry = state
+        .registries
+        .read()
+        .await
+        .get(&launcher_id)
+        .cloned()
+        .ok_or_else(ApiError::registry_unsynced)?;
+    let committed = committed_base_price(&state.api, launcher_id).await;
+    let base_price = request.base_price.unwrap_or(committed);
+    if base_price != committed && !is_schedule_base_price(&state.api.price_schedule, base_price) {
+        return Err(ApiError::invalid_register_request());
+    }
+    if !registry_accepts_payment(&registry, payment_asset_id, base_price, REGISTRATION_PERIOD) {
+        return Err(ApiError::payment_not_accepted(base_price));
+    }
+
+    let start_time = request.start_time.unwrap_or_else(|| {
+        confirmed_timestamp
+            + u64::from(registry.info.constants.relative_block_height)
+                * START_TIME_SECONDS_PER_BLOCK
+    });
+    let params = RegisterParams {
+        handle: request.handle.clone(),
+        owner_launcher_id,
+        resolved_launcher_id,
+        num_periods,
+        refund_puzzle_hash,
+        secret,
+        start_time,
+        payment_asset_id,
+        base_price,
+        registration_period: REGISTRATION_PERIOD,
+    };
+    Ok((launcher_id, registry, params))
+}
+
+/// Body value when given, else the current inner puzzle hash of a live followed NFT.
+async fn nft_inner_puzzle_hash(
+    state: &AppState,
+    raw: Option<&str>,
+    launcher_id: Bytes32,
+) -> Result<Bytes32, ApiError> {
+    if let Some(raw) = raw {
+        return parse_launcher_id(raw).ok_or_else(ApiError::invalid_register_request);
+    }
+    let followed = state
+        .api
+        .store
+        .get(launcher_id)
+        .await
+        .ok_or_else(ApiError::nft_inner_puzzle_unknown)?;
+    followed
+        .current
+        .as_ref()
+        .filter(|current| {
+            followed.status == FollowRecordStatus::Active
+                && !current.melted
+                && current.nft.is_some()
+        })
+        .map(|current| current.inner_puzzle_hash)
+        .ok_or_else(ApiError::nft_inner_puzzle_unknown)
+}
+
+fn conditions_hex(ctx: &mut SpendContext, conditions: &Conditions) -> Result<String, CliError> {
+    let ptr = ctx.alloc(conditions)?;
+    Ok(hex::encode(node_to_bytes(ctx, ptr)?))
+}
+
+fn build_failed(error: CliError) -> ApiError {
+    eprintln!("register build error: {error}");
+    ApiError::register_build_failed()
+}
+
+pub(crate) async fn post_register_precommit(
+    State(state): State<AppState>,
+    body: Result<Json<RegisterPrecommitRequest>, JsonRejection>,
+) -> Result<Json<RegisterPrecommitResponse>, ApiError> {
+    let Json(request) = body.map_err(|_| ApiError::invalid_register_request())?;
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(&state.api).await?;
+    let (launcher_id, registry, params) =
+        register_params(&state, &request, confirmed_timestamp).await?;
+
+    let mut ctx = SpendContext::new();
+    let precommit = register_precommit(&mut ctx, &registry, &params).map_err(build_failed)?;
+    let precommit_address = Address::new(
+        precommit.inner_puzzle_hash,
+        state.api.address_prefix.clone(),
+    )
+    .encode()
+    .map_err(|e| build_failed(e.into()))?;
+
+    Ok(Json(RegisterPrecommitResponse {
+        registry_launcher_id: hex32(launcher_id),
+        handle: params.handle,
+        num_periods: params.num_periods,
+        base_price: params.base_price,
+        start_time: params.start_time,
+        payment_asset_id: hex32(params.payment_asset_id),
+        payment_amount: precommit.amount,
+        precommit_value_hash: hex32(precommit.value_hash),
+        precommit_inner_puzzle_hash: hex32(precommit.inner_puzzle_hash),
+        precommit_puzzle_hash: hex32(precommit.puzzle_hash),
+        precommit_address,
+        refund_puzzle_hash: hex32(params.refund_puzzle_hash),
+        relative_block_height: registry.info.constants.relative_block_height,
+        indexed_peak_height,
+        confirmed_timestamp,
+    }))
+}
+
+pub(crate) async fn post_register_spend(
+    State(state): State<AppState>,
+    body: Result<Json<RegisterSpendRequest>, JsonRejection>,
+) -> Result<Json<RegisterSpendResponse>, ApiError> {
+    let Json(request) = body.map_err(|_| ApiError::invalid_register_request())?;
+    // A defaulted start time or base price would drift from the precommitted ones.
+    if request.registration.start_time.is_none() || request.registration.base_price.is_none() {
+        return Err(ApiError::invalid_register_request());
+    }
+    let (indexed_peak_height, confirmed_timestamp) = require_fresh(&state.api).await?;
+    let (launcher_id, registry, params) =
+        register_params(&state, &request.registration, confirmed_timestamp).await?;
+
+    let precommit_parent_id = parse_launcher_id(&request.precommit_parent_coin_id)
+        .ok_or_else(ApiError::invalid_register_request)?;
+    let proof = &request.precommit_lineage_proof;
+    let precommit_lineage_proof = LineageProof {
+        parent_parent_coin_info: parse_launcher_id(&proof.parent_parent_coin_info)
+            .ok_or_else(ApiError::invalid_register_request)?,
+        parent_inner_puzzle_hash: parse_launcher_id(&proof.parent_inner_puzzle_hash)
+            .ok_or_else(ApiError::invalid_register_request)?,
+        parent_amount: proof.parent_amount,
+    };
+    let owner_nft_inner_puzzle_hash = nft_inner_puzzle_hash(
+        &state,
+        request.owner_nft_inner_puzzle_hash.as_deref(),
+        params.owner_launcher_id,
+    )
+    .await?;
+    let resolved_nft_inner_puzzle_hash = if params.resolved_launcher_id == params.owner_launcher_id
+        && request.resolved_nft_inner_puzzle_hash.is_none()
+    {
+        owner_nft_inner_puzzle_hash
+    } else {
+        nft_inner_puzzle_hash(
+            &state,
+            request.resolved_nft_inner_puzzle_hash.as_deref(),
+            params.resolved_launcher_id,
+        )
+        .await?
+    };
+
+    let mut ctx = SpendContext::new();
+    let handle_hash: Bytes32 = params.handle.tree_hash().into();
+    let (left_slot, right_slot) = {
+        let db = state.db.lock().await;
+        db.get_xchandles_neighbors::<XchandlesHandleSlotValue>(&mut ctx, launcher_id, handle_hash)
+            .await
+    }
+    .map_err(|e| neighbors_lookup_error(e, ApiError::handle_not_available))?;
+    // Neighbors that do not point at each other bracket a live slot for this handle.
+    if left_slot.info.value.neighbors.right_value != right_slot.info.value.handle_hash {
+        return Err(ApiError::handle_not_available());
+    }
+
+    let spend = build_register_spend(
+        &mut ctx,
+        registry,
+        left_slot,
+        right_slot,
+        &params,
+        precommit_parent_id,
+        precommit_lineage_proof,
+        owner_nft_inner_puzzle_hash,
+        resolved_nft_inner_puzzle_hash,
+    )
+    .map_err(build_failed)?;
+    let security_conditions =
+        conditions_hex(&mut ctx, &spend.security_conditions).map_err(build_failed)?;
+    let owner_nft_conditions =
+        conditions_hex(&mut ctx, &spend.owner_nft_conditions).map_err(build_failed)?;
+    let resolved_nft_conditions = spend
+        .resolved_nft_conditions
+        .as_ref()
+        .map(|conditions| conditions_hex(&mut ctx, conditions))
+        .transpose()
+        .map_err(build_failed)?;
+
+    Ok(Json(RegisterSpendResponse {
+        registry_launcher_id: hex32(launcher_id),
+        handle: params.handle,
+        spend_bundle: SpendBundle::new(spend.coin_spends, spend.registry_signature),
+        security_conditions,
+        owner_nft_conditions,
+        resolved_nft_conditions,
+        precommit_coin_id: hex32(spend.precommit_coin_id),
+        new_registry_coin_id: hex32(spend.new_registry_coin_id),
+        indexed_peak_height,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Coin;
+    use chia_puzzle_types::{EveProof, Proof};
+    use chia_wallet_sdk::driver::{
+        XchandlesConstants, XchandlesRegistryInfo, XchandlesRegistryState,
+    };
+
+    use super::*;
+
+    fn h(byte: u8) -> Bytes32 {
+        Bytes32::new([byte; 32])
+    }
+
+    fn registry(asset_id: Bytes32, base_price: u64) -> XchandlesRegistry {
+        XchandlesRegistry::new(
+            Coin::new(h(0x01), h(0x02), 1),
+            Proof::Eve(EveProof {
+                parent_parent_coin_info: h(0x03),
+                parent_amount: 1,
+            }),
+            XchandlesRegistryInfo::new(
+                XchandlesRegistryState::from(
+                    asset_id.tree_hash().into(),
+                    base_price,
+                    REGISTRATION_PERIOD,
+                ),
+                XchandlesConstants::new(h(0x90), h(0x92), 32, h(0x93)),
+            ),
+        )
+    }
+
+    fn params(start_time: u64) -> RegisterParams {
+        RegisterParams {
+            handle: "alice".to_string(),
+            owner_launcher_id: h(0x10),
+            resolved_launcher_id: h(0x10),
+            num_periods: 2,
+            refund_puzzle_hash: h(0x20),
+            secret: h(0x30),
+            start_time,
+            payment_asset_id: h(0x91),
+            base_price: 5_000,
+            registration_period: REGISTRATION_PERIOD,
+        }
+    }
+
+    #[test]
+    fn payment_check_requires_matching_asset_and_base_price() {
+        let registry = registry(h(0x91), 5_000);
+        assert!(registry_accepts_payment(
+            &registry,
+            h(0x91),
+            5_000,
+            REGISTRATION_PERIOD
+        ));
+        assert!(!registry_accepts_payment(
+            &registry,
+            h(0x94),
+            5_000,
+            REGISTRATION_PERIOD
+        ));
+        assert!(!registry_accepts_payment(
+            &registry,
+            h(0x91),
+            4_000,
+            REGISTRATION_PERIOD
+        ));
+    }
+
+    #[test]
+    fn precommit_puzzle_is_the_payment_cat_around_the_committed_value() {
+        let registry = registry(h(0x91), 5_000);
+        let mut ctx = SpendContext::new();
+        let precommit = register_precommit(&mut ctx, &registry, &params(1_000)).unwrap();
+
+        assert_eq!(
+            precommit.amount,
+            XchandlesFactorPricingPuzzleArgs::get_price(5_000, "alice", 2)
+        );
+        assert_eq!(
+            precommit.puzzle_hash,
+            Bytes32::from(CatArgs::curry_tree_hash(
+                h(0x91),
+                precommit.inner_puzzle_hash.into()
+            ))
+        );
+        // Both steps re-derive the value, so identical fields must land on the same coin.
+        assert_eq!(
+            register_precommit(&mut ctx, &registry, &params(1_000)).unwrap(),
+            precommit
+        );
+        assert_ne!(
+            register_precommit(&mut ctx, &registry, &params(1_001))
+                .unwrap()
+                .puzzle_hash,
+            precommit.puzzle_hash
+        );
+    }
+}
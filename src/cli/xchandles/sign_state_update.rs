@@ -1,6 +1,5 @@
 use chia_protocol::Bytes;
 use chia_wallet_sdk::{
-    coinset::CoinsetClient,
     driver::{
         MedievalVault, SpendContext, XchandlesExpirePricingPuzzle, XchandlesRegistry,
         XchandlesRegistryReceivedMessagePrefix, XchandlesRegistryState,
@@ -14,13 +13,14 @@ use clvm_utils::ToTreeHash;
 
 use crate::{
     get_constants, hex_string_to_bytes32, multisig_sign_thing_finish, multisig_sign_thing_start,
-    parse_amount, print_registry_state, quick_sync_xchandles, CliError, Db,
+    parse_amount, print_registry_state, quick_sync_xchandles, ChainClient, CliError, Db, Network,
 };
 
 #[allow(clippy::too_many_arguments)]
 pub async fn sync_show_changes_and_compute_new_state(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
+    network: &Network,
     registry_launcher_id_str: String,
     new_payment_asset_id_str: String,
     new_payment_cat_base_price_str: String,
@@ -34,7 +34,7 @@ pub async fn sync_show_changes_and_compute_new_state(
     let new_payment_cat_base_price = parse_amount(&new_payment_cat_base_price_str, true)?;
 
     println!("\nSyncing XCHandles registry... ");
-    let mut db = Db::new(true).await?;
+    let mut db = Db::new(network, true).await?;
     let registry = quick_sync_xchandles(client, &mut db, ctx, registry_launcher_id).await?;
     println!("Done!");
 
@@ -84,15 +84,16 @@ pub async fn xchandles_sign_state_update(
     registration_period: Option<u64>,
     my_pubkey_str: String,
     multisig_launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     debug: bool,
 ) -> Result<(), CliError> {
     let (my_pubkey, mut ctx, client, medieval_vault) =
-        multisig_sign_thing_start(my_pubkey_str, multisig_launcher_id_str, testnet11).await?;
+        multisig_sign_thing_start(my_pubkey_str, multisig_launcher_id_str, network).await?;
 
     let (new_state, registry) = sync_show_changes_and_compute_new_state(
         &mut ctx,
         &client,
+        network,
         registry_launcher_id_str,
         new_payment_asset_id_str,
         new_payment_cat_base_price_str,
@@ -109,7 +110,7 @@ pub async fn xchandles_sign_state_update(
         registry.info.constants.launcher_id,
         medieval_vault.coin,
         &medieval_vault.info,
-        get_constants(testnet11).genesis_challenge,
+        get_constants(network).genesis_challenge,
     )
     .map_err(CliError::Driver)?;
 
@@ -118,7 +119,7 @@ pub async fn xchandles_sign_state_update(
         delegated_puzzle,
         &medieval_vault,
         my_pubkey,
-        testnet11,
+        network,
         debug,
     )
     .await
@@ -3,7 +3,7 @@ use std::time::Instant;
 use chia_bls::Signature;
 use chia_protocol::Bytes32;
 use chia_wallet_sdk::{
-    coinset::{ChiaRpcClient, CoinsetClient},
+    coinset::ChiaRpcClient,
     driver::{
         DriverError, Slot, SpendContext, XchandlesActionLog, XchandlesConstants, XchandlesRegistry,
     },
@@ -11,11 +11,13 @@ use chia_wallet_sdk::{
 };
 use clvm_utils::ToTreeHash;
 
-use crate::{CliError, Db};
+use crate::{ChainClient, CliError, Db};
 
 /// One spent registry coin encountered while walking to the unspent tip.
 #[derive(Debug, Clone)]
 pub struct XchandlesSpentTransition {
+    /// The registry coin that was spent.
+    pub coin_id: Bytes32,
     pub height: u32,
     pub logs: Vec<XchandlesActionLog>,
 }
@@ -36,7 +38,7 @@ fn sync_log(launcher_id: Bytes32, msg: impl AsRef<str>) {
 }
 
 pub async fn sync_xchandles(
-    client: &CoinsetClient,
+    client: &ChainClient,
     db: &mut Db,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
@@ -47,7 +49,7 @@ pub async fn sync_xchandles(
 }
 
 pub async fn sync_xchandles_detailed(
-    client: &CoinsetClient,
+    client: &ChainClient,
     db: &mut Db,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
@@ -201,7 +203,11 @@ pub async fn sync_xchandles_detailed(
         let logs = registry.pending_spend.logs.clone();
         let height = coin_record.spent_block_index;
         registry = registry.child(registry.pending_spend.latest_state.1);
-        spent_transitions.push(XchandlesSpentTransition { height, logs });
+        spent_transitions.push(XchandlesSpentTransition {
+            coin_id,
+            height,
+            logs,
+        });
         steps = steps.saturating_add(1);
         if steps.is_multiple_of(10) {
             sync_log(
@@ -257,7 +263,7 @@ fn resume_from_saved_tip(
 }
 
 async fn xchandles_registry_from_launcher(
-    client: &CoinsetClient,
+    client: &ChainClient,
     ctx: &mut SpendContext,
     launcher_id: Bytes32,
 ) -> Result<
@@ -289,7 +295,7 @@ async fn xchandles_registry_from_launcher(
 
 pub async fn find_xchandles_update_slot(
     ctx: &mut SpendContext,
-    client: &CoinsetClient,
+    client: &ChainClient,
     constants: XchandlesConstants,
     update_initiator_coin_id: Bytes32,
     handle_hash: Bytes32,
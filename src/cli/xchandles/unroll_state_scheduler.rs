@@ -7,16 +7,16 @@ use chia_wallet_sdk::{
     },
     types::{
         puzzles::{DefaultCatMakerArgs, XchandlesFactorPricingPuzzleArgs},
-        Conditions, Mod, MAINNET_CONSTANTS, TESTNET11_CONSTANTS,
+        Conditions, Mod,
     },
 };
 use clvm_utils::ToTreeHash;
 
 use crate::{
-    assets_xch_only, confirm_pushed_transaction, get_coinset_client, get_last_onchain_timestamp,
-    hex_string_to_bytes32, load_xchandles_state_schedule_csv, no_assets, parse_amount,
-    quick_sync_xchandles, sync_multisig_singleton, sync_xchandles, yes_no_prompt, CliError, Db,
-    MultisigSingleton, SageClient,
+    assets_xch_only, confirm_pushed_transaction, get_chain_client, get_constants,
+    get_last_onchain_timestamp, hex_string_to_bytes32, load_xchandles_state_schedule_csv,
+    no_assets, parse_amount, quick_sync_xchandles, sync_multisig_singleton, sync_xchandles,
+    yes_no_prompt, CliError, Db, MultisigSingleton, Network, SageClient,
 };
 
 /// True when the latest confirmed transaction-block timestamp has reached activation.
@@ -26,14 +26,14 @@ fn scheduler_activation_reached(latest_onchain_timestamp: u64, required_timestam
 
 pub async fn xchandles_unroll_state_scheduler(
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     local: bool,
     fee_str: String,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
-    let cli = get_coinset_client(testnet11);
-    let mut db = Db::new(false).await?;
+    let cli = get_chain_client(network)?;
+    let mut db = Db::new(network, false).await?;
     let mut ctx = SpendContext::new();
 
     let mut registry = if local {
@@ -77,7 +77,7 @@ pub async fn xchandles_unroll_state_scheduler(
         hex::encode(new_state.cat_maker_puzzle_hash)
     );
 
-    let filename = if testnet11 {
+    let filename = if network.is_testnet() {
         "xchandles_price_schedule_testnet11.csv"
     } else {
         "xchandles_price_schedule_mainnet.csv"
@@ -153,11 +153,7 @@ pub async fn xchandles_unroll_state_scheduler(
         security_coin,
         security_coin_conditions,
         &security_coin_sk,
-        if testnet11 {
-            &TESTNET11_CONSTANTS
-        } else {
-            &MAINNET_CONSTANTS
-        },
+        get_constants(network),
     )?;
 
     state_scheduler.spend(&mut ctx, registry_inner_ph.into())?;
@@ -20,11 +20,11 @@ use clvm_utils::ToTreeHash;
 use clvmr::{serde::node_from_bytes, NodePtr};
 
 use crate::{
-    controller_matches_configured, get_coinset_client, get_prefix, hex_string_to_bytes32,
+    controller_matches_configured, get_chain_client, get_prefix, hex_string_to_bytes32,
     load_xchandles_launch_csv, load_xchandles_state_schedule_csv, metadata_for_handle_nft,
     price_singleton_public_keys, print_medieval_vault_configuration, CliError, MultisigSingleton,
-    XchandlesStateScheduleRecord, PRICE_SINGLETON_M, REGISTRATION_PERIOD, ROYALTY_BASIS_POINTS,
-    ROYALTY_PUZZLE_HASH,
+    Network, XchandlesStateScheduleRecord, PRICE_SINGLETON_M, REGISTRATION_PERIOD,
+    ROYALTY_BASIS_POINTS, ROYALTY_PUZZLE_HASH,
 };
 
 use crate::sync_multisig_singleton;
@@ -67,20 +67,23 @@ pub async fn xchandles_verify_deployment(
     launcher_id_str: String,
     premine: String,
     expected_count: Option<usize>,
-    testnet11: bool,
+    network: &Network,
 ) -> Result<(), CliError> {
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
 
-    let price_schedule_csv_filename = if testnet11 {
+    let price_schedule_csv_filename = if network.is_testnet() {
         "xchandles_price_schedule_testnet11.csv"
     } else {
         "xchandles_price_schedule_mainnet.csv"
     };
 
-    println!("Verifying XCHandles deployment (testnet: {})...", testnet11);
+    println!(
+        "Verifying XCHandles deployment (network: {})...",
+        network.name
+    );
 
     println!("Let's start with the XCHandles registry.");
     println!(
@@ -300,8 +303,7 @@ pub async fn xchandles_verify_deployment(
             if royalty_puzzle_hash.is_none() {
                 println!(
                     " Royalty address: {}",
-                    Address::new(eve_nft.info.royalty_puzzle_hash, get_prefix(testnet11))
-                        .encode()?
+                    Address::new(eve_nft.info.royalty_puzzle_hash, get_prefix(network)).encode()?
                 );
                 royalty_puzzle_hash = Some(eve_nft.info.royalty_puzzle_hash);
             } else if royalty_puzzle_hash != Some(eve_nft.info.royalty_puzzle_hash) {
@@ -328,7 +330,7 @@ pub async fn xchandles_verify_deployment(
             if eve_nft_temp_inner_ph.is_none() {
                 println!(
                     " Temporary eve NFT address: {}",
-                    Address::new(inner_puzzle_hash, get_prefix(testnet11)).encode()?
+                    Address::new(inner_puzzle_hash, get_prefix(network)).encode()?
                 );
                 eve_nft_temp_inner_ph = Some(inner_puzzle_hash);
             } else if eve_nft_temp_inner_ph != Some(inner_puzzle_hash) {
@@ -452,7 +454,7 @@ pub async fn xchandles_verify_deployment(
         ));
     }
 
-    if !testnet11 {
+    if !network.is_testnet() {
         let Some(royalty_ph) = royalty_puzzle_hash else {
             return Err(CliError::Custom(
                 "Could not determine royalty puzzle hash from premine NFTs".to_string(),
@@ -478,7 +480,7 @@ pub async fn xchandles_verify_deployment(
         );
     }
 
-    if !testnet11 {
+    if !network.is_testnet() {
         let expected_controller = MedievalVaultInfo::new(
             registry.info.constants.price_singleton_launcher_id,
             PRICE_SINGLETON_M,
@@ -503,7 +505,7 @@ pub async fn xchandles_verify_deployment(
         MultisigSingleton::Vault(vault) => {
             println!("Current (latest unspent) vault info:");
             print_medieval_vault_configuration(vault.info.m, &vault.info.public_key_list)?;
-            if !testnet11
+            if !network.is_testnet()
                 && !controller_matches_configured(vault.info.m, &vault.info.public_key_list)?
             {
                 return Err(CliError::Custom(
@@ -9,8 +9,8 @@ use chia_wallet_sdk::{
 use clvm_utils::ToTreeHash;
 
 use crate::{
-    get_coinset_client, get_prefix, hex_string_to_bytes32, parse_amount, quick_sync_xchandles,
-    CliError, Db,
+    get_chain_client, get_prefix, hex_string_to_bytes32, parse_amount, quick_sync_xchandles,
+    CliError, Db, Network,
 };
 
 pub fn print_registry_state(
@@ -95,7 +95,7 @@ pub fn print_registry_state(
 #[allow(clippy::too_many_arguments)]
 pub async fn xchandles_view(
     launcher_id_str: String,
-    testnet11: bool,
+    network: &Network,
     payment_asset_id_str: Option<String>,
     payment_cat_base_price_str: Option<String>,
     registration_period: Option<u64>,
@@ -103,10 +103,10 @@ pub async fn xchandles_view(
     let launcher_id = hex_string_to_bytes32(&launcher_id_str)?;
 
     let mut ctx = SpendContext::new();
-    let cli = get_coinset_client(testnet11);
+    let cli = get_chain_client(network)?;
 
     print!("Syncing registry... ");
-    let mut db = Db::new(false).await?;
+    let mut db = Db::new(network, false).await?;
     let registry = quick_sync_xchandles(&cli, &mut db, &mut ctx, launcher_id).await?;
     println!("done.\n");
 
@@ -127,7 +127,7 @@ pub async fn xchandles_view(
         "  Precommit payout address: {}",
         Address::new(
             registry.info.constants.precommit_payout_puzzle_hash,
-            get_prefix(testnet11)
+            get_prefix(network)
         )
         .encode()?
     );
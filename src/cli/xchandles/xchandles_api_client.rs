@@ -5,10 +5,22 @@ use chia_wallet_sdk::types::puzzles::{SlotInfo, XchandlesHandleSlotValue, Xchand
 use reqwest::Client;
 use std::time::Duration;
 
-use crate::{hex_string_to_bytes32, CliError};
+use crate::{hex_string_to_bytes32, ApiErrorBody, CliError, Network};
 
 use super::XchandlesNeighborsResponse;
 
+/// Turn a listener `ApiError` body (e.g. `index_stale`) into a readable `CliError`.
+pub(crate) async fn listener_error(response: reqwest::Response) -> CliError {
+    let status = response.status();
+    match response.json::<ApiErrorBody>().await {
+        Ok(body) => CliError::Custom(format!(
+            "listener returned {status} {}: {}",
+            body.code, body.message
+        )),
+        Err(_) => CliError::Custom(format!("listener returned {status}")),
+    }
+}
+
 pub struct XchandlesApiClient {
     client: Client,
     base_url: String,
@@ -35,11 +47,11 @@ impl XchandlesApiClient {
         Self::new("http://localhost:8080")
     }
 
-    pub fn get(testnet11: bool) -> Self {
-        if testnet11 {
-            Self::testnet()
-        } else {
-            Self::mainnet()
+    pub fn get(network: &Network) -> Self {
+        match &network.xchandles_api_url {
+            Some(url) => Self::new(url),
+            None if network.is_testnet() => Self::testnet(),
+            None => Self::mainnet(),
         }
     }
 
@@ -78,6 +90,9 @@ impl XchandlesApiClient {
             hex::encode(handle_hash)
         );
         let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(listener_error(response).await);
+        }
 
         let neighbors_resp = response.json::<XchandlesNeighborsResponse>().await?;
 
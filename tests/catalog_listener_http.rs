@@ -0,0 +1,329 @@
+//! Real-HTTP CATalog listener fixture and golden contracts.
+//!
+//! Shares the error envelope goldens with `listener_http.rs`; CATalog-only
+//! shapes live under `catalog_*` in the same goldens directory.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chia_protocol::Bytes32;
+use serde_json::Value;
+use slot_machine::{
+    catalog_router, CatStore, CatalogApiState, CatalogIndexer, CatalogRegistryStore,
+    FreshnessState, ListenerMetrics, MemoryCatStore, MemoryCatalogRegistryStore, RegistryTip,
+    StoredCatMetadata, StoredCatalogState,
+};
+use tokio::sync::RwLock;
+
+fn load_golden(name: &str) -> Value {
+    let path = format!(
+        "{}/tests/fixtures/listener/goldens/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let raw = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {path}: {e}"));
+    serde_json::from_str(&raw).expect("parse golden")
+}
+
+fn normalize_request_id(mut v: Value) -> Value {
+    if let Some(obj) = v.as_object_mut() {
+        if obj.contains_key("request_id") {
+            obj.insert(
+                "request_id".into(),
+                Value::String("FIXED_REQUEST_ID".into()),
+            );
+        }
+    }
+    v
+}
+
+fn b32(byte: u8) -> Bytes32 {
+    Bytes32::new([byte; 32])
+}
+
+struct RunningCatalogListener {
+    base: String,
+    indexer: CatalogIndexer,
+    freshness: Arc<RwLock<FreshnessState>>,
+    registry_tip: Arc<RwLock<Option<RegistryTip>>>,
+    _join: tokio::task::JoinHandle<()>,
+}
+
+impl RunningCatalogListener {
+    async fn spawn(freshness: FreshnessState) -> Self {
+        let registry = MemoryCatalogRegistryStore::shared() as Arc<dyn CatalogRegistryStore>;
+        let cats = MemoryCatStore::shared() as Arc<dyn CatStore>;
+        let freshness = Arc::new(RwLock::new(freshness));
+        let registry_tip = Arc::new(RwLock::new(None));
+        let metrics = ListenerMetrics::shared("catalog");
+        let indexer = CatalogIndexer::new(
+            Arc::clone(&registry),
+            Arc::clone(&cats),
+            Arc::clone(&freshness),
+        )
+        .with_metrics(Arc::clone(&metrics));
+        let state = CatalogApiState {
+            registry,
+            cats,
+            freshness: Arc::clone(&freshness),
+            registry_tip: Arc::clone(&registry_tip),
+            registry_launcher_id: b32(0xaa),
+            now_unix_override: None,
+            metrics,
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = catalog_router(state);
+        let join = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        let base = format!("http://{addr}");
+        for _ in 0..100 {
+            if reqwest::get(format!("{base}/healthz")).await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        Self {
+            base,
+            indexer,
+            freshness,
+            registry_tip,
+            _join: join,
+        }
+    }
+}
+
+fn registry_state(tag: u8, height: u32, price: u64) -> StoredCatalogState {
+    StoredCatalogState {
+        coin_id: b32(tag),
+        parent_coin_id: b32(tag.wrapping_sub(1)),
+        cat_maker_puzzle_hash: b32(0xcc),
+        registration_price: price,
+        confirmation_height: height,
+    }
+}
+
+fn metadata(ticker: &str) -> StoredCatMetadata {
+    StoredCatMetadata {
+        ticker: ticker.to_string(),
+        name: "Test Token".to_string(),
+        description: "A token used by the listener goldens".to_string(),
+        precision: 3,
+        hidden_puzzle_hash: None,
+        image_uris: vec!["https://example.com/tt.png".to_string()],
+        image_hash: b32(0x44),
+        metadata_uris: Vec::new(),
+        metadata_hash: None,
+        license_uris: Vec::new(),
+        license_hash: None,
+    }
+}
+
+/// Two registry spends: one CAT with metadata at 100, one without at 110.
+async fn seed_two_registrations(server: &RunningCatalogListener) {
+    server
+        .indexer
+        .on_registry_transition(
+            b32(0xaa),
+            registry_state(0x01, 100, 1_000),
+            &[(b32(0x11), Some(metadata("TT")))],
+        )
+        .await
+        .unwrap();
+    server
+        .indexer
+        .on_registry_transition(
+            b32(0xaa),
+            registry_state(0x02, 110, 2_000),
+            &[(b32(0x12), None)],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn real_http_golden_catalog_shapes_and_errors() {
+    let server =
+        RunningCatalogListener::spawn(FreshnessState::fresh_at(120, FreshnessState::now_unix()))
+            .await;
+    let client = reqwest::Client::new();
+    seed_two_registrations(&server).await;
+
+    let resp = client
+        .get(format!("{}/cat/{}", server.base, hex::encode(b32(0x11))))
+        .header("Origin", "https://evil.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("access-control-allow-origin").unwrap(),
+        "*"
+    );
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("catalog_cat_success.json"));
+
+    let body: Value = client
+        .get(format!("{}/recent-registrations", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        load_golden("catalog_recent_registrations_success.json")
+    );
+
+    let body: Value = client
+        .get(format!("{}/price", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body, load_golden("catalog_price_success.json"));
+
+    let head = client
+        .head(format!("{}/cat/{}", server.base, hex::encode(b32(0x13))))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(head.status(), 404);
+    let err = client
+        .get(format!("{}/cat/{}", server.base, hex::encode(b32(0x13))))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_cat_not_registered.json")
+    );
+
+    let err = client
+        .get(format!("{}/cat/not-an-asset-id", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_invalid_asset_id.json")
+    );
+
+    *server.freshness.write().await = FreshnessState {
+        indexed_peak_height: 100,
+        upstream_peak_height: 200,
+        last_successful_peak_unix: FreshnessState::now_unix(),
+        confirmed_timestamp: FreshnessState::now_unix(),
+        rolling_back: false,
+        resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
+    };
+    let err = client
+        .get(format!("{}/cat/{}", server.base, hex::encode(b32(0x11))))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 503);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_index_stale.json")
+    );
+}
+
+#[tokio::test]
+async fn real_http_catalog_rollback_and_readiness() {
+    let server =
+        RunningCatalogListener::spawn(FreshnessState::fresh_at(120, FreshnessState::now_unix()))
+            .await;
+    let client = reqwest::Client::new();
+
+    // No registry state or tip yet: fresh, but not ready and no price to quote.
+    let err = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 503);
+    let body: Value = err.json().await.unwrap();
+    assert_eq!(
+        body["details"]["unsynced_registry"].as_str().unwrap(),
+        hex::encode(b32(0xaa))
+    );
+    let err = client
+        .get(format!("{}/price", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 503);
+
+    seed_two_registrations(&server).await;
+    *server.registry_tip.write().await = Some(RegistryTip {
+        coin_id: b32(0x02),
+        indexed_height: 120,
+    });
+    let ready = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ready.status(), 200);
+
+    // A reorg from 105 orphans the second registration and its price change.
+    server.indexer.rollback(105).await.unwrap();
+    let status: Value = client
+        .get(format!("{}/status", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(status["rolling_back"], Value::Bool(true));
+    assert_eq!(status["last_reorg_height"], 105);
+    let err = client
+        .get(format!("{}/price", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 503);
+
+    server
+        .indexer
+        .note_peak(120, 120, FreshnessState::now_unix())
+        .await;
+    let recent: Value = client
+        .get(format!("{}/recent-registrations", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(recent["total_registered"], 1);
+    assert_eq!(recent["items"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        recent["items"][0]["asset_id"].as_str().unwrap(),
+        hex::encode(b32(0x11))
+    );
+    let price: Value = client
+        .get(format!("{}/price", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(price["registration_price"], 1_000);
+    let err = client
+        .get(format!("{}/cat/{}", server.base, hex::encode(b32(0x12))))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+}
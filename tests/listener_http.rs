@@ -21,14 +21,17 @@ use clvm_utils::ToTreeHash;
 use clvmr::Allocator;
 use serde_json::Value;
 use slot_machine::{
-    discover_singleton_in_block, generations_for_network, listener_router, push_handle_replacement,
-    push_pending_replacement, push_registration_replacement, push_replacement, rollback_to_before,
-    DiscoveryResult, FollowRecordStatus, FollowedSingleton, FreshnessState, HandleSlotRecord,
-    HandleSlotStore, ListenerApiState, MemoryHandleSlotStore, MemoryPendingUpdateStore,
-    MemoryRegistrationStore, MemorySingletonStore, ParsedNftState, PendingUpdateRecord,
-    PendingUpdateStore, RegistrationActionKind, RegistrationRecord, RegistrationStore,
-    RegistryPricing, RegistryRegistrationStats, SingletonIndexer, SingletonStore, StoredHandleSlot,
-    StoredPendingUpdate, StoredRegistration, StoredRegistrationEvent, StoredSingletonState,
+    discover_singleton_in_block, generations_for_network, listener_router, push_activity_delta,
+    push_handle_replacement, push_pending_replacement, push_registration_replacement,
+    push_replacement, rollback_to_before, DiscoveryResult, FollowRecordStatus, FollowedSingleton,
+    FreshnessState, HandleEventFeed, HandleHistoryAction, HandleHistoryStore, HandleSlotRecord,
+    HandleSlotStore, ListenerApiState, ListenerMetrics, MemoryHandleHistoryStore,
+    MemoryHandleSlotStore, MemoryPendingUpdateStore, MemoryRegistrationStore, MemorySingletonStore,
+    Network, ParsedNftState, PendingUpdateRecord, PendingUpdateStore, RegistrationActionKind,
+    RegistrationRecord, RegistrationStore, RegistryActivity, RegistryPricing,
+    RegistryRegistrationStats, RegistryTip, SingletonIndexer, SingletonStore, StoredActivityDelta,
+    StoredHandleHistoryEntry, StoredHandleHistoryUpdate, StoredHandleSlot, StoredPendingUpdate,
+    StoredRegistration, StoredRegistrationEvent, StoredSingletonState, SECONDS_PER_DAY,
 };
 use tokio::sync::RwLock;
 
@@ -64,6 +67,9 @@ struct RunningListener {
     registry_pricing: Arc<RwLock<HashMap<Bytes32, RegistryPricing>>>,
     #[allow(dead_code)]
     committed_base_price: Arc<RwLock<HashMap<Bytes32, u64>>>,
+    registry_tips: Arc<RwLock<HashMap<Bytes32, RegistryTip>>>,
+    events: Arc<HandleEventFeed>,
+    handle_history: Arc<MemoryHandleHistoryStore>,
     _join: tokio::task::JoinHandle<()>,
 }
 
@@ -117,6 +123,9 @@ impl RunningListener {
             }
             Arc::new(RwLock::new(committed))
         };
+        let registry_tips = Arc::new(RwLock::new(HashMap::new()));
+        let events = HandleEventFeed::shared();
+        let handle_history = MemoryHandleHistoryStore::shared();
         let state = ListenerApiState {
             store: store.clone() as Arc<dyn SingletonStore>,
             handle_slots: handle_slots.clone() as Arc<dyn HandleSlotStore>,
@@ -126,8 +135,13 @@ impl RunningListener {
             registry_pricing: Arc::clone(&registry_pricing),
             price_schedule: Arc::new(price_schedule),
             committed_base_price: Arc::clone(&committed_base_price),
+            registry_tips: Arc::clone(&registry_tips),
             registry_launcher_ids,
             now_unix_override,
+            metrics: ListenerMetrics::shared("xchandles"),
+            address_prefix: "xch".to_string(),
+            events: Arc::clone(&events),
+            handle_history: handle_history.clone() as Arc<dyn HandleHistoryStore>,
         };
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
@@ -151,6 +165,9 @@ impl RunningListener {
             freshness,
             registry_pricing,
             committed_base_price,
+            registry_tips,
+            events,
+            handle_history,
             _join: join,
         }
     }
@@ -183,7 +200,8 @@ async fn upsert_handle_slot(server: &RunningListener, slot: StoredHandleSlot) {
             current: Some(slot),
             history: Vec::new(),
         })
-        .await;
+        .await
+        .unwrap();
 }
 
 async fn upsert_registration(server: &RunningListener, reg: StoredRegistration) {
@@ -195,7 +213,7 @@ async fn upsert_registration(server: &RunningListener, reg: StoredRegistration)
     };
     let height = reg.confirmation_height;
     push_registration_replacement(&mut record, reg, height);
-    server.registrations.upsert(record).await;
+    server.registrations.upsert(record).await.unwrap();
 }
 
 #[tokio::test]
@@ -227,7 +245,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
     server
         .store
         .upsert(active_record(launcher, nft_state))
-        .await;
+        .await
+        .unwrap();
 
     let resp = client
         .get(format!(
@@ -280,7 +299,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
                 coin_id: b32(0x99),
             },
         ))
-        .await;
+        .await
+        .unwrap();
     let body: Value = client
         .get(format!(
             "{}/singletons/{}",
@@ -313,7 +333,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
                 coin_id: b32(0x01),
             },
         ))
-        .await;
+        .await
+        .unwrap();
     let body: Value = client
         .get(format!(
             "{}/singletons/{}",
@@ -357,7 +378,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
+        .await
+        .unwrap();
     let err = client
         .get(format!(
             "{}/singletons/{}",
@@ -386,7 +408,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
+        .await
+        .unwrap();
     let err = client
         .get(format!(
             "{}/singletons/{}",
@@ -409,6 +432,8 @@ async fn real_http_golden_singleton_shapes_and_errors() {
         confirmed_timestamp: FreshnessState::now_unix(),
         rolling_back: false,
         resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
     };
     let err = client
         .get(format!(
@@ -503,7 +528,8 @@ async fn discovery_follow_melt_rollback_cleanup_and_rediscovery() -> anyhow::Res
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
+        .await
+        .unwrap();
 
     let mut allocator = Allocator::new();
     indexer
@@ -539,22 +565,22 @@ async fn discovery_follow_melt_rollback_cleanup_and_rediscovery() -> anyhow::Res
     melted_state.melted = true;
     melted_state.melt_height = Some(9);
     push_replacement(&mut rec, melted_state, 9);
-    store.upsert(rec).await;
+    store.upsert(rec).await.unwrap();
 
     let mut rec = store.get(launcher_id).await.unwrap();
     rollback_to_before(&mut rec, 9);
-    store.upsert(rec).await;
+    store.upsert(rec).await.unwrap();
     let rec = store.get(launcher_id).await.unwrap();
     assert!(!rec.current.as_ref().unwrap().melted);
 
     // Dereference finality cleanup
-    store.drop_reference(launcher_id, 10).await;
+    store.drop_reference(launcher_id, 10).await.unwrap();
     for launcher in store.all_launcher_ids().await {
         let rec = store.get(launcher).await.unwrap();
         if rec.reference_count == 0 {
             if let Some(deref_h) = rec.dereference_height {
                 if 42 >= deref_h.saturating_add(32) {
-                    store.remove(launcher).await;
+                    store.remove(launcher).await.unwrap();
                 }
             }
         }
@@ -604,8 +630,9 @@ async fn discovery_follow_melt_rollback_cleanup_and_rediscovery() -> anyhow::Res
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
-    indexer.rollback(15).await;
+        .await
+        .unwrap();
+    indexer.rollback(15).await.unwrap();
     assert!(store.get(launcher_id).await.is_none());
 
     Ok(())
@@ -677,7 +704,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
     server
         .store
         .upsert(active_record(resolved, nft_state))
-        .await;
+        .await
+        .unwrap();
 
     // Distinct incomplete Owner must not break Resolved proof.
     server
@@ -693,7 +721,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
+        .await
+        .unwrap();
 
     upsert_handle_slot(
         &server,
@@ -799,7 +828,7 @@ async fn real_http_golden_handle_proofs_and_errors() {
     );
 
     // Missing Resolved → resolution_incomplete
-    server.store.remove(resolved).await;
+    server.store.remove(resolved).await.unwrap();
     let err = client
         .get(format!("{}/handle/{handle}", server.base))
         .send()
@@ -825,7 +854,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
             reference_count: 1,
             dereference_height: None,
         })
-        .await;
+        .await
+        .unwrap();
     let err = client
         .get(format!("{}/handle/{handle}", server.base))
         .send()
@@ -854,7 +884,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
                 coin_id: b32(0x88),
             },
         ))
-        .await;
+        .await
+        .unwrap();
     let body: Value = client
         .get(format!("{}/handle/{handle}", server.base))
         .send()
@@ -883,7 +914,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
                 coin_id: b32(0x88),
             },
         ))
-        .await;
+        .await
+        .unwrap();
     let body: Value = client
         .get(format!("{}/handle/{handle}", server.base))
         .send()
@@ -947,6 +979,8 @@ async fn real_http_golden_handle_proofs_and_errors() {
         confirmed_timestamp: FreshnessState::now_unix(),
         rolling_back: false,
         resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
     };
     let err = client
         .get(format!(
@@ -1022,7 +1056,7 @@ async fn handle_proof_restores_prior_slot_after_reorganization() {
     };
     push_handle_replacement(&mut record, slot_a.clone(), 10);
     push_handle_replacement(&mut record, slot_b.clone(), 20);
-    handle_slots.upsert(record).await;
+    handle_slots.upsert(record).await.unwrap();
 
     store
         .upsert(active_record(
@@ -1039,7 +1073,8 @@ async fn handle_proof_restores_prior_slot_after_reorganization() {
                 coin_id: b32(0x61),
             },
         ))
-        .await;
+        .await
+        .unwrap();
     store
         .upsert(active_record(
             resolved_b,
@@ -1055,7 +1090,8 @@ async fn handle_proof_restores_prior_slot_after_reorganization() {
                 coin_id: b32(0x62),
             },
         ))
-        .await;
+        .await
+        .unwrap();
 
     let state = ListenerApiState {
         store: store.clone() as Arc<dyn SingletonStore>,
@@ -1072,8 +1108,13 @@ async fn handle_proof_restores_prior_slot_after_reorganization() {
         )]))),
         price_schedule: Arc::new(Vec::new()),
         committed_base_price: Arc::new(RwLock::new(HashMap::from([(registry, 5_000u64)]))),
+        registry_tips: Arc::new(RwLock::new(HashMap::new())),
         registry_launcher_ids: vec![registry],
         now_unix_override: Some(1_700_000_000),
+        metrics: ListenerMetrics::shared("xchandles"),
+        address_prefix: "xch".to_string(),
+        events: HandleEventFeed::shared(),
+        handle_history: MemoryHandleHistoryStore::shared(),
     };
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
@@ -1105,7 +1146,7 @@ async fn handle_proof_restores_prior_slot_after_reorganization() {
     assert_eq!(before["slot_confirmation_height"].as_u64().unwrap(), 20);
 
     // Reorganization orphans height 20; prior slot restored.
-    indexer.rollback(20).await;
+    indexer.rollback(20).await.unwrap();
     // Clear rolling_back so reads succeed; production would note_peak after recovery.
     indexer
         .note_peak(
@@ -1201,6 +1242,8 @@ async fn registration_golden_and_lifecycle_semantics() {
         confirmed_timestamp: FreshnessState::now_unix(),
         rolling_back: false,
         resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
     };
     let stale = client
         .get(format!("{}/registrations/{handle}", server.base))
@@ -1260,7 +1303,7 @@ async fn registration_readable_after_expiration_and_replaced_by_expire_not_exten
         },
         150,
     );
-    server.registrations.upsert(record).await;
+    server.registrations.upsert(record).await.unwrap();
 
     // Extension leaves the latest registration unchanged - we simply do not project it.
     let body: Value = client
@@ -1304,7 +1347,11 @@ async fn registrations_recent_golden_limit_and_total_semantics() {
     }
     // Premine-style register already counted above; expire does not increment.
     // Extend/transfer/expiration would also not touch this projection.
-    server.registrations.set_stats(registry, stats).await;
+    server
+        .registrations
+        .set_stats(registry, stats)
+        .await
+        .unwrap();
 
     let resp = client
         .get(format!("{}/recent-registrations?limit=50", server.base))
@@ -1431,7 +1478,7 @@ async fn registration_reorganization_restores_prior_and_reverses_total() {
         },
         20,
     );
-    registrations.upsert(record).await;
+    registrations.upsert(record).await.unwrap();
     registrations
         .set_stats(
             registry,
@@ -1449,9 +1496,11 @@ async fn registration_reorganization_restores_prior_and_reverses_total() {
                         confirmation_height: 20,
                     },
                 ],
+                ..Default::default()
             },
         )
-        .await;
+        .await
+        .unwrap();
 
     let state = ListenerApiState {
         store: store.clone() as Arc<dyn SingletonStore>,
@@ -1468,8 +1517,13 @@ async fn registration_reorganization_restores_prior_and_reverses_total() {
         )]))),
         price_schedule: Arc::new(Vec::new()),
         committed_base_price: Arc::new(RwLock::new(HashMap::from([(registry, 5_000u64)]))),
+        registry_tips: Arc::new(RwLock::new(HashMap::new())),
         registry_launcher_ids: vec![registry],
         now_unix_override: None,
+        metrics: ListenerMetrics::shared("xchandles"),
+        address_prefix: "xch".to_string(),
+        events: HandleEventFeed::shared(),
+        handle_history: MemoryHandleHistoryStore::shared(),
     };
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
@@ -1509,7 +1563,7 @@ async fn registration_reorganization_restores_prior_and_reverses_total() {
     assert_eq!(recent_before["total_registered"].as_u64().unwrap(), 2);
     assert_eq!(recent_before["items"].as_array().unwrap().len(), 2);
 
-    indexer.rollback(20).await;
+    indexer.rollback(20).await.unwrap();
     indexer
         .note_peak(
             200,
@@ -1613,7 +1667,8 @@ async fn project_registrations_from_logs_covers_register_expire_skips_extend() {
     });
     indexer
         .project_registrations_from_logs(registry, 10, &[register_log])
-        .await;
+        .await
+        .unwrap();
 
     // Extension must not replace the registration fact or change total.
     let extend_log = XchandlesActionLog::Extend(XchandlesExtendActionLog {
@@ -1624,7 +1679,8 @@ async fn project_registrations_from_logs_covers_register_expire_skips_extend() {
     });
     indexer
         .project_registrations_from_logs(registry, 15, &[extend_log])
-        .await;
+        .await
+        .unwrap();
 
     let after_extend = registrations
         .get(registry, "alice".tree_hash().into())
@@ -1650,7 +1706,8 @@ async fn project_registrations_from_logs_covers_register_expire_skips_extend() {
     });
     indexer
         .project_registrations_from_logs(registry, 20, &[expire_log])
-        .await;
+        .await
+        .unwrap();
 
     let after_expire = registrations
         .get(registry, "alice".tree_hash().into())
@@ -1705,7 +1762,8 @@ async fn register_keeps_left_neighbor_nft_follow_after_finality() {
     };
     store
         .upsert(active_record(alice_nft, alice_state.clone()))
-        .await;
+        .await
+        .unwrap();
     store
         .upsert(active_record(
             bob_nft,
@@ -1714,7 +1772,8 @@ async fn register_keeps_left_neighbor_nft_follow_after_finality() {
                 ..alice_state
             },
         ))
-        .await;
+        .await
+        .unwrap();
 
     let slot = |handle: &str, counter: u64, owner: Bytes32| {
         XchandlesHandleSlotValue::new(
@@ -1833,9 +1892,11 @@ async fn registration_registry_selection_matches_handle_semantics() {
                     action_kind: RegistrationActionKind::Register,
                     confirmation_height: 90,
                 }],
+                ..Default::default()
             },
         )
-        .await;
+        .await
+        .unwrap();
     server
         .registrations
         .set_stats(
@@ -1847,9 +1908,11 @@ async fn registration_registry_selection_matches_handle_semantics() {
                     action_kind: RegistrationActionKind::Register,
                     confirmation_height: 91,
                 }],
+                ..Default::default()
             },
         )
-        .await;
+        .await
+        .unwrap();
 
     let def: Value = client
         .get(format!("{}/registrations/{handle}", server.base))
@@ -1914,7 +1977,7 @@ async fn upsert_pending(server: &RunningListener, pending: StoredPendingUpdate)
     };
     let height = pending.update_confirmation_height;
     push_pending_replacement(&mut record, pending, height);
-    server.pending_updates.upsert(record).await;
+    server.pending_updates.upsert(record).await.unwrap();
 }
 
 fn unexpired_slot(registry: Bytes32, handle_hash: Bytes32, owner: Bytes32) -> StoredHandleSlot {
@@ -1967,7 +2030,8 @@ async fn pending_transfer_golden_future_ready_and_exact_fields() {
             owner,
             owner_executor(owner, initiator, executor),
         ))
-        .await;
+        .await
+        .unwrap();
     upsert_pending(
         &server,
         StoredPendingUpdate {
@@ -2066,7 +2130,8 @@ async fn pending_transfer_returns_204_for_non_performable_cases() {
             owner,
             owner_executor(owner, initiator, executor),
         ))
-        .await;
+        .await
+        .unwrap();
     let none = client
         .get(format!("{}/handle/{handle}/pending-transfer", server.base))
         .send()
@@ -2104,7 +2169,7 @@ async fn pending_transfer_returns_204_for_non_performable_cases() {
         .await
         .unwrap();
     cleared.current = None;
-    server.pending_updates.upsert(cleared).await;
+    server.pending_updates.upsert(cleared).await.unwrap();
     assert_eq!(
         client
             .get(format!("{}/handle/{handle}/pending-transfer", server.base))
@@ -2131,7 +2196,11 @@ async fn pending_transfer_returns_204_for_non_performable_cases() {
     .await;
     let mut spent = owner_executor(owner, b32(0x99), b32(0xaa));
     spent.parent_coin_id = b32(0x99); // not initiator
-    server.store.upsert(active_record(owner, spent)).await;
+    server
+        .store
+        .upsert(active_record(owner, spent))
+        .await
+        .unwrap();
     assert_eq!(
         client
             .get(format!("{}/handle/{handle}/pending-transfer", server.base))
@@ -2159,7 +2228,8 @@ async fn pending_transfer_returns_204_for_non_performable_cases() {
                 coin_id: executor,
             },
         ))
-        .await;
+        .await
+        .unwrap();
     assert_eq!(
         client
             .get(format!("{}/handle/{handle}/pending-transfer", server.base))
@@ -2177,7 +2247,8 @@ async fn pending_transfer_returns_204_for_non_performable_cases() {
             owner,
             owner_executor(owner, initiator, executor),
         ))
-        .await;
+        .await
+        .unwrap();
     let mut expired = unexpired_slot(registry, handle_hash, owner);
     expired.expiration = 1_000_000_000;
     upsert_handle_slot(&server, expired).await;
@@ -2216,7 +2287,8 @@ async fn pending_transfer_stale_invalid_unknown_and_registry_semantics() {
             owner,
             owner_executor(owner, initiator, executor),
         ))
-        .await;
+        .await
+        .unwrap();
     upsert_pending(
         &server,
         StoredPendingUpdate {
@@ -2260,6 +2332,8 @@ async fn pending_transfer_stale_invalid_unknown_and_registry_semantics() {
         confirmed_timestamp: FreshnessState::now_unix(),
         rolling_back: false,
         resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
     };
     let stale = client
         .get(format!("{}/handle/{handle}/pending-transfer", server.base))
@@ -2333,13 +2407,15 @@ async fn pending_transfer_reorganization_restores_or_removes() {
             current: Some(unexpired_slot(registry, handle_hash, owner)),
             history: Vec::new(),
         })
-        .await;
+        .await
+        .unwrap();
     store
         .upsert(active_record(
             owner,
             owner_executor(owner, initiator, executor),
         ))
-        .await;
+        .await
+        .unwrap();
 
     let mut record = PendingUpdateRecord {
         registry_launcher_id: registry,
@@ -2374,7 +2450,7 @@ async fn pending_transfer_reorganization_restores_or_removes() {
         },
         120,
     );
-    pending_updates.upsert(record).await;
+    pending_updates.upsert(record).await.unwrap();
 
     let state = ListenerApiState {
         store: store.clone() as Arc<dyn SingletonStore>,
@@ -2391,8 +2467,13 @@ async fn pending_transfer_reorganization_restores_or_removes() {
         )]))),
         price_schedule: Arc::new(Vec::new()),
         committed_base_price: Arc::new(RwLock::new(HashMap::from([(registry, 5_000u64)]))),
+        registry_tips: Arc::new(RwLock::new(HashMap::new())),
         registry_launcher_ids: vec![registry],
         now_unix_override: Some(1_700_000_000),
+        metrics: ListenerMetrics::shared("xchandles"),
+        address_prefix: "xch".to_string(),
+        events: HandleEventFeed::shared(),
+        handle_history: MemoryHandleHistoryStore::shared(),
     };
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
@@ -2424,7 +2505,7 @@ async fn pending_transfer_reorganization_restores_or_removes() {
     );
 
     // Roll back the later initiate - prior pending is restored.
-    indexer.rollback(120).await;
+    indexer.rollback(120).await.unwrap();
     *freshness.write().await = FreshnessState::fresh_at(200, FreshnessState::now_unix());
 
     let restored: Value = client
@@ -2442,7 +2523,7 @@ async fn pending_transfer_reorganization_restores_or_removes() {
     );
 
     // Roll back the original initiate - pending removed → 204.
-    indexer.rollback(100).await;
+    indexer.rollback(100).await.unwrap();
     *freshness.write().await = FreshnessState::fresh_at(200, FreshnessState::now_unix());
     assert_eq!(
         client
@@ -2499,7 +2580,8 @@ async fn project_pending_updates_from_logs_covers_initiate_execute_invalidate()
     });
     indexer
         .project_pending_updates_from_logs(registry, 100, &[initiate])
-        .await;
+        .await
+        .unwrap();
     let after_init = pending_updates
         .get(registry, handle_hash)
         .await
@@ -2518,7 +2600,8 @@ async fn project_pending_updates_from_logs_covers_initiate_execute_invalidate()
     });
     indexer
         .project_pending_updates_from_logs(registry, 110, &[extend])
-        .await;
+        .await
+        .unwrap();
     assert!(pending_updates
         .get(registry, handle_hash)
         .await
@@ -2535,7 +2618,8 @@ async fn project_pending_updates_from_logs_covers_initiate_execute_invalidate()
     });
     indexer
         .project_pending_updates_from_logs(registry, 120, &[initiate2])
-        .await;
+        .await
+        .unwrap();
     assert!(pending_updates
         .get(registry, handle_hash)
         .await
@@ -2555,7 +2639,8 @@ async fn project_pending_updates_from_logs_covers_initiate_execute_invalidate()
     });
     indexer
         .project_pending_updates_from_logs(registry, 130, &[execute])
-        .await;
+        .await
+        .unwrap();
     assert!(pending_updates
         .get(registry, handle_hash)
         .await
@@ -2764,6 +2849,8 @@ async fn expiring_invalid_view_and_stale_refuse_entire_page() {
         confirmed_timestamp: EXPIRING_CONFIRMED,
         rolling_back: false,
         resyncing: false,
+        last_reorg_unix: None,
+        last_reorg_height: None,
     };
     let stale = client
         .get(format!("{}/expiring?view=active", server.base))
@@ -2905,7 +2992,7 @@ async fn expiring_membership_updates_after_fork_rollback() {
     let mut later = named_slot(registry, handle, EXPIRING_NOW + 7 * 86_400);
     later.confirmation_height = 120;
     push_handle_replacement(&mut slot_rec, later, 120);
-    handle_slots.upsert(slot_rec).await;
+    handle_slots.upsert(slot_rec).await.unwrap();
     let mut reg_rec = RegistrationRecord {
         registry_launcher_id: registry,
         handle_hash,
@@ -2913,7 +3000,7 @@ async fn expiring_membership_updates_after_fork_rollback() {
         history: Vec::new(),
     };
     push_registration_replacement(&mut reg_rec, named_registration(registry, handle), 100);
-    registrations.upsert(reg_rec).await;
+    registrations.upsert(reg_rec).await.unwrap();
 
     let state = ListenerApiState {
         store: store.clone() as Arc<dyn SingletonStore>,
@@ -2930,8 +3017,13 @@ async fn expiring_membership_updates_after_fork_rollback() {
         )]))),
         price_schedule: Arc::new(Vec::new()),
         committed_base_price: Arc::new(RwLock::new(HashMap::from([(registry, 5_000u64)]))),
+        registry_tips: Arc::new(RwLock::new(HashMap::new())),
         registry_launcher_ids: vec![registry],
         now_unix_override: Some(EXPIRING_NOW),
+        metrics: ListenerMetrics::shared("xchandles"),
+        address_prefix: "xch".to_string(),
+        events: HandleEventFeed::shared(),
+        handle_history: MemoryHandleHistoryStore::shared(),
     };
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
@@ -2959,7 +3051,7 @@ async fn expiring_membership_updates_after_fork_rollback() {
     assert_eq!(before["items"].as_array().unwrap().len(), 1);
     assert_eq!(before["items"][0]["expiration"], EXPIRING_NOW + 7 * 86_400);
 
-    indexer.rollback(120).await;
+    indexer.rollback(120).await.unwrap();
     indexer
         .note_peak(200, 200, EXPIRING_NOW, EXPIRING_CONFIRMED)
         .await;
@@ -3033,7 +3125,7 @@ async fn expiring_new_peak_refreshes_projected_pricing() {
 #[tokio::test]
 async fn schedule_returns_full_generation_list_without_freshness() {
     let registry = b32(0xaa);
-    let schedule = generations_for_network(true);
+    let schedule = generations_for_network(&Network::testnet11());
     let server = RunningListener::spawn_with_pricing(
         expiring_freshness(),
         vec![registry],
@@ -3064,7 +3156,7 @@ async fn schedule_returns_full_generation_list_without_freshness() {
 #[tokio::test]
 async fn price_reports_committed_base_and_remaining_due_unrolls() {
     let registry = b32(0xaa);
-    let schedule = generations_for_network(true);
+    let schedule = generations_for_network(&Network::testnet11());
     let confirmed = 1_786_935_600; // third testnet row (base 6) is due
     let freshness = FreshnessState::fresh_at(116, EXPIRING_NOW).with_confirmed_timestamp(confirmed);
     let server = RunningListener::spawn_with_pricing(
@@ -3093,7 +3185,7 @@ async fn price_reports_committed_base_and_remaining_due_unrolls() {
 #[tokio::test]
 async fn price_omits_applied_generations_when_committed_matches_last_due() {
     let registry = b32(0xaa);
-    let schedule = generations_for_network(true);
+    let schedule = generations_for_network(&Network::testnet11());
     let confirmed = 1_786_935_600;
     let freshness = FreshnessState::fresh_at(116, EXPIRING_NOW).with_confirmed_timestamp(confirmed);
     let server = RunningListener::spawn_with_pricing(
@@ -3130,3 +3222,990 @@ async fn schedule_rejects_unknown_launcher() {
     .unwrap();
     assert_eq!(resp.status(), 404);
 }
+
+#[tokio::test]
+async fn readyz_fails_closed_and_status_reports_listener_view() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        FreshnessState::fresh_at(116, 1_800_000_000),
+        vec![registry],
+        Some(1_800_000_010),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    // Fresh but the registry has never finished a sync: not ready.
+    let resp = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 503);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["code"], "index_stale");
+    assert_eq!(body["details"]["unsynced_registry"], hex::encode(registry));
+    let status: Value = reqwest::get(format!("{}/status", server.base))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(status["ready"], false);
+    assert_eq!(status["registries"][0]["tip_coin_id"], Value::Null);
+
+    server.registry_tips.write().await.insert(
+        registry,
+        RegistryTip {
+            coin_id: b32(0xc1),
+            indexed_height: 116,
+        },
+    );
+    let resp = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["indexed_peak_height"], 116);
+    let head = client
+        .head(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(head.status(), 200);
+
+    let resp = reqwest::get(format!("{}/status", server.base))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.json::<Value>().await.unwrap(),
+        load_golden("status_success.json")
+    );
+
+    // A rollback marks the index not ready and is reported until the next peak.
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    );
+    indexer.rollback(110).await.unwrap();
+    indexer.note_upstream_peak(140).await;
+    let resp = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 503);
+    let status: Value = reqwest::get(format!("{}/status", server.base))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(status["ready"], false);
+    assert_eq!(
+        status["stale_reasons"],
+        serde_json::json!(["rolling_back", "peak_lag"])
+    );
+    assert_eq!(status["peak_lag"], 24);
+    assert_eq!(status["last_reorg_height"], 110);
+    assert!(status["last_reorg_unix"].as_u64().is_some());
+
+    // Liveness is unaffected by readiness.
+    let health = reqwest::get(format!("{}/healthz", server.base))
+        .await
+        .unwrap();
+    assert_eq!(health.status(), 200);
+
+    indexer
+        .note_peak(141, 141, 1_800_000_010, 1_800_000_000)
+        .await;
+    let resp = client
+        .get(format!("{}/readyz", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+async fn metrics_endpoint_reports_routes_and_indexer_counters() {
+    let server =
+        RunningListener::spawn(FreshnessState::fresh_at(116, FreshnessState::now_unix())).await;
+    let metrics = ListenerMetrics::shared("xchandles");
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    )
+    .with_metrics(Arc::clone(&metrics));
+    let mut allocator = Allocator::new();
+    indexer.on_block(&mut allocator, 117, &[]).await.unwrap();
+    indexer.rollback(117).await.unwrap();
+    let text = metrics.render();
+    assert!(text.contains("slot_machine_blocks_processed_total{listener=\"xchandles\"} 1"));
+    assert!(text.contains("slot_machine_rollbacks_total{listener=\"xchandles\"} 1"));
+
+    let missing = reqwest::get(format!("{}/handle/nobody", server.base))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+    let resp = reqwest::get(format!("{}/metrics", server.base))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let body = resp.text().await.unwrap();
+    assert!(body.contains(
+        "slot_machine_http_request_duration_seconds_count{listener=\"xchandles\",method=\"GET\",route=\"/handle/{handle}\",status=\"404\"} 1"
+    ));
+}
+
+async fn seed_search_handles(server: &RunningListener, registry: Bytes32, other: Bytes32) {
+    seed_named_handle(server, registry, "alice", EXPIRING_NOW + 10 * 86_400).await;
+    seed_named_handle(server, registry, "alicia", EXPIRING_NOW - 2 * 86_400).await;
+    seed_named_handle(server, registry, "malik", EXPIRING_NOW + 20 * 86_400).await;
+    seed_named_handle(server, registry, "bob", EXPIRING_NOW + 5 * 86_400).await;
+    seed_named_handle(server, other, "alex", EXPIRING_NOW + 86_400).await;
+}
+
+async fn search_handles(client: &reqwest::Client, base: &str, query: &str) -> Vec<String> {
+    let body: Value = client
+        .get(format!("{base}/search?{query}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    body["items"]
+        .as_array()
+        .unwrap_or_else(|| panic!("no items for {query}: {body}"))
+        .iter()
+        .map(|item| item["handle"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn search_golden_modes_filters_and_errors() {
+    let registry = b32(0xaa);
+    let other = b32(0xbb);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry, other],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    seed_search_handles(&server, registry, other).await;
+
+    let resp = client
+        .get(format!("{}/search?q=ali", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("search_prefix_success.json"));
+
+    assert_eq!(
+        search_handles(&client, &server.base, "q=ali&mode=substring").await,
+        ["alice", "alicia", "malik"]
+    );
+    let fuzzy: Value = client
+        .get(format!("{}/search?q=alice&mode=fuzzy", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fuzzy["items"][0]["handle"], "alice");
+    assert_eq!(fuzzy["items"][0]["distance"], 0);
+    assert_eq!(fuzzy["items"][1]["handle"], "alicia");
+    assert_eq!(fuzzy["items"][1]["distance"], 2);
+    assert_eq!(fuzzy["items"].as_array().unwrap().len(), 2);
+
+    // Registry, availability and expiration-window filters.
+    assert_eq!(
+        search_handles(
+            &client,
+            &server.base,
+            &format!("q=al&launcher_id={}", hex::encode(other))
+        )
+        .await,
+        ["alex"]
+    );
+    assert_eq!(
+        search_handles(&client, &server.base, "q=ali&mode=substring&available=true").await,
+        ["alicia"]
+    );
+    assert_eq!(
+        search_handles(
+            &client,
+            &server.base,
+            "q=ali&mode=substring&available=false"
+        )
+        .await,
+        ["alice", "malik"]
+    );
+    assert_eq!(
+        search_handles(
+            &client,
+            &server.base,
+            &format!(
+                "q=a&mode=substring&min_expiration={}&max_expiration={}",
+                EXPIRING_NOW,
+                EXPIRING_NOW + 15 * 86_400
+            )
+        )
+        .await,
+        ["alex", "alice"]
+    );
+    assert_eq!(
+        search_handles(&client, &server.base, "q=ali&mode=substring&limit=1").await,
+        ["alice"]
+    );
+
+    let err = client
+        .get(format!("{}/search?q=Ali", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_invalid_search_term.json")
+    );
+    let err = client
+        .get(format!("{}/search?q=ali&mode=regex", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+    let err = client
+        .get(format!(
+            "{}/search?q=ali&launcher_id={}",
+            server.base,
+            hex::encode(b32(0xcc))
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+}
+
+#[tokio::test]
+async fn search_drops_handles_orphaned_by_rollback() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    );
+
+    seed_named_handle(&server, registry, "alice", EXPIRING_NOW + 10 * 86_400).await;
+    let mut slot = named_slot(registry, "alicorn", EXPIRING_NOW + 10 * 86_400);
+    slot.confirmation_height = 120;
+    upsert_handle_slot(&server, slot).await;
+    let mut reg = named_registration(registry, "alicorn");
+    reg.confirmation_height = 120;
+    upsert_registration(&server, reg).await;
+    assert_eq!(
+        search_handles(&client, &server.base, "q=ali").await,
+        ["alice", "alicorn"]
+    );
+
+    indexer.rollback(120).await.unwrap();
+    indexer
+        .note_peak(200, 200, EXPIRING_NOW, EXPIRING_CONFIRMED)
+        .await;
+    assert_eq!(
+        search_handles(&client, &server.base, "q=ali").await,
+        ["alice"]
+    );
+}
+
+#[tokio::test]
+async fn owner_and_resolved_handles_list_active_slots_for_launcher() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    seed_named_handle(&server, registry, "alice", EXPIRING_NOW + 10 * 86_400).await;
+    // Expired Handles still name the launcher but are no longer active.
+    seed_named_handle(&server, registry, "bob", EXPIRING_NOW - 86_400).await;
+    let mut carol = named_slot(registry, "carol", EXPIRING_NOW + 10 * 86_400);
+    carol.owner_launcher_id = b32(0x12);
+    upsert_handle_slot(&server, carol).await;
+    upsert_registration(&server, named_registration(registry, "carol")).await;
+
+    let resp = client
+        .get(format!(
+            "{}/owners/{}/handles",
+            server.base,
+            hex::encode(b32(0x11))
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("owner_handles_success.json"));
+
+    // carol resolves to 0x11 while owned by 0x12.
+    let resolved: Value = client
+        .get(format!(
+            "{}/resolved/{}/handles",
+            server.base,
+            hex::encode(b32(0x11))
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let handles: Vec<_> = resolved["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["handle"].as_str().unwrap())
+        .collect();
+    assert_eq!(handles, ["alice", "carol"]);
+    let owned_by_other: Value = client
+        .get(format!(
+            "{}/owners/{}/handles?registry_launcher_id={}",
+            server.base,
+            hex::encode(b32(0x12)),
+            hex::encode(registry)
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(owned_by_other["items"][0]["handle"], "carol");
+
+    let err = client
+        .get(format!("{}/owners/not-a-launcher/handles", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_invalid_launcher_id.json")
+    );
+    let err = client
+        .get(format!(
+            "{}/owners/{}/handles?registry_launcher_id={}",
+            server.base,
+            hex::encode(b32(0x11)),
+            hex::encode(b32(0xcc))
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+}
+
+fn resolved_nft(launcher: Bytes32, p2_puzzle_hash: Bytes32) -> StoredSingletonState {
+    StoredSingletonState {
+        launcher_id: launcher,
+        parent_coin_id: b32(0x22),
+        amount: 1,
+        inner_puzzle_hash: b32(0x33),
+        confirmation_height: 100,
+        melted: false,
+        melt_height: None,
+        nft: Some(ParsedNftState {
+            metadata_treehash: b32(0x44),
+            metadata_updater_puzzle_hash: b32(0x55),
+            current_owner: None,
+            royalty_puzzle_hash: b32(0x66),
+            royalty_basis_points: 420,
+            p2_puzzle_hash,
+            metadata_clvm: None,
+        }),
+        coin_id: b32(0x88),
+    }
+}
+
+#[tokio::test]
+async fn handle_address_resolves_current_nft_p2_and_fails_closed() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    seed_named_handle(&server, registry, "alice", EXPIRING_NOW + 10 * 86_400).await;
+    server
+        .store
+        .upsert(active_record(b32(0x11), resolved_nft(b32(0x11), b32(0x77))))
+        .await
+        .unwrap();
+
+    let resp = client
+        .get(format!("{}/handle/alice/address", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("handle_address_success.json"));
+    let head = client
+        .head(format!("{}/handle/alice/address", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(head.status(), 200);
+
+    // A transfer of the NFT moves the address without touching the Handle slot.
+    server
+        .store
+        .upsert(active_record(b32(0x11), resolved_nft(b32(0x11), b32(0x78))))
+        .await
+        .unwrap();
+    let moved: Value = client
+        .get(format!("{}/handle/alice/address", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(moved["p2_puzzle_hash"], hex::encode(b32(0x78)));
+
+    let mut melted = resolved_nft(b32(0x11), b32(0x78));
+    melted.melted = true;
+    melted.melt_height = Some(110);
+    server
+        .store
+        .upsert(active_record(b32(0x11), melted))
+        .await
+        .unwrap();
+    let err = client
+        .get(format!("{}/handle/alice/address", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 409);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_resolution_not_addressable.json")
+    );
+
+    seed_named_handle(&server, registry, "bob", EXPIRING_NOW - 86_400).await;
+    let err = client
+        .get(format!("{}/handle/bob/address", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 410);
+    let err = client
+        .get(format!("{}/handle/carol/address", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+}
+
+/// Read SSE messages (skipping keep-alive comments) until `count` have arrived.
+async fn read_sse(resp: &mut reqwest::Response, count: usize) -> Vec<(String, Value)> {
+    let mut buffer = String::new();
+    let mut messages = Vec::new();
+    while messages.len() < count {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), resp.chunk())
+            .await
+            .expect("sse message before timeout")
+            .unwrap()
+            .expect("sse stream still open");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        while let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let mut name = String::new();
+            let mut data = String::new();
+            for line in block.lines() {
+                if let Some(v) = line.strip_prefix("event:") {
+                    name = v.trim().to_string();
+                } else if let Some(v) = line.strip_prefix("data:") {
+                    data.push_str(v.trim());
+                }
+            }
+            if !data.is_empty() {
+                messages.push((name, serde_json::from_str(&data).unwrap()));
+            }
+        }
+    }
+    messages
+}
+
+#[tokio::test]
+async fn events_stream_announcements_and_retract_after_rollback() {
+    use chia_wallet_sdk::driver::{
+        XchandlesActionLog, XchandlesExtendActionLog, XchandlesPrecommitValue,
+        XchandlesRegisterActionLog,
+    };
+    use chia_wallet_sdk::types::puzzles::{XchandlesHandleSlotValue, XchandlesPricingSolution};
+
+    let registry = b32(0xaa);
+    let server =
+        RunningListener::spawn(FreshnessState::fresh_at(116, FreshnessState::now_unix())).await;
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    )
+    .with_events(Arc::clone(&server.events));
+    let client = reqwest::Client::new();
+
+    let handle_slot = |handle: &str, counter: u64, expiration: u64| {
+        XchandlesHandleSlotValue::new(
+            counter,
+            handle.tree_hash().into(),
+            Bytes32::default(),
+            Bytes32::new([0xff; 32]),
+            expiration,
+            b32(0x11),
+            b32(0x11),
+        )
+    };
+    let register = XchandlesActionLog::Register(XchandlesRegisterActionLog {
+        spent_left_slot: handle_slot("left", 0, 0),
+        spent_right_slot: handle_slot("right", 0, 0),
+        created_left_slot: handle_slot("left", 1, 0),
+        created_handle_slot: handle_slot("alice", 0, 1_900_000_000),
+        created_right_slot: handle_slot("right", 1, 0),
+        precommit_value: XchandlesPrecommitValue::new(
+            b32(0x01),
+            (),
+            b32(0x02),
+            XchandlesPricingSolution {
+                buy_time: 1_700_000_000,
+                current_expiration: 0,
+                handle: "alice".to_string(),
+                num_periods: 1,
+            },
+            "alice".to_string(),
+            b32(0xa1),
+            b32(0x11),
+            b32(0x11),
+        ),
+        total_price: 1000,
+        registered_time: 31_557_600,
+        owner_full_puzzle_hash: b32(0x31),
+        resolved_full_puzzle_hash: None,
+        owner_inner_puzzle_hash: b32(0x32),
+        resolved_inner_puzzle_hash: b32(0x32),
+    });
+    let extend = XchandlesActionLog::Extend(XchandlesExtendActionLog {
+        spent_slot: handle_slot("alice", 0, 1_900_000_000),
+        created_slot: handle_slot("alice", 1, 1_931_557_600),
+        total_price: 1000,
+        registered_time: 31_557_600,
+    });
+
+    indexer
+        .project_registrations_from_logs(registry, 100, std::slice::from_ref(&register))
+        .await
+        .unwrap();
+    indexer
+        .announce_actions_from_logs(registry, 100, &[register])
+        .await;
+
+    let mut stream = client
+        .get(format!("{}/events?from_height=0", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stream.status(), 200);
+    let replayed = read_sse(&mut stream, 1).await;
+    assert_eq!(replayed[0].0, "registered");
+    assert_eq!(replayed[0].1["handle"], "alice");
+    assert_eq!(replayed[0].1["expiration"], 1_900_000_000u64);
+    let registered_cursor = replayed[0].1["cursor"].as_str().unwrap().to_string();
+
+    // Live: the extension arrives on the open stream, then a reorg withdraws it.
+    indexer
+        .announce_actions_from_logs(registry, 110, &[extend])
+        .await;
+    indexer.rollback(105).await.unwrap();
+    let live = read_sse(&mut stream, 2).await;
+    assert_eq!(live[0].0, "extended");
+    assert_eq!(live[0].1["handle"], "alice");
+    assert_eq!(live[1].0, "retracted");
+    assert_eq!(live[1].1["retracts"], live[0].1["cursor"]);
+    assert_eq!(live[1].1["height"], 105);
+
+    // Resuming after the registration replays both, retraction included.
+    let mut resumed = client
+        .get(format!("{}/events", server.base))
+        .header("Last-Event-ID", &registered_cursor)
+        .send()
+        .await
+        .unwrap();
+    let kinds: Vec<_> = read_sse(&mut resumed, 2)
+        .await
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect();
+    assert_eq!(kinds, ["extended", "retracted"]);
+
+    let err = client
+        .get(format!("{}/events?cursor=latest", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+    let body: Value = err.json().await.unwrap();
+    assert_eq!(body["code"], "invalid_event_cursor");
+}
+
+fn history_entry(
+    registry: Bytes32,
+    handle: &str,
+    height: u32,
+    action: HandleHistoryAction,
+) -> StoredHandleHistoryEntry {
+    StoredHandleHistoryEntry {
+        registry_launcher_id: registry,
+        handle_hash: handle.tree_hash().into(),
+        confirmation_height: height,
+        log_index: 0,
+        action,
+        registry_coin_id: Some(b32(0xc0)),
+        expiration: 1_900_000_000,
+        owner_launcher_id: b32(0x11),
+        resolved_launcher_id: b32(0x11),
+        total_price: None,
+        update: None,
+    }
+}
+
+#[tokio::test]
+async fn handle_history_pages_newest_first_and_drops_orphaned_entries() {
+    let registry = b32(0xaa);
+    let server =
+        RunningListener::spawn(FreshnessState::fresh_at(116, FreshnessState::now_unix())).await;
+    let client = reqwest::Client::new();
+
+    let mut register = history_entry(registry, "alice", 100, HandleHistoryAction::Register);
+    register.total_price = Some(1_000);
+    let mut initiate = history_entry(registry, "alice", 105, HandleHistoryAction::InitiateUpdate);
+    initiate.update = Some(StoredHandleHistoryUpdate {
+        new_owner_launcher_id: b32(0x12),
+        new_resolved_launcher_id: b32(0x13),
+        minimum_execution_height: 113,
+        update_initiator_coin_id: b32(0x55),
+    });
+    let mut execute = history_entry(registry, "alice", 115, HandleHistoryAction::ExecuteUpdate);
+    execute.owner_launcher_id = b32(0x12);
+    execute.resolved_launcher_id = b32(0x13);
+    for entry in [register, initiate, execute] {
+        server.handle_history.append(entry).await.unwrap();
+    }
+
+    let resp = client
+        .get(format!("{}/handle/alice/history?limit=2", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let page: Value = resp.json().await.unwrap();
+    assert_eq!(page, load_golden("handle_history_success.json"));
+
+    let rest: Value = client
+        .get(format!(
+            "{}/handle/alice/history?limit=2&cursor={}",
+            server.base,
+            page["next_cursor"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(rest["items"].as_array().unwrap().len(), 1);
+    assert_eq!(rest["items"][0]["action"], "register");
+    assert_eq!(rest["items"][0]["total_price"], 1_000);
+    assert!(rest.get("next_cursor").is_none());
+
+    // A reorg from 110 orphans the executed update.
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    )
+    .with_handle_history(server.handle_history.clone() as Arc<dyn HandleHistoryStore>);
+    indexer.rollback(110).await.unwrap();
+    indexer
+        .note_peak(116, 116, FreshnessState::now_unix(), 0)
+        .await;
+    let after: Value = client
+        .get(format!("{}/handle/alice/history", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let actions: Vec<_> = after["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["initiate_update", "register"]);
+
+    let err = client
+        .get(format!("{}/handle/carol/history", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+    assert_eq!(
+        normalize_request_id(err.json().await.unwrap()),
+        load_golden("error_handle_not_found.json")
+    );
+    let err = client
+        .get(format!("{}/handle/Alice/history", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 400);
+}
+
+#[tokio::test]
+async fn stats_buckets_daily_activity_counts_handles_and_survives_rollback() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    seed_named_handle(&server, registry, "alice", EXPIRING_NOW + 10 * 86_400).await;
+    seed_named_handle(&server, registry, "carol", EXPIRING_NOW - 2 * 86_400).await;
+    seed_named_handle(&server, registry, "bob", EXPIRING_NOW + 5 * 86_400).await;
+    seed_named_handle(&server, registry, "zed", EXPIRING_NOW - 86_400).await;
+
+    let today = EXPIRING_CONFIRMED / SECONDS_PER_DAY;
+    let mut stats = server.registrations.get_stats(registry).await;
+    stats.total_registered = 4;
+    for (height, day, activity) in [
+        (
+            100,
+            today - 1,
+            RegistryActivity {
+                registrations: 2,
+                protocol_fees: 2_000,
+                ..Default::default()
+            },
+        ),
+        (
+            110,
+            today,
+            RegistryActivity {
+                extensions: 1,
+                expires: 1,
+                protocol_fees: 9_000,
+                premium_revenue: 7_000,
+                ..Default::default()
+            },
+        ),
+        (
+            115,
+            today,
+            RegistryActivity {
+                registrations: 1,
+                protocol_fees: 1_000,
+                ..Default::default()
+            },
+        ),
+    ] {
+        push_activity_delta(
+            &mut stats,
+            StoredActivityDelta {
+                confirmation_height: height,
+                day,
+                activity,
+            },
+            height,
+        );
+    }
+    server
+        .registrations
+        .set_stats(registry, stats)
+        .await
+        .unwrap();
+
+    let from = EXPIRING_CONFIRMED - 2 * SECONDS_PER_DAY;
+    let resp = client
+        .get(format!("{}/stats?from={from}", server.base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("stats_success.json"));
+
+    let weekly: Value = client
+        .get(format!("{}/stats?bucket=week&from={from}", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(weekly["bucket"], "week");
+    assert_eq!(weekly["totals"], body["totals"]);
+    for bucket in weekly["buckets"].as_array().unwrap() {
+        let start_day = bucket["start"].as_u64().unwrap() / SECONDS_PER_DAY;
+        // 1970-01-05 was the first Monday.
+        assert_eq!((start_day - 4) % 7, 0);
+    }
+
+    // A reorg from 112 orphans the block at 115 only.
+    let indexer = SingletonIndexer::new(
+        server.store.clone() as Arc<dyn SingletonStore>,
+        server.handle_slots.clone() as Arc<dyn HandleSlotStore>,
+        server.registrations.clone() as Arc<dyn RegistrationStore>,
+        server.pending_updates.clone() as Arc<dyn PendingUpdateStore>,
+        Arc::clone(&server.freshness),
+    );
+    indexer.rollback(112).await.unwrap();
+    indexer
+        .note_peak(116, 116, EXPIRING_NOW, EXPIRING_CONFIRMED)
+        .await;
+    let after: Value = client
+        .get(format!("{}/stats?from={from}", server.base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(after["buckets"][2]["registrations"], 0);
+    assert_eq!(after["buckets"][2]["expires"], 1);
+    assert_eq!(after["totals"]["protocol_fees"], 11_000);
+
+    for (query, status, code) in [
+        ("bucket=year", 400, "invalid_stats_bucket"),
+        ("from=200&to=100", 400, "invalid_stats_range"),
+        ("from=0&to=100000000", 400, "invalid_stats_range"),
+    ] {
+        let err = client
+            .get(format!("{}/stats?{query}", server.base))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(err.status(), status, "{query}");
+        let body: Value = err.json().await.unwrap();
+        assert_eq!(body["code"], code, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn quote_prices_a_cart_at_the_projected_timestamp() {
+    let registry = b32(0xaa);
+    let server = RunningListener::spawn_with_registries(
+        expiring_freshness(),
+        vec![registry],
+        Some(EXPIRING_NOW),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    seed_named_handle(&server, registry, "alice", EXPIRING_NOW - 10 * 86_400).await;
+    seed_named_handle(&server, registry, "bob", EXPIRING_NOW + 5 * 86_400).await;
+    seed_named_handle(&server, registry, "carol", EXPIRING_NOW + 100 * 86_400).await;
+    seed_named_handle(&server, registry, "old", EXPIRING_NOW - 40 * 86_400).await;
+
+    let resp = client
+        .post(format!("{}/quote", server.base))
+        .json(&serde_json::json!({
+            "handles": ["fresh", "alice", "bob", "carol", "old", "Bad!"],
+            "num_periods": 2,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, load_golden("quote_success.json"));
+
+    // The in-auction line matches the directory's one-period premium at the same projection.
+    let single: Value = client
+        .post(format!("{}/quote", server.base))
+        .json(&serde_json::json!({ "handles": ["alice"] }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(single["num_periods"], 1);
+    assert_eq!(single["items"][0]["total_registration_fee"], 97_407_974);
+
+    for body in [
+        serde_json::json!({ "handles": [] }),
+        serde_json::json!({ "handles": ["alice"], "num_periods": 0 }),
+        serde_json::json!({ "handles": ["alice"], "num_periods": 101 }),
+        serde_json::json!({ "handles": vec!["alice"; 101] }),
+        serde_json::json!({ "num_periods": 1 }),
+    ] {
+        let err = client
+            .post(format!("{}/quote", server.base))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(err.status(), 400, "{body}");
+        let err: Value = err.json().await.unwrap();
+        assert_eq!(err["code"], "invalid_quote_request", "{body}");
+    }
+    let err = client
+        .post(format!("{}/quote", server.base))
+        .json(&serde_json::json!({ "handles": ["alice"], "launcher_id": "bb".repeat(32) }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(err.status(), 404);
+}